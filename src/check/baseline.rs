@@ -0,0 +1,161 @@
+use crate::{
+    check::{
+        file_config::FileConfig,
+        jobs,
+        utils::{InvalidItem, ValidatorKind},
+        validate, ValidateOptions,
+    },
+    foundry_config::CheckPaths,
+    progress,
+};
+use itertools::Itertools;
+use serde_json::json;
+use std::{collections::HashMap, error::Error, fs, path::Path};
+
+/// The file, relative to the project root, that the baseline of existing findings is written to.
+pub const BASELINE_FILE: &str = ".scopelint-baseline.json";
+
+/// Identifies a finding independently of its position, so unrelated edits that move code around
+/// don't turn baselined findings into new ones: (rule name, file, message, trimmed source line).
+type Fingerprint = (String, String, String, String);
+
+/// Findings that existed when the baseline was written, which `scopelint check` doesn't report.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    /// How many findings with each fingerprint are baselined and not yet matched.
+    remaining: HashMap<Fingerprint, usize>,
+    /// How many findings were suppressed so far.
+    suppressed: usize,
+}
+
+/// Checks every file with every rule and writes the active findings to [`BASELINE_FILE`], so
+/// subsequent `scopelint check` runs only fail on new findings.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be parsed or the baseline cannot be written.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let path_config = CheckPaths::load();
+    let options = ValidateOptions { rules: &ValidatorKind::ALL, verbose: 0, jobs: jobs(None)? };
+    let file_config = FileConfig::load();
+    let results =
+        validate(&path_config, &file_config, &[], &[], options, &mut Baseline::default())?;
+    let active = results.items().iter().filter(|item| !item.is_disabled && !item.is_ignored);
+    let findings = Baseline::to_json(active.clone());
+    fs::write(BASELINE_FILE, findings)?;
+    progress::info(format_args!(
+        "Wrote {} finding(s) to {BASELINE_FILE}, `scopelint check` now only fails on new findings",
+        active.count()
+    ));
+    Ok(())
+}
+
+impl Baseline {
+    /// Loads the baseline from [`BASELINE_FILE`] in the current directory, returning `None` if it
+    /// doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load() -> Result<Option<Self>, String> {
+        let path = Path::new(BASELINE_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {BASELINE_FILE}: {e}"))?;
+        Self::from_json(&content).map(Some)
+    }
+
+    /// Parses a baseline from its JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or doesn't match the baseline format.
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let json: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| format!("Invalid baseline: {e}"))?;
+        let findings = json
+            .get("findings")
+            .and_then(serde_json::Value::as_array)
+            .ok_or_else(|| "Baseline must have a `findings` array".to_string())?;
+
+        let mut baseline = Self::default();
+        for finding in findings {
+            let field = |key: &str| {
+                finding
+                    .get(key)
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("Each baseline finding must have a `{key}` string"))
+            };
+            let fingerprint = (field("rule")?, field("file")?, field("text")?, field("source")?);
+            *baseline.remaining.entry(fingerprint).or_default() += 1;
+        }
+        Ok(baseline)
+    }
+
+    /// Serializes `items` as a baseline, sorted so that rewriting an unchanged baseline produces
+    /// the same file.
+    fn to_json<'a>(items: impl Iterator<Item = &'a InvalidItem>) -> String {
+        let findings: Vec<serde_json::Value> = items
+            .map(fingerprint)
+            .sorted()
+            .map(|(rule, file, text, source)| {
+                json!({ "rule": rule, "file": file, "text": text, "source": source })
+            })
+            .collect();
+        let baseline = json!({ "version": 1, "findings": findings });
+        serde_json::to_string_pretty(&baseline).unwrap_or_default() + "\n"
+    }
+
+    /// Marks the active findings in `items` that are in the baseline as ignored. Each baselined
+    /// finding suppresses at most one finding, so a second copy of a legacy violation is still
+    /// reported.
+    pub fn suppress(&mut self, items: &mut [InvalidItem]) {
+        for item in items.iter_mut().filter(|item| !item.is_disabled && !item.is_ignored) {
+            if let Some(remaining) =
+                self.remaining.get_mut(&fingerprint(item)).filter(|remaining| **remaining > 0)
+            {
+                *remaining -= 1;
+                item.is_ignored = true;
+                self.suppressed += 1;
+            }
+        }
+    }
+
+    /// Returns how many findings were suppressed so far.
+    #[must_use]
+    pub const fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+}
+
+/// Returns the fingerprint of `item`.
+fn fingerprint(item: &InvalidItem) -> Fingerprint {
+    (
+        item.kind.rule_name().to_string(),
+        item.file.clone(),
+        item.text.clone(),
+        item.source_line.trim().to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_json() {
+        let finding = r#"{"rule": "error", "file": "./src/Vault.sol", "text": "Error 'Paused' should be prefixed with 'Vault_'", "source": "error Paused();"}"#;
+        let baseline = Baseline::from_json(&format!(
+            r#"{{"version": 1, "findings": [{finding}, {finding}]}}"#
+        ))
+        .unwrap();
+        assert_eq!(baseline.remaining.len(), 1);
+        assert_eq!(baseline.remaining.values().next(), Some(&2));
+
+        assert!(Baseline::from_json("{}").is_err());
+        assert!(Baseline::from_json(r#"{"findings": [{"rule": "error"}]}"#).is_err());
+    }
+}
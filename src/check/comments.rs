@@ -217,6 +217,9 @@ impl CommentWithMetadata {
 pub struct Comments {
     prefixes: VecDeque<CommentWithMetadata>,
     postfixes: VecDeque<CommentWithMetadata>,
+    /// Byte ranges of all comments, sorted by start. Not in upstream: lets inline config and
+    /// validators look up comments without re-scanning the source.
+    spans: Vec<(usize, usize)>,
 }
 
 impl Comments {
@@ -226,19 +229,59 @@ impl Comments {
         let mut last_comment = None;
 
         comments.sort_by_key(|comment| comment.loc());
+        let spans =
+            comments.iter().map(|comment| (comment.loc().start(), comment.loc().end())).collect();
         for comment in comments {
             let comment = CommentWithMetadata::from_comment_and_src(comment, src, last_comment);
             let vec = if comment.is_prefix() { &mut prefixes } else { &mut postfixes };
             vec.push_back(comment);
             last_comment = Some(vec.back().unwrap());
         }
-        Self { prefixes, postfixes }
+        Self { prefixes, postfixes, spans }
     }
 
     pub(crate) fn iter(&self) -> impl Iterator<Item = &CommentWithMetadata> {
         self.prefixes.iter().merge(self.postfixes.iter())
     }
 
+    /// Returns the byte range of the comment containing `offset`, if any.
+    pub fn comment_at(&self, offset: usize) -> Option<(usize, usize)> {
+        let idx = self.spans.partition_point(|(start, _)| *start <= offset);
+        let (start, end) = *self.spans.get(idx.checked_sub(1)?)?;
+        (offset < end).then_some((start, end))
+    }
+
+    /// Returns whether `offset` is inside a comment.
+    pub fn is_in_comment(&self, offset: usize) -> bool {
+        self.comment_at(offset).is_some()
+    }
+
+    /// Returns the offset of the first character at or after `offset` that is neither whitespace
+    /// nor part of a comment, i.e. the start of the next piece of code.
+    pub fn next_code_offset(&self, src: &str, mut offset: usize) -> Option<usize> {
+        loop {
+            let (idx, _) = src[offset..].char_indices().find(|(_, ch)| !ch.is_whitespace())?;
+            match self.comment_at(offset + idx) {
+                Some((_, end)) => offset = end,
+                None => return Some(offset + idx),
+            }
+        }
+    }
+
+    /// Returns the doc comments (`///` and `/** */`) that lie entirely within `start..end`, e.g.
+    /// the NatSpec between a declaration and the item before it.
+    pub fn doc_comments_in(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl Iterator<Item = &CommentWithMetadata> + '_ {
+        self.iter().filter(move |comment| {
+            matches!(comment.ty, CommentType::DocLine | CommentType::DocBlock) &&
+                comment.loc.start() >= start &&
+                comment.loc.end() <= end
+        })
+    }
+
     /// Parse all comments to return a list of inline config items. This will return an iterator of
     /// results of parsing comments which start with `scopelint:`
     pub fn parse_inline_config_items(
@@ -416,3 +459,22 @@ impl CommentStringExt for str {
         CommentStateCharIndices::new(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_index() {
+        let src = "// scopelint: ignore-src\n  /* a */ /// b\n  function foo() {} // c";
+        let (_, comments) = crate::parser::parse_solidity(src, 0).unwrap();
+        let comments = Comments::new(comments, src);
+
+        assert!(comments.is_in_comment(0));
+        assert!(comments.is_in_comment(30));
+        assert!(!comments.is_in_comment(24));
+        assert_eq!(comments.next_code_offset(src, 24), Some(src.find("function").unwrap()));
+        assert_eq!(comments.next_code_offset(src, src.find("// c").unwrap()), None);
+        assert_eq!(comments.doc_comments_in(0, src.len()).count(), 1);
+    }
+}
@@ -0,0 +1,381 @@
+use crate::{
+    check::{
+        file_config::FileConfig,
+        jobs, load_baseline,
+        report::Report,
+        utils::{Edit, Fix, InvalidItem, Severity, ValidatorKind},
+        validate, Parsed, ValidateOptions,
+    },
+    foundry_config::{CheckPaths, PROFILE_ENV},
+    progress,
+};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+use tiny_keccak::{Hasher, Keccak};
+
+#[cfg(unix)]
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    os::unix::{
+        fs::{FileTypeExt, MetadataExt},
+        net::{UnixListener, UnixStream},
+    },
+    process,
+    time::Duration,
+};
+
+/// How long the daemon waits for a client to send its request or read the response, so a client
+/// that connects and stalls can't block the checks of every other client.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The files parsed by earlier checks of the daemon, by path. `None` outside the daemon, so other
+/// runs don't hold on to the files they parsed.
+static PARSED: Mutex<Option<HashMap<PathBuf, Parsed>>> = Mutex::new(None);
+
+/// Serves `scopelint check --use-daemon` for the current project until it's stopped, or stops
+/// the daemon that does if `stop` is set.
+///
+/// Each check reparses only the files whose source changed since the previous one, then runs every
+/// rule on them as usual, so the findings are the same as without the daemon.
+///
+/// # Errors
+///
+/// Returns an error if a daemon already runs for the project or its socket can't be created.
+#[cfg(unix)]
+pub fn run(stop: bool) -> Result<(), Box<dyn Error>> {
+    let socket = socket_path()?;
+    if stop {
+        match UnixStream::connect(&socket) {
+            Ok(stream) => {
+                exchange(stream, &json!({ "stop": true }))?;
+                progress::info(format_args!("Stopped the daemon at {}", socket.display()));
+            }
+            Err(_) => progress::info(format_args!("No daemon running for this project")),
+        }
+        return Ok(());
+    }
+    if UnixStream::connect(&socket).is_ok() {
+        eprintln!(
+            "{}: A daemon is already running for this project at {}",
+            "error".bold().red(),
+            socket.display()
+        );
+        return Err("daemon already running".into());
+    }
+
+    let listener = bind(&socket).inspect_err(|err| {
+        eprintln!("{}: Can't listen on {}: {err}", "error".bold().red(), socket.display());
+    })?;
+    *parsed_files() = Some(HashMap::new());
+    progress::info(format_args!(
+        "Listening on {}, stop with `scopelint daemon --stop`",
+        socket.display()
+    ));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("{err}");
+                continue;
+            }
+        };
+        let mut request = String::new();
+        if stream.set_read_timeout(Some(CLIENT_TIMEOUT)).is_err() ||
+            stream.set_write_timeout(Some(CLIENT_TIMEOUT)).is_err() ||
+            BufReader::new(&stream).read_line(&mut request).is_err()
+        {
+            continue;
+        }
+        let request: Value = serde_json::from_str(&request).unwrap_or_default();
+        if request["stop"] == true {
+            let _ = writeln!(stream, "{}", json!({ "stopped": true }));
+            break;
+        }
+        let _ = writeln!(stream, "{}", serve(&request));
+    }
+    fs::remove_file(&socket)?;
+    Ok(())
+}
+
+/// Serves `scopelint check --use-daemon` for the current project until it's stopped.
+///
+/// # Errors
+///
+/// Always returns an error, since the daemon listens on a Unix domain socket.
+#[cfg(not(unix))]
+pub fn run(_stop: bool) -> Result<(), Box<dyn Error>> {
+    eprintln!("{}: `scopelint daemon` is only supported on Unix", "error".bold().red());
+    Err("daemon not supported".into())
+}
+
+/// Listens on `socket`, replacing the socket left behind by a daemon that was killed, which nothing
+/// listens on anymore, but only if it's a socket of the current user.
+#[cfg(unix)]
+fn bind(socket: &Path) -> io::Result<UnixListener> {
+    if let Some(dir) = socket.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    // Listen on a fresh path first: its owner is the current user, which std has no other way to
+    // tell, and renaming it over the stale socket replaces that atomically.
+    let fresh = socket.with_extension(format!("{}.sock", process::id()));
+    let _ = fs::remove_file(&fresh);
+    let listener = UnixListener::bind(&fresh)?;
+    let replaced =
+        fs::symlink_metadata(&fresh).and_then(|own| match fs::symlink_metadata(socket) {
+            Ok(stale) if !stale.file_type().is_socket() || stale.uid() != own.uid() => {
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "the path exists and isn't a socket of the current user",
+                ))
+            }
+            Ok(_) => fs::rename(&fresh, socket),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => fs::rename(&fresh, socket),
+            Err(err) => Err(err),
+        });
+    if let Err(err) = replaced {
+        let _ = fs::remove_file(&fresh);
+        return Err(err);
+    }
+    Ok(listener)
+}
+
+/// Checks the `selected` files with `rules` through the daemon running for the current project.
+/// Returns the report and how many findings the baseline suppressed, or `None` if no daemon is
+/// running, in which case the caller checks in-process.
+#[cfg(unix)]
+pub(super) fn check(
+    selected: &[PathBuf],
+    rules: &[ValidatorKind],
+) -> Result<Option<(Report, usize)>, Box<dyn Error>> {
+    let Ok(stream) = UnixStream::connect(socket_path()?) else {
+        progress::info(format_args!("No daemon running for this project, checking in-process"));
+        return Ok(None);
+    };
+    let paths: Vec<String> = selected.iter().map(|path| path.display().to_string()).collect();
+    let rules: Vec<&str> = rules.iter().map(ValidatorKind::rule_name).collect();
+    let response = exchange(stream, &json!({ "paths": paths, "rules": rules }))?;
+    if let Some(err) = response["error"].as_str() {
+        eprintln!("{}: {err}", "error".bold().red());
+        return Err(err.into());
+    }
+
+    let mut results = Report::default();
+    for file in response["files"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+        results.add_file(file.to_string());
+    }
+    let items: Option<Vec<InvalidItem>> =
+        response["items"].as_array().into_iter().flatten().map(decode).collect();
+    results.add_items(items.ok_or("malformed response from the daemon")?);
+    let suppressed = response["suppressed"].as_u64().and_then(|n| usize::try_from(n).ok());
+    Ok(Some((results, suppressed.unwrap_or_default())))
+}
+
+/// Checks the `selected` files with `rules` through the daemon, which isn't supported here, so
+/// the caller checks in-process.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)] // Same signature as on Unix.
+pub(super) fn check(
+    _selected: &[PathBuf],
+    _rules: &[ValidatorKind],
+) -> Result<Option<(Report, usize)>, Box<dyn Error>> {
+    progress::info(format_args!("`--use-daemon` is only supported on Unix, checking in-process"));
+    Ok(None)
+}
+
+/// Takes the parse of `file` kept from an earlier check, if this runs in the daemon. The caller
+/// only reuses it if the source of the file is unchanged.
+pub(super) fn take(file: &Path) -> Option<Parsed> {
+    parsed_files().as_mut()?.remove(file)
+}
+
+/// Keeps the `parsed` files for the next check, if this runs in the daemon, and forgets files that
+/// were deleted.
+pub(super) fn keep(parsed: Vec<Parsed>) {
+    if let Some(files) = parsed_files().as_mut() {
+        files.extend(parsed.into_iter().map(|parsed| (parsed.file.clone(), parsed)));
+        files.retain(|file, _| file.is_file());
+    }
+}
+
+// Locks the parsed files. A check that panicked can't have left them inconsistent, since each file
+// is taken out while it's checked.
+fn parsed_files() -> MutexGuard<'static, Option<HashMap<PathBuf, Parsed>>> {
+    PARSED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the socket of the daemon for the current directory, Foundry profile, config file, and
+/// ignore file, so each project and configuration gets its own daemon.
+///
+/// Sockets are kept in `$XDG_RUNTIME_DIR`, which only the current user can access, or in the
+/// project's `cache` directory, never in the shared temp directory.
+fn socket_path() -> Result<PathBuf, Box<dyn Error>> {
+    let cwd = env::current_dir()?;
+    let mut keccak = Keccak::v256();
+    for part in [
+        cwd.display().to_string(),
+        env::var(PROFILE_ENV).unwrap_or_default(),
+        env::var(super::file_config::CONFIG_ENV).unwrap_or_default(),
+        env::var(super::file_config::IGNORE_PATH_ENV).unwrap_or_default(),
+    ] {
+        keccak.update(part.as_bytes());
+        keccak.update(&[0]);
+    }
+    let mut hash = [0u8; 32];
+    keccak.finalize(&mut hash);
+    let dir = env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| Path::new(dir).is_absolute())
+        .map_or_else(|| PathBuf::from("cache"), PathBuf::from);
+    Ok(dir.join(format!("scopelint-{}.sock", crate::selectors::hex(&hash[..8]))))
+}
+
+// Sends `request` as a line of JSON and reads the JSON response, which ends when the daemon closes
+// the connection.
+#[cfg(unix)]
+fn exchange(mut stream: UnixStream, request: &Value) -> Result<Value, Box<dyn Error>> {
+    writeln!(stream, "{request}")?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(serde_json::from_str(&response)?)
+}
+
+// Checks the paths and rules of a `check --use-daemon` request, and returns the report as JSON.
+#[cfg(unix)]
+fn serve(request: &Value) -> Value {
+    let strings = |key: &str| {
+        request[key].as_array().into_iter().flatten().filter_map(Value::as_str).collect::<Vec<_>>()
+    };
+    let selected: Vec<PathBuf> = strings("paths").into_iter().map(PathBuf::from).collect();
+    let rules: Vec<ValidatorKind> =
+        strings("rules").into_iter().filter_map(ValidatorKind::from_rule_name).collect();
+    let checked = load_baseline().and_then(|mut baseline| {
+        let options = ValidateOptions { rules: &rules, verbose: 0, jobs: jobs(None)? };
+        let file_config = FileConfig::load();
+        let results =
+            validate(&CheckPaths::load(), &file_config, &selected, &[], options, &mut baseline)?;
+        Ok((results, baseline.suppressed()))
+    });
+    match checked {
+        Ok((results, suppressed)) => {
+            let items: Vec<Value> = results.items().iter().map(encode).collect();
+            json!({ "files": results.files(), "items": items, "suppressed": suppressed })
+        }
+        Err(err) => json!({ "error": err.to_string() }),
+    }
+}
+
+/// Serializes every field of `item`, unlike [`InvalidItem::to_json`], so the client can report it
+/// exactly as if it was found in-process.
+fn encode(item: &InvalidItem) -> Value {
+    let fix = item.fix.as_ref().map(|fix| {
+        let edits: Vec<Value> = fix
+            .edits
+            .iter()
+            .map(|edit| json!({ "start": edit.start, "end": edit.end, "replacement": edit.replacement }))
+            .collect();
+        json!({
+            "help": fix.help,
+            "edits": edits,
+            "before": fix.before,
+            "after": fix.after,
+            "automatic": fix.automatic,
+        })
+    });
+    json!({
+        "rule": item.kind.rule_name(),
+        "severity": item.severity.name(),
+        "file": item.file,
+        "text": item.text,
+        "line": item.line,
+        "column": item.column,
+        "end_column": item.end_column,
+        "source_line": item.source_line,
+        "is_disabled": item.is_disabled,
+        "is_ignored": item.is_ignored,
+        "fix": fix,
+    })
+}
+
+/// Deserializes an item serialized by [`encode`], or returns `None` if it's malformed.
+fn decode(item: &Value) -> Option<InvalidItem> {
+    let string = |value: &Value| value.as_str().map(str::to_string);
+    let number = |value: &Value| value.as_u64().and_then(|n| usize::try_from(n).ok());
+    let fix = match &item["fix"] {
+        Value::Null => None,
+        fix => Some(Fix {
+            help: string(&fix["help"])?,
+            edits: fix["edits"]
+                .as_array()?
+                .iter()
+                .map(|edit| {
+                    Some(Edit {
+                        start: number(&edit["start"])?,
+                        end: number(&edit["end"])?,
+                        replacement: string(&edit["replacement"])?,
+                    })
+                })
+                .collect::<Option<_>>()?,
+            before: string(&fix["before"])?,
+            after: string(&fix["after"])?,
+            automatic: fix["automatic"].as_bool()?,
+        }),
+    };
+    Some(InvalidItem {
+        kind: ValidatorKind::from_rule_name(item["rule"].as_str()?)?,
+        severity: Severity::from_str(item["severity"].as_str()?, false).ok()?,
+        file: string(&item["file"])?,
+        text: string(&item["text"])?,
+        line: number(&item["line"])?,
+        column: number(&item["column"])?,
+        end_column: number(&item["end_column"])?,
+        source_line: string(&item["source_line"])?,
+        is_disabled: item["is_disabled"].as_bool()?,
+        is_ignored: item["is_ignored"].as_bool()?,
+        fix,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, validators};
+
+    #[test]
+    fn test_encode_decode() {
+        let content = "contract C {\n    uint public total;\n    uint256 public count;\n}\n";
+        let mut parsed = parse_source(Path::new("./src/C.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.uint]\nstyle = \"short\"").unwrap();
+        let items = validators::int_types::validate(&parsed);
+        assert_eq!(items.len(), 1);
+        assert!(items[0].fix.is_some());
+        for item in items {
+            assert!(decode(&encode(&item)) == Some(item));
+        }
+
+        let mut malformed = encode(&InvalidItem::new(
+            ValidatorKind::IntType,
+            &parsed,
+            solang_parser::pt::Loc::File(0, 0, 8),
+            "Use `uint` instead of `uint256`".to_string(),
+        ));
+        assert!(decode(&malformed).is_some());
+        malformed["rule"] = "nonexistent".into();
+        assert!(decode(&malformed).is_none());
+    }
+
+    #[test]
+    fn test_keep_and_take() {
+        let parsed = parse_source(Path::new("./src/C.sol"), "contract C {}").unwrap();
+        // Outside the daemon, nothing is kept.
+        keep(vec![parsed]);
+        assert!(take(Path::new("./src/C.sol")).is_none());
+    }
+}
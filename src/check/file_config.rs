@@ -17,35 +17,331 @@
 //! [ignore.overrides]
 //! "src/BaseBridgeReceiver.sol" = ["src"]
 //! "src/legacy/**/*.sol" = ["src", "error"]
+//!
+//! # Opt-in: per-chain directories that must not import from each other
+//! [rules.chain.dirs]
+//! mainnet = "script/deploy/mainnet"
+//! optimism = "script/deploy/optimism"
+//!
+//! # Opt-in: lowest compiler version that `pragma solidity` may admit
+//! [rules.pragma]
+//! min_solc = "0.8.20"
+//!
+//! # Opt-in: `pragma solidity` must pin an exact compiler version in files of these directories
+//! # (defaults to "src")
+//! [rules.floating]
+//! enabled = true
+//! dirs = ["src", "script"]
+//!
+//! # Opt-in: selectors that contract functions must not use, e.g. a proxy's admin functions
+//! [rules.selector]
+//! reserved = ["upgradeTo(address)", "0xf851a440"]
+//!
+//! # Opt-in: events that share a signature must be declared the same way
+//! [rules.event]
+//! enabled = true
+//!
+//! # Opt-in: no `else` after an `if` branch that ends in `return` or `revert`
+//! [rules.else]
+//! enabled = true
+//!
+//! # Opt-in: no comparisons of booleans to `true` or `false`
+//! [rules.bool]
+//! enabled = true
+//!
+//! # Opt-in: no scaling by literals like `10**18` or `1e18` where `1 ether` would do
+//! [rules.units]
+//! enabled = true
+//!
+//! # Opt-in: functions with named returns don't mix `return x;` with implicit returns
+//! [rules.returns]
+//! enabled = true
+//!
+//! # Opt-in: state variables declare their visibility instead of relying on the implicit
+//! # `internal`
+//! [rules.visibility]
+//! enabled = true
+//!
+//! # Opt-in: constants of src contracts holding chain-dependent values must be `immutable`.
+//! # Addresses always are, and so are constants whose names match one of `patterns` (defaults to
+//! # "*CHAIN_ID*" and "*CHAINID*").
+//! [rules.immutable]
+//! enabled = true
+//! patterns = ["*CHAIN_ID*", "*ROUTER*"]
+//!
+//! # Opt-in: src files must be named after the contract, interface, or library they define. Other
+//! # definitions in the file are flagged unless their names match one of `helpers`.
+//! [rules.filename]
+//! enabled = true
+//! helpers = ["*Lib", "Mock*"]
+//!
+//! # Opt-in: src files must declare at most one non-abstract contract. Libraries and interfaces
+//! # next to it are flagged too, unless `allow_companions` is set.
+//! [rules.onecontract]
+//! enabled = true
+//! allow_companions = true
+//!
+//! # Opt-in: public and external functions of src contracts must have these NatSpec tags
+//! # (defaults to all of "notice", "param", and "return")
+//! [rules.natspec]
+//! enabled = true
+//! tags = ["notice", "param"]
+//!
+//! # Opt-in: conventions for `foundry.toml`. `[profile.default]` must set the `required` keys, no
+//! # profile but the `exempt_profiles` may enable a `forbidden` key, and profiles must set the
+//! # `consistent` keys to the value of `[profile.default]`. The defaults are shown.
+//! [rules.foundry]
+//! enabled = true
+//! required = ["solc", "optimizer_runs"]
+//! forbidden = ["ffi"]
+//! exempt_profiles = []
+//! consistent = ["solc", "evm_version"]
+//!
+//! # Opt-in: remappings in remappings.txt and foundry.toml must point to existing directories,
+//! # must not conflict, and must be used by an import
+//! [rules.remappings]
+//! enabled = true
+//!
+//! # Opt-in: `pragma solidity` in src files must admit the compiler version foundry.toml pins
+//! [rules.solc]
+//! enabled = true
+//!
+//! # Opt-in: deployable src contracts must have a `@custom:security-contact` tag
+//! [rules.contact]
+//! enabled = true
+//! exempt = ["MockToken"]
+//!
+//! # Opt-in: each src file must have a test file at one of these paths, relative to the test
+//! # directory. `{dir}` is the src file's directory relative to the src directory, and `{name}`
+//! # is its file name without the `.sol` extension.
+//! [rules.mirror]
+//! enabled = true
+//! patterns = ["{dir}/{name}.t.sol", "{dir}/{name}/"]
+//!
+//! # Suffix that script contract names must end with (defaults to "Script", "" to allow any name)
+//! [rules.scriptfile]
+//! suffix = "Script"
+//!
+//! # Deepest nesting of blocks, conditionals, and loops in src function bodies (defaults to 4)
+//! [rules.nesting]
+//! max_depth = 4
+//!
+//! # How 256-bit integer types are written: "explicit" for `uint256` (default), "short" for `uint`
+//! [rules.uint]
+//! style = "explicit"
+//!
+//! # URL template for finding hyperlinks, e.g. for CI logs
+//! [output]
+//! link_template = "https://github.com/org/repo/blob/main/{path}#L{line}"
+//!
+//! # Solidity files excluded from the formatting check and `scopelint fmt`, e.g. for intentionally
+//! # aligned tables. Other rules still apply to them.
+//! [fmt]
+//! ignore = ["src/Constants.sol"]
+//!
+//! # Findings below error severity that `scopelint check` tolerates before failing, for gradual
+//! # adoption in legacy codebases (overridden by `--max-warnings`)
+//! [check]
+//! max_warnings = 25
+//!
+//! # License written to missing SPDX headers by `scopelint check --fix` (defaults to "UNLICENSED")
+//! [fix]
+//! spdx_license = "MIT"
+//!
+//! # Opt-in: env vars that deployment scripts may read
+//! [scripts.env]
+//! required = ["DEPLOYER", "RPC_URL"]
 //! ```
 
-use crate::check::utils::ValidatorKind;
+use crate::{
+    check::{
+        utils::{DeprecatedRule, IsFileKind, ValidatorKind, DEPRECATED_RULES},
+        validators::{
+            chain_constants,
+            floating_pragma::{self, PinnedDir},
+            foundry_toml::{self, FoundryConventions},
+            int_types::IntStyle,
+            natspec::{self, NatspecTag},
+            nesting_depth,
+            pragma_min_version::{self, SolcVersion},
+            script_file,
+            selector_collisions::{self, Selector},
+            test_mirror,
+        },
+    },
+    foundry_config::CheckPaths,
+};
+use colored::Colorize;
 use globset::{Glob, GlobMatcher};
-use std::path::{Path, PathBuf};
+use serde_json::json;
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// The env var that points at a config file to use instead of `.scopelint`, set by `--config`.
+pub const CONFIG_ENV: &str = "SCOPELINT_CONFIG";
+
+/// The env var that points at a file of gitignore-style patterns of files to ignore in addition to
+/// `[ignore] files`, set by `--ignore-path`.
+pub const IGNORE_PATH_ENV: &str = "SCOPELINT_IGNORE_PATH";
+
+/// Every key `.scopelint` reads, as dotted paths. A path ending in `.*` is a table whose keys are
+/// free-form, e.g. file patterns or chain names.
+const KNOWN_KEYS: &[&str] = &[
+    "ignore.files",
+    "ignore.overrides.*",
+    "fmt.ignore",
+    "rules.chain.dirs.*",
+    "rules.pragma.min_solc",
+    "rules.floating.enabled",
+    "rules.floating.dirs",
+    "rules.selector.reserved",
+    "rules.event.enabled",
+    "rules.else.enabled",
+    "rules.bool.enabled",
+    "rules.units.enabled",
+    "rules.returns.enabled",
+    "rules.visibility.enabled",
+    "rules.remappings.enabled",
+    "rules.solc.enabled",
+    "rules.immutable.enabled",
+    "rules.immutable.patterns",
+    "rules.filename.enabled",
+    "rules.filename.helpers",
+    "rules.onecontract.enabled",
+    "rules.onecontract.allow_companions",
+    "rules.natspec.enabled",
+    "rules.natspec.tags",
+    "rules.foundry.enabled",
+    "rules.foundry.required",
+    "rules.foundry.forbidden",
+    "rules.foundry.exempt_profiles",
+    "rules.foundry.consistent",
+    "rules.contact.enabled",
+    "rules.contact.exempt",
+    "rules.mirror.enabled",
+    "rules.mirror.patterns",
+    "rules.scriptfile.suffix",
+    "rules.nesting.max_depth",
+    "rules.uint.style",
+    "output.link_template",
+    "fix.spdx_license",
+    "check.max_warnings",
+    "scripts.env.required",
+];
 
 /// Configuration loaded from `.scopelint` file
 #[derive(Debug, Default, Clone)]
+#[allow(clippy::struct_excessive_bools)] // One flag per opt-in rule.
 pub struct FileConfig {
     /// Directory where the `.scopelint` file was found (project root)
     config_dir: Option<PathBuf>,
     /// Patterns for files to ignore entirely
     ignored_file_patterns: Vec<GlobMatcher>,
+    /// Patterns for Solidity files excluded from formatting
+    fmt_ignored_file_patterns: Vec<GlobMatcher>,
     /// Rule-specific overrides: file pattern -> list of rules to ignore
     rule_overrides: Vec<(GlobMatcher, Vec<ValidatorKind>)>,
+    /// Warnings for renamed or removed rule names in `[ignore.overrides]`.
+    deprecated_rule_warnings: Vec<String>,
+    /// Per-chain directories for the opt-in `chain` rule: (chain name, directory)
+    chain_dirs: Vec<(String, String)>,
+    /// Env vars scripts may read, for the opt-in `env` rule. `None` if `[scripts.env]` is absent.
+    required_env_vars: Option<Vec<String>>,
+    /// Lowest compiler version pragmas may admit, for the opt-in `pragma` rule.
+    min_solc: Option<SolcVersion>,
+    /// Directories whose files must pin the compiler version, for the opt-in `floating` rule.
+    /// `None` if the rule is disabled.
+    pinned_pragma_dirs: Option<Vec<PinnedDir>>,
+    /// Selectors functions must not use, for the `selector` rule: (entry as written, selector).
+    reserved_selectors: Vec<(String, Selector)>,
+    /// Whether the opt-in `event` rule is enabled.
+    event_rule_enabled: bool,
+    /// Whether the opt-in `else` rule is enabled.
+    else_rule_enabled: bool,
+    /// Whether the opt-in `bool` rule is enabled.
+    bool_rule_enabled: bool,
+    /// Whether the opt-in `units` rule is enabled.
+    units_rule_enabled: bool,
+    /// Whether the opt-in `returns` rule is enabled.
+    returns_rule_enabled: bool,
+    /// Whether the opt-in `visibility` rule is enabled.
+    visibility_rule_enabled: bool,
+    /// Whether the opt-in `remappings` rule is enabled.
+    remappings_rule_enabled: bool,
+    /// Whether the opt-in `solc` rule is enabled.
+    solc_rule_enabled: bool,
+    /// Whether the opt-in `contact` rule is enabled.
+    contact_rule_enabled: bool,
+    /// Names of contracts that don't need a security contact, for the `contact` rule.
+    contact_exempt: Vec<String>,
+    /// Test file patterns for the opt-in `mirror` rule. `None` if the rule is disabled.
+    mirror_patterns: Option<Vec<String>>,
+    /// Names of constants holding chain-dependent values, for the opt-in `immutable` rule.
+    /// `None` if the rule is disabled.
+    chain_value_patterns: Option<Vec<GlobMatcher>>,
+    /// Names of definitions allowed next to the main contract of a src file, for the opt-in
+    /// `filename` rule. `None` if the rule is disabled.
+    filename_helpers: Option<Vec<GlobMatcher>>,
+    /// Whether libraries and interfaces may be declared next to the contract of a src file, for
+    /// the opt-in `onecontract` rule. `None` if the rule is disabled.
+    one_contract_companions: Option<bool>,
+    /// `NatSpec` tags public and external functions must have, for the opt-in `natspec` rule.
+    /// `None` if the rule is disabled.
+    natspec_tags: Option<Vec<NatspecTag>>,
+    /// Conventions `foundry.toml` must follow, for the opt-in `foundry` rule. `None` if the rule
+    /// is disabled.
+    foundry_conventions: Option<FoundryConventions>,
+    /// Suffix that script contract names must end with, for the `scriptfile` rule. `None` if not
+    /// configured, in which case the default is used.
+    script_contract_suffix: Option<String>,
+    /// Deepest nesting allowed in function bodies, for the `nesting` rule. `None` if not
+    /// configured, in which case the default is used.
+    max_nesting_depth: Option<usize>,
+    /// How 256-bit integer types are written, for the `uint` rule.
+    int_style: IntStyle,
+    /// URL template for finding hyperlinks in the human-readable report.
+    link_template: Option<String>,
+    /// License that `--fix` writes to missing SPDX headers. `None` if not configured, in which
+    /// case `UNLICENSED` is used.
+    spdx_license: Option<String>,
+    /// Findings below error severity that don't fail the check. `None` if not configured, in which
+    /// case any finding fails the check.
+    max_warnings: Option<usize>,
 }
 
 impl FileConfig {
     /// Load configuration from `.scopelint` file.
-    /// Searches up the directory tree from the current working directory to find the file.
+    /// Searches up the directory tree from the current working directory to find the file, unless
+    /// another file is selected with [`CONFIG_ENV`], whose patterns are then relative to the
+    /// current working directory.
     /// Returns default config if file doesn't exist or can't be parsed.
+    /// Files matching the patterns of the ignore file selected with [`IGNORE_PATH_ENV`], if any,
+    /// are ignored too.
     #[must_use]
     pub fn load() -> Self {
-        let config_path = Self::find_config_file();
-        let Some(config_path) = config_path else {
-            return Self::default();
-        };
+        let mut config = Self::load_config_file();
+        if let Some(ignore_path) = Self::ignore_path() {
+            match Self::read_ignore_file(&ignore_path) {
+                Ok(patterns) => config.ignored_file_patterns.extend(patterns),
+                Err(err) => eprintln!("Warning: {err}. Ignoring it."),
+            }
+        }
+        config
+    }
 
-        let config_dir = config_path.parent().map(PathBuf::from);
+    // Loads the config file, without the patterns of the ignore file.
+    fn load_config_file() -> Self {
+        let (config_path, config_dir) = if let Some(config_path) = Self::config_override() {
+            (config_path, std::env::current_dir().ok())
+        } else {
+            let Some(config_path) = Self::find_config_file() else {
+                return Self::default();
+            };
+            let config_dir = config_path.parent().map(PathBuf::from);
+            (config_path, config_dir)
+        };
 
         match std::fs::read_to_string(&config_path) {
             Ok(content) => {
@@ -63,6 +359,63 @@ impl FileConfig {
         }
     }
 
+    /// Checks that the config file selected with [`CONFIG_ENV`] and the ignore file selected with
+    /// [`IGNORE_PATH_ENV`], if any, exist and are valid, since unlike a `.scopelint` that was
+    /// found, it's an error to silently fall back to the defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a selected file can't be read or parsed.
+    pub fn validate_override() -> Result<(), Box<dyn Error>> {
+        if let Some(config_path) = Self::config_override() {
+            let parsed = std::fs::read_to_string(&config_path)
+                .map_err(|err| format!("Failed to read {}: {err}", config_path.display()))
+                .and_then(|content| Self::from_toml(&content));
+            if let Err(err) = parsed {
+                eprintln!("{}: {err}", "error".bold().red());
+                return Err(err.into());
+            }
+        }
+        if let Some(Err(err)) = Self::ignore_path().map(|path| Self::read_ignore_file(&path)) {
+            eprintln!("{}: {err}", "error".bold().red());
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the path of the ignore file selected with [`IGNORE_PATH_ENV`], if any.
+    #[must_use]
+    pub fn ignore_path() -> Option<PathBuf> {
+        std::env::var_os(IGNORE_PATH_ENV).filter(|path| !path.is_empty()).map(PathBuf::from)
+    }
+
+    // Reads the ignore file at `path` and compiles its patterns.
+    fn read_ignore_file(path: &Path) -> Result<Vec<GlobMatcher>, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        ignore_file_globs(&content)
+            .map_err(|err| format!("{}: {err}", path.display()))?
+            .iter()
+            .map(|pattern| {
+                Glob::new(pattern)
+                    .map(|glob| glob.compile_matcher())
+                    .map_err(|e| format!("Invalid glob pattern '{pattern}': {e}"))
+            })
+            .collect()
+    }
+
+    /// Returns the path of the config file [`FileConfig::load`] reads, if any: the one selected
+    /// with [`CONFIG_ENV`], or the closest `.scopelint`.
+    #[must_use]
+    pub fn path() -> Option<PathBuf> {
+        Self::config_override().or_else(Self::find_config_file)
+    }
+
+    // The config file selected with `CONFIG_ENV`, if any.
+    fn config_override() -> Option<PathBuf> {
+        std::env::var_os(CONFIG_ENV).filter(|path| !path.is_empty()).map(PathBuf::from)
+    }
+
     /// Search up the directory tree to find `.scopelint` file.
     /// Returns the path to the config file if found, None otherwise.
     fn find_config_file() -> Option<PathBuf> {
@@ -85,7 +438,7 @@ impl FileConfig {
     }
 
     /// Parse configuration from TOML string
-    fn from_toml(content: &str) -> Result<Self, String> {
+    pub(crate) fn from_toml(content: &str) -> Result<Self, String> {
         let toml: toml::Value =
             toml::from_str(content).map_err(|e| format!("Invalid TOML: {e}"))?;
 
@@ -121,7 +474,18 @@ impl FileConfig {
                         let rule_name = rule_str
                             .as_str()
                             .ok_or_else(|| "Rule names must be strings".to_string())?;
-                        let kind = parse_rule_name(rule_name)
+                        if let Some(deprecated) = DeprecatedRule::find(DEPRECATED_RULES, rule_name)
+                        {
+                            config.deprecated_rule_warnings.push(format!(
+                                "[ignore.overrides] \"{pattern_str}\" uses `{rule_name}`, which {}, run `scopelint migrate-config` to update it",
+                                deprecated.status()
+                            ));
+                            // Removed rules never report anything, so there's nothing to ignore.
+                            if deprecated.replacement.is_none() {
+                                continue;
+                            }
+                        }
+                        let kind = ValidatorKind::from_rule_name(rule_name)
                             .ok_or_else(|| format!("Unknown rule: '{rule_name}'"))?;
                         validator_kinds.push(kind);
                     }
@@ -131,9 +495,404 @@ impl FileConfig {
             }
         }
 
+        // Parse [fmt] section
+        if let Some(files) = toml.get("fmt").and_then(|f| f.get("ignore")) {
+            let files =
+                files.as_array().ok_or_else(|| "[fmt] ignore must be an array".to_string())?;
+            for file_pattern in files {
+                let pattern_str = file_pattern
+                    .as_str()
+                    .ok_or_else(|| "[fmt] ignore patterns must be strings".to_string())?;
+                let glob = Glob::new(pattern_str)
+                    .map_err(|e| format!("Invalid glob pattern '{pattern_str}': {e}"))?;
+                config.fmt_ignored_file_patterns.push(glob.compile_matcher());
+            }
+        }
+
+        // Parse [rules] section
+        if let Some(rules) = toml.get("rules") {
+            config.parse_rules(rules)?;
+        }
+
+        // Parse [output] section
+        if let Some(template) = toml.get("output").and_then(|o| o.get("link_template")) {
+            let template = template
+                .as_str()
+                .ok_or_else(|| "[output] link_template must be a string".to_string())?;
+            config.link_template = Some(template.to_string());
+        }
+
+        // Parse [fix] section
+        if let Some(license) = toml.get("fix").and_then(|f| f.get("spdx_license")) {
+            let license = license
+                .as_str()
+                .ok_or_else(|| "[fix] spdx_license must be a string".to_string())?;
+            config.spdx_license = Some(license.to_string());
+        }
+
+        // Parse [check] section
+        if let Some(max_warnings) = toml.get("check").and_then(|c| c.get("max_warnings")) {
+            let max_warnings = max_warnings
+                .as_integer()
+                .and_then(|max| usize::try_from(max).ok())
+                .ok_or_else(|| "[check] max_warnings must be a non-negative integer".to_string())?;
+            config.max_warnings = Some(max_warnings);
+        }
+
+        // Parse [scripts.env] section
+        if let Some(env) = toml.get("scripts").and_then(|s| s.get("env")) {
+            let mut names = Vec::new();
+            let required = match env.get("required") {
+                Some(required) => required
+                    .as_array()
+                    .ok_or_else(|| "[scripts.env] required must be an array".to_string())?
+                    .as_slice(),
+                None => &[],
+            };
+            for name in required {
+                let name =
+                    name.as_str().ok_or_else(|| "Env var names must be strings".to_string())?;
+                names.push(name.to_string());
+            }
+            config.required_env_vars = Some(names);
+        }
+
         Ok(config)
     }
 
+    /// Parses the `[rules]` section, which configures the opt-in rules.
+    fn parse_rules(&mut self, rules: &toml::Value) -> Result<(), String> {
+        let section = |name| RuleSection { name, table: rules.get(name) };
+
+        // [rules.chain.dirs] maps chain names to directories, rather than setting fixed keys.
+        if let Some(dirs) = rules.get("chain").and_then(|c| c.get("dirs")) {
+            let dirs =
+                dirs.as_table().ok_or_else(|| "[rules.chain.dirs] must be a table".to_string())?;
+            for (chain, dir) in dirs {
+                let dir = dir
+                    .as_str()
+                    .ok_or_else(|| format!("Directory for chain '{chain}' must be a string"))?;
+                let dir = dir.trim_start_matches("./").trim_end_matches('/').to_string();
+                self.chain_dirs.push((chain.clone(), dir));
+            }
+        }
+
+        if let Some(min_solc) =
+            section("pragma").get("min_solc", "a string", toml::Value::as_str)?
+        {
+            self.min_solc = Some(
+                pragma_min_version::parse_version(min_solc)
+                    .ok_or_else(|| format!("Invalid min_solc version: '{min_solc}'"))?,
+            );
+        }
+
+        for entry in section("selector").strings("reserved")?.unwrap_or_default() {
+            let selector = selector_collisions::parse_reserved(entry)
+                .ok_or_else(|| format!("Invalid reserved selector: '{entry}'"))?;
+            self.reserved_selectors.push((entry.to_string(), selector));
+        }
+
+        // Rules that are only enabled or disabled.
+        for (name, enabled) in [
+            ("event", &mut self.event_rule_enabled),
+            ("else", &mut self.else_rule_enabled),
+            ("bool", &mut self.bool_rule_enabled),
+            ("units", &mut self.units_rule_enabled),
+            ("returns", &mut self.returns_rule_enabled),
+            ("visibility", &mut self.visibility_rule_enabled),
+            ("remappings", &mut self.remappings_rule_enabled),
+            ("solc", &mut self.solc_rule_enabled),
+            ("contact", &mut self.contact_rule_enabled),
+        ] {
+            *enabled = section(name).flag("enabled")?;
+        }
+        let contact_exempt = section("contact").strings("exempt")?.unwrap_or_default();
+        self.contact_exempt.extend(contact_exempt.into_iter().map(str::to_string));
+
+        // Rules with settings that only apply when they're enabled. Settings are validated either
+        // way, so a typo doesn't go unnoticed until the rule is enabled.
+        let floating = section("floating");
+        let dirs = floating.list("dirs", "\"src\", \"test\", or \"script\"", |dir| {
+            dir.as_str().and_then(PinnedDir::parse)
+        })?;
+        self.pinned_pragma_dirs =
+            floating.flag("enabled")?.then(|| or_defaults(dirs, &floating_pragma::DEFAULT_DIRS));
+
+        let mirror = section("mirror");
+        let patterns = mirror.strings("patterns")?;
+        self.mirror_patterns = mirror.flag("enabled")?.then(|| {
+            or_defaults(patterns, &[test_mirror::DEFAULT_PATTERN])
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        });
+
+        let immutable = section("immutable");
+        let patterns =
+            or_defaults(immutable.strings("patterns")?, &chain_constants::DEFAULT_PATTERNS);
+        let matchers = glob_matchers(&patterns)?;
+        self.chain_value_patterns = immutable.flag("enabled")?.then_some(matchers);
+
+        let filename = section("filename");
+        let matchers = glob_matchers(&filename.strings("helpers")?.unwrap_or_default())?;
+        self.filename_helpers = filename.flag("enabled")?.then_some(matchers);
+
+        let one_contract = section("onecontract");
+        let allow_companions = one_contract.flag("allow_companions")?;
+        self.one_contract_companions = one_contract.flag("enabled")?.then_some(allow_companions);
+
+        let natspec = section("natspec");
+        let tags = natspec.list("tags", "\"notice\", \"param\", or \"return\"", |tag| {
+            tag.as_str().and_then(NatspecTag::parse)
+        })?;
+        self.natspec_tags =
+            natspec.flag("enabled")?.then(|| or_defaults(tags, &natspec::DEFAULT_TAGS));
+
+        // Lists that are set replace the defaults, so e.g. `required = []` requires nothing.
+        let foundry = section("foundry");
+        let mut conventions = FoundryConventions::default();
+        for (key, list) in [
+            ("required", &mut conventions.required),
+            ("forbidden", &mut conventions.forbidden),
+            ("exempt_profiles", &mut conventions.exempt_profiles),
+            ("consistent", &mut conventions.consistent),
+        ] {
+            if let Some(configured) = foundry.strings(key)? {
+                *list = configured.into_iter().map(str::to_string).collect();
+            }
+        }
+        self.foundry_conventions = foundry.flag("enabled")?.then_some(conventions);
+
+        // Rules with a single setting, which enables them.
+        let suffix = section("scriptfile").get("suffix", "a string", toml::Value::as_str)?;
+        self.script_contract_suffix = suffix.map(str::to_string);
+        self.max_nesting_depth =
+            section("nesting").get("max_depth", "a positive integer", |max_depth| {
+                max_depth
+                    .as_integer()
+                    .and_then(|max| usize::try_from(max).ok())
+                    .filter(|max| *max > 0)
+            })?;
+        if let Some(style) = section("uint").get("style", "\"explicit\" or \"short\"", |style| {
+            style.as_str().and_then(IntStyle::parse)
+        })? {
+            self.int_style = style;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `names` to `[scripts.env] required` in the `.scopelint` file, creating the file in
+    /// the current directory if none is found, and sets them in this config. Other settings and
+    /// comments are preserved.
+    ///
+    /// Returns the path of the file that was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the existing file is not valid TOML or the file cannot be written.
+    pub fn write_required_env_vars(&mut self, names: Vec<String>) -> Result<PathBuf, String> {
+        let config_path = Self::path().unwrap_or_else(|| PathBuf::from(".scopelint"));
+        let content = if config_path.exists() {
+            std::fs::read_to_string(&config_path)
+                .map_err(|e| format!("Failed to read {}: {e}", config_path.display()))?
+        } else {
+            String::new()
+        };
+
+        let content = set_required_env_vars(&content, &names)?;
+        std::fs::write(&config_path, content)
+            .map_err(|e| format!("Failed to write {}: {e}", config_path.display()))?;
+        self.required_env_vars = Some(names);
+        Ok(config_path)
+    }
+
+    /// Returns the env vars scripts are allowed to read, or `None` if the `env` rule is not
+    /// configured.
+    #[must_use]
+    pub fn required_env_vars(&self) -> Option<&[String]> {
+        self.required_env_vars.as_deref()
+    }
+
+    /// Returns the lowest compiler version pragmas may admit, or `None` if the `pragma` rule is
+    /// not configured.
+    #[must_use]
+    pub const fn min_solc(&self) -> Option<SolcVersion> {
+        self.min_solc
+    }
+
+    /// Returns the directories whose files must pin the compiler version for the `floating` rule,
+    /// or `None` if it's disabled.
+    #[must_use]
+    pub fn pinned_pragma_dirs(&self) -> Option<&[PinnedDir]> {
+        self.pinned_pragma_dirs.as_deref()
+    }
+
+    /// Returns the selectors functions must not use, with each entry as written in `.scopelint`.
+    #[must_use]
+    pub fn reserved_selectors(&self) -> &[(String, Selector)] {
+        &self.reserved_selectors
+    }
+
+    /// Returns whether the `event` rule is enabled.
+    #[must_use]
+    pub const fn event_rule_enabled(&self) -> bool {
+        self.event_rule_enabled
+    }
+
+    /// Returns whether the `else` rule is enabled.
+    #[must_use]
+    pub const fn else_rule_enabled(&self) -> bool {
+        self.else_rule_enabled
+    }
+
+    /// Returns whether the `bool` rule is enabled.
+    #[must_use]
+    pub const fn bool_rule_enabled(&self) -> bool {
+        self.bool_rule_enabled
+    }
+
+    /// Returns whether the `units` rule is enabled.
+    #[must_use]
+    pub const fn units_rule_enabled(&self) -> bool {
+        self.units_rule_enabled
+    }
+
+    /// Returns whether the `returns` rule is enabled.
+    #[must_use]
+    pub const fn returns_rule_enabled(&self) -> bool {
+        self.returns_rule_enabled
+    }
+
+    /// Returns whether the `visibility` rule is enabled.
+    #[must_use]
+    pub const fn visibility_rule_enabled(&self) -> bool {
+        self.visibility_rule_enabled
+    }
+
+    /// Returns whether the `remappings` rule is enabled.
+    #[must_use]
+    pub const fn remappings_rule_enabled(&self) -> bool {
+        self.remappings_rule_enabled
+    }
+
+    /// Returns whether the `solc` rule is enabled.
+    #[must_use]
+    pub const fn solc_rule_enabled(&self) -> bool {
+        self.solc_rule_enabled
+    }
+
+    /// Returns whether the `contact` rule is enabled.
+    #[must_use]
+    pub const fn contact_rule_enabled(&self) -> bool {
+        self.contact_rule_enabled
+    }
+
+    /// Returns the test file patterns for the `mirror` rule, or `None` if it's disabled.
+    #[must_use]
+    pub fn mirror_patterns(&self) -> Option<&[String]> {
+        self.mirror_patterns.as_deref()
+    }
+
+    /// Returns the name patterns of constants holding chain-dependent values for the `immutable`
+    /// rule, or `None` if it's disabled.
+    #[must_use]
+    pub fn chain_value_patterns(&self) -> Option<&[GlobMatcher]> {
+        self.chain_value_patterns.as_deref()
+    }
+
+    /// Returns the name patterns of definitions allowed next to the main contract of a src file
+    /// for the `filename` rule, or `None` if it's disabled.
+    #[must_use]
+    pub fn filename_helpers(&self) -> Option<&[GlobMatcher]> {
+        self.filename_helpers.as_deref()
+    }
+
+    /// Returns whether libraries and interfaces may be declared next to the contract of a src file
+    /// for the `onecontract` rule, or `None` if it's disabled.
+    #[must_use]
+    pub const fn one_contract_companions(&self) -> Option<bool> {
+        self.one_contract_companions
+    }
+
+    /// Returns the `NatSpec` tags public and external functions must have for the `natspec` rule,
+    /// or `None` if it's disabled.
+    #[must_use]
+    pub fn natspec_tags(&self) -> Option<&[NatspecTag]> {
+        self.natspec_tags.as_deref()
+    }
+
+    /// Returns the conventions `foundry.toml` must follow for the `foundry` rule, or `None` if
+    /// it's disabled.
+    #[must_use]
+    pub const fn foundry_conventions(&self) -> Option<&FoundryConventions> {
+        self.foundry_conventions.as_ref()
+    }
+
+    /// Returns the suffix that script contract names must end with, for the `scriptfile` rule.
+    #[must_use]
+    pub fn script_contract_suffix(&self) -> &str {
+        self.script_contract_suffix.as_deref().unwrap_or(script_file::DEFAULT_SUFFIX)
+    }
+
+    /// Returns the deepest nesting allowed in function bodies, for the `nesting` rule.
+    #[must_use]
+    pub fn max_nesting_depth(&self) -> usize {
+        self.max_nesting_depth.unwrap_or(nesting_depth::DEFAULT_MAX_DEPTH)
+    }
+
+    /// Returns how 256-bit integer types are written, for the `uint` rule.
+    #[must_use]
+    pub const fn int_style(&self) -> IntStyle {
+        self.int_style
+    }
+
+    /// Returns the URL template for finding hyperlinks, if configured.
+    #[must_use]
+    pub fn link_template(&self) -> Option<String> {
+        self.link_template.clone()
+    }
+
+    /// Returns the number of findings below error severity that don't fail the check, if
+    /// configured.
+    #[must_use]
+    pub const fn max_warnings(&self) -> Option<usize> {
+        self.max_warnings
+    }
+
+    /// Returns the license that `--fix` writes to missing SPDX headers.
+    #[must_use]
+    pub fn spdx_license(&self) -> &str {
+        self.spdx_license.as_deref().unwrap_or("UNLICENSED")
+    }
+
+    /// Returns whether the contract is exempt from the `contact` rule.
+    #[must_use]
+    pub fn is_contact_exempt(&self, contract_name: &str) -> bool {
+        self.contact_exempt.iter().any(|name| name == contract_name)
+    }
+
+    /// Returns the chain whose configured directory contains the file, if any. When directories
+    /// are nested, the most specific one wins.
+    #[must_use]
+    pub fn chain_for_file(&self, file_path: &Path) -> Option<&str> {
+        let normalized = self.normalize_path(file_path);
+
+        self.chain_dirs
+            .iter()
+            .filter(|(_, dir)| {
+                normalized.strip_prefix(dir.as_str()).is_some_and(|rest| rest.starts_with('/'))
+            })
+            .max_by_key(|(_, dir)| dir.len())
+            .map(|(chain, _)| chain.as_str())
+    }
+
+    /// Returns `true` if any per-chain directories are configured.
+    #[must_use]
+    pub const fn has_chain_dirs(&self) -> bool {
+        !self.chain_dirs.is_empty()
+    }
+
     /// Check if a file should be ignored entirely
     #[must_use]
     pub fn is_file_ignored(&self, file_path: &Path) -> bool {
@@ -142,6 +901,14 @@ impl FileConfig {
         self.ignored_file_patterns.iter().any(|matcher| matcher.is_match(&normalized))
     }
 
+    /// Check if a file is excluded from formatting by `[fmt] ignore`
+    #[must_use]
+    pub fn is_fmt_ignored(&self, file_path: &Path) -> bool {
+        let normalized = self.normalize_path(file_path);
+
+        self.fmt_ignored_file_patterns.iter().any(|matcher| matcher.is_match(&normalized))
+    }
+
     /// Get list of rules to ignore for a specific file
     #[must_use]
     pub fn get_ignored_rules(&self, file_path: &Path) -> Vec<ValidatorKind> {
@@ -156,6 +923,190 @@ impl FileConfig {
         ignored_rules
     }
 
+    /// Returns the effective settings as JSON, in the layout of `.scopelint`: every setting that
+    /// is configured, and the defaults of those that aren't. Opt-in rules that are disabled only
+    /// have `enabled = false`.
+    #[must_use]
+    #[allow(clippy::too_many_lines)] // One entry per rule.
+    pub fn to_json(&self) -> serde_json::Value {
+        let globs = |matchers: &[GlobMatcher]| -> Vec<String> {
+            matchers.iter().map(|matcher| matcher.glob().glob().to_string()).collect()
+        };
+        let overrides: serde_json::Map<String, serde_json::Value> = self
+            .rule_overrides
+            .iter()
+            .map(|(matcher, rules)| {
+                let rules: Vec<&str> = rules.iter().map(ValidatorKind::rule_name).collect();
+                (matcher.glob().glob().to_string(), json!(rules))
+            })
+            .collect();
+        let chain_dirs: serde_json::Map<String, serde_json::Value> =
+            self.chain_dirs.iter().map(|(chain, dir)| (chain.clone(), json!(dir))).collect();
+        let enabled = |enabled: bool| json!({ "enabled": enabled });
+        let tag_names =
+            |tags: &[NatspecTag]| -> Vec<&str> { tags.iter().map(|tag| tag.name()).collect() };
+        let dir_names =
+            |dirs: &[PinnedDir]| -> Vec<&str> { dirs.iter().map(|dir| dir.name()).collect() };
+
+        let mut rules = json!({
+            "event": enabled(self.event_rule_enabled),
+            "else": enabled(self.else_rule_enabled),
+            "bool": enabled(self.bool_rule_enabled),
+            "units": enabled(self.units_rule_enabled),
+            "returns": enabled(self.returns_rule_enabled),
+            "visibility": enabled(self.visibility_rule_enabled),
+            "remappings": enabled(self.remappings_rule_enabled),
+            "solc": enabled(self.solc_rule_enabled),
+            "contact": { "enabled": self.contact_rule_enabled, "exempt": self.contact_exempt },
+            "mirror": { "enabled": false, "patterns": [test_mirror::DEFAULT_PATTERN] },
+            "immutable": { "enabled": false, "patterns": chain_constants::DEFAULT_PATTERNS },
+            "filename": { "enabled": false, "helpers": [] },
+            "floating": { "enabled": false, "dirs": dir_names(&floating_pragma::DEFAULT_DIRS) },
+            "onecontract": {
+                "enabled": self.one_contract_companions.is_some(),
+                "allow_companions": self.one_contract_companions.unwrap_or(false),
+            },
+            "natspec": { "enabled": false, "tags": tag_names(&natspec::DEFAULT_TAGS) },
+            "foundry": {
+                "enabled": false,
+                "required": foundry_toml::DEFAULT_REQUIRED,
+                "forbidden": foundry_toml::DEFAULT_FORBIDDEN,
+                "exempt_profiles": [],
+                "consistent": foundry_toml::DEFAULT_CONSISTENT,
+            },
+            "scriptfile": { "suffix": self.script_contract_suffix() },
+            "nesting": { "max_depth": self.max_nesting_depth() },
+            "uint": { "style": self.int_style.name() },
+        });
+        if let Some(patterns) = &self.mirror_patterns {
+            rules["mirror"] = json!({ "enabled": true, "patterns": patterns });
+        }
+        if let Some(patterns) = &self.chain_value_patterns {
+            rules["immutable"] = json!({ "enabled": true, "patterns": globs(patterns) });
+        }
+        if let Some(helpers) = &self.filename_helpers {
+            rules["filename"] = json!({ "enabled": true, "helpers": globs(helpers) });
+        }
+        if let Some(tags) = &self.natspec_tags {
+            rules["natspec"] = json!({ "enabled": true, "tags": tag_names(tags) });
+        }
+        if let Some(dirs) = &self.pinned_pragma_dirs {
+            rules["floating"] = json!({ "enabled": true, "dirs": dir_names(dirs) });
+        }
+        if let Some(conventions) = &self.foundry_conventions {
+            rules["foundry"] = json!({
+                "enabled": true,
+                "required": conventions.required,
+                "forbidden": conventions.forbidden,
+                "exempt_profiles": conventions.exempt_profiles,
+                "consistent": conventions.consistent,
+            });
+        }
+        if let Some((major, minor, patch)) = self.min_solc {
+            rules["pragma"] = json!({ "min_solc": format!("{major}.{minor}.{patch}") });
+        }
+        if !chain_dirs.is_empty() {
+            rules["chain"] = json!({ "dirs": chain_dirs });
+        }
+        if !self.reserved_selectors.is_empty() {
+            let reserved: Vec<&str> =
+                self.reserved_selectors.iter().map(|(entry, _)| entry.as_str()).collect();
+            rules["selector"] = json!({ "reserved": reserved });
+        }
+
+        let mut config = json!({
+            "ignore": {
+                "files": globs(&self.ignored_file_patterns),
+                "overrides": overrides,
+            },
+            "fmt": { "ignore": globs(&self.fmt_ignored_file_patterns) },
+            "rules": rules,
+            "fix": { "spdx_license": self.spdx_license() },
+        });
+        if let Some(required) = &self.required_env_vars {
+            config["scripts"] = json!({ "env": { "required": required } });
+        }
+        if let Some(max_warnings) = self.max_warnings {
+            config["check"] = json!({ "max_warnings": max_warnings });
+        }
+        if let Some(link_template) = &self.link_template {
+            config["output"] = json!({ "link_template": link_template });
+        }
+        config
+    }
+
+    /// Returns a warning for each renamed or removed rule name in `[ignore.overrides]`. Such names
+    /// keep working until `scopelint migrate-config` rewrites them.
+    #[must_use]
+    pub fn deprecated_rule_warnings(&self) -> &[String] {
+        &self.deprecated_rule_warnings
+    }
+
+    /// Returns a warning for each rule in `[ignore.overrides]` that never runs on any of the
+    /// `files` its pattern matches, e.g. the `test` rule on src files. Such entries are almost
+    /// always mistakes that silently do nothing. Patterns that match none of the `files` aren't
+    /// flagged, since they may be meant for files that don't exist yet.
+    #[must_use]
+    pub fn ineffective_overrides(&self, files: &[PathBuf], paths: &CheckPaths) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for (matcher, rules) in &self.rule_overrides {
+            let matched: Vec<&PathBuf> =
+                files.iter().filter(|file| matcher.is_match(self.normalize_path(file))).collect();
+            if matched.is_empty() {
+                continue;
+            }
+            for rule in rules {
+                let Some(kinds) = rule.file_kinds() else { continue };
+                let runs = matched
+                    .iter()
+                    .any(|file| kinds.iter().any(|kind| file.is_file_kind(*kind, paths)));
+                if !runs {
+                    warnings.push(format!(
+                        "[ignore.overrides] \"{}\" ignores `{}`, which never runs on the {} file(s) it matches",
+                        matcher.glob(),
+                        rule.rule_name(),
+                        matched.len()
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Returns the settings of the rule `kind` as dotted keys with their default, e.g.
+    /// `rules.nesting.max_depth` and `4`. Settings without a default, such as the directories of
+    /// `rules.chain.dirs.*`, default to `null`.
+    #[must_use]
+    pub fn rule_settings(kind: &ValidatorKind) -> Vec<(&'static str, serde_json::Value)> {
+        let section = match kind {
+            ValidatorKind::Env => "scripts.env.".to_string(),
+            kind => format!("rules.{}.", kind.rule_name()),
+        };
+        let defaults = Self::default().to_json();
+        KNOWN_KEYS
+            .iter()
+            .filter(|key| key.starts_with(&section))
+            .map(|key| {
+                let default = key.split('.').try_fold(&defaults, |value, part| value.get(part));
+                (*key, default.cloned().unwrap_or_default())
+            })
+            .collect()
+    }
+
+    /// Returns the dotted path of each key in `content` that scopelint doesn't read, e.g. a
+    /// misspelled `[rules.contact] enabeld`, which would otherwise be silently ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` is not valid TOML.
+    pub fn unknown_keys(content: &str) -> Result<Vec<String>, String> {
+        let toml: toml::Table =
+            toml::from_str(content).map_err(|e| format!("Invalid TOML: {e}"))?;
+        let mut unknown = Vec::new();
+        collect_unknown_keys(&toml, "", &mut unknown);
+        Ok(unknown)
+    }
+
     /// Normalize file path for glob matching:
     /// - Convert to relative path from config directory (project root)
     /// - Normalize path separators to forward slashes
@@ -185,21 +1136,148 @@ impl FileConfig {
     }
 }
 
-/// Maps a rule name (e.g., "error") to a `ValidatorKind`
-fn parse_rule_name(rule: &str) -> Option<ValidatorKind> {
-    match rule {
-        "error" => Some(ValidatorKind::Error),
-        "import" => Some(ValidatorKind::Import),
-        "variable" => Some(ValidatorKind::Variable),
-        "constant" => Some(ValidatorKind::Constant),
-        "test" => Some(ValidatorKind::Test),
-        "script" => Some(ValidatorKind::Script),
-        "src" => Some(ValidatorKind::Src),
-        "eip712" => Some(ValidatorKind::Eip712),
-        _ => None,
+/// Converts the gitignore-style patterns of an ignore file, one per line, to globs relative to the
+/// project root. Blank lines and `#` comments are skipped. A pattern with a `/` other than a
+/// trailing one is anchored to the project root, and otherwise matches at any depth. A pattern
+/// matching a directory also matches everything in it.
+fn ignore_file_globs(content: &str) -> Result<Vec<String>, String> {
+    let mut globs = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('!') {
+            return Err(format!("Negated pattern '{line}' is not supported"));
+        }
+        let (pattern, is_dir) =
+            line.strip_suffix('/').map_or((line, false), |pattern| (pattern, true));
+        let pattern = match pattern.strip_prefix('/').or_else(|| pattern.strip_prefix("./")) {
+            Some(anchored) => anchored.to_string(),
+            None if pattern.contains('/') => pattern.to_string(),
+            None => format!("**/{pattern}"),
+        };
+        if !is_dir {
+            globs.push(pattern.clone());
+        }
+        globs.push(format!("{pattern}/**"));
+    }
+    Ok(globs)
+}
+
+/// Returns `content` with `[scripts.env] required` set to `names`, one per line.
+fn set_required_env_vars(content: &str, names: &[String]) -> Result<String, String> {
+    let mut doc: toml_edit::DocumentMut =
+        content.parse().map_err(|e| format!("Invalid TOML: {e}"))?;
+
+    let mut required = toml_edit::Array::new();
+    for name in names {
+        required.push_formatted(toml_edit::Value::from(name.as_str()).decorated("\n    ", ""));
+    }
+    required.set_trailing("\n");
+    required.set_trailing_comma(!names.is_empty());
+
+    let scripts = doc
+        .entry("scripts")
+        .or_insert_with(toml_edit::table)
+        .as_table_mut()
+        .ok_or_else(|| "`scripts` in .scopelint must be a table".to_string())?;
+    scripts.set_implicit(true);
+    let env = scripts
+        .entry("env")
+        .or_insert_with(toml_edit::table)
+        .as_table_mut()
+        .ok_or_else(|| "`scripts.env` in .scopelint must be a table".to_string())?;
+    env["required"] = toml_edit::value(required);
+
+    Ok(doc.to_string())
+}
+
+// Appends the keys of `table`, prefixed by `prefix`, that aren't in `KNOWN_KEYS` to `unknown`.
+fn collect_unknown_keys(table: &toml::Table, prefix: &str, unknown: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+        let nested = format!("{path}.");
+        if KNOWN_KEYS.iter().any(|known| *known == path || known.strip_suffix('*') == Some(&nested))
+        {
+            continue;
+        }
+        match value.as_table() {
+            Some(table) if KNOWN_KEYS.iter().any(|known| known.starts_with(&nested)) => {
+                collect_unknown_keys(table, &path, unknown);
+            }
+            _ => unknown.push(path),
+        }
     }
 }
 
+/// A `[rules.<name>]` section of `.scopelint`, whose settings are read with the same errors for
+/// every rule.
+struct RuleSection<'a> {
+    /// The name of the rule, e.g. `natspec`.
+    name: &'a str,
+    /// The section, or `None` if it isn't in the file.
+    table: Option<&'a toml::Value>,
+}
+
+impl<'a> RuleSection<'a> {
+    /// Reads `key` with `parse`, or returns `None` if it isn't set. The error says that it must be
+    /// `expected`, e.g. "a string".
+    fn get<T>(
+        &self,
+        key: &str,
+        expected: &str,
+        parse: impl FnOnce(&'a toml::Value) -> Option<T>,
+    ) -> Result<Option<T>, String> {
+        self.table
+            .and_then(|table| table.get(key))
+            .map(|value| {
+                parse(value)
+                    .ok_or_else(|| format!("[rules.{}] {key} must be {expected}", self.name))
+            })
+            .transpose()
+    }
+
+    /// Reads the boolean `key`, which defaults to `false`.
+    fn flag(&self, key: &str) -> Result<bool, String> {
+        Ok(self.get(key, "a boolean", toml::Value::as_bool)?.unwrap_or_default())
+    }
+
+    /// Reads the array `key`, parsing each item with `parse`, or returns `None` if it isn't set.
+    /// The error says that the items must be `expected`, e.g. "strings".
+    fn list<T>(
+        &self,
+        key: &str,
+        expected: &str,
+        parse: impl Fn(&'a toml::Value) -> Option<T>,
+    ) -> Result<Option<Vec<T>>, String> {
+        self.get(key, &format!("an array of {expected}"), |value| {
+            value.as_array()?.iter().map(parse).collect()
+        })
+    }
+
+    /// Reads the array of strings `key`, or returns `None` if it isn't set.
+    fn strings(&self, key: &str) -> Result<Option<Vec<&'a str>>, String> {
+        self.list(key, "strings", toml::Value::as_str)
+    }
+}
+
+/// Returns the `configured` list, or `defaults` if it isn't set or is empty.
+fn or_defaults<T: Clone>(configured: Option<Vec<T>>, defaults: &[T]) -> Vec<T> {
+    configured.filter(|configured| !configured.is_empty()).unwrap_or_else(|| defaults.to_vec())
+}
+
+/// Compiles the glob `patterns` of a rule setting.
+fn glob_matchers(patterns: &[&str]) -> Result<Vec<GlobMatcher>, String> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Glob::new(pattern)
+                .map(|glob| glob.compile_matcher())
+                .map_err(|e| format!("Invalid glob pattern '{pattern}': {e}"))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,6 +1295,35 @@ files = ["src/legacy.sol", "test/integration/*.sol"]
         assert!(!config.is_file_ignored(Path::new("src/normal.sol")));
     }
 
+    #[test]
+    fn test_ignore_file_globs() {
+        let ignore =
+            "# Generated bindings\nGenerated.sol\nsrc/legacy/\n/test/mocks/*.sol\n\n./out/\n";
+        assert_eq!(
+            ignore_file_globs(ignore).unwrap(),
+            [
+                "**/Generated.sol",
+                "**/Generated.sol/**",
+                "src/legacy/**",
+                "test/mocks/*.sol",
+                "test/mocks/*.sol/**",
+                "out/**",
+            ]
+        );
+        assert!(ignore_file_globs("src/\n!src/Keep.sol").is_err());
+    }
+
+    #[test]
+    fn test_parse_fmt_ignore() {
+        let config = FileConfig::from_toml("[fmt]\nignore = [\"src/Constants.sol\"]").unwrap();
+
+        assert!(config.is_fmt_ignored(Path::new("./src/Constants.sol")));
+        assert!(!config.is_fmt_ignored(Path::new("src/Token.sol")));
+        // Files excluded from formatting are still checked.
+        assert!(!config.is_file_ignored(Path::new("src/Constants.sol")));
+        assert!(FileConfig::from_toml("[fmt]\nignore = \"src/Constants.sol\"").is_err());
+    }
+
     #[test]
     fn test_parse_rule_overrides() {
         let toml = r#"
@@ -237,10 +1344,297 @@ files = ["src/legacy.sol", "test/integration/*.sol"]
         assert!(ignored.contains(&ValidatorKind::Error));
     }
 
+    #[test]
+    fn test_ineffective_overrides() {
+        let toml = r#"
+[ignore.overrides]
+"src/**/*.sol" = ["test", "variable", "import"]
+"test/**/*.sol" = ["test", "rpc"]
+"script/**/*.sol" = ["src"]
+"#;
+        let mut config = FileConfig::from_toml(toml).unwrap();
+        config.config_dir = Some(PathBuf::from("."));
+        let paths = CheckPaths {
+            src_paths: vec!["./src".to_string()],
+            script_paths: vec!["./script".to_string()],
+            test_paths: vec!["./test".to_string()],
+            ..CheckPaths::default()
+        };
+        let files = [PathBuf::from("./src/Token.sol"), PathBuf::from("./test/utils/Base.sol")];
+
+        // Patterns that match no files, like `script/**/*.sol` here, aren't flagged.
+        assert_eq!(
+            config.ineffective_overrides(&files, &paths),
+            [
+                "[ignore.overrides] \"src/**/*.sol\" ignores `test`, which never runs on the 1 file(s) it matches",
+                "[ignore.overrides] \"test/**/*.sol\" ignores `test`, which never runs on the 1 file(s) it matches",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_chain_dirs() {
+        let toml = r#"
+[rules.chain.dirs]
+mainnet = "./script/deploy/mainnet/"
+mainnet-legacy = "script/deploy/mainnet/legacy"
+optimism = "script/deploy/optimism"
+"#;
+        let mut config = FileConfig::from_toml(toml).unwrap();
+        config.config_dir = Some(PathBuf::from("."));
+
+        assert!(config.has_chain_dirs());
+        assert_eq!(
+            config.chain_for_file(Path::new("./script/deploy/mainnet/Deploy.s.sol")),
+            Some("mainnet")
+        );
+        assert_eq!(
+            config.chain_for_file(Path::new("./script/deploy/mainnet/legacy/Deploy.s.sol")),
+            Some("mainnet-legacy")
+        );
+        assert_eq!(
+            config.chain_for_file(Path::new("script/deploy/optimism/A.sol")),
+            Some("optimism")
+        );
+        assert_eq!(config.chain_for_file(Path::new("./script/deploy/mainnetish/A.sol")), None);
+        assert_eq!(config.chain_for_file(Path::new("./src/Counter.sol")), None);
+    }
+
+    #[test]
+    fn test_parse_required_env_vars() {
+        let config = FileConfig::from_toml("").unwrap();
+        assert_eq!(config.required_env_vars(), None);
+
+        let config = FileConfig::from_toml("[scripts.env]").unwrap();
+        assert_eq!(config.required_env_vars(), Some(&[][..]));
+
+        let config = FileConfig::from_toml(
+            r#"
+[scripts.env]
+required = ["DEPLOYER", "RPC_URL"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.required_env_vars(),
+            Some(&["DEPLOYER".to_string(), "RPC_URL".to_string()][..])
+        );
+
+        assert!(FileConfig::from_toml("[scripts.env]\nrequired = \"DEPLOYER\"").is_err());
+    }
+
+    #[test]
+    fn test_set_required_env_vars() {
+        let content = r#"# Project config
+[ignore]
+files = ["src/legacy.sol"]
+"#;
+        let names = vec!["DEPLOYER".to_string(), "RPC_URL".to_string()];
+        let updated = set_required_env_vars(content, &names).unwrap();
+        assert_eq!(
+            updated,
+            r#"# Project config
+[ignore]
+files = ["src/legacy.sol"]
+
+[scripts.env]
+required = [
+    "DEPLOYER",
+    "RPC_URL",
+]
+"#
+        );
+
+        // Writing again replaces the list rather than appending to it.
+        let updated = set_required_env_vars(&updated, &names[..1]).unwrap();
+        let config = FileConfig::from_toml(&updated).unwrap();
+        assert_eq!(config.required_env_vars(), Some(&names[..1]));
+    }
+
+    #[test]
+    fn test_parse_reserved_selectors() {
+        assert!(FileConfig::from_toml("").unwrap().reserved_selectors().is_empty());
+
+        let config = FileConfig::from_toml(
+            "[rules.selector]\nreserved = [\"upgradeTo(address)\", \"0xf851a440\"]",
+        )
+        .unwrap();
+        assert_eq!(
+            config.reserved_selectors(),
+            [
+                ("upgradeTo(address)".to_string(), 0x3659_cfe6),
+                ("0xf851a440".to_string(), 0xf851_a440)
+            ]
+        );
+
+        assert!(FileConfig::from_toml("[rules.selector]\nreserved = [\"upgradeTo\"]").is_err());
+        assert!(FileConfig::from_toml("[rules.selector]\nreserved = \"admin()\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_min_solc() {
+        assert_eq!(FileConfig::from_toml("").unwrap().min_solc(), None);
+
+        let config = FileConfig::from_toml("[rules.pragma]\nmin_solc = \"0.8.20\"").unwrap();
+        assert_eq!(config.min_solc(), Some((0, 8, 20)));
+
+        let config = FileConfig::from_toml("[rules.pragma]\nmin_solc = \"0.8\"").unwrap();
+        assert_eq!(config.min_solc(), Some((0, 8, 0)));
+
+        assert!(FileConfig::from_toml("[rules.pragma]\nmin_solc = \"^0.8.20\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_max_warnings() {
+        assert_eq!(FileConfig::from_toml("").unwrap().max_warnings(), None);
+
+        let config = FileConfig::from_toml("[check]\nmax_warnings = 25").unwrap();
+        assert_eq!(config.max_warnings(), Some(25));
+
+        assert!(FileConfig::from_toml("[check]\nmax_warnings = -1").is_err());
+        assert!(FileConfig::from_toml("[check]\nmax_warnings = \"25\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_rule_sections() {
+        // Opt-in rules are off unless enabled.
+        let config = FileConfig::from_toml("[rules.filename]\nhelpers = [\"Mock*\"]").unwrap();
+        assert!(!config.bool_rule_enabled());
+        assert_eq!(config.pinned_pragma_dirs(), None);
+        assert!(config.filename_helpers().is_none());
+
+        // Lists that aren't set or are empty fall back to the rule's defaults.
+        let config = FileConfig::from_toml(
+            "[rules.bool]\nenabled = true\n[rules.floating]\nenabled = true\ndirs = []\n\
+             [rules.natspec]\nenabled = true",
+        )
+        .unwrap();
+        assert!(config.bool_rule_enabled());
+        assert_eq!(config.pinned_pragma_dirs(), Some(&floating_pragma::DEFAULT_DIRS[..]));
+        assert_eq!(config.natspec_tags(), Some(&natspec::DEFAULT_TAGS[..]));
+
+        let config = FileConfig::from_toml(
+            "[rules.floating]\nenabled = true\ndirs = [\"src\", \"script\"]\n\
+             [rules.filename]\nenabled = true\nhelpers = [\"Mock*\"]",
+        )
+        .unwrap();
+        assert_eq!(config.pinned_pragma_dirs(), Some(&[PinnedDir::Src, PinnedDir::Script][..]));
+        assert!(config.filename_helpers().unwrap()[0].is_match("MockToken"));
+        assert_eq!(config.to_json()["rules"]["floating"]["dirs"], json!(["src", "script"]));
+        assert_eq!(config.to_json()["rules"]["filename"]["helpers"], json!(["Mock*"]));
+
+        // Settings are validated even when the rule is disabled, with the same errors for every
+        // rule.
+        for (toml, expected) in [
+            ("[rules.bool]\nenabled = 1", "[rules.bool] enabled must be a boolean"),
+            ("[rules.contact]\nexempt = [1]", "[rules.contact] exempt must be an array of strings"),
+            (
+                "[rules.filename]\nhelpers = \"Mock*\"",
+                "[rules.filename] helpers must be an array of strings",
+            ),
+            ("[rules.filename]\nhelpers = [\"[\"]", "Invalid glob pattern '['"),
+            (
+                "[rules.floating]\ndirs = [\"lib\"]",
+                "[rules.floating] dirs must be an array of \"src\", \"test\", or \"script\"",
+            ),
+            (
+                "[rules.natspec]\ntags = [\"dev\"]",
+                "[rules.natspec] tags must be an array of \"notice\", \"param\", or \"return\"",
+            ),
+            (
+                "[rules.nesting]\nmax_depth = 0",
+                "[rules.nesting] max_depth must be a positive integer",
+            ),
+        ] {
+            let err = FileConfig::from_toml(toml).unwrap_err();
+            assert!(err.starts_with(expected), "{toml}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_parse_one_contract_companions() {
+        assert_eq!(FileConfig::from_toml("").unwrap().one_contract_companions(), None);
+        // Companions are only allowed if the rule is enabled.
+        let config = FileConfig::from_toml("[rules.onecontract]\nallow_companions = true").unwrap();
+        assert_eq!(config.one_contract_companions(), None);
+
+        let config = FileConfig::from_toml("[rules.onecontract]\nenabled = true").unwrap();
+        assert_eq!(config.one_contract_companions(), Some(false));
+        let config =
+            FileConfig::from_toml("[rules.onecontract]\nenabled = true\nallow_companions = true")
+                .unwrap();
+        assert_eq!(config.one_contract_companions(), Some(true));
+        assert_eq!(config.to_json()["rules"]["onecontract"]["allow_companions"], json!(true));
+
+        assert!(FileConfig::from_toml("[rules.onecontract]\nallow_companions = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_foundry_conventions() {
+        assert_eq!(FileConfig::from_toml("").unwrap().foundry_conventions(), None);
+
+        let config = FileConfig::from_toml("[rules.foundry]\nenabled = true").unwrap();
+        assert_eq!(config.foundry_conventions(), Some(&FoundryConventions::default()));
+
+        let config = FileConfig::from_toml(
+            "[rules.foundry]\nenabled = true\nrequired = []\nexempt_profiles = [\"ffi\"]",
+        )
+        .unwrap();
+        let conventions = config.foundry_conventions().unwrap();
+        assert!(conventions.required.is_empty());
+        assert_eq!(conventions.exempt_profiles, ["ffi"]);
+        assert_eq!(conventions.forbidden, foundry_toml::DEFAULT_FORBIDDEN);
+        assert_eq!(config.to_json()["rules"]["foundry"]["exempt_profiles"], json!(["ffi"]));
+
+        assert!(FileConfig::from_toml("[rules.foundry]\nforbidden = \"ffi\"").is_err());
+        assert!(FileConfig::from_toml("[rules.foundry]\nrequired = [1]").is_err());
+    }
+
     #[test]
     fn test_parse_empty_config() {
         let config = FileConfig::from_toml("").unwrap();
         assert!(!config.is_file_ignored(Path::new("src/test.sol")));
         assert!(config.get_ignored_rules(Path::new("src/test.sol")).is_empty());
     }
+
+    #[test]
+    fn test_unknown_keys() {
+        let toml = r#"
+[ignore]
+files = ["src/legacy/*.sol"]
+[ignore.overrides]
+"src/**/*.sol" = ["src"]
+[rules.chain.dirs]
+mainnet = "script/deploy/mainnet"
+[rules.contact]
+enabeld = true
+[rules.unknown]
+enabled = true
+[output]
+link = "https://example.com"
+"#;
+        assert_eq!(
+            FileConfig::unknown_keys(toml).unwrap(),
+            vec!["output.link", "rules.contact.enabeld", "rules.unknown"]
+        );
+        assert!(FileConfig::unknown_keys("").unwrap().is_empty());
+        assert!(FileConfig::unknown_keys("[ignore").is_err());
+    }
+
+    #[test]
+    fn test_rule_settings() {
+        use serde_json::json;
+        assert_eq!(
+            FileConfig::rule_settings(&ValidatorKind::Nesting),
+            vec![("rules.nesting.max_depth", json!(4))]
+        );
+        assert_eq!(
+            FileConfig::rule_settings(&ValidatorKind::Env),
+            vec![("scripts.env.required", serde_json::Value::Null)]
+        );
+        let mirror = FileConfig::rule_settings(&ValidatorKind::TestMirror);
+        assert_eq!(mirror[0], ("rules.mirror.enabled", json!(false)));
+        assert_eq!(mirror[1].0, "rules.mirror.patterns");
+        assert!(FileConfig::rule_settings(&ValidatorKind::Error).is_empty());
+    }
 }
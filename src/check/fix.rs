@@ -0,0 +1,284 @@
+use crate::{
+    check::{
+        file_config::FileConfig,
+        parse_source,
+        utils::{Edit, InvalidItem, ValidatorKind},
+        validators,
+    },
+    foundry_config::CheckPaths,
+    progress,
+};
+use similar::TextDiff;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+/// The source of a file before and after its fixes.
+struct FileFix<'a> {
+    /// The path of the file, as reported in findings.
+    file: &'a str,
+    /// The current source.
+    original: String,
+    /// The source with every fix applied.
+    fixed: String,
+}
+
+/// The outcome of resolving the fixes of a set of findings.
+struct Fixes<'a> {
+    /// The files changed by the fixes.
+    files: Vec<FileFix<'a>>,
+    /// The findings whose fixes were skipped because they overlap another fix.
+    skipped: Vec<&'a InvalidItem>,
+}
+
+/// Applies the automatic fixes of the active findings in `items` and returns the number of files
+/// that were rewritten. Findings without an automatic fix are left for the report.
+///
+/// Edits attached to findings are applied first. When the edits of two findings overlap, e.g.
+/// because two rules rename the same identifier, only the first finding is fixed and the other is
+/// reported as skipped, so a file is never corrupted. Unused imports are then removed from the
+/// rewritten source, since the removals of several unused symbols from one import statement
+/// would overlap as separate edits.
+///
+/// # Errors
+///
+/// Returns an error if a file can't be read, parsed, or written.
+pub fn apply(
+    items: &[InvalidItem],
+    path_config: &CheckPaths,
+    file_config: &FileConfig,
+) -> Result<usize, Box<dyn Error>> {
+    let fixes = fixes(items, path_config, file_config)?;
+    for fix in &fixes.files {
+        fs::write(fix.file, &fix.fixed)?;
+    }
+    report_skipped(&fixes.skipped);
+    Ok(fixes.files.len())
+}
+
+/// Prints a unified diff per file of the fixes [`apply`] would make, followed by the number of
+/// active findings in `items` that can't be fixed automatically. Nothing is written.
+///
+/// # Errors
+///
+/// Returns an error if a file can't be read or parsed.
+pub fn preview(
+    items: &[InvalidItem],
+    path_config: &CheckPaths,
+    file_config: &FileConfig,
+) -> Result<(), Box<dyn Error>> {
+    let fixes = fixes(items, path_config, file_config)?;
+    for fix in &fixes.files {
+        let file = fix.file.trim_start_matches("./");
+        print!(
+            "{}",
+            TextDiff::from_lines(&fix.original, &fix.fixed)
+                .unified_diff()
+                .header(&format!("a/{file}"), &format!("b/{file}"))
+        );
+    }
+
+    let unfixable = items
+        .iter()
+        .filter(|item| !item.is_disabled && !item.is_ignored && !is_fixable(item))
+        .count();
+    report_skipped(&fixes.skipped);
+    progress::info(format_args!(
+        "Would apply fixes in {} file(s), {unfixable} finding(s) can't be fixed automatically",
+        fixes.files.len()
+    ));
+    Ok(())
+}
+
+// Prints the findings whose fixes were skipped because they conflict with another fix.
+fn report_skipped(skipped: &[&InvalidItem]) {
+    for item in skipped {
+        progress::info(format_args!(
+            "Skipped the fix for {}, it overlaps the fix of another finding. Run `--fix` again \
+             to apply it",
+            item.description()
+        ));
+    }
+}
+
+// Whether `--fix` fixes the finding: unused imports are removed, and other findings are fixed if
+// their fix is automatic.
+fn is_fixable(item: &InvalidItem) -> bool {
+    item.kind == ValidatorKind::Import || item.fix.as_ref().is_some_and(|fix| fix.automatic)
+}
+
+/// Returns the files changed by the automatic fixes of the active findings in `items`, and the
+/// findings whose fixes were skipped because they overlap another fix.
+fn fixes<'a>(
+    items: &'a [InvalidItem],
+    path_config: &CheckPaths,
+    file_config: &FileConfig,
+) -> Result<Fixes<'a>, Box<dyn Error>> {
+    let mut fixable: BTreeMap<&str, Vec<&InvalidItem>> = BTreeMap::new();
+    let mut unused_imports: BTreeMap<&str, HashSet<String>> = BTreeMap::new();
+    for item in items.iter().filter(|item| !item.is_disabled && !item.is_ignored) {
+        if item.kind == ValidatorKind::Import {
+            unused_imports.entry(&item.file).or_default().insert(unused_import_symbol(&item.text));
+        } else if item.fix.as_ref().is_some_and(|fix| fix.automatic) {
+            fixable.entry(&item.file).or_default().push(item);
+        }
+    }
+
+    let files: BTreeSet<&str> = fixable.keys().chain(unused_imports.keys()).copied().collect();
+    let mut file_fixes = Vec::new();
+    let mut skipped = Vec::new();
+    for file in files {
+        let path = Path::new(file);
+        if !path.exists() {
+            continue;
+        }
+        let original = fs::read_to_string(path)?;
+        let (edits, conflicting) = without_conflicts(fixable.get(file).map_or(&[], Vec::as_slice));
+        skipped.extend(conflicting);
+        let mut src = apply_edits(&original, &edits);
+        if let Some(symbols) = unused_imports.get(file) {
+            let mut parsed = parse_source(path, &src)?;
+            parsed.file_config = file_config.clone();
+            parsed.path_config = path_config.clone();
+            if let Some(new_src) = validators::unused_imports::fix_source(&parsed, Some(symbols)) {
+                src = new_src;
+            }
+        }
+        if src != original {
+            file_fixes.push(FileFix { file, original, fixed: src });
+        }
+    }
+    Ok(Fixes { files: file_fixes, skipped })
+}
+
+/// Returns the edits of the findings in `items` whose fixes can be applied together, and the
+/// findings whose fixes can't. A fix is applied entirely or not at all, since e.g. a rename that
+/// misses one reference breaks the code, so a finding is skipped if any of its edits overlaps an
+/// edit of an earlier finding. Identical edits don't conflict, since they're applied once.
+fn without_conflicts<'a>(items: &[&'a InvalidItem]) -> (Vec<&'a Edit>, Vec<&'a InvalidItem>) {
+    let mut accepted: Vec<&Edit> = Vec::new();
+    let mut skipped = Vec::new();
+    for item in items {
+        let Some(fix) = &item.fix else { continue };
+        let conflicts = fix
+            .edits
+            .iter()
+            .any(|edit| accepted.iter().any(|other| edit != *other && overlaps(edit, other)));
+        if conflicts {
+            skipped.push(*item);
+        } else {
+            accepted.extend(&fix.edits);
+        }
+    }
+    (accepted, skipped)
+}
+
+// Whether the byte ranges of two edits overlap. An insertion only overlaps an edit that replaces
+// the bytes around it.
+const fn overlaps(a: &Edit, b: &Edit) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Applies `edits` to `src`. Duplicate edits, e.g. a reference renamed by two findings, are applied
+/// once, and an edit that overlaps an earlier one is skipped so the source is never mangled.
+fn apply_edits(src: &str, edits: &[&Edit]) -> String {
+    let mut edits = edits.to_vec();
+    edits.sort();
+    edits.dedup();
+
+    let mut fixed = String::with_capacity(src.len());
+    let mut cursor = 0;
+    for edit in edits {
+        if edit.start < cursor {
+            continue;
+        }
+        fixed.push_str(&src[cursor..edit.start]);
+        fixed.push_str(&edit.replacement);
+        cursor = edit.end;
+    }
+    fixed.push_str(&src[cursor..]);
+    fixed
+}
+
+/// Extracts the symbol name from an "Unused import: '`SymbolName`'" message.
+fn unused_import_symbol(text: &str) -> String {
+    const PREFIX: &str = "Unused import: '";
+    const SUFFIX: char = '\'';
+    if let Some(stripped) = text.strip_prefix(PREFIX) {
+        if let Some(symbol) = stripped.strip_suffix(SUFFIX) {
+            return symbol.to_string();
+        }
+    }
+    text.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::utils::Fix;
+
+    fn edit(start: usize, end: usize, replacement: &str) -> Edit {
+        Edit { start, end, replacement: replacement.to_string() }
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let src = "uint256 x = y + x;";
+        let edits =
+            [edit(8, 9, "_x"), edit(16, 17, "_x"), edit(16, 17, "_x"), edit(0, 0, "// A\n")];
+        let edits: Vec<&Edit> = edits.iter().collect();
+        assert_eq!(apply_edits(src, &edits), "// A\nuint256 _x = y + _x;");
+
+        // An edit overlapping an earlier one is skipped.
+        let edits = [edit(8, 13, "z"), edit(12, 13, "_y")];
+        let edits: Vec<&Edit> = edits.iter().collect();
+        assert_eq!(apply_edits(src, &edits), "uint256 z + x;");
+    }
+
+    #[test]
+    fn test_without_conflicts() {
+        let item = |edits: Vec<Edit>| InvalidItem {
+            kind: ValidatorKind::Variable,
+            severity: ValidatorKind::Variable.severity(),
+            file: "./src/Counter.sol".to_string(),
+            text: String::new(),
+            line: 1,
+            column: 0,
+            end_column: 0,
+            source_line: String::new(),
+            is_disabled: false,
+            is_ignored: false,
+            fix: Some(Fix {
+                help: String::new(),
+                edits,
+                before: String::new(),
+                after: String::new(),
+                automatic: true,
+            }),
+        };
+        let rename = item(vec![edit(8, 9, "_x"), edit(16, 17, "_x")]);
+        // Renames the same reference to the same name, so it doesn't conflict.
+        let same_rename = item(vec![edit(16, 17, "_x")]);
+        // Renames a reference of the first rename to another name.
+        let other_rename = item(vec![edit(0, 0, "// A\n"), edit(16, 17, "y")]);
+        let insertion = item(vec![edit(0, 0, "// B\n")]);
+        let items = [&rename, &same_rename, &other_rename, &insertion];
+
+        let (edits, skipped) = without_conflicts(&items);
+        // None of the edits of a skipped finding are applied.
+        assert_eq!(
+            edits,
+            [&edit(8, 9, "_x"), &edit(16, 17, "_x"), &edit(16, 17, "_x"), &edit(0, 0, "// B\n")]
+        );
+        assert!(skipped == [&other_rename]);
+    }
+
+    #[test]
+    fn test_unused_import_symbol() {
+        assert_eq!(unused_import_symbol("Unused import: 'IERC20'"), "IERC20");
+        assert_eq!(unused_import_symbol("IERC20"), "IERC20");
+    }
+}
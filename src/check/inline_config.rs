@@ -6,19 +6,22 @@
 // - `// scopelint: ignore-error-line` - ignores current line
 // - `// scopelint: ignore-error-next-line` - ignores next line
 // - `// scopelint: ignore-error-start` / `// scopelint: ignore-error-end` - ignore a region
+// - `// scopelint: ignore-error-contract` - ignores the body of the next contract definition
 // - `// scopelint: ignore-error-file` - ignores entire file for error_prefix validator
 //
-// Supported rules: error, import, variable, constant, test, script, src, eip712
+// Formatting directive:
+// - `// scopelint: disable-fmt-file` - excludes the file from the formatting check and `scopelint
+//   fmt`, while still running all other validators
+//
+// Rules are referred to by their rule name from `RuleMeta`, e.g. `error` or `variable`. Run
+// `scopelint explain` to list them all.
 
 // We disable clippy in this file to keep this file as close to the original as possible, so it's
 // easier to merge in upstream changes.
 #![allow(clippy::all, clippy::pedantic, clippy::cargo, clippy::nursery)]
-use crate::check::{
-    comments::{CommentState, CommentStringExt},
-    utils::ValidatorKind,
-};
+use crate::check::{comments::Comments, utils::ValidatorKind};
 use itertools::Itertools;
-use solang_parser::pt::Loc;
+use solang_parser::pt::{Loc, SourceUnit, SourceUnitPart};
 use std::{fmt, str::FromStr};
 
 /// An inline config item
@@ -35,6 +38,8 @@ pub enum InlineConfigItem {
     DisableStart,
     /// Disables formatting for any code that precedes this and after the previous "disable-start"
     DisableEnd,
+    /// Excludes the whole file from formatting, but not from linting rules
+    DisableFmtFile,
     /// Ignores the next code item for linting rules
     IgnoreNextItem,
     /// Ignores the current line for linting rules
@@ -49,7 +54,7 @@ pub enum InlineConfigItem {
     IgnoreRule {
         /// The validator kind to ignore
         kind: ValidatorKind,
-        /// The scope of the ignore (next-item, line, next-line, start, end)
+        /// The scope of the ignore (next-item, line, next-line, start, end, contract)
         scope: RuleIgnoreScope,
     },
 }
@@ -63,7 +68,7 @@ impl FromStr for InlineConfigItem {
             // Check for "ignore-<rule>-file" first (before splitting on '-')
             if rest.ends_with("-file") {
                 if let Some(rule) = rest.strip_suffix("-file") {
-                    if let Some(kind) = parse_rule_name(rule) {
+                    if let Some(kind) = ValidatorKind::from_rule_name(rule) {
                         return Ok(InlineConfigItem::IgnoreRule {
                             kind,
                             scope: RuleIgnoreScope::File,
@@ -73,20 +78,21 @@ impl FromStr for InlineConfigItem {
             }
             // Then check for other scopes like "ignore-<rule>-next-item"
             if let Some((rule, scope_str)) = rest.split_once('-') {
-                if let Some(kind) = parse_rule_name(rule) {
+                if let Some(kind) = ValidatorKind::from_rule_name(rule) {
                     let scope = match scope_str {
                         "next-item" => RuleIgnoreScope::NextItem,
                         "line" => RuleIgnoreScope::Line,
                         "next-line" => RuleIgnoreScope::NextLine,
                         "start" => RuleIgnoreScope::Start,
                         "end" => RuleIgnoreScope::End,
+                        "contract" => RuleIgnoreScope::Contract,
                         _ => return Err(InvalidInlineConfigItem(s.into())),
                     };
                     return Ok(InlineConfigItem::IgnoreRule { kind, scope });
                 }
             }
             // Check if it's just "ignore-<rule>" (defaults to next-item scope for better usability)
-            if let Some(kind) = parse_rule_name(rest) {
+            if let Some(kind) = ValidatorKind::from_rule_name(rest) {
                 return Ok(InlineConfigItem::IgnoreRule { kind, scope: RuleIgnoreScope::NextItem });
             }
         }
@@ -98,6 +104,7 @@ impl FromStr for InlineConfigItem {
             "disable-next-line" => InlineConfigItem::DisableNextLine,
             "disable-start" => InlineConfigItem::DisableStart,
             "disable-end" => InlineConfigItem::DisableEnd,
+            "disable-fmt-file" => InlineConfigItem::DisableFmtFile,
             "ignore-next-item" => InlineConfigItem::IgnoreNextItem,
             "ignore-line" => InlineConfigItem::IgnoreLine,
             "ignore-next-line" => InlineConfigItem::IgnoreNextLine,
@@ -123,21 +130,8 @@ pub enum RuleIgnoreScope {
     End,
     /// Ignores the entire file for this rule
     File,
-}
-
-/// Maps a rule name (e.g., "error") to a `ValidatorKind`
-fn parse_rule_name(rule: &str) -> Option<ValidatorKind> {
-    match rule {
-        "error" => Some(ValidatorKind::Error),
-        "import" => Some(ValidatorKind::Import),
-        "variable" => Some(ValidatorKind::Variable),
-        "constant" => Some(ValidatorKind::Constant),
-        "test" => Some(ValidatorKind::Test),
-        "script" => Some(ValidatorKind::Script),
-        "src" => Some(ValidatorKind::Src),
-        "eip712" => Some(ValidatorKind::Eip712),
-        _ => None,
-    }
+    /// Ignores the next contract, interface, or library definition, including its body
+    Contract,
 }
 
 #[derive(Debug)]
@@ -206,8 +200,22 @@ pub struct InlineConfig {
 
 impl InlineConfig {
     /// Build a new inline config with an iterator of inline config items and their locations in a
-    /// source file
-    pub fn new(items: impl IntoIterator<Item = (Loc, InlineConfigItem)>, src: &str) -> Self {
+    /// source file. The file's comments are used to find the code that directives apply to, so the
+    /// source isn't scanned for comments again, and its parse tree to find the span of contracts.
+    pub fn new(
+        items: impl IntoIterator<Item = (Loc, InlineConfigItem)>,
+        src: &str,
+        comments: &Comments,
+        pt: &SourceUnit,
+    ) -> Self {
+        let contract_locs: Vec<Loc> =
+            pt.0.iter()
+                .filter_map(|part| match part {
+                    SourceUnitPart::ContractDefinition(contract) => Some(contract.loc),
+                    _ => None,
+                })
+                .collect();
+
         // Disable ranges (for formatting)
         let mut disabled_ranges = vec![];
         let mut disabled_range_start = None;
@@ -229,20 +237,9 @@ impl InlineConfig {
         for (loc, item) in items.into_iter().sorted_by_key(|(loc, _)| loc.start()) {
             match item {
                 InlineConfigItem::DisableNextItem => {
-                    let offset = loc.end();
-                    let mut char_indices = src[offset..]
-                        .comment_state_char_indices()
-                        .filter_map(|(state, idx, ch)| match state {
-                            CommentState::None => Some((idx, ch)),
-                            _ => None,
-                        })
-                        .skip_while(|(_, ch)| ch.is_whitespace());
-                    if let Some((mut start, _)) = char_indices.next() {
-                        start += offset;
-                        let end = char_indices
-                            .find(|(_, ch)| !ch.is_whitespace())
-                            .map(|(idx, _)| offset + idx)
-                            .unwrap_or(src.len());
+                    if let Some(start) = comments.next_code_offset(src, loc.end()) {
+                        let next = start + src[start..].chars().next().map_or(0, char::len_utf8);
+                        let end = comments.next_code_offset(src, next).unwrap_or(src.len());
                         disabled_ranges.push(DisabledRange { start, end, loose: true });
                     }
                 }
@@ -290,17 +287,10 @@ impl InlineConfig {
                         }
                     }
                 }
+                // Only affects which files are formatted, see `formatting::fmt_paths`.
+                InlineConfigItem::DisableFmtFile => {}
                 InlineConfigItem::IgnoreNextItem => {
-                    let offset = loc.end();
-                    let mut char_indices = src[offset..]
-                        .comment_state_char_indices()
-                        .filter_map(|(state, idx, ch)| match state {
-                            CommentState::None => Some((idx, ch)),
-                            _ => None,
-                        })
-                        .skip_while(|(_, ch)| ch.is_whitespace());
-                    if let Some((mut start, _)) = char_indices.next() {
-                        start += offset;
+                    if let Some(start) = comments.next_code_offset(src, loc.end()) {
                         // Find the end of the function declaration by looking for the closing brace
                         let mut brace_count = 0;
                         let mut found_function_start = false;
@@ -374,16 +364,7 @@ impl InlineConfig {
 
                     match scope {
                         RuleIgnoreScope::NextItem => {
-                            let offset = loc.end();
-                            let mut char_indices = src[offset..]
-                                .comment_state_char_indices()
-                                .filter_map(|(state, idx, ch)| match state {
-                                    CommentState::None => Some((idx, ch)),
-                                    _ => None,
-                                })
-                                .skip_while(|(_, ch)| ch.is_whitespace());
-                            if let Some((mut start, _)) = char_indices.next() {
-                                start += offset;
+                            if let Some(start) = comments.next_code_offset(src, loc.end()) {
                                 // Find the end of the function declaration by looking for the
                                 // closing brace
                                 let mut brace_count = 0;
@@ -455,6 +436,19 @@ impl InlineConfig {
                                 }
                             }
                         }
+                        RuleIgnoreScope::Contract => {
+                            // Contract-level ignore: ignore the span of the first contract that
+                            // starts after the directive
+                            if let Some(contract) =
+                                contract_locs.iter().find(|contract| contract.start() >= loc.end())
+                            {
+                                ranges.push(IgnoredRange {
+                                    start: contract.start(),
+                                    end: contract.end(),
+                                    loose: true,
+                                });
+                            }
+                        }
                         RuleIgnoreScope::File => {
                             // File-level ignore: ignore from start of file to end
                             // Use loose: true to ensure any location in the file is covered
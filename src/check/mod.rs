@@ -1,25 +1,52 @@
 use crate::{
+    bug_report,
     check::{
         comments::Comments,
         inline_config::{InlineConfig, InvalidInlineConfigItem},
     },
+    config::{CheckArgs, OutputFormat},
+    failure::{self, Failure},
     foundry_config::CheckPaths,
+    progress,
 };
 use colored::Colorize;
 use itertools::Itertools;
-use solang_parser::pt::{Loc, SourceUnit};
+use solang_parser::pt::{Loc, SourceUnit, SourceUnitPart};
 use std::{
-    collections::HashSet,
+    collections::BTreeMap,
     error::Error,
     ffi::OsStr,
     fs,
-    path::{Path, PathBuf},
+    io::{self, Read, Write},
+    ops::ControlFlow,
+    panic,
+    path::{Component, Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Instant,
 };
 use walkdir::WalkDir;
 
+/// The env var that caps how many files are checked at once, unless `--jobs` is given. Defaults to
+/// the number of available CPUs.
+pub const JOBS_ENV: &str = "SCOPELINT_JOBS";
+
+/// `foundry.toml` as a path relative to the project root, like changed or selected paths.
+const FOUNDRY_TOML: &str = "foundry.toml";
+
+/// Contains the baseline of existing findings that `scopelint check` doesn't report.
+pub mod baseline;
+
 /// Contains all the types and methods to parse comments.
 pub mod comments;
 
+/// Contains the daemon that keeps parsed files in memory between checks.
+pub mod daemon;
+
 /// Contains all the types and methods to define and parse inline config items.
 pub mod inline_config;
 
@@ -29,6 +56,12 @@ pub mod file_config;
 /// Contains all the types and methods to generate a report of all the invalid items found.
 pub mod report;
 
+/// Contains the pipeline that applies the mechanical fixes attached to findings.
+pub mod fix;
+
+/// Contains the limits on regex scans of source code, so generated sources can't hang a check.
+pub mod scan;
+
 /// Contains helper methods, traits, etc. used by the validators and report generation.
 pub mod utils;
 
@@ -36,122 +69,488 @@ pub mod utils;
 pub mod validators;
 
 /// Validates the code formatting, and print details on any conventions that are not being followed.
+///
+/// Each flag of `args` is documented on [`CheckArgs`]. In short, they select:
+///
+/// - Which files are checked: all of them, `args.paths`, the files changed according to git with
+///   `args.diff` or `args.staged`, the files of `args.contract`, `args.bug_report`, or the source
+///   read from stdin with `args.stdin`.
+/// - Which rules run: all of them, or only `args.only`, without `args.skip`. Formatting is only
+///   checked when `args.only` is empty.
+/// - What happens around the check: `args.write` regenerates the env var manifest first, `args.fix`
+///   applies fixes first, or only previews them with `args.dry_run`, and `args.bug_report` writes a
+///   diagnostic bundle after.
+/// - How the check runs and reports: `args.jobs`, `args.use_daemon`, `args.fail_fast`,
+///   `args.summary`, and `args.summary_only`.
+///
 /// # Errors
-/// Returns an error if the formatting or convention validations fail.
-pub fn run(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    // We run the formatting check separate to just indicate whether or not the user needs to format
-    // the codebase, whereas the other validators return granular information about what to fix
-    // since they currently can't be fixed automatically.
-    let valid_names = validate_conventions();
-    let valid_fmt = validators::formatting::validate(taplo_opts);
+///
+/// Returns an error if the manifest cannot be written, or if the formatting or convention
+/// validations fail.
+pub fn run(taplo_opts: &taplo::formatter::Options, args: &CheckArgs) -> Result<(), Box<dyn Error>> {
+    let mut buffers = match &args.stdin_filepath {
+        Some(file) if args.stdin => vec![read_stdin(file)?],
+        _ => Vec::new(),
+    };
+    let selected = match &args.bug_report {
+        Some(file) => selected_paths(std::slice::from_ref(file))?,
+        None if args.diff || args.staged => {
+            let changed = changed_paths(args.staged)?;
+            if changed.is_empty() {
+                progress::info(format_args!("No changed files to check"));
+                return Ok(());
+            }
+            if args.staged {
+                // The staged version of a file is what gets committed, not the one on disk.
+                buffers = read_staged(&changed)?;
+                changed.iter().map(|path| normalize(path)).collect()
+            } else {
+                selected_paths(&changed)?
+            }
+        }
+        None => match &args.contract {
+            Some(name) => contract_paths(name)?,
+            None => selected_paths(&args.paths)?,
+        },
+    };
+    let mut rules =
+        if args.only.is_empty() { utils::ValidatorKind::ALL.to_vec() } else { args.only.clone() };
+    rules.retain(|kind| !args.skip.contains(kind));
+    // `.scopelint` is read once, so every step of the check sees the same config.
+    let mut file_config = file_config::FileConfig::load();
+    if args.write {
+        write_env_manifest(&mut file_config)?;
+    }
+    for warning in file_config.deprecated_rule_warnings() {
+        eprintln!("Warning: {warning}");
+    }
+    if selected.is_empty() && buffers.is_empty() {
+        warn_ineffective_overrides(&file_config);
+    }
+    if args.fix {
+        let path_config = CheckPaths::load();
+        let mut baseline = load_baseline()?;
+        let options =
+            ValidateOptions { rules: &rules, verbose: args.verbose, jobs: jobs(args.jobs)? };
+        let results = validate(&path_config, &file_config, &selected, &[], options, &mut baseline)?;
+        if args.dry_run {
+            return fix::preview(results.items(), &path_config, &file_config);
+        }
+        let fixed_count = fix::apply(results.items(), &path_config, &file_config)?;
+        if fixed_count > 0 {
+            progress::info(format_args!("Applied fixes in {fixed_count} file(s)"));
+        }
+    }
 
-    if valid_names.is_ok() && valid_fmt.is_ok() {
-        Ok(())
+    // We run the formatting check separate to just indicate whether or not the user needs to format
+    // the codebase, whereas the other validators return granular information about what to fix.
+    let valid_names = validate_conventions(args, &file_config, &selected, &buffers, &rules);
+    // With `--fail-fast`, findings already tell that the check fails, so formatting is skipped.
+    let stopped = args.fail_fast && valid_names.is_err();
+    let valid_fmt = if args.only.is_empty() && !args.stdin && !stopped {
+        if args.staged {
+            validators::formatting::validate_staged(taplo_opts, &file_config, &buffers)
+        } else {
+            validators::formatting::validate(taplo_opts, &file_config, &selected)
+        }
     } else {
-        Err("One or more checks failed, review above output".into())
+        Ok(())
+    };
+    if let Some(file) = &args.bug_report {
+        let outcome = match &valid_names {
+            Ok(()) => "check passed".to_string(),
+            Err(err) => format!("check failed: {err}"),
+        };
+        bug_report::report(file, &outcome)?;
+    }
+
+    match (valid_names, valid_fmt) {
+        (Ok(()), Ok(())) => Ok(()),
+        (Err(err), Ok(())) | (Ok(()), Err(err)) => Err(err),
+        // Errors that stopped a check from completing take precedence over findings, and findings
+        // over formatting, so the exit code reflects the most serious failure.
+        (Err(names_err), Err(fmt_err)) => {
+            Err(if failure::exit_code(fmt_err.as_ref()) == failure::INTERNAL_ERROR {
+                fmt_err
+            } else {
+                names_err
+            })
+        }
     }
 }
 
-/// Applies safe fixes (e.g. remove unused imports), then runs check.
+/// Applies safe fixes (e.g. remove unused imports), then runs check. Equivalent to
+/// `scopelint check --fix`.
 ///
 /// # Errors
 ///
 /// Returns an error if fixes could not be applied or if convention checks still fail after
 /// fixing.
-pub fn run_fix(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    let path_config = CheckPaths::load();
-    let results = validate(&path_config)?;
+pub fn run_fix(taplo_opts: &taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
+    run(taplo_opts, &CheckArgs { fix: true, ..CheckArgs::default() })
+}
 
-    let fixable_imports: Vec<&utils::InvalidItem> = results
-        .items()
-        .iter()
-        .filter(|item| {
-            item.kind == utils::ValidatorKind::Import && !item.is_disabled && !item.is_ignored
-        })
-        .collect();
+/// Runs the convention checks on the whole project, or only on `paths` if any are given, and
+/// returns the findings without printing them, for embedders that present findings themselves.
+///
+/// Only `rules` are run, or all rules if it's empty. Formatting is not checked. Findings in the
+/// baseline, or suppressed by inline directives or `[ignore.overrides]`, are kept in the report
+/// and marked, see [`report::Report::active`] and [`report::Report::suppressed`].
+///
+/// # Errors
+///
+/// Returns an error if a path doesn't exist, the baseline can't be read, or a file fails to parse.
+pub fn collect(
+    paths: &[PathBuf],
+    rules: &[utils::ValidatorKind],
+) -> Result<report::Report, Box<dyn Error>> {
+    let selected = selected_paths(paths)?;
+    let rules = if rules.is_empty() { utils::ValidatorKind::ALL.as_slice() } else { rules };
+    let mut baseline = load_baseline()?;
+    let options = ValidateOptions { rules, verbose: 0, jobs: jobs(None)? };
+    let file_config = file_config::FileConfig::load();
+    validate(&CheckPaths::load(), &file_config, &selected, &[], options, &mut baseline)
+}
 
-    if fixable_imports.is_empty() {
-        // No fixable import issues; run normal check and return its result.
-        let valid_names = validate_conventions();
-        let valid_fmt = validators::formatting::validate(taplo_opts);
-        if valid_names.is_ok() && valid_fmt.is_ok() {
-            return Ok(());
+/// Collects the env vars read by all scripts and writes them to `[scripts.env] required` in
+/// `.scopelint`, and in `file_config`.
+fn write_env_manifest(file_config: &mut file_config::FileConfig) -> Result<(), Box<dyn Error>> {
+    let path_config = CheckPaths::load();
+    let mut names: Vec<String> = Vec::new();
+    for script_path in path_config.script_paths.iter().filter(|path| Path::new(path).is_dir()) {
+        for dent in WalkDir::new(script_path).into_iter().filter_map(Result::ok) {
+            if !dent.file_type().is_file() ||
+                dent.path().extension() != Some(OsStr::new("sol")) ||
+                file_config.is_file_ignored(dent.path())
+            {
+                continue;
+            }
+            let mut parsed = parse(dent.path())?;
+            parsed.path_config = path_config.clone();
+            names.extend(
+                validators::script_env::env_var_names(&parsed).into_iter().map(|(_, name)| name),
+            );
         }
-        return Err("One or more checks failed, review above output".into());
     }
+    names.sort();
+    names.dedup();
 
-    let file_config = file_config::FileConfig::load();
+    let config_path = file_config.write_required_env_vars(names)?;
+    let count = file_config.required_env_vars().map_or(0, <[String]>::len);
+    progress::info(format_args!("Wrote {count} env var(s) to {}", config_path.display()));
+    Ok(())
+}
+
+// =============================
+// ======== Validations ========
+// =============================
 
-    // Group fixable import items by file and collect symbol names to remove.
-    let by_file: std::collections::HashMap<&str, HashSet<String>> = fixable_imports
+/// Normalizes the files and directories given on the command line so they can be compared with the
+/// paths of walked files, e.g. `./src/Token.sol` for `src/Token.sol` or its absolute path.
+fn selected_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let cwd = std::env::current_dir()?;
+    paths
         .iter()
-        .map(|item| {
-            let symbol = extract_unused_import_symbol(&item.text);
-            (item.file.as_str(), symbol)
+        .map(|path| {
+            if !path.exists() {
+                eprintln!("{}: {} does not exist", "error".bold().red(), path.display());
+                return Err(format!("{} does not exist", path.display()).into());
+            }
+            Ok(normalize(path.strip_prefix(&cwd).unwrap_or(path)))
         })
-        .fold(std::collections::HashMap::new(), |mut acc, (file, symbol)| {
-            acc.entry(file).or_default().insert(symbol);
-            acc
-        });
-
-    let mut fixed_count = 0_usize;
-    for (file_path, symbols) in &by_file {
-        let path = Path::new(file_path);
-        if !path.exists() {
-            continue;
-        }
-        let mut parsed = parse(path)?;
-        parsed.file_config = file_config.clone();
-        parsed.path_config = path_config.clone();
+        .collect()
+}
 
-        if let Some(new_src) = validators::unused_imports::fix_source(&parsed, Some(symbols)) {
-            fs::write(path, new_src)?;
-            fixed_count += 1;
+/// Returns the Solidity files and `foundry.toml` files changed since the last commit, or only those
+/// with staged changes if `staged` is set. Deleted files are skipped.
+fn changed_paths(staged: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut args = vec!["diff", "--name-only", "--relative", "--diff-filter=d"];
+    args.push(if staged { "--cached" } else { "HEAD" });
+    let output = process::Command::new("git").args(&args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}: git {}: {}", "error".bold().red(), args.join(" "), stderr.trim());
+        return Err("git command failed".into());
+    }
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(PathBuf::from)
+        .filter(|path| {
+            path.extension() == Some(OsStr::new("sol")) ||
+                path.file_name() == Some(OsStr::new("foundry.toml"))
+        })
+        .collect())
+}
+
+/// Returns the Solidity files defining a contract, interface, or library named `name`, and the
+/// files importing them, relative to the project root.
+fn contract_paths(name: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut imports = Vec::new();
+    let mut defining = Vec::new();
+    for file in walk(&CheckPaths::load(), &[], 0) {
+        let Ok(src) = fs::read_to_string(&file) else { continue };
+        let Ok((pt, _)) = crate::parser::parse_solidity(&src, 0) else { continue };
+        let file = normalize(&file);
+        for element in &pt.0 {
+            match element {
+                SourceUnitPart::ContractDefinition(c)
+                    if c.name.as_ref().is_some_and(|id| id.name == name) =>
+                {
+                    defining.push(file.clone());
+                }
+                SourceUnitPart::ImportDirective(import) => {
+                    if let Some(path) = import.literal() {
+                        imports.push((file.clone(), utils::resolve_import(&file, &path.string)));
+                    }
+                }
+                _ => {}
+            }
         }
     }
+    if defining.is_empty() {
+        eprintln!(
+            "{}: No contract named '{name}' in the src, test, or script directories",
+            "error".bold().red()
+        );
+        return Err(format!("no contract named '{name}'").into());
+    }
+    let mut paths: Vec<PathBuf> = imports
+        .into_iter()
+        .filter(|(_, target)| defining.contains(target))
+        .map(|(file, _)| file)
+        .collect();
+    paths.extend(defining);
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Source code checked as if it were the contents of `file`, e.g. read from stdin or from the git
+/// index.
+pub(crate) struct Buffer {
+    /// The path the source is checked as, relative to the project root with a `./` prefix like
+    /// walked files.
+    pub(crate) file: PathBuf,
+    /// The source code.
+    pub(crate) src: String,
+}
+
+/// Reads the source code to check as `file` from stdin.
+fn read_stdin(file: &Path) -> Result<Buffer, Box<dyn Error>> {
+    let mut src = String::new();
+    io::stdin().read_to_string(&mut src).inspect_err(|err| {
+        eprintln!("{}: Failed to read stdin: {err}", "error".bold().red());
+    })?;
+    let cwd = std::env::current_dir()?;
+    let file = Path::new(".").join(normalize(file.strip_prefix(&cwd).unwrap_or(file)));
+    Ok(Buffer { file, src })
+}
 
-    if fixed_count > 0 {
-        eprintln!("{}: Fixed unused imports in {} file(s)", "info".bold().green(), fixed_count);
+/// Reads the staged version of `paths`, relative to the current directory, from the git index.
+fn read_staged(paths: &[PathBuf]) -> Result<Vec<Buffer>, Box<dyn Error>> {
+    let mut buffers = Vec::new();
+    for path in paths {
+        // `:./path` is the path's blob in the index, relative to the current directory.
+        let object = format!(":./{}", path.display());
+        let output = process::Command::new("git").args(["show", &object]).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            eprintln!("{}: git show {object}: {}", "error".bold().red(), stderr.trim());
+            return Err("git command failed".into());
+        }
+        let src = String::from_utf8(output.stdout).inspect_err(|_| {
+            eprintln!("{}: Staged {} is not UTF-8", "error".bold().red(), path.display());
+        })?;
+        buffers.push(Buffer { file: Path::new(".").join(normalize(path)), src });
     }
+    Ok(buffers)
+}
 
-    // Re-run check and report any remaining issues.
-    let valid_names = validate_conventions();
-    let valid_fmt = validators::formatting::validate(taplo_opts);
-    if valid_names.is_ok() && valid_fmt.is_ok() {
-        Ok(())
-    } else {
-        Err("One or more checks failed, review above output".into())
+// Warns about `[ignore.overrides]` entries that ignore a rule which never runs on the files they
+// match. Only the whole project tells whether a pattern matches files the rule runs on, so this is
+// skipped when checking selected files.
+fn warn_ineffective_overrides(file_config: &file_config::FileConfig) {
+    let path_config = CheckPaths::load();
+    let files = walk(&path_config, &[], 0);
+    for warning in file_config.ineffective_overrides(&files, &path_config) {
+        eprintln!("Warning: {warning}");
     }
 }
 
-/// Extracts the symbol name from an "Unused import: '`SymbolName`'" message.
-fn extract_unused_import_symbol(text: &str) -> String {
-    const PREFIX: &str = "Unused import: '";
-    const SUFFIX: char = '\'';
-    if let Some(stripped) = text.strip_prefix(PREFIX) {
-        if let Some(symbol) = stripped.strip_suffix(SUFFIX) {
-            return symbol.to_string();
+// Opens the destination of machine-readable output: the `--out` file, or stdout.
+fn output(args: &CheckArgs) -> Result<Box<dyn Write>, Box<dyn Error>> {
+    Ok(match &args.out {
+        Some(_) if args.format == OutputFormat::Human => {
+            eprintln!("{}: `--out` requires a machine-readable `--format`", "error".bold().red());
+            return Err("--out requires a machine-readable --format".into());
         }
-    }
-    text.to_string()
+        Some(path) => Box::new(fs::File::create(path).inspect_err(|err| {
+            eprintln!("{}: Failed to create {}: {err}", "error".bold().red(), path.display());
+        })?),
+        None => Box::new(io::stdout()),
+    })
 }
 
-// =============================
-// ======== Validations ========
-// =============================
+// Loads the baseline of existing findings, or an empty one if there is none.
+fn load_baseline() -> Result<baseline::Baseline, Box<dyn Error>> {
+    baseline::Baseline::load().map(Option::unwrap_or_default).map_err(|err| {
+        eprintln!("{}: {err}", "error".bold().red());
+        err.into()
+    })
+}
 
-fn validate_conventions() -> Result<(), Box<dyn Error>> {
-    let path_config = CheckPaths::load();
-    let results = validate(&path_config)?;
+// Prints a diagnostic line for `-v`, on stderr so it never mixes with machine-readable output.
+fn log_verbose(message: std::fmt::Arguments<'_>) {
+    eprintln!("{}: {message}", "verbose".bold().blue());
+}
+
+// Drops `.` components, so `./src/Token.sol` and `src/Token.sol` compare equal.
+fn normalize(path: &Path) -> PathBuf {
+    path.components().filter(|c| *c != Component::CurDir).collect()
+}
+
+// Whether `file` is one of the `selected` files or in one of the `selected` directories. Nothing
+// selected means the whole project is checked.
+fn is_selected(file: &Path, selected: &[PathBuf]) -> bool {
+    selected.is_empty() || selected.iter().any(|path| normalize(file).starts_with(path))
+}
 
-    if !results.is_valid() {
-        eprint!("{results}");
-        eprintln!("{}: Convention checks failed, see details above", "error".bold().red());
-        return Err("Invalid names found".into());
+fn validate_conventions(
+    args: &CheckArgs,
+    file_config: &file_config::FileConfig,
+    selected: &[PathBuf],
+    buffers: &[Buffer],
+    rules: &[utils::ValidatorKind],
+) -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+    let mut out = output(args)?;
+    let on_findings = &mut |items: &[utils::InvalidItem]| {
+        let active: Vec<&utils::InvalidItem> = items
+            .iter()
+            .filter(|item| !item.is_disabled && !item.is_ignored)
+            .filter(|item| item.severity >= args.min_severity)
+            .sorted_unstable()
+            .collect();
+        // Stream findings as they are found, so large repos don't wait for the whole walk.
+        if args.format == OutputFormat::Jsonl {
+            for item in &active {
+                let mut finding = item.to_json();
+                if args.ci {
+                    finding["severity"] = utils::Severity::Error.name().into();
+                }
+                let _ = writeln!(out, "{finding}");
+            }
+        }
+        if args.fail_fast && !active.is_empty() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    };
+    let mut results = check_conventions(args, file_config, selected, buffers, rules, on_findings)?;
+    results.retain_min_severity(args.min_severity);
+    if args.ci {
+        results.deny_warnings();
+    }
+    let link_template = args.link_template.clone().or_else(|| file_config.link_template());
+    results.set_hyperlinks(report::Hyperlinks::detect(link_template));
+    let max_warnings = args.max_warnings.or_else(|| file_config.max_warnings());
+
+    // Machine-readable output always goes to stdout (or `--out`), even when there are no findings,
+    // so consumers can rely on it being present.
+    let output = match args.format {
+        OutputFormat::Human | OutputFormat::Jsonl => None,
+        OutputFormat::Json => {
+            Some(results.to_json(rules, args.min_severity, results.passes(max_warnings)))
+        }
+        OutputFormat::Sarif => Some(results.to_sarif()),
+        OutputFormat::Junit => Some(results.to_junit()),
+        OutputFormat::Checkstyle => Some(results.to_checkstyle()),
+        OutputFormat::Markdown => Some(results.to_markdown()),
+        OutputFormat::Html => Some(results.to_html()),
+    };
+    if let Some(output) = output {
+        writeln!(out, "{output}")?;
+    }
+
+    if args.suggest_ignores {
+        print!("{}", results.to_ignore_suggestions());
+    } else if args.format == OutputFormat::Human && !results.is_valid() && !args.summary_only {
+        if args.group_by_file {
+            eprint!("{results:#}");
+        } else {
+            eprint!("{results}");
+        }
+    }
+    // The summary goes to stderr so it never mixes with machine-readable output.
+    if args.summary || args.summary_only {
+        eprintln!("{}", results.summary(start.elapsed()));
+    }
+    if args.summary_only {
+        let result = if results.passes(max_warnings) { "passed" } else { "failed" };
+        eprintln!("  Result: {result}");
+    }
+    if let Some(path) = &args.manifest {
+        fs::write(path, results.to_manifest(rules, results.passes(max_warnings))).inspect_err(
+            |err| {
+                eprintln!("{}: Failed to write {}: {err}", "error".bold().red(), path.display());
+            },
+        )?;
+    }
+    if !results.passes(max_warnings) {
+        if !args.summary_only {
+            eprintln!("{}: Convention checks failed, see details above", "error".bold().red());
+        }
+        return Err(Failure::Findings.into());
+    }
+    if let Some(max) = max_warnings.filter(|_| !results.is_valid()) {
+        progress::info(format_args!(
+            "{} warning(s) found, within the limit of {max}",
+            results.warning_count()
+        ));
     }
     Ok(())
 }
 
+// Runs the convention checks, in the `scopelint daemon` of the project with `args.use_daemon` if
+// one is running, and reports how many findings the baseline suppressed. The daemon only reads
+// files from disk, so `buffers` are always checked here.
+fn check_conventions(
+    args: &CheckArgs,
+    file_config: &file_config::FileConfig,
+    selected: &[PathBuf],
+    buffers: &[Buffer],
+    rules: &[utils::ValidatorKind],
+    on_findings: &mut dyn FnMut(&[utils::InvalidItem]) -> ControlFlow<()>,
+) -> Result<report::Report, Box<dyn Error>> {
+    let served =
+        if args.use_daemon && buffers.is_empty() { daemon::check(selected, rules)? } else { None };
+    let (results, suppressed) = if let Some((results, suppressed)) = served {
+        let _ = on_findings(results.items());
+        (results, suppressed)
+    } else {
+        let mut baseline = load_baseline()?;
+        let options = ValidateOptions { rules, verbose: args.verbose, jobs: jobs(args.jobs)? };
+        let results = validate_streaming(
+            &CheckPaths::load(),
+            file_config,
+            selected,
+            buffers,
+            options,
+            &mut baseline,
+            on_findings,
+        )?;
+        (results, baseline.suppressed())
+    };
+    if suppressed > 0 {
+        progress::info(format_args!(
+            "{suppressed} finding(s) in {} were not reported",
+            baseline::BASELINE_FILE
+        ));
+    }
+    Ok(results)
+}
+
 /// Result of parsing the source code. This is the same struct used in forge's fmt module.
 #[derive(Debug)]
 pub struct Parsed {
@@ -179,8 +578,15 @@ pub struct Parsed {
 ///
 /// Returns an error if the file cannot be read or its source code cannot be parsed.
 pub fn parse(file: &Path) -> Result<Parsed, Box<dyn Error>> {
-    let src = &fs::read_to_string(file)?;
+    parse_source(file, &fs::read_to_string(file)?)
+}
 
+/// Parses `src` as the contents of `file`, e.g. for a version of the file read from git.
+///
+/// # Errors
+///
+/// Returns an error if the source code cannot be parsed.
+pub fn parse_source(file: &Path, src: &str) -> Result<Parsed, Box<dyn Error>> {
     let (pt, comments) = crate::parser::parse_solidity(src, 0).map_err(|d| {
         eprintln!("{d:?}");
         "Failed to parse file".to_string()
@@ -189,14 +595,14 @@ pub fn parse(file: &Path) -> Result<Parsed, Box<dyn Error>> {
     let comments = Comments::new(comments, src);
     let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
         comments.parse_inline_config_items().partition_result();
-    let inline_config = InlineConfig::new(inline_config_items, src);
+    let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
     // File config and path config will be set by the caller (validate function)
     let file_config = file_config::FileConfig::default();
     let path_config = CheckPaths::default();
 
     Ok(Parsed {
         file: file.to_owned(),
-        src: src.clone(),
+        src: src.to_string(),
         pt,
         comments,
         inline_config,
@@ -206,17 +612,399 @@ pub fn parse(file: &Path) -> Result<Parsed, Box<dyn Error>> {
     })
 }
 
-// Core validation method that walks the directory and validates all Solidity files.
-fn validate(path_config: &CheckPaths) -> Result<report::Report, Box<dyn Error>> {
+// A check that validates a single file.
+type FileCheck = fn(&Parsed) -> Vec<utils::InvalidItem>;
+
+// A check that needs to see all files at once, e.g. to compare contracts across files.
+type ProjectCheck = fn(&[Parsed]) -> Vec<utils::InvalidItem>;
+
+// The per-file checks, paired with the rule they report so unselected rules are never run.
+const FILE_CHECKS: [(utils::ValidatorKind, FileCheck); 30] = [
+    (utils::ValidatorKind::Test, validators::test_names::validate),
+    (utils::ValidatorKind::Rpc, validators::test_fork_rpc::validate),
+    (utils::ValidatorKind::Src, validators::src_names_internal::validate),
+    (utils::ValidatorKind::Upgradeable, validators::upgradeable_constructor::validate),
+    (utils::ValidatorKind::Nesting, validators::nesting_depth::validate),
+    (utils::ValidatorKind::ElseAfterReturn, validators::else_after_return::validate),
+    (utils::ValidatorKind::BoolComparison, validators::bool_comparisons::validate),
+    (utils::ValidatorKind::IntType, validators::int_types::validate),
+    (utils::ValidatorKind::EtherUnits, validators::ether_units::validate),
+    (utils::ValidatorKind::NamedReturns, validators::named_returns::validate),
+    (utils::ValidatorKind::StateVisibility, validators::state_visibility::validate),
+    (utils::ValidatorKind::ChainConstant, validators::chain_constants::validate),
+    (utils::ValidatorKind::SecurityContact, validators::security_contact::validate),
+    (utils::ValidatorKind::Natspec, validators::natspec::validate),
+    (utils::ValidatorKind::FileName, validators::file_name::validate),
+    (utils::ValidatorKind::OneContract, validators::one_contract::validate),
+    (utils::ValidatorKind::TestMirror, validators::test_mirror::validate),
+    (utils::ValidatorKind::Script, validators::script_has_public_run_method::validate),
+    (utils::ValidatorKind::ScriptFile, validators::script_file::validate),
+    (utils::ValidatorKind::Determinism, validators::script_determinism::validate),
+    (utils::ValidatorKind::Env, validators::script_env::validate),
+    (utils::ValidatorKind::Constant, validators::constant_names::validate),
+    (utils::ValidatorKind::Spdx, validators::src_spdx_header::validate),
+    (utils::ValidatorKind::Console, validators::console_logs::validate),
+    (utils::ValidatorKind::Pragma, validators::pragma_min_version::validate),
+    (utils::ValidatorKind::FloatingPragma, validators::floating_pragma::validate),
+    (utils::ValidatorKind::Variable, validators::variable_names::validate),
+    (utils::ValidatorKind::Error, validators::error_prefix::validate),
+    (utils::ValidatorKind::Eip712, validators::eip712_typehash::validate),
+    (utils::ValidatorKind::Import, validators::unused_imports::validate),
+];
+
+// The project-level checks, paired with the rule they report.
+const PROJECT_CHECKS: [(utils::ValidatorKind, ProjectCheck); 6] = [
+    (utils::ValidatorKind::Chain, validators::chain_segregation::validate),
+    (utils::ValidatorKind::Storage, validators::storage_layout::validate),
+    (utils::ValidatorKind::Selector, validators::selector_collisions::validate),
+    (utils::ValidatorKind::Event, validators::event_signatures::validate),
+    (utils::ValidatorKind::Eip712, validators::eip712_typehash::validate_duplicates),
+    (utils::ValidatorKind::SolcPin, validators::solc_pin::validate),
+];
+
+// How `validate` runs.
+#[derive(Debug, Clone, Copy)]
+struct ValidateOptions<'a> {
+    // Only the checks of these rules are run.
+    rules: &'a [utils::ValidatorKind],
+    // Above 0, the files walked and the time spent on each are printed, and above 1, also the
+    // time spent parsing each file and in each of its checks.
+    verbose: u8,
+    // How many files are checked at once, see `jobs`.
+    jobs: usize,
+}
+
+// Core validation method that walks the directory and validates all Solidity files, or only the
+// `selected` ones if any are given, or only the `buffers` if any are given, e.g. read from stdin.
+// Project-level checks then only see the selected files.
+fn validate(
+    path_config: &CheckPaths,
+    file_config: &file_config::FileConfig,
+    selected: &[PathBuf],
+    buffers: &[Buffer],
+    options: ValidateOptions<'_>,
+    baseline: &mut baseline::Baseline,
+) -> Result<report::Report, Box<dyn Error>> {
+    validate_streaming(path_config, file_config, selected, buffers, options, baseline, &mut |_| {
+        ControlFlow::Continue(())
+    })
+}
+
+// Same as `validate`, but also passes each file's findings to `on_findings` as soon as the file is
+// validated, and the findings of each project-level check once it has run. Findings in the
+// `baseline` are marked as ignored before they are passed on. Validation stops early if
+// `on_findings` breaks, e.g. on the first finding with `--fail-fast`.
+fn validate_streaming(
+    path_config: &CheckPaths,
+    file_config: &file_config::FileConfig,
+    selected: &[PathBuf],
+    buffers: &[Buffer],
+    options: ValidateOptions<'_>,
+    baseline: &mut baseline::Baseline,
+    on_findings: &mut dyn FnMut(&[utils::InvalidItem]) -> ControlFlow<()>,
+) -> Result<report::Report, Box<dyn Error>> {
+    let ValidateOptions { rules, verbose, jobs } = options;
     let mut results = report::Report::default();
-    let file_config = file_config::FileConfig::load();
+    // Parsed files are kept around for the project-level validators that run after the walk.
+    let mut parsed_files: Vec<Parsed> = Vec::new();
+    if verbose > 0 {
+        log_verbose(format_args!(
+            "Running {}",
+            rules.iter().map(utils::ValidatorKind::rule_name).join(", ")
+        ));
+    }
+
+    let file_paths: Vec<PathBuf> = files(path_config, selected, buffers, verbose)
+        .into_iter()
+        .filter(|file_path| {
+            // Check if file should be ignored entirely
+            let is_ignored = file_config.is_file_ignored(file_path);
+            if is_ignored && verbose > 0 {
+                log_verbose(format_args!("{}: ignored", file_path.display()));
+            }
+            !is_ignored
+        })
+        .collect();
+
+    let check = |file_path: &Path| {
+        let buffer = buffers.iter().find(|buffer| buffer.file == file_path);
+        // Files parsed by earlier checks of the daemon, if this runs in one.
+        let cached = daemon::take(file_path);
+        check_file(file_path, buffer, cached, file_config, path_config, rules, verbose)
+    };
+    // Findings are passed on in walk order, so the output doesn't depend on the number of jobs.
+    let mut add = |checked: CheckedFile| -> Result<ControlFlow<()>, Box<dyn Error>> {
+        let (parsed, mut items) = checked?;
+        results.add_file(parsed.file.display().to_string());
+        baseline.suppress(&mut items);
+        let flow = on_findings(&items);
+        results.add_items(items);
+        parsed_files.push(parsed);
+        Ok(flow)
+    };
+    let flow = if jobs == 1 {
+        let mut flow = ControlFlow::Continue(());
+        for file_path in &file_paths {
+            flow = add(check(file_path))?;
+            if flow.is_break() {
+                break;
+            }
+        }
+        flow
+    } else {
+        check_in_parallel(&file_paths, jobs, &check, &mut add)?
+    };
+    if flow.is_break() {
+        return Ok(results);
+    }
+
+    // Run project-level checks, which need to see all files at once.
+    for (kind, check) in PROJECT_CHECKS {
+        if !rules.contains(&kind) {
+            continue;
+        }
+        let check_start = Instant::now();
+        let mut items = check(&parsed_files);
+        if verbose > 0 {
+            log_verbose(format_args!(
+                "{} across {} file(s): {:.2?}",
+                kind.rule_name(),
+                parsed_files.len(),
+                check_start.elapsed()
+            ));
+        }
+        baseline.suppress(&mut items);
+        let flow = on_findings(&items);
+        results.add_items(items);
+        if flow.is_break() {
+            daemon::keep(parsed_files);
+            return Ok(results);
+        }
+    }
+
+    // Config files such as `foundry.toml` aren't Solidity, so they're checked on their own: along
+    // with the whole project, or `foundry.toml` when it's selected, e.g. because it changed.
+    // Remappings are resolved against every file, so they're only checked with the whole project.
+    let whole_project = selected.is_empty() && buffers.is_empty();
+    let foundry_toml_selected = selected.iter().any(|path| path == Path::new(FOUNDRY_TOML));
+    let config_checks: [(utils::ValidatorKind, &dyn Fn() -> Vec<utils::InvalidItem>); 2] = [
+        (utils::ValidatorKind::FoundryConfig, &|| check_foundry_toml(file_config, buffers)),
+        (utils::ValidatorKind::Remappings, &|| {
+            validators::remappings::validate(file_config, &parsed_files)
+        }),
+    ];
+    for (kind, check) in config_checks {
+        let is_checked =
+            whole_project || (kind == utils::ValidatorKind::FoundryConfig && foundry_toml_selected);
+        if !is_checked || !rules.contains(&kind) {
+            continue;
+        }
+        let mut items = check();
+        baseline.suppress(&mut items);
+        let flow = on_findings(&items);
+        results.add_items(items);
+        if flow.is_break() {
+            break;
+        }
+    }
+    daemon::keep(parsed_files);
+    Ok(results)
+}
+
+// Checks `foundry.toml`, as staged in the index if it's among the `buffers`, or else on disk.
+fn check_foundry_toml(
+    file_config: &file_config::FileConfig,
+    buffers: &[Buffer],
+) -> Vec<utils::InvalidItem> {
+    buffers.iter().find(|buffer| normalize(&buffer.file) == Path::new(FOUNDRY_TOML)).map_or_else(
+        || validators::foundry_toml::validate(file_config),
+        |buffer| validators::foundry_toml::validate_src(file_config, &buffer.src),
+    )
+}
 
-    for path in path_config.as_array() {
+// The result of checking a file on a worker thread, with errors as strings so they can be sent
+// between threads.
+type CheckedFile = Result<(Parsed, Vec<utils::InvalidItem>), String>;
+
+// Runs `check` on each of `file_paths` on `jobs` threads, which each take the next file that no
+// thread has taken yet, so a slow file only holds up its own thread. Results are passed to `add`
+// in the order of `file_paths`, and checking stops once `add` breaks or fails.
+fn check_in_parallel(
+    file_paths: &[PathBuf],
+    jobs: usize,
+    check: &(dyn Fn(&Path) -> CheckedFile + Sync),
+    add: &mut dyn FnMut(CheckedFile) -> Result<ControlFlow<()>, Box<dyn Error>>,
+) -> Result<ControlFlow<()>, Box<dyn Error>> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(file_paths.len()))
+            .map(|_| {
+                let (next, sender) = (&next, sender.clone());
+                scope.spawn(move || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(file_path) = file_paths.get(index) else { break };
+                    // Sending fails once results are no longer received, e.g. with `--fail-fast`,
+                    // and then the thread stops.
+                    if sender.send((index, check(file_path))).is_err() {
+                        break;
+                    }
+                })
+            })
+            .collect();
+        drop(sender);
+
+        // Results that arrived before the results of files earlier in the walk.
+        let mut pending = BTreeMap::new();
+        let mut next_to_add = 0;
+        for (index, checked) in receiver {
+            pending.insert(index, checked);
+            while let Some(checked) = pending.remove(&next_to_add) {
+                next_to_add += 1;
+                if add(checked)?.is_break() {
+                    return Ok(ControlFlow::Break(()));
+                }
+            }
+        }
+        for worker in workers {
+            worker.join().unwrap_or_else(|err| panic::resume_unwind(err));
+        }
+        Ok(ControlFlow::Continue(()))
+    })
+}
+
+// Parses the file at `file_path`, or the `buffer` if given, and runs the per-file checks of the
+// given `rules` on it. A `cached` parse of the file is reused if its source is unchanged. Errors
+// are strings so files can be checked on other threads.
+fn check_file(
+    file_path: &Path,
+    buffer: Option<&Buffer>,
+    cached: Option<Parsed>,
+    file_config: &file_config::FileConfig,
+    path_config: &CheckPaths,
+    rules: &[utils::ValidatorKind],
+    verbose: u8,
+) -> Result<(Parsed, Vec<utils::InvalidItem>), String> {
+    // Get the parse tree (pt) of the file and extract inline configs.
+    bug_report::set_current_file(Some(file_path));
+    if verbose > 1 {
+        log_verbose(format_args!("Checking {}", file_path.display()));
+    }
+    let file_start = Instant::now();
+    let mut parsed = match (buffer, cached) {
+        (Some(buffer), _) => parse_source(&buffer.file, &buffer.src),
+        (None, Some(cached))
+            if fs::read_to_string(file_path).is_ok_and(|src| src == cached.src) =>
+        {
+            Ok(cached)
+        }
+        (None, _) => parse(file_path),
+    }
+    .map_err(|err| {
+        bug_report::set_current_file_failed();
+        err.to_string()
+    })?;
+    if verbose > 1 {
+        log_verbose(format_args!("  parse: {:.2?}", file_start.elapsed()));
+    }
+    // Attach file config and path config to parsed struct
+    parsed.file_config = file_config.clone();
+    parsed.path_config = path_config.clone();
+
+    let mut items = Vec::new();
+
+    // If there are any invalid inline config items, add them to the results.
+    let directives = if rules.contains(&utils::ValidatorKind::Directive) {
+        parsed.invalid_inline_config_items.as_slice()
+    } else {
+        &[]
+    };
+    for invalid_item in directives {
+        items.push(utils::InvalidItem::new(
+            utils::ValidatorKind::Directive,
+            &parsed,
+            invalid_item.0,
+            invalid_item.1.to_string(),
+        ));
+    }
+
+    // Run all checks.
+    for (kind, check) in FILE_CHECKS {
+        if rules.contains(&kind) {
+            let check_start = Instant::now();
+            items.extend(check(&parsed));
+            if verbose > 1 {
+                log_verbose(format_args!("  {}: {:.2?}", kind.rule_name(), check_start.elapsed()));
+            }
+        }
+    }
+    if verbose > 0 {
+        log_verbose(format_args!("{}: {:.2?}", parsed.file.display(), file_start.elapsed()));
+    }
+    bug_report::set_current_file(None);
+    Ok((parsed, items))
+}
+
+/// Returns how many files are checked at once: `requested`, e.g. with `--jobs`, or else the value
+/// of [`JOBS_ENV`], or else the number of available CPUs.
+///
+/// # Errors
+///
+/// Returns an error if `requested` is not given and [`JOBS_ENV`] is set to something other than a
+/// positive integer.
+pub fn jobs(requested: Option<u16>) -> Result<usize, String> {
+    if let Some(jobs) = requested {
+        return Ok(usize::from(jobs));
+    }
+    match std::env::var(JOBS_ENV) {
+        Ok(jobs) if !jobs.is_empty() => {
+            jobs.parse().ok().filter(|jobs| *jobs > 0).ok_or_else(|| {
+                eprintln!(
+                    "{}: {JOBS_ENV} must be a positive number of jobs, got '{jobs}'",
+                    "error".bold().red()
+                );
+                format!("invalid {JOBS_ENV}")
+            })
+        }
+        _ => Ok(thread::available_parallelism().map_or(1, usize::from)),
+    }
+}
+
+// Returns the files to check: the `buffers`' if any are given, or the walked ones. Like walked
+// files, a buffer outside the src, test, and script directories isn't checked.
+fn files(
+    path_config: &CheckPaths,
+    selected: &[PathBuf],
+    buffers: &[Buffer],
+    verbose: u8,
+) -> Vec<PathBuf> {
+    if buffers.is_empty() {
+        return walk(path_config, selected, verbose);
+    }
+    buffers
+        .iter()
+        .filter(|buffer| buffer.file.extension() == Some(OsStr::new("sol")))
+        .filter(|buffer| path_config.all().iter().any(|path| buffer.file.starts_with(path)))
+        .map(|buffer| buffer.file.clone())
+        .collect()
+}
+
+// Walks the src, test, and script directories and returns the Solidity files in them, or only the
+// `selected` ones if any are given.
+pub(crate) fn walk(path_config: &CheckPaths, selected: &[PathBuf], verbose: u8) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in path_config.all() {
         // Skip if the directory doesn't exist (e.g., script folder may not be created yet).
         let path_buf = Path::new(path);
         if !path_buf.exists() || !path_buf.is_dir() {
+            if verbose > 0 {
+                log_verbose(format_args!("Skipping {path}, it doesn't exist"));
+            }
             continue;
         }
+        if verbose > 0 {
+            log_verbose(format_args!("Walking {path}"));
+        }
 
         for result in WalkDir::new(path) {
             let dent = match result {
@@ -230,41 +1018,10 @@ fn validate(path_config: &CheckPaths) -> Result<report::Report, Box<dyn Error>>
             if !dent.file_type().is_file() || dent.path().extension() != Some(OsStr::new("sol")) {
                 continue;
             }
-
-            let file_path = dent.path();
-
-            // Check if file should be ignored entirely
-            if file_config.is_file_ignored(file_path) {
-                continue;
-            }
-
-            // Get the parse tree (pt) of the file and extract inline configs.
-            let mut parsed = parse(file_path)?;
-            // Attach file config and path config to parsed struct
-            parsed.file_config = file_config.clone();
-            parsed.path_config = path_config.clone();
-
-            // If there are any invalid inline config items, add them to the results.
-            for invalid_item in &parsed.invalid_inline_config_items {
-                results.add_item(utils::InvalidItem::new(
-                    utils::ValidatorKind::Directive,
-                    &parsed,
-                    invalid_item.0,
-                    invalid_item.1.to_string(),
-                ));
+            if is_selected(dent.path(), selected) {
+                files.push(dent.into_path());
             }
-
-            // Run all checks.
-            results.add_items(validators::test_names::validate(&parsed));
-            results.add_items(validators::src_names_internal::validate(&parsed));
-            results.add_items(validators::script_has_public_run_method::validate(&parsed));
-            results.add_items(validators::constant_names::validate(&parsed));
-            results.add_items(validators::src_spdx_header::validate(&parsed));
-            results.add_items(validators::variable_names::validate(&parsed));
-            results.add_items(validators::error_prefix::validate(&parsed));
-            results.add_items(validators::eip712_typehash::validate(&parsed));
-            results.add_items(validators::unused_imports::validate(&parsed));
         }
     }
-    Ok(results)
+    files
 }
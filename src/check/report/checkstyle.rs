@@ -0,0 +1,42 @@
+use super::{escape_xml, Report};
+use crate::check::utils::Severity;
+use std::fmt::Write;
+
+impl Report {
+    /// Serializes the report as a checkstyle XML document, as understood by tools such as
+    /// reviewdog and the Jenkins warnings-ng plugin.
+    ///
+    /// Every checked file is listed, with one `<error>` element per finding. The `source` of each
+    /// error is `scopelint.<code>`, e.g. `scopelint.SL006`.
+    #[must_use]
+    pub fn to_checkstyle(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<checkstyle version=\"4.3\">\n");
+        for file in self.all_files() {
+            let _ = writeln!(xml, "  <file name=\"{}\">", escape_xml(file));
+            for item in self.active().filter(|item| item.file == file) {
+                let _ = writeln!(
+                    xml,
+                    "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"scopelint.{}\"/>",
+                    item.line,
+                    item.column,
+                    severity(item.severity),
+                    escape_xml(&item.text),
+                    item.kind.code()
+                );
+            }
+            xml.push_str("  </file>\n");
+        }
+        xml.push_str("</checkstyle>");
+        xml
+    }
+}
+
+/// Returns the checkstyle severity of a severity.
+const fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warn => "warning",
+        Severity::Error => "error",
+    }
+}
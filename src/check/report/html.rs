@@ -0,0 +1,125 @@
+use super::{escape_xml, Report};
+use itertools::Itertools;
+use std::fmt::Write;
+
+const STYLE: &str = r"
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { margin-bottom: 0.25rem; }
+.counts { margin: 1rem 0; }
+.counts span { display: inline-block; margin: 0 0.5rem 0.5rem 0; padding: 0.25rem 0.5rem; border-radius: 4px; background: #f6f8fa; border: 1px solid #d0d7de; }
+.filters { margin: 1rem 0; }
+.filters select { margin-right: 1rem; }
+.finding { border: 1px solid #d0d7de; border-radius: 6px; margin: 0.75rem 0; padding: 0.5rem 0.75rem; }
+.finding .location { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; }
+.finding .code { font-weight: 600; }
+.severity-error { border-left: 4px solid #cf222e; }
+.severity-warn { border-left: 4px solid #bf8700; }
+.severity-info { border-left: 4px solid #0969da; }
+pre { background: #f6f8fa; padding: 0.5rem; overflow-x: auto; margin: 0.5rem 0 0; }
+";
+
+const SCRIPT: &str = r"
+const rule = document.getElementById('rule');
+const file = document.getElementById('file');
+const shown = document.getElementById('shown');
+function applyFilters() {
+  let count = 0;
+  for (const finding of document.querySelectorAll('.finding')) {
+    const visible = (!rule.value || finding.dataset.rule === rule.value) &&
+      (!file.value || finding.dataset.file === file.value);
+    finding.hidden = !visible;
+    if (visible) count++;
+  }
+  shown.textContent = count;
+}
+rule.addEventListener('change', applyFilters);
+file.addEventListener('change', applyFilters);
+";
+
+impl Report {
+    /// Serializes the report as a self-contained HTML page, with finding counts per rule, source
+    /// snippets, and filters by rule and file, for sharing with reviewers such as auditors.
+    ///
+    /// Findings are grouped by file and ordered by line. Styles and scripts are inlined, so the
+    /// page can be opened or attached without any other files.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let items =
+            self.active().sorted_by_key(|item| (&item.file, item.line, item.column)).collect_vec();
+
+        let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\">\n<title>scopelint report</title>\n");
+        let _ = writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>");
+        html.push_str("<h1>scopelint report</h1>\n");
+        let _ = writeln!(
+            html,
+            "<p>{} finding(s) in {} checked file(s), <span id=\"shown\">{}</span> shown.</p>",
+            items.len(),
+            self.all_files().len(),
+            items.len()
+        );
+
+        let rules = items.iter().map(|item| &item.kind).sorted().dedup_with_count().collect_vec();
+        html.push_str("<div class=\"counts\">\n");
+        for (count, kind) in &rules {
+            let _ = writeln!(
+                html,
+                "<span>{} <code>{}</code>: {count}</span>",
+                kind.code(),
+                kind.rule_name()
+            );
+        }
+        html.push_str("</div>\n");
+
+        html.push_str("<div class=\"filters\">\n<label>Rule <select id=\"rule\">\n");
+        html.push_str("<option value=\"\">All rules</option>\n");
+        for (_, kind) in &rules {
+            let _ = writeln!(
+                html,
+                "<option value=\"{}\">{} {}</option>",
+                kind.code(),
+                kind.code(),
+                kind.rule_name()
+            );
+        }
+        html.push_str("</select></label>\n<label>File <select id=\"file\">\n");
+        html.push_str("<option value=\"\">All files</option>\n");
+        for file in items.iter().map(|item| &item.file).dedup() {
+            let file = escape_xml(file);
+            let _ = writeln!(html, "<option value=\"{file}\">{file}</option>");
+        }
+        html.push_str("</select></label>\n</div>\n");
+
+        if items.is_empty() {
+            html.push_str("<p>No findings.</p>\n");
+        }
+        for item in &items {
+            let _ = writeln!(
+                html,
+                "<div class=\"finding severity-{}\" data-rule=\"{}\" data-file=\"{}\">",
+                item.severity.name(),
+                item.kind.code(),
+                escape_xml(&item.file)
+            );
+            let _ = writeln!(
+                html,
+                "<div><span class=\"location\">{}:{}:{}</span> <span class=\"code\">{} {}</span> \
+                 ({}): {}</div>",
+                escape_xml(&item.file),
+                item.line,
+                item.column,
+                item.kind.code(),
+                item.kind.rule_name(),
+                item.severity.name(),
+                escape_xml(&item.text)
+            );
+            if let Some(snippet) = item.snippet() {
+                let _ = writeln!(html, "<pre>{}</pre>", escape_xml(&snippet));
+            }
+            html.push_str("</div>\n");
+        }
+
+        let _ = write!(html, "<script>{SCRIPT}</script>\n</body>\n</html>");
+        html
+    }
+}
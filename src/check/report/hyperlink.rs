@@ -0,0 +1,90 @@
+use super::super::utils::InvalidItem;
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+
+/// Turns file locations in the human-readable report into OSC-8 terminal hyperlinks.
+///
+/// Without a URL template, findings link to the local file, which terminals such as iTerm2, kitty,
+/// and VS Code open on click. With a template, e.g. for GitHub blob URLs in CI logs, `{path}` is
+/// replaced with the file path relative to the project root, `{line}` with the line, and
+/// `{column}` with the column.
+#[derive(Debug, Clone)]
+pub struct Hyperlinks {
+    /// URL template for findings, or `None` to link to local files.
+    template: Option<String>,
+    /// Directory that relative finding paths are resolved against for local file links.
+    root: PathBuf,
+}
+
+impl Hyperlinks {
+    /// Returns the hyperlinks to emit, or `None` if findings shouldn't be linked.
+    ///
+    /// With a template, links are always emitted, since they're meant for CI logs. Otherwise links
+    /// are only emitted when stderr is a terminal that isn't known to lack support for them, and
+    /// output is colored, so `--color never` and `NO_COLOR` keep output free of escape codes.
+    #[must_use]
+    pub fn detect(template: Option<String>) -> Option<Self> {
+        let is_terminal = std::io::stderr().is_terminal() &&
+            std::env::var("TERM").map_or(true, |term| term != "dumb") &&
+            colored::control::SHOULD_COLORIZE.should_colorize();
+        if template.is_none() && !is_terminal {
+            return None;
+        }
+        let root = std::env::current_dir().unwrap_or_default();
+        Some(Self { template, root })
+    }
+
+    /// Returns the URL that a finding links to.
+    #[must_use]
+    pub fn url(&self, item: &InvalidItem) -> String {
+        let relative = item.file.trim_start_matches("./").replace('\\', "/");
+        self.template.as_ref().map_or_else(
+            || format!("file://{}", self.root.join(Path::new(&relative)).display()),
+            |template| {
+                template
+                    .replace("{path}", &relative)
+                    .replace("{line}", &item.line.to_string())
+                    .replace("{column}", &item.column.to_string())
+            },
+        )
+    }
+
+    /// Wraps `text` in a hyperlink to the finding.
+    #[must_use]
+    pub fn link(&self, text: &str, item: &InvalidItem) -> String {
+        format!("\x1b]8;;{}\x1b\\{text}\x1b]8;;\x1b\\", self.url(item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{parse_source, utils::ValidatorKind};
+    use solang_parser::pt::Loc;
+
+    #[test]
+    fn test_url() {
+        let parsed = parse_source(Path::new("./src/Counter.sol"), "\ncontract Counter {}").unwrap();
+        let item = InvalidItem::new(
+            ValidatorKind::Src,
+            &parsed,
+            Loc::File(0, 10, 17),
+            "Counter".to_string(),
+        );
+
+        let github = Hyperlinks {
+            template: Some("https://github.com/org/repo/blob/main/{path}#L{line}".to_string()),
+            root: PathBuf::from("/repo"),
+        };
+        assert_eq!(github.url(&item), "https://github.com/org/repo/blob/main/src/Counter.sol#L2");
+        assert_eq!(
+            github.link("./src/Counter.sol", &item),
+            "\x1b]8;;https://github.com/org/repo/blob/main/src/Counter.sol#L2\x1b\\./src/Counter.sol\x1b]8;;\x1b\\"
+        );
+
+        let local = Hyperlinks { template: None, root: PathBuf::from("/repo") };
+        assert_eq!(local.url(&item), "file:///repo/src/Counter.sol");
+    }
+}
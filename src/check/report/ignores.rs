@@ -0,0 +1,56 @@
+use super::Report;
+use itertools::Itertools;
+use std::fmt::Write;
+
+impl Report {
+    /// Returns ready-to-paste directives that suppress the report's findings, grouped per file: an
+    /// inline directive for the line before each flagged line, and `[ignore.overrides]` entries for
+    /// `.scopelint` that ignore the flagged rules for whole files instead.
+    ///
+    /// A line flagged by a single rule gets a rule-specific `ignore-<rule>-next-line` directive, so
+    /// other rules still apply to it. A line flagged by several rules gets `ignore-next-line`,
+    /// since each comment can only hold one directive.
+    #[must_use]
+    pub fn to_ignore_suggestions(&self) -> String {
+        let items = self.active().sorted_by_key(|item| (&item.file, item.line)).collect_vec();
+        if items.is_empty() {
+            return String::new();
+        }
+
+        let mut out = String::from("# Inline, on the line before each finding:\n");
+        let mut overrides = Vec::new();
+        for (file, file_items) in &items.into_iter().group_by(|item| &item.file) {
+            let file_items = file_items.collect_vec();
+            let _ = writeln!(out, "\n{file}");
+            for (line, line_items) in &file_items.iter().group_by(|item| item.line) {
+                let rules = line_items.map(|item| item.kind.rule_name()).unique().collect_vec();
+                let directive = match rules.as_slice() {
+                    [rule] => format!("ignore-{rule}-next-line"),
+                    _ => "ignore-next-line".to_string(),
+                };
+                let _ = writeln!(
+                    out,
+                    "  line {line} ({}): // scopelint: {directive}",
+                    rules.join(", ")
+                );
+            }
+            let rules = file_items
+                .iter()
+                .map(|item| item.kind.rule_name())
+                .unique()
+                .sorted()
+                .map(|rule| format!("\"{rule}\""))
+                .join(", ");
+            overrides.push(format!("\"{}\" = [{rules}]", file.trim_start_matches("./")));
+        }
+
+        let _ = writeln!(
+            out,
+            "\n# Or, to ignore these rules for whole files, merge into .scopelint:\n[ignore.overrides]"
+        );
+        for entry in overrides {
+            let _ = writeln!(out, "{entry}");
+        }
+        out
+    }
+}
@@ -0,0 +1,86 @@
+use super::Report;
+use crate::check::utils::{InvalidItem, Severity, ValidatorKind};
+use serde_json::json;
+
+impl Report {
+    /// Serializes the report as a JSON document with a summary of the run and one entry per
+    /// finding.
+    ///
+    /// The summary tells how the run was configured, so dashboards don't have to infer it from
+    /// the findings: the scopelint version, the `rules` that ran with their code and severity, the
+    /// severities reported given `min_severity`, the number of findings per severity, and whether
+    /// the check `passed`. Findings are in the same order as the human-readable report, and
+    /// ignored and disabled findings are omitted.
+    #[must_use]
+    pub fn to_json(&self, rules: &[ValidatorKind], min_severity: Severity, passed: bool) -> String {
+        let findings: Vec<serde_json::Value> = self.active().map(InvalidItem::to_json).collect();
+        let severities = [Severity::Error, Severity::Warn, Severity::Info];
+        let counts: serde_json::Map<String, serde_json::Value> = severities
+            .iter()
+            .map(|severity| {
+                let count = self.active().filter(|item| item.severity == *severity).count();
+                (severity.name().to_string(), json!(count))
+            })
+            .collect();
+        let rules: Vec<serde_json::Value> = rules
+            .iter()
+            .map(|kind| {
+                json!({
+                    "rule": kind.rule_name(),
+                    "code": kind.code(),
+                    "severity": kind.severity().name(),
+                })
+            })
+            .collect();
+        let reported: Vec<&str> = severities
+            .iter()
+            .filter(|severity| **severity >= min_severity)
+            .map(|severity| severity.name())
+            .collect();
+        json!({
+            "summary": {
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+                "severities": reported,
+                "files": self.all_files().len(),
+                "counts": counts,
+                "suppressed": self.suppressed().count(),
+                "passed": passed,
+            },
+            "findings": findings,
+        })
+        .to_string()
+    }
+}
+
+impl InvalidItem {
+    /// Serializes the finding as a JSON object, as listed by `to_json` and streamed one per line
+    /// by `--format jsonl`.
+    ///
+    /// Findings with a suggested fix include it as `fix`, with the byte ranges of the file to
+    /// replace, so editors and bots can apply it.
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut finding = json!({
+            "file": self.file,
+            "line": self.line,
+            "column": self.column,
+            "rule": self.kind.rule_name(),
+            "code": self.kind.code(),
+            "message": self.text,
+            "severity": self.severity.name(),
+        });
+        if let Some(fix) = &self.fix {
+            let edits: Vec<serde_json::Value> = fix
+                .edits
+                .iter()
+                .map(|edit| {
+                    json!({ "start": edit.start, "end": edit.end, "replacement": edit.replacement })
+                })
+                .collect();
+            finding["fix"] =
+                json!({ "help": fix.help, "automatic": fix.automatic, "edits": edits });
+        }
+        finding
+    }
+}
@@ -0,0 +1,65 @@
+use super::{escape_xml, Report};
+use crate::check::utils::{InvalidItem, ValidatorKind};
+use itertools::Itertools;
+use std::fmt::Write;
+
+impl Report {
+    /// Serializes the report as a `JUnit` XML document.
+    ///
+    /// Each checked file is a test suite with one test case per rule, and each finding is a
+    /// failure of the test case for its rule. Files and rules without findings show up as passing
+    /// test cases.
+    #[must_use]
+    pub fn to_junit(&self) -> String {
+        let items = self.active().collect_vec();
+        let files = self.all_files();
+
+        let tests = files.len() * ValidatorKind::ALL.len();
+        let failures = items.iter().map(|item| (&item.file, &item.kind)).unique().count();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"scopelint\" tests=\"{tests}\" failures=\"{failures}\">"
+        );
+        for file in files {
+            let file_items = items.iter().filter(|item| item.file == file).copied().collect_vec();
+            let file_failures = file_items.iter().map(|item| &item.kind).unique().count();
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{file_failures}\">",
+                escape_xml(file),
+                ValidatorKind::ALL.len()
+            );
+            for kind in &ValidatorKind::ALL {
+                let rule_items: Vec<&InvalidItem> =
+                    file_items.iter().filter(|item| item.kind == *kind).copied().collect();
+                let name =
+                    format!("name=\"{}\" classname=\"{}\"", kind.rule_name(), escape_xml(file));
+                let Some(first) = rule_items.first() else {
+                    let _ = writeln!(xml, "    <testcase {name}/>");
+                    continue;
+                };
+
+                let details = rule_items
+                    .iter()
+                    .map(|item| {
+                        format!("{}:{}:{}: {}", item.file, item.line, item.column, item.text)
+                    })
+                    .join("\n");
+                let _ = writeln!(xml, "    <testcase {name}>");
+                let _ = writeln!(
+                    xml,
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                    kind.code(),
+                    escape_xml(&first.text),
+                    escape_xml(&details)
+                );
+                let _ = writeln!(xml, "    </testcase>");
+            }
+            let _ = writeln!(xml, "  </testsuite>");
+        }
+        xml.push_str("</testsuites>");
+        xml
+    }
+}
@@ -0,0 +1,83 @@
+use super::Report;
+use crate::{
+    check::{
+        baseline::BASELINE_FILE, file_config::FileConfig, utils::ValidatorKind,
+        validators::selector_collisions,
+    },
+    foundry_config::CheckPaths,
+    selectors,
+    storage::SNAPSHOT_FILE,
+};
+use itertools::Itertools;
+use serde_json::json;
+use std::{fs, path::Path, process::Command};
+
+impl Report {
+    /// Serializes a reproducibility manifest of the run as JSON: the scopelint version, the git
+    /// commit, the rules that ran, the keccak256 hashes of the config files and of every checked
+    /// file, and a summary of the findings, as evidence that a commit was checked with a given
+    /// configuration.
+    ///
+    /// Nothing is sent anywhere, and the manifest has no timestamps, so checking the same tree
+    /// twice produces the same manifest.
+    #[must_use]
+    pub fn to_manifest(&self, rules: &[ValidatorKind], passed: bool) -> String {
+        let config_files = [
+            CheckPaths::find_foundry_toml(),
+            FileConfig::path(),
+            Some(Path::new(SNAPSHOT_FILE).to_path_buf()).filter(|path| path.exists()),
+            Some(Path::new(BASELINE_FILE).to_path_buf()).filter(|path| path.exists()),
+        ];
+        // Config files found in parent directories are listed relative to the project root.
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let config: Vec<serde_json::Value> = config_files
+            .iter()
+            .flatten()
+            .map(|path| {
+                let relative = path.strip_prefix(&cwd).unwrap_or(path);
+                json!({ "path": relative.display().to_string(), "keccak256": hash(path) })
+            })
+            .collect();
+        let files: Vec<serde_json::Value> = self
+            .all_files()
+            .into_iter()
+            .map(|file| json!({ "path": file, "keccak256": hash(Path::new(file)) }))
+            .collect();
+
+        let items = self.active().collect_vec();
+        let suppressed =
+            self.invalid_items.iter().filter(|item| item.is_disabled || item.is_ignored).count();
+        let by_rule: serde_json::Map<String, serde_json::Value> = items
+            .iter()
+            .map(|item| item.kind.rule_name())
+            .counts()
+            .into_iter()
+            .sorted()
+            .map(|(rule, count)| (rule.to_string(), json!(count)))
+            .collect();
+
+        let manifest = json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "commit": commit(),
+            "rules": rules.iter().map(ValidatorKind::rule_name).collect_vec(),
+            "config": config,
+            "files": files,
+            "findings": { "total": items.len(), "suppressed": suppressed, "by_rule": by_rule },
+            "passed": passed,
+        });
+        format!("{manifest:#}\n")
+    }
+}
+
+// The `0x`-prefixed keccak256 hash of the file's content, or `None` if it can't be read.
+fn hash(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    Some(format!("0x{}", selectors::hex(&selector_collisions::keccak256(&content))))
+}
+
+// The commit checked out in the current git repository, or `None` outside of one.
+fn commit() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok()?;
+    let commit = String::from_utf8(output.stdout).ok()?;
+    output.status.success().then(|| commit.trim().to_string())
+}
@@ -0,0 +1,47 @@
+use super::Report;
+use itertools::Itertools;
+use std::fmt::Write;
+
+impl Report {
+    /// Serializes the report as markdown, with one table of findings per rule, suitable for a PR
+    /// description or a bot comment.
+    ///
+    /// Within each table, findings are grouped by file and ordered by line.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let items = self.active().collect_vec();
+        if items.is_empty() {
+            return "## scopelint\n\nNo findings.".to_string();
+        }
+
+        let mut md = format!("## scopelint\n\n{} finding(s)\n", items.len());
+        for (kind, rule_items) in &items.into_iter().group_by(|item| &item.kind) {
+            let rule_items =
+                rule_items.sorted_by_key(|item| (&item.file, item.line, item.column)).collect_vec();
+            let _ = write!(
+                md,
+                "\n### {} `{}` ({})\n\n{}\n\n| File | Line | Message |\n| --- | --- | --- |\n",
+                kind.code(),
+                kind.rule_name(),
+                rule_items.len(),
+                kind.summary()
+            );
+            for item in rule_items {
+                let _ = writeln!(
+                    md,
+                    "| `{}` | {} | {} |",
+                    item.file,
+                    item.line,
+                    escape_table_cell(&item.text)
+                );
+            }
+        }
+        md.truncate(md.trim_end().len());
+        md
+    }
+}
+
+/// Escapes text so it stays within a single markdown table cell.
+fn escape_table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
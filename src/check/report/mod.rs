@@ -0,0 +1,245 @@
+use super::utils::{InvalidItem, Severity, ValidatorKind};
+use itertools::Itertools;
+use std::{collections::BTreeMap, fmt};
+
+/// Serializes the report as checkstyle XML.
+mod checkstyle;
+
+/// Serializes the report as a self-contained HTML page.
+mod html;
+
+/// Links findings in the human-readable report to their source.
+mod hyperlink;
+pub use hyperlink::Hyperlinks;
+
+/// Suggests directives that suppress the report's findings.
+mod ignores;
+
+/// Serializes the report as JSON.
+mod json;
+
+/// Serializes the report as `JUnit` XML.
+mod junit;
+
+/// Serializes a reproducibility manifest of the run.
+mod manifest;
+
+/// Serializes the report as markdown.
+mod markdown;
+
+/// Serializes the report as a SARIF log.
+mod sarif;
+
+/// Summarizes the report for the end of a run.
+mod summary;
+
+/// A collection of invalid items to generate a report from.
+#[derive(Default)]
+pub struct Report {
+    /// A list of invalid items.
+    invalid_items: Vec<InvalidItem>,
+    /// Every file that was checked, whether or not it has findings.
+    files: Vec<String>,
+    /// Hyperlinks for findings in the human-readable report, if they should be emitted.
+    hyperlinks: Option<Hyperlinks>,
+}
+
+/// Lists findings one per line, each followed by a source snippet and any suggested fix as a diff.
+/// The alternate form (`{:#}`) instead groups findings under a header per file, sorted by line,
+/// without snippets.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            for (file, file_items) in self.items_by_file() {
+                writeln!(f, "{file}")?;
+                for item in file_items {
+                    let location = format!("{}:{}", item.line, item.column);
+                    writeln!(
+                        f,
+                        "  {}  [{}] {}",
+                        self.link(&location, item),
+                        item.kind.code(),
+                        item.text
+                    )?;
+                }
+            }
+            return Ok(());
+        }
+        self.active().try_for_each(|item| {
+            let description =
+                item.description().replacen(&item.file, &self.link(&item.file, item), 1);
+            writeln!(f, "{description}")?;
+            if let Some(snippet) = item.snippet() {
+                writeln!(f, "{snippet}")?;
+            }
+            item.suggestion().map_or(Ok(()), |suggestion| writeln!(f, "{suggestion}"))
+        })
+    }
+}
+
+impl Report {
+    /// Records that a file was checked, so formats that list passing checks can include it.
+    pub fn add_file(&mut self, file: String) {
+        self.files.push(file);
+    }
+
+    /// Sets the hyperlinks to emit for findings in the human-readable report.
+    pub fn set_hyperlinks(&mut self, hyperlinks: Option<Hyperlinks>) {
+        self.hyperlinks = hyperlinks;
+    }
+
+    /// Wraps `text` in a hyperlink to the item, if hyperlinks are enabled.
+    fn link(&self, text: &str, item: &InvalidItem) -> String {
+        self.hyperlinks.as_ref().map_or_else(|| text.to_string(), |links| links.link(text, item))
+    }
+
+    /// Extends the report with the invalid item.
+    pub fn add_item(&mut self, item: InvalidItem) {
+        self.invalid_items.push(item);
+    }
+
+    /// Extends the report with a list of invalid items.
+    pub fn add_items(&mut self, items: Vec<InvalidItem>) {
+        self.invalid_items.extend(items);
+    }
+
+    /// Drops invalid items below `min_severity`, so they are neither reported nor fail the check.
+    pub fn retain_min_severity(&mut self, min_severity: Severity) {
+        self.invalid_items.retain(|item| item.severity >= min_severity);
+    }
+
+    /// Promotes invalid items below error severity to errors, so they fail the check even when
+    /// `max_warnings` would tolerate them.
+    pub fn deny_warnings(&mut self) {
+        for item in &mut self.invalid_items {
+            item.severity = Severity::Error;
+        }
+    }
+
+    /// Returns all invalid items (including ignored/disabled).
+    #[must_use]
+    pub fn items(&self) -> &[InvalidItem] {
+        &self.invalid_items
+    }
+
+    /// Returns every file that was checked, whether or not it has findings, in the order checked.
+    #[must_use]
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+
+    /// Returns every checked file, plus any file with findings, sorted and deduplicated.
+    fn all_files(&self) -> Vec<&str> {
+        self.files
+            .iter()
+            .chain(self.invalid_items.iter().map(|item| &item.file))
+            .map(String::as_str)
+            .sorted_unstable()
+            .dedup()
+            .collect()
+    }
+
+    /// Returns the findings that are neither ignored nor disabled, in report order. These are the
+    /// findings every format reports.
+    pub fn active(&self) -> impl Iterator<Item = &InvalidItem> {
+        self.invalid_items
+            .iter()
+            .filter(|item| !item.is_disabled && !item.is_ignored)
+            .sorted_unstable()
+    }
+
+    /// Returns the findings suppressed by an inline directive, `[ignore.overrides]`, or the
+    /// baseline, in report order.
+    pub fn suppressed(&self) -> impl Iterator<Item = &InvalidItem> {
+        self.invalid_items
+            .iter()
+            .filter(|item| item.is_disabled || item.is_ignored)
+            .sorted_unstable()
+    }
+
+    /// Returns the active findings grouped by rule, in rule order, each group in report order.
+    #[must_use]
+    pub fn items_by_rule(&self) -> BTreeMap<&ValidatorKind, Vec<&InvalidItem>> {
+        let mut groups: BTreeMap<&ValidatorKind, Vec<&InvalidItem>> = BTreeMap::new();
+        for item in self.active() {
+            groups.entry(&item.kind).or_default().push(item);
+        }
+        groups
+    }
+
+    /// Returns the active findings grouped by file, sorted by path, each group sorted by line and
+    /// column.
+    #[must_use]
+    pub fn items_by_file(&self) -> BTreeMap<&str, Vec<&InvalidItem>> {
+        let mut groups: BTreeMap<&str, Vec<&InvalidItem>> = BTreeMap::new();
+        for item in self.active().sorted_by_key(|item| (item.line, item.column)) {
+            groups.entry(item.file.as_str()).or_default().push(item);
+        }
+        groups
+    }
+
+    /// Returns true if no issues were found.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        !self.invalid_items.iter().any(|item| !item.is_disabled && !item.is_ignored)
+    }
+
+    /// Returns the number of findings below error severity, which `--max-warnings` tolerates.
+    #[must_use]
+    pub fn warning_count(&self) -> usize {
+        self.active().filter(|item| item.severity < Severity::Error).count()
+    }
+
+    /// Returns true if the check passes: no issues were found, or only findings below error
+    /// severity and at most `max_warnings` of them.
+    #[must_use]
+    pub fn passes(&self, max_warnings: Option<usize>) -> bool {
+        max_warnings.map_or_else(
+            || self.is_valid(),
+            |max| {
+                let warnings = self.warning_count();
+                warnings == self.active().count() && warnings <= max
+            },
+        )
+    }
+}
+
+/// Escapes the characters that are not allowed in XML attribute values and text.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{parse_source, validators};
+    use std::path::Path;
+
+    #[test]
+    fn test_queries() {
+        let mut report = Report::default();
+        for file in ["./src/B.sol", "./src/A.sol"] {
+            let content =
+                "contract C {\n    uint256 constant x = 1;\n    uint256 constant y = 2;\n}\n";
+            let parsed = parse_source(Path::new(file), content).unwrap();
+            report.add_items(validators::constant_names::validate(&parsed));
+        }
+        report.invalid_items[0].is_ignored = true;
+
+        assert_eq!(report.active().count(), 3);
+        assert_eq!(report.suppressed().count(), 1);
+        let by_rule = report.items_by_rule();
+        assert_eq!(by_rule.len(), 1);
+        assert_eq!(by_rule[&ValidatorKind::Constant].len(), 3);
+        let by_file: Vec<(&str, Vec<usize>)> = report
+            .items_by_file()
+            .into_iter()
+            .map(|(file, items)| (file, items.iter().map(|item| item.line).collect()))
+            .collect();
+        assert_eq!(by_file, [("./src/A.sol", vec![2, 3]), ("./src/B.sol", vec![3])]);
+    }
+}
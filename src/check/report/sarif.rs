@@ -0,0 +1,105 @@
+use super::Report;
+use crate::check::utils::{Fix, Severity, ValidatorKind};
+use serde_json::json;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/ScopeLift/scopelint";
+
+impl Report {
+    /// Serializes the report as a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+    /// log, e.g. for upload to GitHub code scanning.
+    ///
+    /// Every rule is listed in the tool metadata with its code as the id and its rule name as the
+    /// name, and file paths are relative to the project root. Suggested fixes are included as
+    /// `fixes` with byte regions of the file to replace.
+    #[must_use]
+    pub fn to_sarif(&self) -> String {
+        let rules: Vec<serde_json::Value> = ValidatorKind::ALL
+            .iter()
+            .map(|kind| {
+                json!({
+                    "id": kind.code(),
+                    "name": kind.rule_name(),
+                    "shortDescription": { "text": kind.summary() },
+                    "defaultConfiguration": { "level": level(kind.severity()) },
+                })
+            })
+            .collect();
+
+        let results: Vec<serde_json::Value> = self
+            .active()
+            .map(|item| {
+                let rule_index = ValidatorKind::ALL.iter().position(|kind| *kind == item.kind);
+                let uri = item.file.trim_start_matches("./").replace('\\', "/");
+                let fixes: Vec<serde_json::Value> =
+                    item.fix.iter().map(|fix| sarif_fix(fix, &uri)).collect();
+                json!({
+                    "ruleId": item.kind.code(),
+                    "ruleIndex": rule_index,
+                    "level": level(item.severity),
+                    "message": { "text": item.text },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": uri,
+                                "uriBaseId": "%SRCROOT%",
+                            },
+                            "region": {
+                                "startLine": item.line.max(1),
+                                "startColumn": item.column.max(1),
+                            },
+                        },
+                    }],
+                    "fixes": fixes,
+                })
+            })
+            .collect();
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "scopelint",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": INFORMATION_URI,
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        })
+        .to_string()
+    }
+}
+
+/// Returns a SARIF fix object that applies `fix` to the file at `uri`.
+fn sarif_fix(fix: &Fix, uri: &str) -> serde_json::Value {
+    let replacements: Vec<serde_json::Value> = fix
+        .edits
+        .iter()
+        .map(|edit| {
+            json!({
+                "deletedRegion": { "byteOffset": edit.start, "byteLength": edit.end - edit.start },
+                "insertedContent": { "text": edit.replacement },
+            })
+        })
+        .collect();
+    json!({
+        "description": { "text": fix.help },
+        "artifactChanges": [{
+            "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" },
+            "replacements": replacements,
+        }],
+    })
+}
+
+/// Returns the SARIF level of a severity.
+const fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "note",
+        Severity::Warn => "warning",
+        Severity::Error => "error",
+    }
+}
@@ -0,0 +1,24 @@
+use super::Report;
+use colored::Colorize;
+use std::{fmt::Write, time::Duration};
+
+impl Report {
+    /// Returns an at-a-glance summary of the run: files checked, findings per rule, how many
+    /// findings were suppressed by ignore or disable directives, and the elapsed time.
+    #[must_use]
+    pub fn summary(&self, elapsed: Duration) -> String {
+        let by_rule = self.items_by_rule();
+        let findings: usize = by_rule.values().map(Vec::len).sum();
+        let suppressed = self.suppressed().count();
+
+        let mut summary = format!("{}\n", "Summary".bold());
+        let _ = writeln!(summary, "  Files checked: {}", self.all_files().len());
+        let _ = writeln!(summary, "  Findings: {findings} ({suppressed} suppressed)");
+        for (kind, rule_items) in by_rule {
+            let _ =
+                writeln!(summary, "    {} {}: {}", kind.code(), kind.rule_name(), rule_items.len());
+        }
+        let _ = write!(summary, "  Elapsed: {:.2}s", elapsed.as_secs_f64());
+        summary
+    }
+}
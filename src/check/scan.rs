@@ -0,0 +1,113 @@
+use crate::check::utils::ValidatorKind;
+use regex::{Regex, RegexBuilder};
+use std::path::Path;
+
+/// Largest compiled size, in bytes, of a regex built from source code, e.g. from an imported
+/// symbol. The limits of the `regex` crate are 10 MiB, far more than any identifier needs.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Bytes a rule may scan with regexes in one file, counting every pass over the source.
+///
+/// The `regex` crate matches in linear time, so this caps the time spent on a file, even when a
+/// generated source imports thousands of symbols that are each searched in the whole file.
+pub const SCAN_BUDGET: usize = 64 << 20;
+
+/// Tracks the regex scans of one rule in one file, so they stop when [`SCAN_BUDGET`] is used up.
+///
+/// Rules scanning source code with regexes built from it, such as `import` and `eip712`, use
+/// this instead of `Regex::new`. When a limit is hit, a warning is printed once, and the rule
+/// skips the rest of its scans, reporting nothing for them rather than a finding it can't back.
+pub struct ScanBudget<'a> {
+    file: &'a Path,
+    kind: ValidatorKind,
+    remaining: usize,
+    exhausted: bool,
+}
+
+impl<'a> ScanBudget<'a> {
+    /// Starts a budget of [`SCAN_BUDGET`] bytes for the scans of `kind` in `file`.
+    #[must_use]
+    pub const fn new(file: &'a Path, kind: ValidatorKind) -> Self {
+        Self::with_limit(file, kind, SCAN_BUDGET)
+    }
+
+    /// Starts a budget of `limit` bytes for the scans of `kind` in `file`.
+    #[must_use]
+    pub const fn with_limit(file: &'a Path, kind: ValidatorKind, limit: usize) -> Self {
+        Self { file, kind, remaining: limit, exhausted: false }
+    }
+
+    /// Compiles `pattern` with size limits, or returns `None` with a warning if it's too large.
+    /// Like a used up budget, a pattern that is too large skips the rest of the scans.
+    pub fn regex(&mut self, pattern: &str) -> Option<Regex> {
+        if self.exhausted {
+            return None;
+        }
+        let regex = RegexBuilder::new(pattern)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .dfa_size_limit(REGEX_SIZE_LIMIT)
+            .build();
+        if regex.is_err() {
+            self.warn("a pattern built from it is too large");
+            self.exhausted = true;
+        }
+        regex.ok()
+    }
+
+    /// Charges a scan of `text` to the budget. Returns whether it may run, or `false` with a
+    /// warning once the budget is used up.
+    pub fn scan(&mut self, text: &str) -> bool {
+        if self.exhausted || text.len() > self.remaining {
+            self.warn("scanning it exceeds the scan budget");
+            self.exhausted = true;
+            return false;
+        }
+        self.remaining -= text.len();
+        true
+    }
+
+    /// Whether a limit was hit, so the findings of the rule in this file may be incomplete.
+    #[must_use]
+    pub const fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    // Warns that the rule skipped scans of the file, once per budget.
+    fn warn(&self, reason: &str) {
+        if !self.exhausted {
+            eprintln!(
+                "Warning: Skipped `{}` checks in {}, {reason}, so findings may be incomplete",
+                self.kind.rule_name(),
+                self.file.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan() {
+        let mut budget = ScanBudget::with_limit(Path::new("src/A.sol"), ValidatorKind::Import, 10);
+        assert!(budget.scan("12345"));
+        assert!(budget.scan("12345"));
+        assert!(!budget.is_exhausted());
+        assert!(!budget.scan("1"));
+        assert!(budget.is_exhausted());
+        // Once exhausted, even empty scans are skipped.
+        assert!(!budget.scan(""));
+    }
+
+    #[test]
+    fn test_regex() {
+        let mut budget = ScanBudget::new(Path::new("src/A.sol"), ValidatorKind::Import);
+        assert!(budget.regex(r"\bERC20\b").is_some());
+        assert!(!budget.is_exhausted());
+        // Nested repetitions compile to a program far beyond the size limit.
+        assert!(budget.regex(r"(\w{100}){100}").is_none());
+        assert!(budget.is_exhausted());
+        assert!(budget.regex(r"\bERC20\b").is_none());
+    }
+}
@@ -3,17 +3,58 @@
 // extensions manually with `ends_with`.
 #![allow(clippy::case_sensitive_file_extension_comparisons)]
 
-use super::Parsed;
+use super::{file_config::FileConfig, Parsed};
 use crate::foundry_config::CheckPaths;
-use solang_parser::pt::{
-    FunctionAttribute, FunctionDefinition, FunctionTy, Loc, SourceUnit, Visibility,
+use regex::Regex;
+use solang_parser::{
+    helpers::CodeLocation,
+    lexer::{Lexer, Token},
+    pt::{
+        CatchClause, ContractPart, Expression, FunctionAttribute, FunctionDefinition, FunctionTy,
+        Identifier, Loc, SourceUnit, SourceUnitPart, Statement, Type, VariableDeclaration,
+        Visibility,
+    },
 };
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    ops::Range,
+    path::{Component, Path},
+    sync::LazyLock,
+};
+
+// Matches the quoted path of an import directive, e.g. `"./Foo.sol"` in `import {Foo} from
+// "./Foo.sol";`.
+static RE_IMPORT_PATH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"["']([^"']+)["']"#).unwrap());
 
 // =======================================
 // ======== For validator methods ========
 // ===============================-=======
 
+/// How serious an invalid item is. Findings below `--min-severity` are not reported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum Severity {
+    /// Worth knowing about, but not necessarily worth acting on.
+    #[default]
+    Info,
+    /// Likely worth fixing, but not a violated convention or a likely bug.
+    Warn,
+    /// A violated convention or a likely bug.
+    Error,
+}
+
+impl Severity {
+    #[must_use]
+    /// Returns the name of the severity, as accepted by `--min-severity`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
 /// The type of validator that found the invalid item.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub enum ValidatorKind {
@@ -21,7 +62,7 @@ pub enum ValidatorKind {
     Constant,
     /// A script file.
     Script,
-    /// A source contract.
+    /// An internal or private function in a source contract.
     Src,
     /// A test contract.
     Test,
@@ -35,17 +76,657 @@ pub enum ValidatorKind {
     Eip712,
     /// An unused import.
     Import,
+    /// An import that leaks one chain's deployment files into another chain's directory.
+    Chain,
+    /// A deployment script pattern that makes deployments non-reproducible.
+    Determinism,
+    /// An env var read by a script that is not declared in the `.scopelint` manifest.
+    Env,
+    /// A fork created from an inline RPC URL instead of an alias or env var.
+    Rpc,
+    /// A `pragma solidity` that admits compilers older than the configured minimum.
+    Pragma,
+    /// An upgradeable contract constructor that emits events or writes storage.
+    Upgradeable,
+    /// A change to the storage layout recorded in the snapshot that isn't an append.
+    Storage,
+    /// Two external or public functions of a contract that share a 4-byte selector.
+    Selector,
+    /// An event whose signature is shared with a differently declared event elsewhere.
+    Event,
+    /// A deployable src contract without a `@custom:security-contact` tag.
+    SecurityContact,
+    /// A src file with no corresponding test file.
+    TestMirror,
+    /// A deployment script with the wrong file suffix or contract name, or sharing its file.
+    ScriptFile,
+    /// A src file without an SPDX license header.
+    Spdx,
+    /// A function body that nests statements deeper than the configured maximum.
+    Nesting,
+    /// An `else` following an `if` branch that ends in `return` or `revert`.
+    ElseAfterReturn,
+    /// A comparison of a boolean expression to `true` or `false`.
+    BoolComparison,
+    /// A 256-bit integer type not written in the configured style, e.g. `uint` for `uint256`.
+    IntType,
+    /// A literal power of ten scaling an amount where an ether unit would do, e.g. `x * 1e18`.
+    EtherUnits,
+    /// A function with named returns that mixes `return` with values and implicit returns.
+    NamedReturns,
+    /// A constant of a src contract holding a chain-dependent value, such as an address.
+    ChainConstant,
+    /// A public or external function of a src contract with incomplete `NatSpec`.
+    Natspec,
+    /// A state variable declared without a visibility, relying on the implicit `internal`.
+    StateVisibility,
+    /// A src file whose contracts don't match its name, e.g. `Counter.sol` defining `Counters`.
+    FileName,
+    /// A `foundry.toml` setting that breaks the configured conventions, e.g. `ffi = true`.
+    FoundryConfig,
+    /// A src file declaring another contract, library, or interface next to its contract.
+    OneContract,
+    /// A remapping that points nowhere, conflicts with another one, or is never used.
+    Remappings,
+    /// A `pragma solidity` that admits more than one compiler, e.g. `^0.8.28`.
+    FloatingPragma,
+    /// A `pragma solidity` that excludes the compiler version `foundry.toml` pins with `solc`.
+    SolcPin,
+    /// A console logging import or call in a src file, e.g. `console.log`.
+    Console,
+}
+
+/// Static metadata describing a rule. Every place that names a rule, from the report to inline
+/// directives and `.scopelint` overrides, reads it from here, so they can't disagree.
+#[derive(Debug)]
+pub struct RuleMeta {
+    /// The stable code, e.g. `SL001`. Codes are never reused or renumbered, so they can be
+    /// referenced from docs and CI configuration.
+    pub code: &'static str,
+    /// The rule name, as used in ignore directives, `.scopelint` overrides, and machine-readable
+    /// output.
+    pub name: &'static str,
+    /// The category shown before the location of each finding, e.g. `Invalid test name`.
+    pub display_name: &'static str,
+    /// A one-sentence summary of what the rule checks.
+    pub summary: &'static str,
+    /// The rationale and examples for the rule, as shown by `scopelint explain`.
+    pub explanation: &'static str,
+    /// The severity of findings of this rule. Most rules flag violated conventions or likely
+    /// bugs, which are errors.
+    pub severity: Severity,
+    /// Whether findings point at a line, rather than at the file as a whole.
+    pub has_line: bool,
+}
+
+/// A rule name that is no longer used, because the rule was renamed or removed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeprecatedRule {
+    /// The old rule name. Like current names, it never contains `-`, so directives such as
+    /// `ignore-<name>-line` can be split unambiguously.
+    pub name: &'static str,
+    /// The name of the rule that replaced it, or `None` if the rule was removed.
+    pub replacement: Option<&'static str>,
+}
+
+/// Every rule name that was renamed or removed.
+///
+/// Entries are never dropped, so `.scopelint` overrides, inline directives, and `--only`/`--skip`
+/// written for any earlier version keep working until `scopelint migrate-config` rewrites them.
+pub const DEPRECATED_RULES: &[DeprecatedRule] = &[];
+
+impl DeprecatedRule {
+    /// Returns the deprecated rule with the given name in `rules`, usually [`DEPRECATED_RULES`].
+    #[must_use]
+    pub fn find<'a>(rules: &'a [Self], name: &str) -> Option<&'a Self> {
+        rules.iter().find(|rule| rule.name == name)
+    }
+
+    /// Describes what happened to the rule, e.g. "was renamed to `error`".
+    #[must_use]
+    pub fn status(&self) -> String {
+        self.replacement.map_or_else(
+            || "was removed".to_string(),
+            |replacement| format!("was renamed to `{replacement}`"),
+        )
+    }
+}
+
+impl ValidatorKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [Self; 38] = [
+        Self::Constant,
+        Self::Script,
+        Self::Src,
+        Self::Test,
+        Self::Directive,
+        Self::Variable,
+        Self::Error,
+        Self::Eip712,
+        Self::Import,
+        Self::Chain,
+        Self::Determinism,
+        Self::Env,
+        Self::Rpc,
+        Self::Pragma,
+        Self::Upgradeable,
+        Self::Storage,
+        Self::Selector,
+        Self::Event,
+        Self::SecurityContact,
+        Self::TestMirror,
+        Self::ScriptFile,
+        Self::Spdx,
+        Self::Nesting,
+        Self::ElseAfterReturn,
+        Self::BoolComparison,
+        Self::IntType,
+        Self::EtherUnits,
+        Self::NamedReturns,
+        Self::ChainConstant,
+        Self::Natspec,
+        Self::StateVisibility,
+        Self::FileName,
+        Self::FoundryConfig,
+        Self::OneContract,
+        Self::Remappings,
+        Self::FloatingPragma,
+        Self::SolcPin,
+        Self::Console,
+    ];
+
+    #[must_use]
+    #[allow(clippy::too_many_lines)] // One entry per rule.
+    /// Returns the metadata for this kind.
+    pub const fn meta(&self) -> &'static RuleMeta {
+        match self {
+            Self::Constant => &RuleMeta {
+                code: "SL001",
+                name: "constant",
+                display_name: "Invalid constant or immutable name",
+                summary: "Constants and immutables are named in ALL_CAPS.",
+                explanation: include_str!("rules/constant.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Script => &RuleMeta {
+                code: "SL002",
+                name: "script",
+                display_name: "Invalid script interface",
+                summary: "Scripts have a single public `run` method.",
+                explanation: include_str!("rules/script.md"),
+                severity: Severity::Error,
+                has_line: false,
+            },
+            Self::Src => &RuleMeta {
+                code: "SL003",
+                name: "src",
+                display_name: "Invalid src method name",
+                summary: "Internal and private functions in source contracts start with an \
+                 underscore.",
+                explanation: include_str!("rules/src.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Test => &RuleMeta {
+                code: "SL004",
+                name: "test",
+                display_name: "Invalid test name",
+                summary: "Test names follow the `test(Fork)?(Fuzz)?(_Revert(If|When|On))?_` \
+                 format.",
+                explanation: include_str!("rules/test.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Directive => &RuleMeta {
+                code: "SL005",
+                name: "directive",
+                display_name: "Invalid directive",
+                summary: "Inline `scopelint:` directives are valid.",
+                explanation: include_str!("rules/directive.md"),
+                severity: Severity::Error,
+                has_line: false,
+            },
+            Self::Variable => &RuleMeta {
+                code: "SL006",
+                name: "variable",
+                display_name: "Invalid variable name",
+                summary: "Variable names follow the naming conventions.",
+                explanation: include_str!("rules/variable.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Error => &RuleMeta {
+                code: "SL007",
+                name: "error",
+                display_name: "Invalid error name",
+                summary: "Custom errors are prefixed with the contract name.",
+                explanation: include_str!("rules/error.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Eip712 => &RuleMeta {
+                code: "SL008",
+                name: "eip712",
+                display_name: "Invalid EIP712 typehash",
+                summary: "EIP712 typehashes match the structs they are used with.",
+                explanation: include_str!("rules/eip712.md"),
+                severity: Severity::Error,
+                has_line: false,
+            },
+            Self::Import => &RuleMeta {
+                code: "SL009",
+                name: "import",
+                display_name: "Unused import",
+                summary: "Imported symbols are used.",
+                explanation: include_str!("rules/import.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::Chain => &RuleMeta {
+                code: "SL010",
+                name: "chain",
+                display_name: "Cross-chain import",
+                summary: "Per-chain deployment directories don't import each other's files.",
+                explanation: include_str!("rules/chain.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Determinism => &RuleMeta {
+                code: "SL011",
+                name: "determinism",
+                display_name: "Non-deterministic deployment",
+                summary: "Deployment scripts don't depend on block data, the sender, or unseeded \
+                 randomness.",
+                explanation: include_str!("rules/determinism.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Env => &RuleMeta {
+                code: "SL012",
+                name: "env",
+                display_name: "Undeclared env var",
+                summary: "Env vars read by scripts are declared in `.scopelint`.",
+                explanation: include_str!("rules/env.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Rpc => &RuleMeta {
+                code: "SL013",
+                name: "rpc",
+                display_name: "Inline RPC URL",
+                summary: "Tests create forks from RPC aliases or env vars, not inline URLs.",
+                explanation: include_str!("rules/rpc.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Pragma => &RuleMeta {
+                code: "SL014",
+                name: "pragma",
+                display_name: "Invalid pragma",
+                summary: "`pragma solidity` does not admit compilers below the configured minimum.",
+                explanation: include_str!("rules/pragma.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Upgradeable => &RuleMeta {
+                code: "SL015",
+                name: "upgradeable",
+                display_name: "Invalid upgradeable constructor",
+                summary: "Constructors of upgradeable contracts don't emit events or write \
+                 storage.",
+                explanation: include_str!("rules/upgradeable.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Storage => &RuleMeta {
+                code: "SL016",
+                name: "storage",
+                display_name: "Incompatible storage layout",
+                summary: "Src contracts only append to the storage layout recorded by `scopelint \
+                 storage --write`.",
+                explanation: include_str!("rules/storage.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Selector => &RuleMeta {
+                code: "SL017",
+                name: "selector",
+                display_name: "Selector collision",
+                summary: "External and public functions, including inherited ones, have distinct \
+                 selectors.",
+                explanation: include_str!("rules/selector.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Event => &RuleMeta {
+                code: "SL018",
+                name: "event",
+                display_name: "Ambiguous event",
+                summary:
+                    "Events that share a signature are declared with the same parameter names \
+                 and indexing.",
+                explanation: include_str!("rules/event.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::SecurityContact => &RuleMeta {
+                code: "SL019",
+                name: "contact",
+                display_name: "Missing security contact",
+                summary: "Deployable src contracts have a `@custom:security-contact` tag.",
+                explanation: include_str!("rules/contact.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::TestMirror => &RuleMeta {
+                code: "SL020",
+                name: "mirror",
+                display_name: "Missing test file",
+                summary: "Src files have a test file at the mirrored path in the test directory.",
+                explanation: include_str!("rules/mirror.md"),
+                severity: Severity::Info,
+                has_line: true,
+            },
+            Self::ScriptFile => &RuleMeta {
+                code: "SL021",
+                name: "scriptfile",
+                display_name: "Invalid script file",
+                summary: "Scripts use the `.s.sol` suffix and one contract per file, named with \
+                 the `Script` suffix.",
+                explanation: include_str!("rules/scriptfile.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Spdx => &RuleMeta {
+                code: "SL022",
+                name: "spdx",
+                display_name: "Invalid license header",
+                summary: "Src files start with an `SPDX-License-Identifier` header.",
+                explanation: include_str!("rules/spdx.md"),
+                severity: Severity::Error,
+                has_line: false,
+            },
+            Self::Nesting => &RuleMeta {
+                code: "SL023",
+                name: "nesting",
+                display_name: "Deep nesting",
+                summary: "Function bodies in src files don't nest blocks, conditionals, and loops \
+                 deeper than the configured maximum.",
+                explanation: include_str!("rules/nesting.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::ElseAfterReturn => &RuleMeta {
+                code: "SL024",
+                name: "else",
+                display_name: "Else after return",
+                summary: "No `else` follows an `if` branch that ends in `return` or `revert`.",
+                explanation: include_str!("rules/else.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::BoolComparison => &RuleMeta {
+                code: "SL025",
+                name: "bool",
+                display_name: "Redundant boolean comparison",
+                summary: "Booleans are used directly instead of being compared to `true` or \
+                 `false`.",
+                explanation: include_str!("rules/bool.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::IntType => &RuleMeta {
+                code: "SL026",
+                name: "uint",
+                display_name: "Inconsistent integer type",
+                summary: "256-bit integers are written as `uint256` and `int256`, or as `uint` \
+                 and `int` if configured.",
+                explanation: include_str!("rules/uint.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::EtherUnits => &RuleMeta {
+                code: "SL027",
+                name: "units",
+                display_name: "Unit conversion literal",
+                summary:
+                    "Amounts are scaled with `1 ether`, `1 gwei`, or a named constant instead \
+                 of literals like `10**18` or `1e18`.",
+                explanation: include_str!("rules/units.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::NamedReturns => &RuleMeta {
+                code: "SL028",
+                name: "returns",
+                display_name: "Mixed named returns",
+                summary: "Functions with named returns either always return values explicitly or \
+                 always return implicitly.",
+                explanation: include_str!("rules/returns.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::ChainConstant => &RuleMeta {
+                code: "SL029",
+                name: "immutable",
+                display_name: "Chain-dependent constant",
+                summary: "Addresses, chain IDs, and other chain-dependent values in src contracts \
+                 are `immutable` and set in the constructor, not `constant`.",
+                explanation: include_str!("rules/immutable.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::Natspec => &RuleMeta {
+                code: "SL030",
+                name: "natspec",
+                display_name: "Incomplete NatSpec",
+                summary:
+                    "Public and external functions of src contracts have `@notice`, `@param`, \
+                 and `@return` NatSpec tags.",
+                explanation: include_str!("rules/natspec.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::StateVisibility => &RuleMeta {
+                code: "SL031",
+                name: "visibility",
+                display_name: "Implicit visibility",
+                summary: "State variables declare their visibility explicitly.",
+                explanation: include_str!("rules/visibility.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::FileName => &RuleMeta {
+                code: "SL032",
+                name: "filename",
+                display_name: "File name mismatch",
+                summary: "Src files are named after the contract, interface, or library they \
+                 define.",
+                explanation: include_str!("rules/filename.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::FoundryConfig => &RuleMeta {
+                code: "SL033",
+                name: "foundry",
+                display_name: "Invalid foundry.toml",
+                summary: "`foundry.toml` sets the required keys, enables no forbidden ones, and \
+                 keeps compiler settings the same across profiles.",
+                explanation: include_str!("rules/foundry.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::OneContract => &RuleMeta {
+                code: "SL034",
+                name: "onecontract",
+                display_name: "Multiple contracts in file",
+                summary: "Src files declare at most one non-abstract contract.",
+                explanation: include_str!("rules/onecontract.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::Remappings => &RuleMeta {
+                code: "SL035",
+                name: "remappings",
+                display_name: "Invalid remapping",
+                summary: "Remappings point to existing directories, don't conflict, and are used \
+                 by an import.",
+                explanation: include_str!("rules/remappings.md"),
+                severity: Severity::Warn,
+                has_line: true,
+            },
+            Self::FloatingPragma => &RuleMeta {
+                code: "SL036",
+                name: "floating",
+                display_name: "Floating pragma",
+                summary: "`pragma solidity` pins an exact compiler version in the configured \
+                 directories.",
+                explanation: include_str!("rules/floating.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::SolcPin => &RuleMeta {
+                code: "SL037",
+                name: "solc",
+                display_name: "Pragma excludes pinned solc",
+                summary: "`pragma solidity` in src files admits the compiler version pinned in \
+                 `foundry.toml`.",
+                explanation: include_str!("rules/solc.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+            Self::Console => &RuleMeta {
+                code: "SL038",
+                name: "console",
+                display_name: "Console logging in src",
+                summary: "Src files don't import or call console logging libraries.",
+                explanation: include_str!("rules/console.md"),
+                severity: Severity::Error,
+                has_line: true,
+            },
+        }
+    }
+
+    #[must_use]
+    /// Returns the kind with the given rule name, e.g. `error`, as written in ignore directives and
+    /// `.scopelint` overrides. The old names of renamed rules, see [`DEPRECATED_RULES`], return
+    /// the rule that replaced them.
+    pub fn from_rule_name(name: &str) -> Option<Self> {
+        let name = DeprecatedRule::find(DEPRECATED_RULES, name)
+            .map_or(Some(name), |deprecated| deprecated.replacement)?;
+        Self::ALL.into_iter().find(|kind| kind.rule_name() == name)
+    }
+
+    #[must_use]
+    /// Returns the rule name for this kind, see [`RuleMeta::name`].
+    pub const fn rule_name(&self) -> &'static str {
+        self.meta().name
+    }
+
+    #[must_use]
+    /// Returns the severity of findings of this kind, see [`RuleMeta::severity`].
+    pub const fn severity(&self) -> Severity {
+        self.meta().severity
+    }
+
+    #[must_use]
+    /// Returns the stable code for this kind, see [`RuleMeta::code`].
+    pub const fn code(&self) -> &'static str {
+        self.meta().code
+    }
+
+    #[must_use]
+    /// Returns a one-sentence summary of what the rule checks, used as rule metadata in
+    /// machine-readable output.
+    pub const fn summary(&self) -> &'static str {
+        self.meta().summary
+    }
+
+    #[must_use]
+    /// Returns the rationale and examples for this rule, as shown by `scopelint explain`.
+    pub const fn explanation(&self) -> &'static str {
+        self.meta().explanation
+    }
+
+    #[must_use]
+    /// Returns the kinds of files this rule runs on, or `None` if it runs on every file.
+    pub const fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        match self {
+            Self::Constant |
+            Self::Directive |
+            Self::Import |
+            Self::Chain |
+            Self::Pragma |
+            Self::FloatingPragma |
+            Self::ElseAfterReturn |
+            Self::BoolComparison |
+            Self::IntType |
+            Self::EtherUnits |
+            Self::NamedReturns |
+            Self::StateVisibility => None,
+            Self::Script | Self::Determinism | Self::Env => Some(&[FileKind::Script]),
+            Self::ScriptFile => Some(&[FileKind::Script, FileKind::ScriptHelper]),
+            Self::Src |
+            Self::Eip712 |
+            Self::Upgradeable |
+            Self::Storage |
+            Self::Selector |
+            Self::Event |
+            Self::SecurityContact |
+            Self::TestMirror |
+            Self::Spdx |
+            Self::Nesting |
+            Self::ChainConstant |
+            Self::Natspec |
+            Self::FileName |
+            Self::OneContract |
+            Self::SolcPin |
+            Self::Console => Some(&[FileKind::Src]),
+            Self::Test => Some(&[FileKind::Test]),
+            // Only run on `foundry.toml` and `remappings.txt`.
+            Self::FoundryConfig | Self::Remappings => Some(&[]),
+            Self::Rpc => Some(&[FileKind::Test, FileKind::TestHelper, FileKind::Handler]),
+            Self::Error => Some(&[FileKind::Src, FileKind::Test, FileKind::Handler]),
+            Self::Variable => {
+                Some(&[FileKind::Src, FileKind::Test, FileKind::Handler, FileKind::Script])
+            }
+        }
+    }
+}
+
+/// A replacement of the bytes in `start..end` of a source file with new text.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Edit {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+/// A mechanical fix for an invalid item, made of one or more edits to its source file.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Fix {
+    pub help: String,     // What to do, e.g. `rename to '_x'`.
+    pub edits: Vec<Edit>, // The edits to apply, the first of which is the one shown as a diff.
+    pub before: String,   // The source line containing the first edit, empty for an insertion.
+    pub after: String,    // The same source line with the first edit applied.
+    pub automatic: bool,  // Whether `--fix` applies it, i.e. the edits cover every reference.
 }
 
 /// A single invalid item found by a validator.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct InvalidItem {
     pub kind: ValidatorKind,
-    pub file: String,      // File name.
-    pub text: String,      // Details to show about the invalid item.
-    pub line: usize,       // Line number.
-    pub is_disabled: bool, // Whether the invalid item is in a disabled region.
-    pub is_ignored: bool,  // Whether the invalid item is in an ignored region.
+    pub severity: Severity,  // How serious the invalid item is.
+    pub file: String,        // File name.
+    pub text: String,        // Details to show about the invalid item.
+    pub line: usize,         // Line number.
+    pub column: usize,       // Column number, in characters from the start of the line.
+    pub end_column: usize,   // Column just past the end of the invalid item, if on the same line.
+    pub source_line: String, // The source line containing the start of the invalid item.
+    pub is_disabled: bool,   // Whether the invalid item is in a disabled region.
+    pub is_ignored: bool,    // Whether the invalid item is in an ignored region.
+    pub fix: Option<Fix>,    // A suggested fix, when the correct code is mechanically derivable.
 }
 
 impl InvalidItem {
@@ -53,74 +734,205 @@ impl InvalidItem {
     /// Creates a new `InvalidItem`.
     pub fn new(kind: ValidatorKind, parsed: &Parsed, loc: Loc, text: String) -> Self {
         let Parsed { file, src, inline_config, file_config, .. } = parsed;
-        let line = offset_to_line(src, loc.start());
         let is_disabled = inline_config.is_disabled(loc);
         // Check both generic ignore and rule-specific ignore (from inline comments)
         let is_ignored_inline =
             inline_config.is_ignored(loc) || inline_config.is_rule_ignored(loc, &kind);
+        let mut item = Self::in_file(kind, file, src, loc, text, file_config);
+        item.is_disabled = is_disabled;
+        item.is_ignored |= is_ignored_inline;
+        item
+    }
+
+    #[must_use]
+    /// Creates a new `InvalidItem` at `loc` in the source `src` of a file that isn't parsed as
+    /// Solidity, e.g. `foundry.toml`, so only `.scopelint` overrides can ignore it.
+    pub fn in_file(
+        kind: ValidatorKind,
+        file: &Path,
+        src: &str,
+        loc: Loc,
+        text: String,
+        file_config: &FileConfig,
+    ) -> Self {
+        let line = offset_to_line(src, loc.start());
+        let column = offset_to_column(src, loc.start());
+        let (source_line, end_column) = source_line(src, loc);
         // Check if rule is ignored in file config
-        let is_ignored_file_config = file_config.get_ignored_rules(file).contains(&kind);
-        let is_ignored = is_ignored_inline || is_ignored_file_config;
-        Self { kind, file: file.display().to_string(), text, line, is_disabled, is_ignored }
+        let is_ignored = file_config.get_ignored_rules(file).contains(&kind);
+        Self {
+            severity: kind.severity(),
+            kind,
+            file: file.display().to_string(),
+            text,
+            line,
+            column,
+            end_column,
+            source_line,
+            is_disabled: false,
+            is_ignored,
+            fix: None,
+        }
+    }
+
+    #[must_use]
+    /// Attaches a suggested fix that renames the identifier at `loc` to `replacement`. Spans that
+    /// cross a line break are ignored, since they can't be shown as a single-line diff.
+    ///
+    /// The fix is only shown, not applied by `--fix`, since other code may still refer to the
+    /// old name. Use [`InvalidItem::with_rename`] when every reference is known.
+    pub fn with_suggested_rename(self, parsed: &Parsed, loc: Loc, replacement: String) -> Self {
+        let (start, end) = (loc.start(), loc.end());
+        if parsed.src.get(start..end).is_none_or(|span| span.contains('\n')) {
+            return self;
+        }
+        let help = format!("rename to '{replacement}'");
+        let mut item = self.with_replacement(parsed, start..end, replacement, help);
+        if let Some(fix) = item.fix.as_mut() {
+            fix.automatic = false;
+        }
+        item
+    }
+
+    #[must_use]
+    /// Attaches a fix that replaces the bytes in `span` with `replacement`, applied by `--fix`.
+    /// Only use it when the replacement preserves behavior on its own. The diff shows the source
+    /// line where `span` starts, and its first line after the replacement.
+    pub fn with_replacement(
+        mut self,
+        parsed: &Parsed,
+        span: Range<usize>,
+        replacement: String,
+        help: String,
+    ) -> Self {
+        let src = &parsed.src;
+        let Range { start, end } = span;
+        if src.get(start..end).is_none() {
+            return self;
+        }
+        let line_start = src[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = src[start..].find('\n').map_or(src.len(), |idx| start + idx);
+        let before = src[line_start..line_end].trim_end_matches('\r');
+        let rest_end = src[end..].find('\n').map_or(src.len(), |idx| end + idx);
+        let edited = format!("{}{replacement}{}", &src[line_start..start], &src[end..rest_end]);
+        let after = edited.lines().next().unwrap_or_default().trim_end_matches('\r').to_string();
+        let edits = vec![Edit { start, end, replacement }];
+        self.fix = Some(Fix { help, edits, before: before.to_string(), after, automatic: true });
+        self
+    }
+
+    #[must_use]
+    /// Attaches a fix that renames the identifier at `loc` to `replacement`, along with every
+    /// reference to it in `scope`, a byte range of the source that the name can't be used outside
+    /// of. The fix is applied by `--fix` unless `replacement` is already used in `scope`, since
+    /// the rename could then change what existing references resolve to.
+    pub fn with_rename(
+        self,
+        parsed: &Parsed,
+        loc: Loc,
+        replacement: &str,
+        scope: Range<usize>,
+    ) -> Self {
+        let src = &parsed.src;
+        let Some(name) = src.get(loc.start()..loc.end()) else {
+            return self;
+        };
+        let references = identifier_references(src, name, scope.clone());
+        let is_shadowed = !identifier_references(src, replacement, scope).is_empty();
+        let mut item = self.with_suggested_rename(parsed, loc, replacement.to_string());
+        if let Some(fix) = item.fix.as_mut().filter(|_| !is_shadowed) {
+            fix.edits.extend(references.into_iter().filter(|span| span.start != loc.start()).map(
+                |span| Edit {
+                    start: span.start,
+                    end: span.end,
+                    replacement: replacement.to_string(),
+                },
+            ));
+            fix.automatic = true;
+        }
+        item
+    }
+
+    #[must_use]
+    /// Attaches a fix that inserts `line` as a new line at byte offset `offset`, which must be the
+    /// start of a line.
+    pub fn with_inserted_line(mut self, offset: usize, line: String) -> Self {
+        let help = format!("insert `{line}`");
+        let edits = vec![Edit { start: offset, end: offset, replacement: format!("{line}\n") }];
+        self.fix = Some(Fix { help, edits, before: String::new(), after: line, automatic: true });
+        self
     }
 
     #[must_use]
-    /// Returns a string describing the invalid item, which is shown to the user so they can triage
-    /// findings.
+    /// Returns the suggested fix as a help line followed by a `-` / `+` diff of the affected source
+    /// line, or `None` if the invalid item has no suggested fix.
+    pub fn suggestion(&self) -> Option<String> {
+        let fix = self.fix.as_ref()?;
+        let help = format!("   = help: {}", fix.help);
+        if fix.before.is_empty() {
+            return Some(format!("{help}\n   + {}", fix.after.trim_end()));
+        }
+        Some(format!("{help}\n   - {}\n   + {}", fix.before.trim_end(), fix.after.trim_end()))
+    }
+
+    #[must_use]
+    /// Returns the source line of the invalid item with carets under the offending code, similar to
+    /// rustc diagnostics, or `None` if the line is blank.
+    pub fn snippet(&self) -> Option<String> {
+        if self.source_line.trim().is_empty() {
+            return None;
+        }
+        let gutter = " ".repeat(self.line.to_string().len());
+        // Keep tabs so the carets line up with the source line however tabs are rendered.
+        let padding: String = self
+            .source_line
+            .chars()
+            .take(self.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let carets = "^".repeat(self.end_column.saturating_sub(self.column).max(1));
+        Some(format!(
+            " {} | {}\n {gutter} | {padding}{carets}",
+            self.line,
+            self.source_line.trim_end()
+        ))
+    }
+
+    #[must_use]
+    /// Returns a string describing the invalid item, prefixed with its rule code, which is shown to
+    /// the user so they can triage findings.
     pub fn description(&self) -> String {
-        match self.kind {
-            ValidatorKind::Test => {
-                format!("Invalid test name in {} on line {}: {}", self.file, self.line, self.text)
-            }
-            ValidatorKind::Constant => {
-                format!(
-                    "Invalid constant or immutable name in {} on line {}: {}",
-                    self.file, self.line, self.text
-                )
-            }
-            ValidatorKind::Script => {
-                format!("Invalid script interface in {}: {}", self.file, self.text)
-            }
-            ValidatorKind::Src => {
-                format!(
-                    "Invalid src method name in {} on line {}: {}",
-                    self.file, self.line, self.text
-                )
-            }
-            ValidatorKind::Directive => {
-                format!("Invalid directive in {}: {}", self.file, self.text)
-            }
-            ValidatorKind::Variable => {
-                format!(
-                    "Invalid variable name in {} on line {}: {}",
-                    self.file, self.line, self.text
-                )
-            }
-            ValidatorKind::Error => {
-                format!("Invalid error name in {} on line {}: {}", self.file, self.line, self.text)
-            }
-            ValidatorKind::Eip712 => {
-                format!("Invalid EIP712 typehash in {}: {}", self.file, self.text)
-            }
-            ValidatorKind::Import => {
-                format!("Unused import in {} on line {}: {}", self.file, self.line, self.text)
-            }
+        let meta = self.kind.meta();
+        if meta.has_line {
+            format!(
+                "[{}] {} in {} on line {}: {}",
+                meta.code, meta.display_name, self.file, self.line, self.text
+            )
+        } else {
+            format!("[{}] {} in {}: {}", meta.code, meta.display_name, self.file, self.text)
         }
     }
 }
 
 /// Categories of file kinds found in forge projects.
 ///
-/// Two additional file kinds are not included here: `ScriptHelpers` and `TestHelpers`. These are
-/// not currently used in any checks so they are excluded for now.
+/// Handlers and helpers can also be given explicitly with the `handlers`, `test_helpers`, and
+/// `script_helpers` globs in the `[check]` section of `foundry.toml`, which take precedence over
+/// the file name conventions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileKind {
     /// Executable script files live in the `scripts` directory and end with `.s.sol`.
     Script,
+    /// Other files in the `scripts` directory, or files matching `script_helpers`.
+    ScriptHelper,
     /// Core contracts live in the `src` directory and end with `.sol`.
     Src,
     /// Contracts with test methods live in the `test` directory and end with `.t.sol`.
     Test,
-    /// Contracts with handler methods live in the `test` directory and end with `.handler.sol`.
+    /// Other files in the `test` directory, or files matching `test_helpers`.
+    TestHelper,
+    /// Contracts with handler methods live in the `test` directory and end with `.handler.sol`, or
+    /// match `handlers`.
     Handler,
 }
 
@@ -133,17 +945,29 @@ pub trait IsFileKind {
 impl IsFileKind for Path {
     fn is_file_kind(&self, kind: FileKind, paths: &CheckPaths) -> bool {
         let path = self.to_str().unwrap();
+        if !path.ends_with(".sol") {
+            return false;
+        }
+        let is_handler = CheckPaths::matches(&paths.handler_globs, self) ||
+            (paths.in_test(self) && path.ends_with(".handler.sol"));
+        let is_test_helper = CheckPaths::matches(&paths.test_helper_globs, self);
+        let is_script_helper = CheckPaths::matches(&paths.script_helper_globs, self);
         match kind {
             FileKind::Script => {
-                path.starts_with(paths.script_path.as_str()) && path.ends_with(".s.sol")
+                paths.in_script(self) && path.ends_with(".s.sol") && !is_script_helper
             }
-            FileKind::Src => path.starts_with(paths.src_path.as_str()) && path.ends_with(".sol"),
+            FileKind::ScriptHelper => {
+                is_script_helper || (paths.in_script(self) && !path.ends_with(".s.sol"))
+            }
+            FileKind::Src => paths.in_src(self),
             FileKind::Test => {
-                path.starts_with(paths.test_path.as_str()) && path.ends_with(".t.sol")
+                paths.in_test(self) && path.ends_with(".t.sol") && !is_handler && !is_test_helper
             }
-            FileKind::Handler => {
-                path.starts_with(paths.test_path.as_str()) && path.ends_with(".handler.sol")
+            FileKind::TestHelper => {
+                !is_handler &&
+                    (is_test_helper || (paths.in_test(self) && !path.ends_with(".t.sol")))
             }
+            FileKind::Handler => is_handler,
         }
     }
 }
@@ -212,6 +1036,397 @@ pub fn offset_to_line(content: &str, start: usize) -> usize {
     unreachable!("content.len() > start")
 }
 
+#[must_use]
+/// Converts a byte offset to a 1-based column, counted in characters from the start of its line.
+pub fn offset_to_column(content: &str, start: usize) -> usize {
+    let before = content.get(..start).unwrap_or(content);
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    before[line_start..].chars().count() + 1
+}
+
+/// Returns the line of `content` containing the start of `loc`, and the column just past the end of
+/// `loc`, or past the end of the line if `loc` spans multiple lines.
+fn source_line(content: &str, loc: Loc) -> (String, usize) {
+    let start = loc.start().min(content.len());
+    let line_start = content[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = content[start..].find('\n').map_or(content.len(), |idx| start + idx);
+    let end = loc.end().clamp(start, line_end);
+    let line = content[line_start..line_end].trim_end_matches('\r');
+    (line.to_string(), content[line_start..end].chars().count() + 1)
+}
+
+/// Returns the spans of the identifiers named `name` in `src[range]`, skipping member accesses
+/// such as `x` in `a.x` and argument names such as `x` in `f({x: 1})`, which refer to something
+/// else. Comments and string literals are never matched.
+fn identifier_references(src: &str, name: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let Some(scope) = src.get(range.clone()) else {
+        return Vec::new();
+    };
+    let (mut comments, mut errors) = (Vec::new(), Vec::new());
+    let tokens: Vec<_> = Lexer::new(scope, 0, &mut comments, &mut errors).collect();
+    let mut references = Vec::new();
+    for (i, (start, token, end)) in tokens.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| tokens[i].1);
+        let next = tokens.get(i + 1).map(|t| t.1);
+        let is_member = previous == Some(Token::Member);
+        let is_argument_name = matches!(previous, Some(Token::OpenCurlyBrace | Token::Comma)) &&
+            next == Some(Token::Colon);
+        if *token == Token::Identifier(name) && !is_member && !is_argument_name {
+            references.push(range.start + start..range.start + end);
+        }
+    }
+    references
+}
+
+/// A user-defined type that parameters can refer to by name.
+enum AbiTypeDef<'a> {
+    Struct(&'a Parsed, &'a [VariableDeclaration]),
+    Enum,
+    Value(&'a Parsed, &'a Expression),
+}
+
+/// The structs, enums, and user-defined value types declared in a set of files, used to resolve
+/// parameter types to the canonical ABI types used in function and event signatures.
+pub struct AbiTypes<'a> {
+    types: HashMap<&'a str, AbiTypeDef<'a>>,
+}
+
+impl<'a> AbiTypes<'a> {
+    // Struct types nested deeper than this are not expanded, which also guards against recursive
+    // structs such as `struct Node { Node[] children; }`.
+    const MAX_DEPTH: usize = 8;
+
+    /// Indexes the user-defined types declared at the top level or in contracts of `files`.
+    #[must_use]
+    pub fn new(files: &'a [Parsed]) -> Self {
+        let mut types = HashMap::new();
+        let mut add = |name: Option<&'a Identifier>, def| {
+            if let Some(name) = name {
+                types.insert(name.name.as_str(), def);
+            }
+        };
+        for parsed in files {
+            for element in &parsed.pt.0 {
+                match element {
+                    SourceUnitPart::StructDefinition(s) => {
+                        add(s.name.as_ref(), AbiTypeDef::Struct(parsed, &s.fields));
+                    }
+                    SourceUnitPart::EnumDefinition(e) => add(e.name.as_ref(), AbiTypeDef::Enum),
+                    SourceUnitPart::TypeDefinition(t) => {
+                        add(Some(&t.name), AbiTypeDef::Value(parsed, &t.ty));
+                    }
+                    SourceUnitPart::ContractDefinition(c) => {
+                        for part in &c.parts {
+                            match part {
+                                ContractPart::StructDefinition(s) => {
+                                    add(s.name.as_ref(), AbiTypeDef::Struct(parsed, &s.fields));
+                                }
+                                ContractPart::EnumDefinition(e) => {
+                                    add(e.name.as_ref(), AbiTypeDef::Enum);
+                                }
+                                ContractPart::TypeDefinition(t) => {
+                                    add(Some(&t.name), AbiTypeDef::Value(parsed, &t.ty));
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Self { types }
+    }
+
+    /// Returns the canonical ABI type of a parameter type declared in `parsed`, e.g. `uint256` for
+    /// `uint` or `(address,uint8)` for a struct with an address and an enum.
+    ///
+    /// Contracts and interfaces are encoded as `address`, as are any other types that can't be
+    /// resolved, e.g. structs declared in files that weren't indexed.
+    #[must_use]
+    pub fn canonical_type(&self, parsed: &Parsed, ty: &Expression) -> String {
+        self.canonical_type_at_depth(parsed, ty, 0)
+    }
+
+    fn canonical_type_at_depth(&self, parsed: &Parsed, ty: &Expression, depth: usize) -> String {
+        match ty {
+            Expression::Type(_, ty) => match ty {
+                Type::Bool => "bool".to_string(),
+                Type::String => "string".to_string(),
+                Type::Int(n) => format!("int{n}"),
+                Type::Uint(n) => format!("uint{n}"),
+                Type::Bytes(n) => format!("bytes{n}"),
+                Type::DynamicBytes => "bytes".to_string(),
+                Type::Function { .. } => "function".to_string(),
+                _ => "address".to_string(),
+            },
+            Expression::ArraySubscript(_, inner, size) => {
+                let size = size.as_ref().map_or("", |size| {
+                    let loc = size.loc();
+                    parsed.src[loc.start()..loc.end()].trim()
+                });
+                format!("{}[{size}]", self.canonical_type_at_depth(parsed, inner, depth))
+            }
+            Expression::Variable(name) | Expression::MemberAccess(_, _, name) => {
+                match self.types.get(name.name.as_str()) {
+                    Some(AbiTypeDef::Struct(struct_parsed, fields)) if depth < Self::MAX_DEPTH => {
+                        let fields = fields
+                            .iter()
+                            .map(|field| {
+                                self.canonical_type_at_depth(struct_parsed, &field.ty, depth + 1)
+                            })
+                            .collect::<Vec<_>>();
+                        format!("({})", fields.join(","))
+                    }
+                    Some(AbiTypeDef::Enum) => "uint8".to_string(),
+                    Some(AbiTypeDef::Value(value_parsed, underlying)) => {
+                        self.canonical_type_at_depth(value_parsed, underlying, depth)
+                    }
+                    // Contracts, interfaces, and anything we can't resolve.
+                    _ => "address".to_string(),
+                }
+            }
+            _ => "address".to_string(),
+        }
+    }
+}
+
+// ===================================
+// ======== For AST traversal ========
+// ===================================
+
+/// Returns every function, modifier, and constructor in `pt`, both free and in contracts, in
+/// source order.
+pub fn function_definitions(pt: &SourceUnit) -> impl Iterator<Item = &FunctionDefinition> {
+    pt.0.iter().flat_map(|element| match element {
+        SourceUnitPart::FunctionDefinition(f) => vec![f.as_ref()],
+        SourceUnitPart::ContractDefinition(c) => c
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                ContractPart::FunctionDefinition(f) => Some(f.as_ref()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    })
+}
+
+/// Calls `f` on every expression in the function bodies and state variable initializers of all
+/// contracts in `pt`, including sub-expressions.
+pub fn visit_contract_expressions<'a>(pt: &'a SourceUnit, f: &mut impl FnMut(&'a Expression)) {
+    for element in &pt.0 {
+        if let SourceUnitPart::ContractDefinition(c) = element {
+            for el in &c.parts {
+                match el {
+                    ContractPart::FunctionDefinition(func) => {
+                        if let Some(body) = &func.body {
+                            visit_statement_expressions(body, f);
+                        }
+                    }
+                    ContractPart::VariableDefinition(v) => {
+                        if let Some(initializer) = &v.initializer {
+                            visit_expressions(initializer, f);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Calls `f` on `stmt` and every statement nested in it, depth first. Assembly blocks are not
+/// entered.
+pub fn visit_statements<'a>(stmt: &'a Statement, f: &mut impl FnMut(&'a Statement)) {
+    f(stmt);
+    match stmt {
+        Statement::Block { statements, .. } => {
+            for s in statements {
+                visit_statements(s, f);
+            }
+        }
+        Statement::If(_, _, then_stmt, else_stmt) => {
+            visit_statements(then_stmt, f);
+            if let Some(else_stmt) = else_stmt {
+                visit_statements(else_stmt, f);
+            }
+        }
+        Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => visit_statements(body, f),
+        Statement::For(_, init, _, _, body) => {
+            if let Some(init) = init {
+                visit_statements(init, f);
+            }
+            if let Some(body) = body {
+                visit_statements(body, f);
+            }
+        }
+        Statement::Try(_, _, returns, catch_clauses) => {
+            if let Some((_, body)) = returns {
+                visit_statements(body, f);
+            }
+            for clause in catch_clauses {
+                match clause {
+                    CatchClause::Simple(_, _, body) | CatchClause::Named(_, _, _, body) => {
+                        visit_statements(body, f);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Calls `f` on every expression in `stmt` and its nested statements, including sub-expressions.
+pub fn visit_statement_expressions<'a>(stmt: &'a Statement, f: &mut impl FnMut(&'a Expression)) {
+    visit_statements(stmt, &mut |s| match s {
+        Statement::Expression(_, expr) |
+        Statement::If(_, expr, ..) |
+        Statement::While(_, expr, _) |
+        Statement::DoWhile(_, _, expr) |
+        Statement::Emit(_, expr) |
+        Statement::Try(_, expr, ..) |
+        Statement::VariableDefinition(_, _, Some(expr)) |
+        Statement::Return(_, Some(expr)) => visit_expressions(expr, f),
+        Statement::For(_, _, cond, next, _) => {
+            for expr in [cond, next].into_iter().flatten() {
+                visit_expressions(expr, f);
+            }
+        }
+        Statement::Revert(_, _, args) => {
+            for expr in args {
+                visit_expressions(expr, f);
+            }
+        }
+        Statement::RevertNamedArgs(_, _, args) | Statement::Args(_, args) => {
+            for arg in args {
+                visit_expressions(&arg.expr, f);
+            }
+        }
+        _ => {}
+    });
+}
+
+/// Calls `f` on `expr` and every sub-expression of it, depth first.
+pub fn visit_expressions<'a>(expr: &'a Expression, f: &mut impl FnMut(&'a Expression)) {
+    f(expr);
+    match expr {
+        Expression::MemberAccess(_, inner, _) => visit_expressions(inner, f),
+        Expression::ArraySubscript(_, array, index) => {
+            visit_expressions(array, f);
+            if let Some(index) = index {
+                visit_expressions(index, f);
+            }
+        }
+        Expression::ArraySlice(_, array, start, end) => {
+            visit_expressions(array, f);
+            for bound in [start, end].into_iter().flatten() {
+                visit_expressions(bound, f);
+            }
+        }
+        Expression::ConditionalOperator(_, cond, then_expr, else_expr) => {
+            visit_expressions(cond, f);
+            visit_expressions(then_expr, f);
+            visit_expressions(else_expr, f);
+        }
+        Expression::FunctionCall(_, callee, args) => {
+            visit_expressions(callee, f);
+            for arg in args {
+                visit_expressions(arg, f);
+            }
+        }
+        Expression::FunctionCallBlock(_, callee, block) => {
+            visit_expressions(callee, f);
+            if let Statement::Args(_, args) = block.as_ref() {
+                for arg in args {
+                    visit_expressions(&arg.expr, f);
+                }
+            }
+        }
+        Expression::NamedFunctionCall(_, callee, args) => {
+            visit_expressions(callee, f);
+            for arg in args {
+                visit_expressions(&arg.expr, f);
+            }
+        }
+        Expression::ArrayLiteral(_, items) => {
+            for item in items {
+                visit_expressions(item, f);
+            }
+        }
+        Expression::List(_, params) => {
+            for param in params.iter().filter_map(|(_, param)| param.as_ref()) {
+                visit_expressions(&param.ty, f);
+            }
+        }
+        _ => {
+            let components: [Option<&Expression>; 2] = expr.components().into();
+            for inner in components.into_iter().flatten() {
+                visit_expressions(inner, f);
+            }
+        }
+    }
+}
+
+// ====================================
+// ======== For import helpers ========
+// ====================================
+
+#[must_use]
+/// Returns the location and the raw path of every import directive in the file, in source order.
+pub fn import_paths(parsed: &Parsed) -> Vec<(Loc, String)> {
+    parsed
+        .pt
+        .0
+        .iter()
+        .filter_map(|element| match element {
+            SourceUnitPart::ImportDirective(import) => {
+                let loc = import.loc();
+                let text = parsed.src.get(loc.start()..loc.end())?;
+                let captures = RE_IMPORT_PATH.captures(text)?;
+                Some((loc, captures[1].to_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[must_use]
+/// Resolves an import path against the importing file.
+///
+/// Relative imports (`./`, `../`) are resolved from the importing file's directory, and all other
+/// imports from the project root. Remapped library imports are returned as-is, so they simply
+/// won't match any project file.
+pub fn resolve_import(file: &Path, import: &str) -> PathBuf {
+    let joined = if import.starts_with("./") || import.starts_with("../") {
+        file.parent().unwrap_or_else(|| Path::new(".")).join(import)
+    } else {
+        PathBuf::from(import)
+    };
+    normalize_path(&joined)
+}
+
+#[must_use]
+/// Lexically normalizes a path by dropping `.` components and folding `..` components, so the
+/// same file is always represented the same way (e.g. `./src/../src/Foo.sol` -> `src/Foo.sol`).
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(normalized.components().next_back(), Some(Component::Normal(_))) {
+                    normalized.pop();
+                } else {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
 // ===========================
 // ======== For tests ========
 // ===========================
@@ -265,8 +1480,23 @@ impl ExpectedFindings {
     /// # Panics
     ///
     /// In practice this should not panic unless one of validations fails.
-    #[allow(clippy::too_many_lines)]
     pub fn assert_eq(&self, src: &str, validate: &dyn Fn(&Parsed) -> Vec<InvalidItem>) {
+        self.assert_eq_with_config(src, &FileConfig::default(), validate);
+    }
+
+    /// Same as [`ExpectedFindings::assert_eq`], but with the given `.scopelint` configuration
+    /// attached to each file. Use this to test opt-in rules.
+    ///
+    /// # Panics
+    ///
+    /// In practice this should not panic unless one of validations fails.
+    #[allow(clippy::too_many_lines)]
+    pub fn assert_eq_with_config(
+        &self,
+        src: &str,
+        file_config: &FileConfig,
+        validate: &dyn Fn(&Parsed) -> Vec<InvalidItem>,
+    ) {
         /// Generates a `Parsed` struct from the given data.
         fn to_parsed(
             path_name: &str,
@@ -275,6 +1505,7 @@ impl ExpectedFindings {
             comments: Comments,
             inline_config: InlineConfig,
             invalid_inline_config_items: Vec<(solang_parser::pt::Loc, InvalidInlineConfigItem)>,
+            file_config: &FileConfig,
         ) -> Parsed {
             Parsed {
                 file: PathBuf::from(path_name),
@@ -283,7 +1514,7 @@ impl ExpectedFindings {
                 comments,
                 inline_config,
                 invalid_inline_config_items,
-                file_config: crate::check::file_config::FileConfig::default(),
+                file_config: file_config.clone(),
                 path_config: CheckPaths::default(),
             }
         }
@@ -295,7 +1526,7 @@ impl ExpectedFindings {
         // recreate `inline_config` and `invalid_inline_config_items` because they cannot be cloned.
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
-        let inline_config = InlineConfig::new(inline_config_items, src);
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
         let invalid_items_script_helper = validate(&to_parsed(
             "./script/MyContract.sol",
             src,
@@ -303,11 +1534,12 @@ impl ExpectedFindings {
             comments.clone(),
             inline_config,
             invalid_inline_config_items,
+            file_config,
         ));
 
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
-        let inline_config = InlineConfig::new(inline_config_items, src);
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
         let invalid_items_script = validate(&to_parsed(
             "./script/MyContract.s.sol",
             src,
@@ -315,11 +1547,12 @@ impl ExpectedFindings {
             comments.clone(),
             inline_config,
             invalid_inline_config_items,
+            file_config,
         ));
 
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
-        let inline_config = InlineConfig::new(inline_config_items, src);
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
         let invalid_items_src = validate(&to_parsed(
             "./src/MyContract.sol",
             src,
@@ -327,11 +1560,12 @@ impl ExpectedFindings {
             comments.clone(),
             inline_config,
             invalid_inline_config_items,
+            file_config,
         ));
 
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
-        let inline_config = InlineConfig::new(inline_config_items, src);
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
         let invalid_items_test_helper = validate(&to_parsed(
             "./test/MyContract.sol",
             src,
@@ -339,11 +1573,12 @@ impl ExpectedFindings {
             comments.clone(),
             inline_config,
             invalid_inline_config_items,
+            file_config,
         ));
 
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
-        let inline_config = InlineConfig::new(inline_config_items, src);
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
         let invalid_items_test = validate(&to_parsed(
             "./test/MyContract.t.sol",
             src,
@@ -351,11 +1586,12 @@ impl ExpectedFindings {
             comments.clone(),
             inline_config,
             invalid_inline_config_items,
+            file_config,
         ));
 
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
-        let inline_config = InlineConfig::new(inline_config_items, src);
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
         let invalid_items_handler = validate(&to_parsed(
             "./test/MyContract.handler.sol",
             src,
@@ -363,6 +1599,7 @@ impl ExpectedFindings {
             comments,
             inline_config,
             invalid_inline_config_items,
+            file_config,
         ));
 
         //  Execute tests.
@@ -0,0 +1,120 @@
+use crate::check::{
+    utils::{function_definitions, visit_statement_expressions, InvalidItem, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{helpers::CodeLocation, pt::Expression};
+
+#[must_use]
+/// Validates that booleans are used directly instead of being compared to `true` or `false`,
+/// e.g. `paused` instead of `paused == true` and `!paused` instead of `paused == false`.
+///
+/// This rule is opt-in and only runs when `[rules.bool] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.file_config.bool_rule_enabled() {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for body in function_definitions(&parsed.pt).filter_map(|f| f.body.as_ref()) {
+        visit_statement_expressions(body, &mut |expr| {
+            let (loc, left, right, is_equal) = match expr {
+                Expression::Equal(loc, left, right) => (loc, left, right, true),
+                Expression::NotEqual(loc, left, right) => (loc, left, right, false),
+                _ => return,
+            };
+            let (operand, value) = match (left.as_ref(), right.as_ref()) {
+                (operand, Expression::BoolLiteral(_, value)) |
+                (Expression::BoolLiteral(_, value), operand) => (operand, *value),
+                _ => return,
+            };
+            let replacement = simplified(&parsed.src, operand, is_equal != value);
+            let help = format!("replace with `{replacement}`");
+            invalid_items.push(
+                InvalidItem::new(
+                    ValidatorKind::BoolComparison,
+                    parsed,
+                    *loc,
+                    format!("Redundant comparison to `{value}`, use `{replacement}` instead"),
+                )
+                .with_replacement(
+                    parsed,
+                    loc.start()..loc.end(),
+                    replacement,
+                    help,
+                ),
+            );
+        });
+    }
+    invalid_items
+}
+
+/// Returns the source of `operand`, negated if `negate` is set. Operands that bind less tightly
+/// than `!` are parenthesized, and negated negations are unwrapped.
+fn simplified(src: &str, operand: &Expression, negate: bool) -> String {
+    let text = |expr: &Expression| {
+        let text = &src[expr.loc().start()..expr.loc().end()];
+        // The location of a parenthesized expression excludes the parentheses.
+        match expr {
+            Expression::Parenthesis(..) => format!("({text})"),
+            _ => text.to_string(),
+        }
+    };
+    if !negate {
+        return text(operand);
+    }
+    match operand {
+        Expression::Not(_, inner) => text(inner),
+        Expression::Variable(_) |
+        Expression::BoolLiteral(..) |
+        Expression::MemberAccess(..) |
+        Expression::ArraySubscript(..) |
+        Expression::FunctionCall(..) |
+        Expression::Parenthesis(..) => format!("!{}", text(operand)),
+        _ => format!("!({})", text(operand)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            contract MyContract {
+                function f(bool a, uint256 b) external {
+                    require(a == true);
+                    if (false != a || b == 1) {}
+                    while (a != true) {}
+                    bool c = a;
+                    c = !a;
+                }
+            }
+        ";
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.bool]\nenabled = true").unwrap();
+        let expected_findings = ExpectedFindings::new(3);
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_fix() {
+        let content = "contract C {\n    function f(bool _a, uint256 _b) external {\n        require(_a == true);\n        require(_a == false && (_b > 1) != true);\n        require(_b > 1 == false || !_a == false);\n    }\n}\n";
+        let mut parsed = parse_source(Path::new("./src/C.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.bool]\nenabled = true").unwrap();
+        let fixes: Vec<_> = validate(&parsed)
+            .into_iter()
+            .map(|item| {
+                let fix = item.fix.unwrap();
+                assert!(fix.automatic);
+                fix.edits[0].replacement.clone()
+            })
+            .collect();
+        assert_eq!(fixes, ["_a", "!_a", "!(_b > 1)", "!(_b > 1)", "_a"]);
+    }
+}
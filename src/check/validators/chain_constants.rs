@@ -0,0 +1,112 @@
+use crate::check::{
+    utils::{visit_expressions, FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::pt::{
+    ContractPart, Expression, SourceUnitPart, Type, VariableAttribute, VariableDefinition,
+};
+
+/// Names of constants holding chain-dependent values when `[rules.immutable] patterns` is not
+/// set.
+pub const DEFAULT_PATTERNS: [&str; 2] = ["*CHAIN_ID*", "*CHAINID*"];
+
+#[must_use]
+/// Validates that constants of src contracts don't hold values that differ between chains.
+///
+/// A constant holds a chain-dependent value if it's an address, if its initializer contains an
+/// address literal, e.g. `IERC20(0xA0b8...)`, or if its name matches one of the configured
+/// patterns. Such values must be `immutable` and set in the constructor, so the same code can be
+/// deployed on every chain. This rule is opt-in and only runs when `[rules.immutable] enabled =
+/// true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(patterns) = parsed.file_config.chain_value_patterns() else {
+        return Vec::new();
+    };
+    if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for element in &parsed.pt.0 {
+        let SourceUnitPart::ContractDefinition(c) = element else { continue };
+        for part in &c.parts {
+            let ContractPart::VariableDefinition(v) = part else { continue };
+            let Some(name) = &v.name else { continue };
+            if !v.attrs.iter().any(|attr| matches!(attr, VariableAttribute::Constant(_))) {
+                continue;
+            }
+            let reason = if holds_address(v) {
+                "holds an address"
+            } else if patterns.iter().any(|pattern| pattern.is_match(&name.name)) {
+                "is named like a chain-dependent value"
+            } else {
+                continue;
+            };
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::ChainConstant,
+                parsed,
+                name.loc,
+                format!(
+                    "Constant '{}' {reason}, make it `immutable` and set it in the constructor",
+                    name.name
+                ),
+            ));
+        }
+    }
+    invalid_items
+}
+
+/// Returns whether `v` is an address, or is initialized from an address literal.
+fn holds_address(v: &VariableDefinition) -> bool {
+    if matches!(v.ty, Expression::Type(_, Type::Address | Type::AddressPayable)) {
+        return true;
+    }
+    let mut found = false;
+    if let Some(initializer) = &v.initializer {
+        visit_expressions(initializer, &mut |expr| {
+            found |= match expr {
+                Expression::AddressLiteral(..) => true,
+                // Checksummed addresses are hex number literals of 20 bytes.
+                Expression::HexNumberLiteral(_, hex, None) => {
+                    hex.trim_start_matches("0x").replace('_', "").len() == 40
+                }
+                _ => false,
+            };
+        });
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            contract MyContract {
+                address public constant WETH = 0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2;
+                IERC20 internal constant USDC = IERC20(0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48);
+                uint256 public constant MAINNET_CHAIN_ID = 1;
+                uint256 public constant FEE = 0x10;
+                bytes32 public constant ROLE = keccak256('ROLE');
+                address public immutable owner;
+                uint256 public immutable CHAIN_ID;
+            }
+        ";
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.immutable]\nenabled = true").unwrap();
+        let expected_findings = ExpectedFindings { src: 3, ..ExpectedFindings::default() };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+
+        let file_config =
+            FileConfig::from_toml("[rules.immutable]\nenabled = true\npatterns = [\"ROLE\"]")
+                .unwrap();
+        let expected_findings = ExpectedFindings { src: 3, ..ExpectedFindings::default() };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+}
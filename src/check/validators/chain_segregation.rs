@@ -0,0 +1,204 @@
+use crate::check::{
+    utils::{import_paths, normalize_path, resolve_import, InvalidItem, ValidatorKind},
+    Parsed,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::PathBuf,
+};
+
+#[must_use]
+/// Validates that files in one chain's directory never import another chain's files.
+///
+/// Imports are followed transitively through other project files. This rule is opt-in and only
+/// runs when `[rules.chain.dirs]` is configured in `.scopelint`.
+pub fn validate(files: &[Parsed]) -> Vec<InvalidItem> {
+    let Some(file_config) = files.first().map(|parsed| &parsed.file_config) else {
+        return Vec::new();
+    };
+    if !file_config.has_chain_dirs() {
+        return Vec::new();
+    }
+
+    // Build the import graph between project files, keyed by normalized path.
+    let imports_by_file: HashMap<PathBuf, Vec<PathBuf>> = files
+        .iter()
+        .map(|parsed| {
+            let imports = import_paths(parsed)
+                .into_iter()
+                .map(|(_, import)| resolve_import(&parsed.file, &import))
+                .collect();
+            (normalize_path(&parsed.file), imports)
+        })
+        .collect();
+    let chain_by_file: HashMap<PathBuf, &str> = files
+        .iter()
+        .filter_map(|parsed| {
+            let chain = file_config.chain_for_file(&parsed.file)?;
+            Some((normalize_path(&parsed.file), chain))
+        })
+        .collect();
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for parsed in files {
+        let Some(chain) = file_config.chain_for_file(&parsed.file) else {
+            continue;
+        };
+
+        for (loc, import) in import_paths(parsed) {
+            let target = resolve_import(&parsed.file, &import);
+            if let Some((leaked_file, leaked_chain)) =
+                find_other_chain_file(&target, chain, &imports_by_file, &chain_by_file)
+            {
+                let via = if leaked_file == target {
+                    String::new()
+                } else {
+                    format!(" via '{}'", leaked_file.display())
+                };
+                invalid_items.push(InvalidItem::new(
+                    ValidatorKind::Chain,
+                    parsed,
+                    loc,
+                    format!(
+                        "'{chain}' file imports '{import}'{via}, which belongs to chain '{leaked_chain}'"
+                    ),
+                ));
+            }
+        }
+    }
+    invalid_items
+}
+
+/// Walks the import graph breadth-first from `start` and returns the first reachable file that
+/// belongs to a chain other than `chain`, along with that chain's name.
+fn find_other_chain_file<'a>(
+    start: &PathBuf,
+    chain: &str,
+    imports_by_file: &HashMap<PathBuf, Vec<PathBuf>>,
+    chain_by_file: &HashMap<PathBuf, &'a str>,
+) -> Option<(PathBuf, &'a str)> {
+    let mut visited: HashSet<&PathBuf> = HashSet::new();
+    let mut queue: VecDeque<&PathBuf> = VecDeque::from([start]);
+
+    while let Some(file) = queue.pop_front() {
+        if !visited.insert(file) {
+            continue;
+        }
+        if let Some(other) = chain_by_file.get(file).filter(|other| **other != chain) {
+            return Some((file.clone(), other));
+        }
+        if let Some(imports) = imports_by_file.get(file) {
+            queue.extend(imports);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        check::{comments::Comments, file_config::FileConfig, inline_config::InlineConfig, Parsed},
+        foundry_config::CheckPaths,
+    };
+    use itertools::Itertools;
+
+    const CONFIG: &str = r#"
+[rules.chain.dirs]
+mainnet = "script/deploy/mainnet"
+optimism = "script/deploy/optimism"
+"#;
+
+    fn parsed_file(path: &str, src: &str, file_config: &FileConfig) -> Parsed {
+        let (pt, comments) = crate::parser::parse_solidity(src, 0).expect("parse");
+        let comments = Comments::new(comments, src);
+        let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
+            comments.parse_inline_config_items().partition_result();
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
+        Parsed {
+            file: PathBuf::from(path),
+            src: src.to_string(),
+            pt,
+            comments,
+            inline_config,
+            invalid_inline_config_items,
+            file_config: file_config.clone(),
+            path_config: CheckPaths::default(),
+        }
+    }
+
+    fn project(file_config: &FileConfig) -> Vec<Parsed> {
+        vec![
+            parsed_file(
+                "./script/deploy/mainnet/Deploy.s.sol",
+                r#"import {MainnetAddresses} from "./Addresses.sol";
+                contract Deploy {}"#,
+                file_config,
+            ),
+            parsed_file(
+                "./script/deploy/mainnet/Addresses.sol",
+                "contract MainnetAddresses {}",
+                file_config,
+            ),
+            parsed_file(
+                "./script/deploy/optimism/Deploy.s.sol",
+                r#"import {MainnetAddresses} from "../mainnet/Addresses.sol";
+                import {Shared} from "script/shared/Shared.sol";
+                contract Deploy {}"#,
+                file_config,
+            ),
+            parsed_file(
+                "./script/shared/Shared.sol",
+                r#"import {OptimismAddresses} from "../deploy/optimism/Addresses.sol";
+                contract Shared {}"#,
+                file_config,
+            ),
+            parsed_file(
+                "./script/deploy/optimism/Addresses.sol",
+                "contract OptimismAddresses {}",
+                file_config,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml(CONFIG).unwrap();
+        let items = validate(&project(&file_config));
+
+        // Only the direct import of mainnet addresses from an optimism script is flagged. The
+        // optimism script importing `Shared`, which imports optimism addresses, is fine.
+        assert_eq!(
+            items.len(),
+            1,
+            "{:?}",
+            items.iter().map(InvalidItem::description).collect_vec()
+        );
+        assert_eq!(items[0].file, "./script/deploy/optimism/Deploy.s.sol");
+        assert_eq!(items[0].line, 1);
+    }
+
+    #[test]
+    fn test_validate_transitive_import() {
+        let file_config = FileConfig::from_toml(CONFIG).unwrap();
+        let mut files = project(&file_config);
+        // A mainnet script that pulls in optimism addresses through a shared helper.
+        files.push(parsed_file(
+            "./script/deploy/mainnet/Upgrade.s.sol",
+            r#"import {Shared} from "../../shared/Shared.sol";
+            contract Upgrade {}"#,
+            &file_config,
+        ));
+        let items = validate(&files);
+
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().any(|item| item.file == "./script/deploy/mainnet/Upgrade.s.sol" &&
+            item.text.contains("via 'script/deploy/optimism/Addresses.sol'")));
+    }
+
+    #[test]
+    fn test_validate_not_configured() {
+        let files = project(&FileConfig::default());
+        assert!(validate(&files).is_empty());
+    }
+}
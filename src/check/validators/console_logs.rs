@@ -0,0 +1,110 @@
+use crate::check::{
+    utils::{
+        import_paths, visit_contract_expressions, FileKind, InvalidItem, IsFileKind, ValidatorKind,
+    },
+    Parsed,
+};
+use solang_parser::pt::Expression;
+
+/// The console logging libraries of Forge and Hardhat, e.g. `forge-std/console2.sol`.
+const CONSOLE_LIBRARIES: [&str; 3] = ["console", "console2", "safeconsole"];
+
+#[must_use]
+/// Validates that src files don't import a console logging library, such as
+/// `forge-std/console.sol` or `hardhat/console.sol`, or call one, e.g. `console.log`.
+///
+/// Console logging is only meant for debugging, but easily slips into a deployment, where it
+/// costs gas and makes the bytecode differ from the audited one.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for (loc, path) in import_paths(parsed) {
+        let file_name = path.rsplit('/').next().unwrap_or(&path);
+        let is_console =
+            file_name.strip_suffix(".sol").is_some_and(|name| CONSOLE_LIBRARIES.contains(&name));
+        if is_console {
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::Console,
+                parsed,
+                loc,
+                format!(
+                    "Import of '{path}' in a src file, remove console logging before deploying"
+                ),
+            ));
+        }
+    }
+
+    visit_contract_expressions(&parsed.pt, &mut |expr| {
+        let Expression::FunctionCall(loc, function, _) = expr else { return };
+        let Expression::MemberAccess(_, library, member) = function.as_ref() else { return };
+        let Expression::Variable(library) = library.as_ref() else { return };
+        if CONSOLE_LIBRARIES.contains(&library.name.as_str()) {
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::Console,
+                parsed,
+                *loc,
+                format!(
+                    "Call to `{}.{}` in a src file, remove console logging before deploying",
+                    library.name, member.name
+                ),
+            ));
+        }
+    });
+    invalid_items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let content = r#"
+            import {console} from "forge-std/console.sol";
+            import {console2} from "forge-std/src/console2.sol";
+            import "hardhat/console.sol";
+            import {Console} from "./Console.sol";
+
+            contract MyContract {
+                function deposit(uint256 amount) external {
+                    console.log("deposit", amount);
+                    console2.logUint(amount);
+                    Console.log(amount);
+                }
+            }
+        "#;
+        let expected_findings = ExpectedFindings { src: 5, ..ExpectedFindings::default() };
+        expected_findings.assert_eq(content, &validate);
+
+        let content = r"
+            contract MyContract {
+                function deposit(uint256 amount) external {}
+            }
+        ";
+        ExpectedFindings::default().assert_eq(content, &validate);
+    }
+
+    #[test]
+    fn test_messages() {
+        let content = r#"
+            import {console2} from "forge-std/console2.sol";
+            contract Vault {
+                function withdraw() external { console2.log(msg.sender); }
+            }
+        "#;
+        let parsed = parse_source(Path::new("./src/Vault.sol"), content).unwrap();
+        let texts: Vec<String> = validate(&parsed).into_iter().map(|item| item.text).collect();
+        assert_eq!(
+            texts,
+            [
+                "Import of 'forge-std/console2.sol' in a src file, remove console logging before deploying",
+                "Call to `console2.log` in a src file, remove console logging before deploying",
+            ]
+        );
+    }
+}
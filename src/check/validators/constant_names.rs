@@ -6,16 +6,20 @@ use regex::Regex;
 use solang_parser::pt::{ContractPart, SourceUnitPart, VariableAttribute, VariableDefinition};
 use std::{path::Path, sync::LazyLock};
 
-// A regex matching valid constant names, see the `validate_constant_names_regex` test for examples.
+// A regex matching valid constant names, see the `test_is_valid_constant_name` test for examples.
+// Names are `SCREAMING_SNAKE_CASE` segments separated by single underscores, with no leading or
+// trailing underscores, and every segment must contain a letter (or `$`), so `_FOO`, `FOO__BAR`,
+// and `FEE_10000` are invalid.
 static RE_VALID_CONSTANT_NAME: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^(?:[$_]*[A-Z0-9][$_]*){1,}$").unwrap());
+    LazyLock::new(|| Regex::new(r"^[A-Z$][A-Z0-9$]*(?:_[A-Z0-9$]*[A-Z$][A-Z0-9$]*)*$").unwrap());
 
 const fn is_matching_file(_file: &Path) -> bool {
     true
 }
 
 #[must_use]
-/// Validates that constant and immutable variable names are in `ALL_CAPS`.
+/// Validates that constant and immutable variable names are in `ALL_CAPS`, with meaningful
+/// underscore-separated segments.
 pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     if !is_matching_file(&parsed.file) {
         return Vec::new();
@@ -48,6 +52,26 @@ fn is_valid_constant_name(name: &str) -> bool {
     RE_VALID_CONSTANT_NAME.is_match(name)
 }
 
+// Converts a name such as `maxSupply` or `_MAX__SUPPLY_` to `MAX_SUPPLY`, or returns `None` if the
+// converted name would still be invalid, such as `FEE_10000`.
+fn screaming_snake_case(name: &str) -> Option<String> {
+    let mut renamed = String::with_capacity(name.len() + 4);
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() &&
+            previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+        {
+            renamed.push('_');
+        }
+        if c != '_' || !(renamed.is_empty() || renamed.ends_with('_')) {
+            renamed.push(c.to_ascii_uppercase());
+        }
+        previous = Some(c);
+    }
+    let renamed = renamed.trim_end_matches('_').to_string();
+    is_valid_constant_name(&renamed).then_some(renamed)
+}
+
 fn validate_name(parsed: &Parsed, v: &VariableDefinition) -> Option<InvalidItem> {
     let is_constant = v
         .attrs
@@ -63,7 +87,12 @@ fn validate_name(parsed: &Parsed, v: &VariableDefinition) -> Option<InvalidItem>
         if is_valid_constant_name(name_string) {
             None
         } else {
-            Some(InvalidItem::new(ValidatorKind::Constant, parsed, name.loc, name_string.clone()))
+            let item =
+                InvalidItem::new(ValidatorKind::Constant, parsed, name.loc, name_string.clone());
+            match screaming_snake_case(name_string) {
+                Some(renamed) => Some(item.with_suggested_rename(parsed, name.loc, renamed)),
+                None => Some(item),
+            }
         }
     })
 }
@@ -99,25 +128,15 @@ mod tests {
     fn test_is_valid_constant_name() {
         let allowed_names = vec![
             "MAX_UINT256",
-            "256_MAXUINT",
-            "256_MAX_11_UINT",
+            "ERC20_TOKEN",
+            "UNISWAP_V3_FACTORY",
             "VARIABLE",
             "VARIABLE_NAME",
-            "VARIABLE_NAME_",
-            "VARIABLE___NAME",
             "VARIABLE_NAME_WOW",
             "VARIABLE_NAME_WOW_AS_MANY_UNDERSCORES_AS_YOU_WANT",
-            "__VARIABLE",
-            "_VARIABLE__NAME",
-            "_VARIABLE_NAME__",
-            "_VARIABLE_NAME_WOW",
-            "_VARIABLE_NAME_WOW_AS_MANY_UNDERSCORES_AS_YOU_WANT",
             "$VARIABLE_NAME",
-            "_$VARIABLE_NAME_",
             "$_VARIABLE_NAME$",
-            "_$VARIABLE_NAME$_",
             "$_VARIABLE_NAME_$",
-            "$_VARIABLE__NAME_",
         ];
 
         let disallowed_names = [
@@ -131,6 +150,22 @@ mod tests {
             "_VARIABLE_name",
             "$VARIABLe",
             "$VARIABLE_name",
+            // Digit-only segments.
+            "256_MAXUINT",
+            "256_MAX_11_UINT",
+            "FEE_10000",
+            // Double, leading, or trailing underscores.
+            "VARIABLE_NAME_",
+            "VARIABLE___NAME",
+            "__VARIABLE",
+            "_VARIABLE__NAME",
+            "_VARIABLE_NAME__",
+            "_VARIABLE_NAME_WOW",
+            "_VARIABLE_NAME_WOW_AS_MANY_UNDERSCORES_AS_YOU_WANT",
+            "_$VARIABLE_NAME_",
+            "_$VARIABLE_NAME$_",
+            "$_VARIABLE__NAME_",
+            "_GOOD__IMMUTABLE_",
         ];
 
         for name in allowed_names {
@@ -141,4 +176,27 @@ mod tests {
             assert!(!is_valid_constant_name(name), "{name}");
         }
     }
+
+    #[test]
+    fn test_screaming_snake_case() {
+        let renames = [
+            ("variable", Some("VARIABLE")),
+            ("variableName", Some("VARIABLE_NAME")),
+            ("_variable_Name", Some("VARIABLE_NAME")),
+            ("uint256Max", Some("UINT256_MAX")),
+            ("VARIABLe", Some("VARIABLE")),
+            ("$VARIABLE_name", Some("$VARIABLE_NAME")),
+            ("_GOOD__IMMUTABLE_", Some("GOOD_IMMUTABLE")),
+            ("_VARIABLE___NAME__", Some("VARIABLE_NAME")),
+            ("FEE_10000", None),
+            ("256_MAXUINT", None),
+        ];
+
+        for (name, expected) in renames {
+            assert_eq!(screaming_snake_case(name).as_deref(), expected, "{name}");
+            if let Some(renamed) = expected {
+                assert!(is_valid_constant_name(renamed), "{renamed}");
+            }
+        }
+    }
 }
@@ -1,10 +1,20 @@
+use itertools::Itertools;
 use regex::Regex;
 use solang_parser::pt::{ContractPart, SourceUnitPart, VariableDefinition};
+use std::{collections::HashMap, sync::LazyLock};
 
 use crate::check::{
+    scan::ScanBudget,
     utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
     Parsed,
 };
+
+// Regex to extract the string of a `StringLiteral` from its debug representation.
+static RE_STRING_LITERAL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"string:\s*"([^"]+)"#).unwrap());
+
+// Regex to extract the parameters of a type string, e.g. `Permit(address owner,uint256 value)`.
+static RE_TYPE_PARAMETERS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\(([^)]+)\)").unwrap());
 #[must_use]
 // Validates that EIP712 typehash parameter counts match their usage in abi.encode calls.
 pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
@@ -30,6 +40,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     }
 
     // Validate typehashes - extract parameter count and compare with usage
+    let mut budget = ScanBudget::new(&parsed.file, ValidatorKind::Eip712);
     for (typehash_name, expected_struct_name, loc, keccak_string) in typehash_variables {
         if let Some(keccak_content) = &keccak_string {
             // Extract parameter count from keccak256 string
@@ -38,7 +49,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
             let param_count = extract_parameter_count(keccak_content);
 
             // Find all usages of this typehash and check each one
-            let usages = find_all_typehash_usages(parsed, &typehash_name);
+            let usages = find_all_typehash_usages(parsed, &typehash_name, &mut budget);
 
             for usage_param_count in usages {
                 if usage_param_count != param_count {
@@ -64,6 +75,69 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     invalid_items
 }
 
+#[must_use]
+/// Validates that typehash constants in src contracts agree across the project.
+///
+/// Two constants hashing the same type string, or constants with the same name hashing different
+/// type strings, usually mean a library and its consumers drifted apart after a copy-paste. Each
+/// type string's and name's first constant, in file order, is the reference the others are
+/// compared to.
+pub fn validate_duplicates(files: &[Parsed]) -> Vec<InvalidItem> {
+    // Type string -> the first constant hashing it, and where it's defined.
+    let mut by_type_string: HashMap<String, (String, String)> = HashMap::new();
+    // Constant name -> the type string of its first definition, and where that is.
+    let mut by_name: HashMap<String, (String, String)> = HashMap::new();
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    let src_files =
+        files.iter().filter(|parsed| is_matching_file(parsed)).sorted_by_key(|parsed| &parsed.file);
+    for parsed in src_files {
+        for element in &parsed.pt.0 {
+            let SourceUnitPart::ContractDefinition(c) = element else { continue };
+            let location = c.name.as_ref().map_or_else(
+                || parsed.file.display().to_string(),
+                |name| format!("{}:{}", parsed.file.display(), name.name),
+            );
+            for part in &c.parts {
+                let ContractPart::VariableDefinition(v) = part else { continue };
+                let Some((name, _, loc, Some(type_string))) = extract_typehash_variable(v) else {
+                    continue;
+                };
+
+                match by_name.get(&name) {
+                    Some((first_type_string, first_location))
+                        if *first_type_string != type_string =>
+                    {
+                        invalid_items.push(InvalidItem::new(
+                            ValidatorKind::Eip712,
+                            parsed,
+                            loc,
+                            format!("Typehash '{name}' hashes '{type_string}' but '{first_type_string}' in {first_location}"),
+                        ));
+                    }
+                    Some(_) => {}
+                    None => {
+                        by_name.insert(name.clone(), (type_string.clone(), location.clone()));
+                    }
+                }
+                match by_type_string.get(&type_string) {
+                    Some((first_name, first_location)) => {
+                        invalid_items.push(InvalidItem::new(
+                            ValidatorKind::Eip712,
+                            parsed,
+                            loc,
+                            format!("Typehash '{name}' hashes the same type string as '{first_name}' in {first_location}, reuse that constant instead"),
+                        ));
+                    }
+                    None => {
+                        by_type_string.insert(type_string, (name, location.clone()));
+                    }
+                }
+            }
+        }
+    }
+    invalid_items
+}
+
 fn is_matching_file(parsed: &Parsed) -> bool {
     parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
 }
@@ -96,8 +170,7 @@ fn extract_keccak256_string(v: &VariableDefinition) -> Option<String> {
         let source_snippet = format!("{initializer:?}");
 
         // Extract string from StringLiteral structure
-        let re = Regex::new(r#"string:\s*"([^"]+)"#).ok()?;
-        if let Some(captures) = re.captures(&source_snippet) {
+        if let Some(captures) = RE_STRING_LITERAL.captures(&source_snippet) {
             if let Some(string_content) = captures.get(1) {
                 return Some(string_content.as_str().to_string());
             }
@@ -111,27 +184,30 @@ fn extract_keccak256_string(v: &VariableDefinition) -> Option<String> {
 fn extract_parameter_count(keccak_string: &str) -> usize {
     // Example: "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
     // Extract the part between parentheses and count the parameters
-    let re = Regex::new(r"\(([^)]+)\)").ok();
-    if let Some(regex) = re {
-        if let Some(captures) = regex.captures(keccak_string) {
-            if let Some(params_str) = captures.get(1) {
-                // Split by comma and count
-                return params_str.as_str().split(',').count();
-            }
+    if let Some(captures) = RE_TYPE_PARAMETERS.captures(keccak_string) {
+        if let Some(params_str) = captures.get(1) {
+            // Split by comma and count
+            return params_str.as_str().split(',').count();
         }
     }
     0
 }
 
-// Find all usages of a typehash and return parameter counts
-fn find_all_typehash_usages(parsed: &Parsed, typehash_name: &str) -> Vec<usize> {
+// Find all usages of a typehash and return parameter counts. Nothing is found once the scan
+// `budget` is used up.
+fn find_all_typehash_usages(
+    parsed: &Parsed,
+    typehash_name: &str,
+    budget: &mut ScanBudget<'_>,
+) -> Vec<usize> {
     let source = &parsed.src;
     let mut usages = Vec::new();
 
     // Look for abi.encode patterns with the typehash and capture the parameters
-    let pattern = format!(r"abi\.encode\s*\(\s*{typehash_name}\s*,\s*([^)]+)\)");
+    let name = regex::escape(typehash_name);
+    let pattern = format!(r"abi\.encode\s*\(\s*{name}\s*,\s*([^)]+)\)");
     // Create regex to find abi.encode calls with our typehash
-    if let Ok(regex) = Regex::new(&pattern) {
+    if let Some(regex) = budget.regex(&pattern).filter(|_| budget.scan(source)) {
         // Find all matches in the source code
         for captures in regex.captures_iter(source) {
             // Extract the parameters part (captured group 1)
@@ -152,7 +228,8 @@ fn find_all_typehash_usages(parsed: &Parsed, typehash_name: &str) -> Vec<usize>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::check::utils::ExpectedFindings;
+    use crate::check::{parse_source, utils::ExpectedFindings};
+    use std::path::Path;
 
     #[test]
     fn test_validate() {
@@ -180,6 +257,53 @@ mod tests {
         expected_findings.assert_eq(content, &validate);
     }
 
+    fn duplicate_findings(files: &[(&str, &str)]) -> Vec<String> {
+        let parsed = files
+            .iter()
+            .map(|(path, src)| parse_source(Path::new(path), src).unwrap())
+            .collect::<Vec<_>>();
+        validate_duplicates(&parsed).into_iter().map(|item| item.text).collect()
+    }
+
+    #[test]
+    fn test_validate_duplicates() {
+        let library = r"
+            library PermitLib {
+                bytes32 internal constant PERMIT_TYPEHASH = keccak256('Permit(address owner,uint256 value,uint256 nonce)');
+                bytes32 internal constant DELEGATION_TYPEHASH = keccak256('Delegation(address delegatee,uint256 nonce)');
+            }
+        ";
+        let good = r"
+            contract Token {
+                bytes32 constant TRANSFER_TYPEHASH = keccak256('Transfer(address to,uint256 value)');
+                bytes32 constant MISSING_TYPEHASH;
+            }
+        ";
+        assert!(duplicate_findings(&[("./src/PermitLib.sol", library), ("./src/Token.sol", good)])
+            .is_empty());
+        // Only src files are compared.
+        assert!(duplicate_findings(&[
+            ("./src/PermitLib.sol", library),
+            ("./test/Token.t.sol", library)
+        ])
+        .is_empty());
+
+        let bad = r"
+            contract Token {
+                bytes32 constant PERMIT_TYPEHASH = keccak256('Permit(address owner,uint256 value)');
+                bytes32 constant DELEGATE_TYPEHASH = keccak256('Delegation(address delegatee,uint256 nonce)');
+            }
+        ";
+        assert_eq!(
+            duplicate_findings(&[("./src/Token.sol", bad), ("./src/PermitLib.sol", library)]),
+            // PermitLib.sol comes first, so the constants in Token.sol are flagged.
+            [
+                "Typehash 'PERMIT_TYPEHASH' hashes 'Permit(address owner,uint256 value)' but 'Permit(address owner,uint256 value,uint256 nonce)' in ./src/PermitLib.sol:PermitLib",
+                "Typehash 'DELEGATE_TYPEHASH' hashes the same type string as 'DELEGATION_TYPEHASH' in ./src/PermitLib.sol:PermitLib, reuse that constant instead",
+            ]
+        );
+    }
+
     #[test]
     fn test_abi_encode_packed() {
         let content = r"
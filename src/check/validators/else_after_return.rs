@@ -0,0 +1,204 @@
+use crate::check::{
+    utils::{function_definitions, visit_statements, InvalidItem, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{
+    helpers::CodeLocation,
+    pt::{Expression, Statement},
+};
+
+#[must_use]
+/// Validates that no `else` follows an `if` branch that ends in `return` or `revert`.
+///
+/// Code after such an `if` only runs when the branch doesn't, so the `else` just adds nesting.
+/// This rule is opt-in and only runs when `[rules.else] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.file_config.else_rule_enabled() {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for body in function_definitions(&parsed.pt).filter_map(|f| f.body.as_ref()) {
+        visit_statements(body, &mut |stmt| {
+            let Statement::If(_, _, then_stmt, Some(else_stmt)) = stmt else { return };
+            if let Some(terminator) = terminator(then_stmt) {
+                invalid_items.push(invalid_item(parsed, stmt, then_stmt, else_stmt, terminator));
+            }
+        });
+    }
+    invalid_items
+}
+
+/// Returns the keyword that `stmt` ends in, if it always ends in a `return` or `revert`.
+fn terminator(stmt: &Statement) -> Option<&'static str> {
+    match stmt {
+        Statement::Block { statements, .. } => statements.last().and_then(terminator),
+        Statement::Return(..) => Some("return"),
+        Statement::Revert(..) | Statement::RevertNamedArgs(..) => Some("revert"),
+        Statement::Expression(_, Expression::FunctionCall(_, callee, _)) if matches!(callee.as_ref(), Expression::Variable(id) if id.name == "revert") => {
+            Some("revert")
+        }
+        _ => None,
+    }
+}
+
+/// Returns the finding for the `else` of `if_stmt`, with a fix that removes it and unindents its
+/// body unless comments next to the `else` would be lost.
+fn invalid_item(
+    parsed: &Parsed,
+    if_stmt: &Statement,
+    then_stmt: &Statement,
+    else_stmt: &Statement,
+    terminator: &str,
+) -> InvalidItem {
+    let src = &parsed.src;
+    let (mut then_end, else_start) = (then_stmt.loc().end(), else_stmt.loc().start());
+    // The location of an expression statement excludes its semicolon.
+    if src[then_end..].starts_with(';') {
+        then_end += 1;
+    }
+    let gap = &src[then_end..else_start];
+    let keyword_start = gap.rfind("else").map_or(then_end, |idx| then_end + idx);
+    let loc = if_stmt.loc().with_start(keyword_start).with_end(keyword_start + "else".len());
+    let item = InvalidItem::new(
+        ValidatorKind::ElseAfterReturn,
+        parsed,
+        loc,
+        format!("Redundant `else` after `{terminator}`, remove it and unindent its body"),
+    );
+    if !gap.split_whitespace().eq(["else"]) {
+        return item;
+    }
+
+    // The statements after the `if` start on a new line, at the indentation of the `if`.
+    let line_start = src[..if_stmt.loc().start()].rfind('\n').map_or(0, |idx| idx + 1);
+    let indent = &src[line_start..if_stmt.loc().start()];
+    let indent = &indent[..indent.len() - indent.trim_start().len()];
+    let Statement::Block { statements, .. } = else_stmt else {
+        let help = "remove the `else`".to_string();
+        return item.with_replacement(parsed, then_end..else_start, format!("\n{indent}"), help);
+    };
+
+    let (block_start, block_end) = (else_stmt.loc().start(), else_stmt.loc().end());
+    let body = unindented(&src[block_start + 1..block_end - 1], indent);
+    let replacement = if body.is_empty() { String::new() } else { format!("\n{body}") };
+    let help = "remove the `else` and unindent its body".to_string();
+    let mut item = item.with_replacement(parsed, then_end..block_end, replacement, help);
+    // Variables declared in the body would move to the enclosing scope, where their names may
+    // already be taken.
+    if statements.iter().any(|stmt| matches!(stmt, Statement::VariableDefinition(..))) {
+        if let Some(fix) = item.fix.as_mut() {
+            fix.automatic = false;
+        }
+    }
+    item
+}
+
+/// Returns the lines of `body`, without the blank lines around them, re-indented so the least
+/// indented line starts with `indent`.
+fn unindented(body: &str, indent: &str) -> String {
+    let lines: Vec<&str> = body.lines().map(str::trim_end).collect();
+    let Some(first) = lines.iter().position(|line| !line.is_empty()) else {
+        return String::new();
+    };
+    let last = lines.iter().rposition(|line| !line.is_empty()).unwrap_or(first);
+    let lines = &lines[first..=last];
+    let common = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    lines
+        .iter()
+        .map(
+            |line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{indent}{}", &line[common..])
+                }
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let content = r#"
+            contract MyContract {
+                function early(uint256 x) external pure returns (uint256) {
+                    if (x == 0) {
+                        return 1;
+                    } else {
+                        x++;
+                    }
+                    if (x == 1) revert("one");
+                    else if (x == 2) {
+                        revert Two();
+                    } else {
+                        uint256 y = x;
+                    }
+                    return x;
+                }
+
+                function fallsThrough(uint256 x) external pure returns (uint256) {
+                    if (x == 0) {
+                        x++;
+                    } else {
+                        return 1;
+                    }
+                    if (x == 1) {
+                        return 1;
+                    } /* two */ else {
+                        return 2;
+                    }
+                    return x;
+                }
+            }
+        "#;
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.else]\nenabled = true").unwrap();
+        let expected_findings = ExpectedFindings::new(4);
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_fix() {
+        let content = "contract C {\n    function f(uint256 _x) external {\n        if (_x == 0) {\n            return;\n        } else {\n            _x++;\n\n            emit X(_x);\n        }\n        if (_x == 1) revert();\n        else if (_x == 2) return;\n    }\n}\n";
+        let mut parsed = parse_source(Path::new("./src/C.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.else]\nenabled = true").unwrap();
+        let items = validate(&parsed);
+        assert_eq!(items.len(), 2);
+
+        let fix = items[0].fix.as_ref().unwrap();
+        assert!(fix.automatic);
+        assert_eq!((fix.before.as_str(), fix.after.as_str()), ("        } else {", "        }"));
+        let mut fixed = content.to_string();
+        for edit in items.iter().rev().flat_map(|item| &item.fix.as_ref().unwrap().edits) {
+            fixed.replace_range(edit.start..edit.end, &edit.replacement);
+        }
+        assert_eq!(
+            fixed,
+            "contract C {\n    function f(uint256 _x) external {\n        if (_x == 0) {\n            return;\n        }\n        _x++;\n\n        emit X(_x);\n        if (_x == 1) revert();\n        if (_x == 2) return;\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_unindented() {
+        let body = "\n            x++;\n\n            if (x > 1) {\n                x--;\n            }\n        ";
+        assert_eq!(unindented(body, "    "), "    x++;\n\n    if (x > 1) {\n        x--;\n    }");
+        assert_eq!(unindented(" x++; ", "  "), "  x++;");
+        assert_eq!(unindented("\n    ", "  "), "");
+    }
+}
@@ -57,12 +57,19 @@ fn validate_name(
     if error_name.starts_with(&expected_prefix) {
         None // Valid - error name is prefixed with contract name
     } else {
-        Some(InvalidItem::new(
-            ValidatorKind::Error,
-            parsed,
-            error_loc,
-            format!("Error '{error_name}' should be prefixed with '{contract_name}_'"),
-        ))
+        Some(
+            InvalidItem::new(
+                ValidatorKind::Error,
+                parsed,
+                error_loc,
+                format!("Error '{error_name}' should be prefixed with '{contract_name}_'"),
+            )
+            .with_suggested_rename(
+                parsed,
+                error_loc,
+                format!("{expected_prefix}{error_name}"),
+            ),
+        )
     }
 }
 
@@ -140,4 +147,31 @@ contract MyContract {
         let expected_findings = ExpectedFindings::new(0);
         expected_findings.assert_eq(content, &validate);
     }
+
+    #[test]
+    fn test_validate_with_ignore_error_contract() {
+        let content = r"contract Other {
+    // This one should still be flagged
+    error InvalidError();
+}
+
+// scopelint: ignore-error-contract
+contract MyContract {
+    error InvalidError();
+
+    function foo() public {}
+
+    error AnotherInvalidError(uint256 value);
+}
+
+contract Later {
+    // This one should still be flagged
+    error InvalidError();
+}";
+
+        // Only the errors outside of `MyContract` should be found
+        let expected_findings =
+            ExpectedFindings { src: 2, test: 2, handler: 2, ..ExpectedFindings::default() };
+        expected_findings.assert_eq(content, &validate);
+    }
 }
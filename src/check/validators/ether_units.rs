@@ -0,0 +1,132 @@
+use crate::check::{
+    utils::{visit_contract_expressions, InvalidItem, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{helpers::CodeLocation, pt::Expression};
+
+#[must_use]
+/// Validates that amounts aren't scaled by literals that spell out an ether unit, e.g.
+/// `x * 10**18` or `amount / 1e18`, which should be written as `1 ether` or a named constant.
+///
+/// Only powers of ten matching the `ether` and `gwei` units are flagged. This rule is opt-in and
+/// only runs when `[rules.units] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.file_config.units_rule_enabled() {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    visit_contract_expressions(&parsed.pt, &mut |expr| {
+        let (Expression::Multiply(_, left, right) |
+        Expression::Divide(_, left, right) |
+        Expression::AssignMultiply(_, left, right) |
+        Expression::AssignDivide(_, left, right)) = expr
+        else {
+            return;
+        };
+        for operand in [left.as_ref(), right.as_ref()] {
+            let Some(unit) = unit_literal(unparenthesized(operand)) else { continue };
+            let loc = unparenthesized(operand).loc();
+            let written = &parsed.src[loc.start()..loc.end()];
+            let replacement = format!("1 {unit}");
+            let help = format!("replace with `{replacement}`, or a named constant");
+            let mut item = InvalidItem::new(
+                ValidatorKind::EtherUnits,
+                parsed,
+                loc,
+                format!("Use `{replacement}` or a named constant instead of `{written}`"),
+            )
+            .with_replacement(parsed, loc.start()..loc.end(), replacement, help);
+            // The literal may scale token decimals rather than ether, where a named constant
+            // reads better than `1 ether`.
+            if let Some(fix) = item.fix.as_mut() {
+                fix.automatic = false;
+            }
+            invalid_items.push(item);
+        }
+    });
+    invalid_items
+}
+
+/// Returns `expr` without the parentheses around it.
+fn unparenthesized(expr: &Expression) -> &Expression {
+    match expr {
+        Expression::Parenthesis(_, inner) => unparenthesized(inner),
+        expr => expr,
+    }
+}
+
+/// Returns the ether unit that `expr` is the value of, if it's a literal power of ten without a
+/// unit, such as `1e18`, `10**9`, or `1_000_000_000`.
+fn unit_literal(expr: &Expression) -> Option<&'static str> {
+    let exponent = match expr {
+        Expression::NumberLiteral(_, integer, exponent, None) => {
+            let digits = integer.replace('_', "");
+            let zeros = digits.strip_prefix('1').filter(|rest| rest.bytes().all(|b| b == b'0'))?;
+            let exponent = if exponent.is_empty() { 0 } else { exponent.parse().ok()? };
+            zeros.len().checked_add_signed(exponent)?
+        }
+        Expression::Power(_, base, exponent) => match (base.as_ref(), exponent.as_ref()) {
+            (
+                Expression::NumberLiteral(_, base, base_exponent, None),
+                Expression::NumberLiteral(_, exponent, exponent_exponent, None),
+            ) if base == "10" && base_exponent.is_empty() && exponent_exponent.is_empty() => {
+                exponent.replace('_', "").parse().ok()?
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match exponent {
+        18 => Some("ether"),
+        9 => Some("gwei"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            contract MyContract {
+                uint256 public constant WAD = 1e18;
+                uint256 public fee = 5 * 10**9;
+
+                function convert(uint256 x) external pure returns (uint256) {
+                    x = x * 10**18 / 1_000_000_000_000_000_000;
+                    x /= (1e18);
+                    x = x * 1 ether + x * 10**6 + x * 2e18 + x / WAD;
+                    return x * 100e16;
+                }
+            }
+        ";
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.units]\nenabled = true").unwrap();
+        let expected_findings = ExpectedFindings::new(5);
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_fix() {
+        let content = "contract C {\n    function f(uint256 _x) external {\n        _x = _x * 10**9 / 1e18;\n    }\n}\n";
+        let mut parsed = parse_source(Path::new("./src/C.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.units]\nenabled = true").unwrap();
+        let fixes: Vec<_> = validate(&parsed)
+            .into_iter()
+            .map(|item| {
+                let fix = item.fix.unwrap();
+                assert!(!fix.automatic);
+                fix.edits[0].replacement.clone()
+            })
+            .collect();
+        assert_eq!(fixes, ["1 ether", "1 gwei"]);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::check::{
+    utils::{AbiTypes, FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use itertools::Itertools;
+use solang_parser::pt::{ContractPart, EventDefinition, SourceUnitPart};
+use std::collections::{hash_map::Entry, HashMap};
+
+#[must_use]
+/// Validates that events in src files that share a signature are declared the same way.
+///
+/// Indexers identify events by the hash of their signature, so two events with the same name and
+/// parameter types but different parameter names or indexed parameters get decoded as one another.
+/// Each signature's first declaration, in file order, is the reference the others are compared to.
+/// This rule is opt-in and only runs when `[rules.event] enabled = true` is set in `.scopelint`.
+pub fn validate(files: &[Parsed]) -> Vec<InvalidItem> {
+    let Some(file_config) = files.first().map(|parsed| &parsed.file_config) else {
+        return Vec::new();
+    };
+    if !file_config.event_rule_enabled() {
+        return Vec::new();
+    }
+    let types = AbiTypes::new(files);
+
+    // Signature -> where it was first declared, and its parameters as declared there.
+    let mut declarations: HashMap<String, (String, String)> = HashMap::new();
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    let src_files = files
+        .iter()
+        .filter(|parsed| parsed.file.is_file_kind(FileKind::Src, &parsed.path_config))
+        .sorted_by_key(|parsed| &parsed.file);
+    for parsed in src_files {
+        for (scope, event) in events(parsed) {
+            // Anonymous events have no signature topic.
+            if event.anonymous {
+                continue;
+            }
+            let Some(name) = &event.name else { continue };
+            let signature = format!(
+                "{}({})",
+                name.name,
+                event.fields.iter().map(|field| types.canonical_type(parsed, &field.ty)).join(",")
+            );
+            let params = event
+                .fields
+                .iter()
+                .map(|field| {
+                    let mut param = types.canonical_type(parsed, &field.ty);
+                    if field.indexed {
+                        param.push_str(" indexed");
+                    }
+                    if let Some(name) = &field.name {
+                        param = format!("{param} {}", name.name);
+                    }
+                    param
+                })
+                .join(", ");
+            let location = scope.map_or_else(
+                || parsed.file.display().to_string(),
+                |scope| format!("{}:{scope}", parsed.file.display()),
+            );
+
+            match declarations.entry(signature) {
+                Entry::Vacant(entry) => {
+                    entry.insert((location, params));
+                }
+                Entry::Occupied(entry) => {
+                    let (first_location, first_params) = entry.get();
+                    if *first_params != params {
+                        invalid_items.push(InvalidItem::new(
+                            ValidatorKind::Event,
+                            parsed,
+                            event.loc,
+                            format!(
+                                "event '{}' is declared as '({params})' but as '({first_params})' in {first_location}",
+                                entry.key()
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    invalid_items
+}
+
+/// Returns the events declared in a file, with the name of the contract that declares each one,
+/// or `None` for events declared at the top level.
+fn events(parsed: &Parsed) -> Vec<(Option<&str>, &EventDefinition)> {
+    let mut events = Vec::new();
+    for element in &parsed.pt.0 {
+        match element {
+            SourceUnitPart::EventDefinition(event) => events.push((None, event.as_ref())),
+            SourceUnitPart::ContractDefinition(c) => {
+                let scope = c.name.as_ref().map(|name| name.name.as_str());
+                for part in &c.parts {
+                    if let ContractPart::EventDefinition(event) = part {
+                        events.push((scope, event.as_ref()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source};
+    use std::path::Path;
+
+    fn findings(files: &[(&str, &str)], file_config: &FileConfig) -> Vec<String> {
+        let parsed = files
+            .iter()
+            .map(|(path, src)| {
+                let mut parsed = parse_source(Path::new(path), src).unwrap();
+                parsed.file_config = file_config.clone();
+                parsed
+            })
+            .collect::<Vec<_>>();
+        validate(&parsed).into_iter().map(|item| item.text).collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml("[rules.event]\nenabled = true").unwrap();
+        let vault = r"
+            contract Vault {
+                event Deposited(address indexed user, uint amount);
+                event Withdrawn(address indexed user, uint256 amount);
+            }
+        ";
+
+        let good = r"
+            interface IStaking {
+                event Deposited(address indexed user, uint256 amount);
+            }
+            contract Staking {
+                event Staked(address indexed staker, uint256 shares);
+                event Withdrawn(address indexed user, uint256 amount) anonymous;
+                event Withdrawn(address user, uint256 amount, uint256 fee);
+            }
+        ";
+        assert!(findings(&[("./src/Vault.sol", vault), ("./src/Staking.sol", good)], &file_config)
+            .is_empty());
+
+        let bad = r"
+            contract Staking {
+                event Deposited(address indexed staker, uint256 shares);
+                event Withdrawn(address user, uint256 amount);
+            }
+        ";
+        assert_eq!(
+            findings(&[("./src/Vault.sol", vault), ("./src/Staking.sol", bad)], &file_config),
+            // Staking.sol comes first, so the declarations in Vault.sol are flagged.
+            [
+                "event 'Deposited(address,uint256)' is declared as '(address indexed user, uint256 amount)' but as '(address indexed staker, uint256 shares)' in ./src/Staking.sol:Staking",
+                "event 'Withdrawn(address,uint256)' is declared as '(address indexed user, uint256 amount)' but as '(address user, uint256 amount)' in ./src/Staking.sol:Staking",
+            ]
+        );
+
+        // Not configured, so nothing is flagged.
+        assert!(findings(
+            &[("./src/Vault.sol", vault), ("./src/Staking.sol", bad)],
+            &FileConfig::default()
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::check::{
+    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::pt::{Identifier, SourceUnitPart};
+
+#[must_use]
+/// Validates that src files are named after the contract, interface, or library they define, e.g.
+/// that `Counter.sol` defines `Counter`.
+///
+/// Other definitions in the file are flagged too, unless their names match one of the
+/// `[rules.filename] helpers` patterns. Files without definitions are skipped. This rule is opt-in
+/// and only runs when `[rules.filename] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(helpers) = parsed.file_config.filename_helpers() else {
+        return Vec::new();
+    };
+    if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+        return Vec::new();
+    }
+    let Some(file_name) = parsed.file.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+    // The stem is everything before the first `.`, so `Counter.sol` and `Counter.v2.sol` both
+    // expect `Counter`.
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+
+    let names: Vec<&Identifier> = parsed
+        .pt
+        .0
+        .iter()
+        .filter_map(|element| match element {
+            SourceUnitPart::ContractDefinition(c) => c.name.as_ref(),
+            _ => None,
+        })
+        .collect();
+    let has_main = names.iter().any(|name| name.name == stem);
+
+    names
+        .into_iter()
+        .filter(|name| name.name != stem)
+        .filter(|name| !helpers.iter().any(|helper| helper.is_match(&name.name)))
+        .map(|name| {
+            let text = if has_main {
+                format!(
+                    "'{}' is defined in {file_name} next to '{stem}', move it to {}.sol or allow it in [rules.filename] helpers",
+                    name.name, name.name
+                )
+            } else {
+                format!(
+                    "{file_name} defines '{}' but nothing named '{stem}', rename the file or the definition to match",
+                    name.name
+                )
+            };
+            InvalidItem::new(ValidatorKind::FileName, parsed, name.loc, text)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml(
+            "[rules.filename]\nenabled = true\nhelpers = [\"*Lib\", \"I*Callback\"]",
+        )
+        .unwrap();
+
+        let content = r"
+            interface IFlashCallback {}
+            library MathLib {}
+            contract MyContract {}
+        ";
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+        // `ExpectedFindings` checks files named `MyContract.sol`.
+        ExpectedFindings::default().assert_eq_with_config(content, &file_config, &validate);
+
+        let content = r"
+            library Helpers {}
+            contract MyContract {}
+            contract Other {}
+        ";
+        let expected_findings = ExpectedFindings { src: 2, ..ExpectedFindings::default() };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+
+        // Files of free functions and structs are skipped.
+        let content = r"
+            struct Position { uint256 amount; }
+            function add(uint256 a, uint256 b) pure returns (uint256) { return a + b; }
+        ";
+        ExpectedFindings::default().assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_messages() {
+        let file_config = FileConfig::from_toml("[rules.filename]\nenabled = true").unwrap();
+        let messages = |file: &str, content: &str| -> Vec<String> {
+            let mut parsed = parse_source(Path::new(file), content).unwrap();
+            parsed.file_config = file_config.clone();
+            validate(&parsed).into_iter().map(|item| item.text).collect()
+        };
+
+        assert_eq!(
+            messages("./src/Counter.sol", "contract Counters {}"),
+            ["Counter.sol defines 'Counters' but nothing named 'Counter', rename the file or the definition to match"]
+        );
+        assert_eq!(
+            messages("./src/Counter.sol", "contract Counter {}\nlibrary Math {}"),
+            ["'Math' is defined in Counter.sol next to 'Counter', move it to Math.sol or allow it in [rules.filename] helpers"]
+        );
+        assert!(messages("./src/Counter.v2.sol", "contract Counter {}").is_empty());
+    }
+}
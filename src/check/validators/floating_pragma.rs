@@ -0,0 +1,170 @@
+use crate::check::{
+    utils::{InvalidItem, ValidatorKind},
+    validators::pragma_min_version::{self, lowest_admitted_version, parse_version},
+    Parsed,
+};
+use regex::Regex;
+use solang_parser::{helpers::CodeLocation, pt::SourceUnitPart};
+use std::sync::LazyLock;
+
+static RE_PRAGMA_SOLIDITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^pragma\s+solidity\s+([^;]+)").unwrap());
+
+/// A directory whose files must pin the compiler version, set by `[rules.floating] dirs` in
+/// `.scopelint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinnedDir {
+    /// The src directories.
+    Src,
+    /// The test directories.
+    Test,
+    /// The script directories.
+    Script,
+}
+
+impl PinnedDir {
+    /// Parses a directory as written in `.scopelint`, i.e. `src`, `test`, or `script`.
+    #[must_use]
+    pub fn parse(dir: &str) -> Option<Self> {
+        match dir {
+            "src" => Some(Self::Src),
+            "test" => Some(Self::Test),
+            "script" => Some(Self::Script),
+            _ => None,
+        }
+    }
+
+    /// Returns the directory as written in `.scopelint`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Src => "src",
+            Self::Test => "test",
+            Self::Script => "script",
+        }
+    }
+}
+
+/// Directories whose files must pin the compiler version when `[rules.floating] dirs` is not set.
+pub const DEFAULT_DIRS: [PinnedDir; 1] = [PinnedDir::Src];
+
+#[must_use]
+/// Validates that `pragma solidity` pins an exact compiler version, e.g. `0.8.28` rather than
+/// `^0.8.28`, in src files.
+///
+/// Tests and scripts may float by default, since they are never deployed as production code;
+/// `[rules.floating] dirs` selects which of the src, test, and script directories must pin. This
+/// rule is opt-in and only runs when `[rules.floating] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(dirs) = parsed.file_config.pinned_pragma_dirs() else {
+        return Vec::new();
+    };
+    let paths = &parsed.path_config;
+    let is_pinned_dir = dirs.iter().any(|dir| match dir {
+        PinnedDir::Src => paths.in_src(&parsed.file),
+        PinnedDir::Test => paths.in_test(&parsed.file),
+        PinnedDir::Script => paths.in_script(&parsed.file),
+    });
+    if !is_pinned_dir {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for element in &parsed.pt.0 {
+        if !matches!(element, SourceUnitPart::PragmaDirective(..)) {
+            continue;
+        }
+        let loc = element.loc();
+        let Some(captures) = RE_PRAGMA_SOLIDITY.captures(&parsed.src[loc.start()..loc.end()])
+        else {
+            continue;
+        };
+        let requirement = captures[1].trim();
+        if is_exact(requirement) {
+            continue;
+        }
+        let example = lowest_admitted_version(requirement)
+            .filter(|lowest| *lowest != (0, 0, 0))
+            .map_or_else(String::new, |lowest| {
+                format!(", e.g. `pragma solidity {};`", pragma_min_version::format_version(lowest))
+            });
+        invalid_items.push(InvalidItem::new(
+            ValidatorKind::FloatingPragma,
+            parsed,
+            loc,
+            format!(
+                "`pragma solidity {requirement}` admits more than one compiler, pin an exact version{example}"
+            ),
+        ));
+    }
+    invalid_items
+}
+
+/// Returns whether a version requirement admits exactly one compiler, e.g. `0.8.28` or `=0.8.28`.
+fn is_exact(requirement: &str) -> bool {
+    let version = requirement.strip_prefix('=').unwrap_or(requirement).trim();
+    version.split('.').count() == 3 && parse_version(version).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            pragma solidity 0.8.28;
+            pragma solidity =0.8.28;
+            pragma solidity ^0.8.28;
+            pragma solidity >=0.8.20 <0.9.0;
+            pragma solidity 0.8;
+            pragma abicoder v2;
+            contract MyContract {}
+        ";
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+
+        // Only src files must pin by default.
+        let file_config = FileConfig::from_toml("[rules.floating]\nenabled = true").unwrap();
+        let expected_findings = ExpectedFindings { src: 3, ..ExpectedFindings::default() };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+
+        let file_config =
+            FileConfig::from_toml("[rules.floating]\nenabled = true\ndirs = [\"src\", \"test\"]")
+                .unwrap();
+        // Test helpers and handlers are in the test directories too.
+        let expected_findings = ExpectedFindings {
+            src: 3,
+            test: 3,
+            test_helper: 3,
+            handler: 3,
+            ..ExpectedFindings::default()
+        };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_messages() {
+        let content = "pragma solidity ^0.8.28;\npragma solidity <0.9.0;\ncontract Vault {}";
+        let mut parsed = parse_source(Path::new("./src/Vault.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.floating]\nenabled = true").unwrap();
+        let texts: Vec<String> = validate(&parsed).into_iter().map(|item| item.text).collect();
+        assert_eq!(
+            texts,
+            [
+                "`pragma solidity ^0.8.28` admits more than one compiler, pin an exact version, e.g. `pragma solidity 0.8.28;`",
+                "`pragma solidity <0.9.0` admits more than one compiler, pin an exact version",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dir() {
+        assert_eq!(PinnedDir::parse("src"), Some(PinnedDir::Src));
+        assert_eq!(PinnedDir::parse("tests"), None);
+        assert_eq!(PinnedDir::parse(PinnedDir::Script.name()), Some(PinnedDir::Script));
+    }
+}
@@ -1,29 +1,170 @@
+use crate::{
+    check::{comments::Comments, file_config::FileConfig, inline_config::InlineConfigItem, Buffer},
+    failure::Failure,
+    foundry_config::CheckPaths,
+};
 use colored::Colorize;
-use std::{error::Error, fs, process};
+use solang_parser::lexer::Lexer;
+use std::{
+    error::Error,
+    ffi::OsStr,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+};
+use walkdir::WalkDir;
 
-/// Validates that Solidity and TOML files are formatted correctly.
+/// Validates that Solidity and TOML files are formatted correctly. If `paths` is not empty, only
+/// the Solidity files and TOML files at those paths are checked.
 /// # Errors
 /// Returns an error if formatting is invalid or parsing fails.
-pub fn validate(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    // Check Solidity with `forge fmt`.
-    let forge_status = process::Command::new("forge").arg("fmt").arg("--check").output()?;
+pub fn validate(
+    taplo_opts: &taplo::formatter::Options,
+    file_config: &FileConfig,
+    paths: &[PathBuf],
+) -> Result<(), Box<dyn Error>> {
+    let (toml_paths, sol_paths): (Vec<&PathBuf>, Vec<&PathBuf>) =
+        paths.iter().partition(|path| path.extension() == Some(OsStr::new("toml")));
 
-    // Print any warnings/errors from `forge fmt`.
+    // Check Solidity with `forge fmt`, unless only TOML files are being checked or every Solidity
+    // file is excluded from formatting.
+    let sol_paths = if !paths.is_empty() && sol_paths.is_empty() {
+        None
+    } else {
+        fmt_paths(&sol_paths.into_iter().cloned().collect::<Vec<_>>(), file_config)
+    };
+    let forge_ok = match sol_paths {
+        None => true,
+        Some(sol_paths) => {
+            let forge_status = process::Command::new("forge")
+                .arg("fmt")
+                .arg("--check")
+                .args(sol_paths)
+                .output()?;
+
+            // Print any warnings/errors from `forge fmt`.
+            let stderr = String::from_utf8(forge_status.stderr)?;
+            print!("{stderr}"); // Prints nothing if stderr is empty.
+            forge_status.status.success() && stderr.is_empty()
+        }
+    };
+
+    // Check TOML with `taplo fmt`: `foundry.toml`, or only the given TOML files if paths are given.
+    let default_toml = [PathBuf::from("./foundry.toml")];
+    let toml_paths = if paths.is_empty() { default_toml.iter().collect() } else { toml_paths };
+    let mut taplo_ok = true;
+    for path in toml_paths {
+        let config_orig = fs::read_to_string(path)?;
+        taplo_ok &= config_orig == taplo::formatter::format(&config_orig, taplo_opts.clone());
+    }
+    formatting_result(forge_ok && taplo_ok)
+}
+
+/// Validates that the Solidity and TOML files in `buffers`, as staged in the git index, are
+/// formatted correctly, so unstaged changes on disk don't affect the result.
+/// # Errors
+/// Returns an error if formatting is invalid or `forge fmt` can't be run.
+pub(crate) fn validate_staged(
+    taplo_opts: &taplo::formatter::Options,
+    file_config: &FileConfig,
+    buffers: &[Buffer],
+) -> Result<(), Box<dyn Error>> {
+    let mut formatted = true;
+    for buffer in buffers {
+        if buffer.file.extension() == Some(OsStr::new("toml")) {
+            formatted &= buffer.src == taplo::formatter::format(&buffer.src, taplo_opts.clone());
+        } else if !file_config.is_fmt_ignored(&buffer.file) && !disables_fmt_file(&buffer.src) {
+            formatted &= forge_fmt_check_stdin(&buffer.src)?;
+        }
+    }
+    formatting_result(formatted)
+}
+
+// Checks the formatting of `src` by piping it to `forge fmt --check -`, and returns whether it's
+// formatted.
+fn forge_fmt_check_stdin(src: &str) -> Result<bool, Box<dyn Error>> {
+    let mut forge = process::Command::new("forge")
+        .args(["fmt", "--check", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // `forge fmt` reads all of stdin before it writes anything, so this can't block on its output.
+    forge.stdin.take().ok_or("forge has no stdin")?.write_all(src.as_bytes())?;
+    let forge_status = forge.wait_with_output()?;
     let stderr = String::from_utf8(forge_status.stderr)?;
-    let forge_ok = forge_status.status.success() && stderr.is_empty();
     print!("{stderr}"); // Prints nothing if stderr is empty.
+    Ok(forge_status.status.success() && stderr.is_empty())
+}
 
-    // Check TOML with `taplo fmt`
-    let config_orig = fs::read_to_string("./foundry.toml")?;
-    let config_fmt = taplo::formatter::format(&config_orig, taplo_opts);
-    let taplo_ok = config_orig == config_fmt;
-
-    if !forge_ok || !taplo_ok {
+// Returns `Ok` if everything is `formatted`, and the formatting failure otherwise.
+fn formatting_result(formatted: bool) -> Result<(), Box<dyn Error>> {
+    if !formatted {
         eprintln!(
             "{}: Formatting validation failed, run `scopelint fmt` to fix",
             "error".bold().red()
         );
-        return Err("Invalid fmt found".into());
+        return Err(Failure::Formatting.into());
     }
     Ok(())
 }
+
+/// Returns the Solidity paths to pass to `forge fmt`, leaving out files excluded from formatting.
+///
+/// Files are excluded by `[fmt] ignore` in `file_config` or a `// scopelint: disable-fmt-file`
+/// comment. `paths` are the files and directories to format, or none for the whole project.
+///
+/// If no file is excluded, `paths` is returned as is, so `forge fmt` uses its own defaults for the
+/// whole project. Otherwise every remaining Solidity file is listed. Returns `None` if every file
+/// is excluded, so `forge fmt` shouldn't run at all.
+#[must_use]
+pub fn fmt_paths(paths: &[PathBuf], file_config: &FileConfig) -> Option<Vec<PathBuf>> {
+    let roots: Vec<PathBuf> = if paths.is_empty() {
+        CheckPaths::load().all().into_iter().map(PathBuf::from).collect()
+    } else {
+        paths.to_vec()
+    };
+
+    let mut files = Vec::new();
+    let mut any_excluded = false;
+    for root in &roots {
+        for dent in WalkDir::new(root).into_iter().filter_map(Result::ok) {
+            let file = dent.path();
+            if !dent.file_type().is_file() || file.extension() != Some(OsStr::new("sol")) {
+                continue;
+            }
+            if file_config.is_fmt_ignored(file) || has_disable_fmt_file(file) {
+                any_excluded = true;
+            } else {
+                files.push(file.to_path_buf());
+            }
+        }
+    }
+
+    if !any_excluded {
+        Some(paths.to_vec())
+    } else if files.is_empty() {
+        None
+    } else {
+        Some(files)
+    }
+}
+
+// Whether the file has a `// scopelint: disable-fmt-file` comment.
+fn has_disable_fmt_file(file: &Path) -> bool {
+    fs::read_to_string(file).is_ok_and(|src| disables_fmt_file(&src))
+}
+
+// Whether `src` has a `// scopelint: disable-fmt-file` comment. Only comments are lexed, so a
+// source that doesn't parse is still handled.
+fn disables_fmt_file(src: &str) -> bool {
+    if !src.contains("disable-fmt-file") {
+        return false;
+    }
+    let (mut comments, mut errors) = (Vec::new(), Vec::new());
+    Lexer::new(src, 0, &mut comments, &mut errors).for_each(drop);
+    Comments::new(comments, src)
+        .parse_inline_config_items()
+        .any(|item| matches!(item, Ok((_, InlineConfigItem::DisableFmtFile))))
+}
@@ -0,0 +1,281 @@
+use crate::check::{
+    file_config::FileConfig,
+    utils::{InvalidItem, ValidatorKind},
+};
+use solang_parser::pt::Loc;
+use std::{fs, ops::Range, path::Path};
+use toml_edit::{ImDocument, Item, Key, TableLike};
+
+/// The path of `foundry.toml`, relative to the project root.
+const FOUNDRY_TOML: &str = "./foundry.toml";
+
+/// Keys `[profile.default]` must set when `[rules.foundry] required` is not set.
+pub const DEFAULT_REQUIRED: [&str; 2] = ["solc", "optimizer_runs"];
+
+/// Keys no profile may enable when `[rules.foundry] forbidden` is not set.
+pub const DEFAULT_FORBIDDEN: [&str; 1] = ["ffi"];
+
+/// Keys every profile must set to the value of `[profile.default]` when `[rules.foundry]
+/// consistent` is not set.
+pub const DEFAULT_CONSISTENT: [&str; 2] = ["solc", "evm_version"];
+
+/// Keys Foundry reads under two names, e.g. `solc` and `solc_version`.
+const ALIASES: [(&str, &str); 1] = [("solc", "solc_version")];
+
+/// The conventions `foundry.toml` must follow, set by `[rules.foundry]` in `.scopelint`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundryConventions {
+    /// Keys `[profile.default]` must set.
+    pub required: Vec<String>,
+    /// Keys no profile may set to anything but `false`, outside of `exempt_profiles`.
+    pub forbidden: Vec<String>,
+    /// Profiles that may set `forbidden` keys, e.g. one only used to run FFI tests.
+    pub exempt_profiles: Vec<String>,
+    /// Keys every profile that sets them must set to the value of `[profile.default]`.
+    pub consistent: Vec<String>,
+}
+
+impl Default for FoundryConventions {
+    fn default() -> Self {
+        let strings = |keys: &[&str]| keys.iter().map(ToString::to_string).collect();
+        Self {
+            required: strings(&DEFAULT_REQUIRED),
+            forbidden: strings(&DEFAULT_FORBIDDEN),
+            exempt_profiles: Vec::new(),
+            consistent: strings(&DEFAULT_CONSISTENT),
+        }
+    }
+}
+
+#[must_use]
+/// Validates that `foundry.toml` follows the conventions set by `[rules.foundry]`.
+///
+/// `[profile.default]` must set the required keys, no profile but the exempt ones may enable a
+/// forbidden key such as `ffi`, and profiles must not override keys such as `solc` that every
+/// build must agree on.
+///
+/// Findings point into `foundry.toml`, so they're ignored with `[ignore.overrides]` rather than
+/// inline directives. A missing or unparsable `foundry.toml` is left to Forge to report. This rule
+/// is opt-in and only runs when `[rules.foundry] enabled = true` is set in `.scopelint`.
+pub fn validate(file_config: &FileConfig) -> Vec<InvalidItem> {
+    let Some(conventions) = file_config.foundry_conventions() else {
+        return Vec::new();
+    };
+    let Ok(src) = fs::read_to_string(FOUNDRY_TOML) else {
+        return Vec::new();
+    };
+    validate_source(Path::new(FOUNDRY_TOML), &src, conventions, file_config)
+}
+
+/// Validates `src` as the content of `foundry.toml`, e.g. as staged in the git index, like
+/// [`validate`].
+#[must_use]
+pub fn validate_src(file_config: &FileConfig, src: &str) -> Vec<InvalidItem> {
+    let Some(conventions) = file_config.foundry_conventions() else {
+        return Vec::new();
+    };
+    validate_source(Path::new(FOUNDRY_TOML), src, conventions, file_config)
+}
+
+/// Validates the `foundry.toml` source `src` at `file` against `conventions`.
+fn validate_source(
+    file: &Path,
+    src: &str,
+    conventions: &FoundryConventions,
+    file_config: &FileConfig,
+) -> Vec<InvalidItem> {
+    if src.trim().is_empty() {
+        return Vec::new();
+    }
+    let Ok(doc) = ImDocument::parse(src) else {
+        return Vec::new();
+    };
+    let item = |span: Option<Range<usize>>, text: String| {
+        let span = span.unwrap_or(0..0);
+        let loc = Loc::File(0, span.start, span.end);
+        InvalidItem::in_file(ValidatorKind::FoundryConfig, file, src, loc, text, file_config)
+    };
+
+    let profiles = doc.get("profile").and_then(Item::as_table_like);
+    let default = profiles.and_then(|profiles| profiles.get_key_value("default"));
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+
+    // Required keys only need to be in the default profile, since other profiles inherit them.
+    match default.and_then(|(key, profile)| Some((key, profile.as_table_like()?))) {
+        Some((key, profile)) => {
+            for required in &conventions.required {
+                if get(profile, required).is_none() {
+                    invalid_items.push(item(
+                        key.span(),
+                        format!("[profile.default] doesn't set `{required}`, which is required"),
+                    ));
+                }
+            }
+        }
+        None if !conventions.required.is_empty() => {
+            invalid_items.push(item(
+                None,
+                format!(
+                    "foundry.toml has no [profile.default], add one that sets {}",
+                    conventions
+                        .required
+                        .iter()
+                        .map(|key| format!("`{key}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+        None => {}
+    }
+
+    let Some(profiles) = profiles else { return invalid_items };
+    let default = default.and_then(|(_, profile)| profile.as_table_like());
+    for (name, profile) in profiles.iter() {
+        let Some(profile) = profile.as_table_like() else { continue };
+
+        if !conventions.exempt_profiles.iter().any(|exempt| exempt == name) {
+            for forbidden in &conventions.forbidden {
+                let Some((key, value)) = get(profile, forbidden) else { continue };
+                if value.as_bool() == Some(false) {
+                    continue;
+                }
+                invalid_items.push(item(
+                    key.span(),
+                    format!(
+                        "[profile.{name}] sets `{} = {}`, move it to a profile listed in [rules.foundry] exempt_profiles",
+                        key.get(),
+                        value_text(value)
+                    ),
+                ));
+            }
+        }
+
+        if name == "default" {
+            continue;
+        }
+        for consistent in &conventions.consistent {
+            let Some((key, value)) = get(profile, consistent) else { continue };
+            let expected = default.and_then(|default| get(default, consistent));
+            let Some((default_key, default_value)) = expected else { continue };
+            if value_text(value) != value_text(default_value) {
+                invalid_items.push(item(
+                    value.span().or_else(|| key.span()),
+                    format!(
+                        "[profile.{name}] sets `{} = {}` but [profile.default] sets `{} = {}`",
+                        key.get(),
+                        value_text(value),
+                        default_key.get(),
+                        value_text(default_value)
+                    ),
+                ));
+            }
+        }
+    }
+    invalid_items
+}
+
+/// Returns the key and value of `key` in `profile`, also looking it up by its alias, if any.
+fn get<'a>(profile: &'a dyn TableLike, key: &str) -> Option<(&'a Key, &'a Item)> {
+    let alias = ALIASES.iter().find_map(|&(name, alias)| {
+        if key == name {
+            Some(alias)
+        } else if key == alias {
+            Some(name)
+        } else {
+            None
+        }
+    });
+    profile.get_key_value(key).or_else(|| alias.and_then(|alias| profile.get_key_value(alias)))
+}
+
+/// Returns the value of `item` as written, without surrounding whitespace or comments.
+fn value_text(item: &Item) -> String {
+    item.as_value().map_or_else(
+        || item.to_string().trim().to_string(),
+        |value| {
+            let mut value = value.clone();
+            value.decor_mut().clear();
+            value.to_string()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(src: &str, conventions: &FoundryConventions) -> Vec<String> {
+        validate_source(Path::new(FOUNDRY_TOML), src, conventions, &FileConfig::default())
+            .into_iter()
+            .map(|item| format!("{}: {}", item.line, item.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let conventions = FoundryConventions::default();
+        let good = r#"
+[profile.default]
+  solc_version = "0.8.28"
+  optimizer_runs = 10_000_000
+  ffi = false
+
+[profile.ci]
+  fuzz = { runs = 5000 }
+  solc = "0.8.28" # Same as the default.
+
+[profile.ffi]
+  ffi = true
+"#;
+        let conventions_with_exempt = FoundryConventions {
+            exempt_profiles: vec!["ffi".to_string()],
+            ..FoundryConventions::default()
+        };
+        assert!(findings(good, &conventions_with_exempt).is_empty());
+        assert_eq!(
+            findings(good, &conventions),
+            ["12: [profile.ffi] sets `ffi = true`, move it to a profile listed in [rules.foundry] exempt_profiles"]
+        );
+
+        let bad = r#"
+[profile.default]
+  solc = "0.8.28"
+  ffi = true
+
+[profile.lite]
+  solc_version = "0.8.19"
+  evm_version = "paris"
+"#;
+        assert_eq!(
+            findings(bad, &conventions),
+            [
+                "2: [profile.default] doesn't set `optimizer_runs`, which is required",
+                "4: [profile.default] sets `ffi = true`, move it to a profile listed in [rules.foundry] exempt_profiles",
+                "7: [profile.lite] sets `solc_version = \"0.8.19\"` but [profile.default] sets `solc = \"0.8.28\"`",
+            ]
+        );
+
+        assert_eq!(
+            findings("[fmt]\n  line_length = 100\n", &conventions),
+            ["1: foundry.toml has no [profile.default], add one that sets `solc`, `optimizer_runs`"]
+        );
+        // Unparsable and empty files are left to Forge.
+        assert!(findings("[profile.default", &conventions).is_empty());
+        assert!(findings("", &conventions).is_empty());
+    }
+
+    #[test]
+    fn test_ignored_by_overrides() {
+        let file_config =
+            FileConfig::from_toml("[ignore.overrides]\n\"foundry.toml\" = [\"foundry\"]").unwrap();
+        let items = validate_source(
+            Path::new(FOUNDRY_TOML),
+            "[profile.default]\n  ffi = true\n",
+            &FoundryConventions::default(),
+            &file_config,
+        );
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(|item| item.is_ignored));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::check::{
+    utils::{InvalidItem, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{
+    lexer::{Lexer, Token},
+    pt::Loc,
+};
+
+/// How 256-bit integer types are written, set by `[rules.uint] style` in `.scopelint`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IntStyle {
+    /// `uint256` and `int256`, so the width is visible next to the other sized types.
+    #[default]
+    Explicit,
+    /// `uint` and `int`, the shorter aliases.
+    Short,
+}
+
+impl IntStyle {
+    /// Parses a style as written in `.scopelint`, i.e. `explicit` or `short`.
+    #[must_use]
+    pub fn parse(style: &str) -> Option<Self> {
+        match style {
+            "explicit" => Some(Self::Explicit),
+            "short" => Some(Self::Short),
+            _ => None,
+        }
+    }
+
+    /// Returns the style as written in `.scopelint`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Explicit => "explicit",
+            Self::Short => "short",
+        }
+    }
+}
+
+#[must_use]
+/// Validates that 256-bit integer types are written in the configured style: `uint256` and
+/// `int256` by default, or `uint` and `int` with `[rules.uint] style = "short"`.
+///
+/// Both spellings are the same type, so each finding has a fix that rewrites it. Types are found
+/// by lexing the source, so types in comments and strings are never flagged.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let style = parsed.file_config.int_style();
+    let src = &parsed.src;
+    let (mut comments, mut errors) = (Vec::new(), Vec::new());
+    Lexer::new(src, 0, &mut comments, &mut errors)
+        .filter(|(_, token, _)| matches!(token, Token::Uint(256) | Token::Int(256)))
+        .filter_map(|(start, _, end)| {
+            let written = &src[start..end];
+            let replacement = match (style, written) {
+                (IntStyle::Explicit, "uint" | "int") => format!("{written}256"),
+                (IntStyle::Short, "uint256" | "int256") => written.trim_end_matches("256").into(),
+                _ => return None,
+            };
+            let help = format!("replace with `{replacement}`");
+            let item = InvalidItem::new(
+                ValidatorKind::IntType,
+                parsed,
+                Loc::File(0, start, end),
+                format!("Use `{replacement}` instead of `{written}`"),
+            );
+            Some(item.with_replacement(parsed, start..end, replacement, help))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let content = r#"
+            contract MyContract {
+                uint public total;
+                int256 public delta;
+                mapping(address => uint) public balances;
+                event Deposit(uint indexed amount, uint8 tier);
+
+                // A uint in a comment, or in a "uint" string, isn't a type.
+                function add(uint256 x) external returns (int) {
+                    total += uint(x);
+                    return int256(x);
+                }
+            }
+        "#;
+
+        let expected_findings = ExpectedFindings::new(5);
+        expected_findings.assert_eq(content, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.uint]\nstyle = \"short\"").unwrap();
+        let expected_findings = ExpectedFindings::new(3);
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_parse_style() {
+        assert_eq!(IntStyle::parse("explicit"), Some(IntStyle::Explicit));
+        assert_eq!(IntStyle::parse("short"), Some(IntStyle::Short));
+        assert_eq!(IntStyle::parse("uint256"), None);
+        assert_eq!(IntStyle::parse(IntStyle::Short.name()), Some(IntStyle::Short));
+    }
+}
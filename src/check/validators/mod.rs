@@ -27,3 +27,89 @@ pub mod eip712_typehash;
 
 /// Validates that all imported symbols are actually used in the file.
 pub mod unused_imports;
+
+/// Validates that per-chain deployment directories don't import each other's files.
+pub mod chain_segregation;
+
+/// Validates that scripts use the `.s.sol` suffix and one contract per file, named with the
+/// configured suffix.
+pub mod script_file;
+
+/// Validates that deployment scripts don't depend on block data, the sender, or unseeded
+/// randomness.
+pub mod script_determinism;
+
+/// Validates that env vars read by scripts are declared in the `.scopelint` manifest.
+pub mod script_env;
+
+/// Validates that tests don't create forks from inline RPC URLs.
+pub mod test_fork_rpc;
+
+/// Validates that `pragma solidity` does not admit compilers below the configured minimum.
+pub mod pragma_min_version;
+
+/// Validates that `pragma solidity` pins an exact compiler version.
+pub mod floating_pragma;
+
+/// Validates that `pragma solidity` admits the compiler version pinned in `foundry.toml`.
+pub mod solc_pin;
+
+/// Validates that src files don't import or call console logging libraries.
+pub mod console_logs;
+
+/// Validates that deployable src contracts have a `@custom:security-contact` tag.
+pub mod security_contact;
+
+/// Validates that each src file has a test file at the mirrored path in the test directory.
+pub mod test_mirror;
+
+/// Validates that constructors of upgradeable contracts don't emit events or write storage.
+pub mod upgradeable_constructor;
+
+/// Validates that src contracts only append to the recorded storage layout snapshot.
+pub mod storage_layout;
+
+/// Validates that no two external or public functions of a contract share a selector.
+pub mod selector_collisions;
+
+/// Validates that events sharing a signature are declared with the same parameters.
+pub mod event_signatures;
+
+/// Validates that function bodies in src files don't nest deeper than the configured maximum.
+pub mod nesting_depth;
+
+/// Validates that no `else` follows an `if` branch that ends in `return` or `revert`.
+pub mod else_after_return;
+
+/// Validates that booleans aren't compared to `true` or `false`.
+pub mod bool_comparisons;
+
+/// Validates that 256-bit integer types are written in the configured style.
+pub mod int_types;
+
+/// Validates that amounts are scaled with ether units instead of literal powers of ten.
+pub mod ether_units;
+
+/// Validates that functions with named returns don't mix explicit and implicit returns.
+pub mod named_returns;
+
+/// Validates that chain-dependent values in src contracts are immutable rather than constant.
+pub mod chain_constants;
+
+/// Validates that public and external functions of src contracts have complete `NatSpec`.
+pub mod natspec;
+
+/// Validates that state variables declare their visibility explicitly.
+pub mod state_visibility;
+
+/// Validates that src files are named after the contract they define.
+pub mod file_name;
+
+/// Validates that src files declare at most one non-abstract contract.
+pub mod one_contract;
+
+/// Validates that `foundry.toml` follows the configured conventions.
+pub mod foundry_toml;
+
+/// Validates that remappings point to existing directories, don't conflict, and are used.
+pub mod remappings;
@@ -0,0 +1,112 @@
+use crate::check::{
+    utils::{function_definitions, visit_statements, InvalidItem, ValidatorKind},
+    Parsed,
+};
+use solang_parser::pt::{Expression, FunctionDefinition, Statement};
+
+#[must_use]
+/// Validates that functions with named return variables return the same way throughout.
+///
+/// Either every return is `return expr;`, or the function always returns implicitly, by
+/// assigning the named variables and using bare `return;` or falling off the end of the body. This
+/// rule is opt-in and only runs when `[rules.returns] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.file_config.returns_rule_enabled() {
+        return Vec::new();
+    }
+
+    function_definitions(&parsed.pt)
+        .filter(|f| has_named_returns(f))
+        .filter_map(|f| {
+            let body = f.body.as_ref()?;
+            let (mut explicit, mut bare) = (0, 0);
+            visit_statements(body, &mut |stmt| match stmt {
+                Statement::Return(_, Some(_)) => explicit += 1,
+                Statement::Return(_, None) => bare += 1,
+                _ => {}
+            });
+            let implicit = bare > 0 || !always_exits(body);
+            (explicit > 0 && implicit).then(|| {
+                let name = f.name.as_ref().map_or_else(|| f.ty.to_string(), |name| name.name.clone());
+                InvalidItem::new(
+                    ValidatorKind::NamedReturns,
+                    parsed,
+                    f.loc,
+                    format!(
+                        "Function '{name}' has named returns but mixes `return` with values and implicit returns, use one style"
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Returns whether every return variable of `f` is named, and there is at least one.
+fn has_named_returns(f: &FunctionDefinition) -> bool {
+    !f.returns.is_empty() &&
+        f.returns.iter().all(|(_, param)| param.as_ref().is_some_and(|p| p.name.is_some()))
+}
+
+/// Returns whether `stmt` always ends in a `return` or `revert`, so execution never continues
+/// after it.
+fn always_exits(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Block { statements, .. } => statements.last().is_some_and(always_exits),
+        Statement::If(_, _, then_stmt, Some(else_stmt)) => {
+            always_exits(then_stmt) && always_exits(else_stmt)
+        }
+        Statement::Return(..) | Statement::Revert(..) | Statement::RevertNamedArgs(..) => true,
+        Statement::Expression(_, Expression::FunctionCall(_, callee, _)) => {
+            matches!(callee.as_ref(), Expression::Variable(id) if id.name == "revert")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            contract MyContract {
+                function explicitOnly(uint256 x) external pure returns (uint256 y) {
+                    if (x == 0) return 1;
+                    if (x == 1) {
+                        return 2;
+                    } else {
+                        revert();
+                    }
+                }
+
+                function implicitOnly(uint256 x) external pure returns (uint256 y) {
+                    if (x == 0) return;
+                    y = x;
+                }
+
+                function unnamed(uint256 x) external pure returns (uint256) {
+                    if (x == 0) return 1;
+                }
+
+                function bareReturn(uint256 x) external pure returns (uint256 y) {
+                    if (x == 0) return;
+                    return x;
+                }
+
+                function fallsThrough(uint256 x) external pure returns (uint256 y, bool ok) {
+                    if (x == 0) return (1, true);
+                    y = x;
+                }
+            }
+        ";
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.returns]\nenabled = true").unwrap();
+        let expected_findings = ExpectedFindings::new(2);
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+}
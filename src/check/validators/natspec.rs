@@ -0,0 +1,259 @@
+use crate::check::{
+    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{
+    helpers::CodeLocation,
+    pt::{
+        ContractPart, FunctionAttribute, FunctionDefinition, FunctionTy, SourceUnitPart, Visibility,
+    },
+};
+
+/// A `NatSpec` tag that public and external functions must have, set by `[rules.natspec] tags` in
+/// `.scopelint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NatspecTag {
+    /// `@notice`, or untagged text before the first tag, which `NatSpec` treats the same.
+    Notice,
+    /// `@param <name>` for each named parameter.
+    Param,
+    /// `@return` for each return value.
+    Return,
+}
+
+impl NatspecTag {
+    /// Parses a tag as written in `.scopelint`, i.e. `notice`, `param`, or `return`.
+    #[must_use]
+    pub fn parse(tag: &str) -> Option<Self> {
+        match tag {
+            "notice" => Some(Self::Notice),
+            "param" => Some(Self::Param),
+            "return" => Some(Self::Return),
+            _ => None,
+        }
+    }
+
+    /// Returns the tag as written in `.scopelint`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Notice => "notice",
+            Self::Param => "param",
+            Self::Return => "return",
+        }
+    }
+}
+
+/// Tags required when `[rules.natspec] tags` is not set.
+pub const DEFAULT_TAGS: [NatspecTag; 3] =
+    [NatspecTag::Notice, NatspecTag::Param, NatspecTag::Return];
+
+#[must_use]
+/// Validates that public and external functions of src contracts have complete `NatSpec`.
+///
+/// By default a function needs a `@notice`, a `@param` for each named parameter, and a `@return`
+/// for each return value; `[rules.natspec] tags` selects which of these are required. Functions
+/// with `@inheritdoc` are complete, since their `NatSpec` is copied from the base. This rule is
+/// opt-in and only runs when `[rules.natspec] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(required) = parsed.file_config.natspec_tags() else {
+        return Vec::new();
+    };
+    if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for element in &parsed.pt.0 {
+        let SourceUnitPart::ContractDefinition(c) = element else { continue };
+        // NatSpec of a function is the doc comments between the previous item of the contract and
+        // the function itself.
+        let mut previous_end = c.loc.start();
+        for part in &c.parts {
+            if let ContractPart::FunctionDefinition(f) = part {
+                let missing = missing_tags(parsed, f, previous_end, required);
+                if let (Some(name), false) = (&f.name, missing.is_empty()) {
+                    invalid_items.push(InvalidItem::new(
+                        ValidatorKind::Natspec,
+                        parsed,
+                        name.loc,
+                        format!(
+                            "Function '{}' is missing NatSpec {}",
+                            name.name,
+                            missing.join(", ")
+                        ),
+                    ));
+                }
+            }
+            previous_end = part.loc().end();
+        }
+    }
+    invalid_items
+}
+
+/// Returns the `required` tags missing from the `NatSpec` of `f`, e.g. `@param amount`, or nothing
+/// if `f` isn't a public or external function.
+fn missing_tags(
+    parsed: &Parsed,
+    f: &FunctionDefinition,
+    natspec_start: usize,
+    required: &[NatspecTag],
+) -> Vec<String> {
+    let is_public = f.attributes.iter().any(|attr| {
+        matches!(
+            attr,
+            FunctionAttribute::Visibility(Visibility::External(_) | Visibility::Public(_))
+        )
+    });
+    if f.ty != FunctionTy::Function || !is_public {
+        return Vec::new();
+    }
+    let tags = tags(parsed, natspec_start, f.loc.start());
+    if tags.iter().any(|(tag, _)| *tag == "inheritdoc") {
+        return Vec::new();
+    }
+
+    let mut missing = Vec::new();
+    for tag in required {
+        match tag {
+            NatspecTag::Notice => {
+                if !tags.iter().any(|(tag, _)| *tag == "notice") {
+                    missing.push("@notice".to_string());
+                }
+            }
+            NatspecTag::Param => {
+                let names = f.params.iter().filter_map(|(_, p)| p.as_ref()?.name.as_ref());
+                for name in names {
+                    let documented = tags.iter().any(|(tag, text)| {
+                        *tag == "param" && text.split_whitespace().next() == Some(&name.name)
+                    });
+                    if !documented {
+                        missing.push(format!("@param {}", name.name));
+                    }
+                }
+            }
+            NatspecTag::Return => {
+                let documented = tags.iter().filter(|(tag, _)| *tag == "return").count();
+                for (_, param) in f.returns.iter().skip(documented) {
+                    match param.as_ref().and_then(|p| p.name.as_ref()) {
+                        Some(name) => missing.push(format!("@return {}", name.name)),
+                        None => missing.push("@return".to_string()),
+                    }
+                }
+            }
+        }
+    }
+    missing
+}
+
+/// Returns the `NatSpec` tags of the doc comments in `start..end`, with the text following each,
+/// e.g. `("param", "amount The amount")`. Untagged text before the first tag is a `notice`.
+fn tags(parsed: &Parsed, start: usize, end: usize) -> Vec<(&str, &str)> {
+    let mut tags = Vec::new();
+    for comment in parsed.comments.doc_comments_in(start, end) {
+        for line in comment.contents().lines() {
+            let line = line.trim().trim_start_matches('*').trim();
+            if let Some(tagged) = line.strip_prefix('@') {
+                let (tag, text) = tagged.split_once(char::is_whitespace).unwrap_or((tagged, ""));
+                tags.push((tag, text.trim()));
+            } else if tags.is_empty() && !line.is_empty() {
+                tags.push(("notice", line));
+            }
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml("[rules.natspec]\nenabled = true").unwrap();
+
+        let content_good = r"
+            /// @notice A vault.
+            contract Vault is IVault {
+                /// @notice Deposits `amount`.
+                /// @param amount The amount to deposit.
+                /// @return shares The shares minted.
+                function deposit(uint256 amount) external returns (uint256 shares) {}
+
+                /**
+                 * Withdraws everything.
+                 * @param owner The owner of the shares, who must have approved
+                 *   the caller.
+                 * @param receiver The receiver of the assets.
+                 */
+                function withdraw(address owner, address receiver, bytes calldata) public {}
+
+                /// @inheritdoc IVault
+                function totalAssets() external view returns (uint256) {}
+
+                function _mint(uint256 shares) internal {}
+                function burn(uint256 shares) private {}
+                constructor(uint256 cap) {}
+                receive() external payable {}
+            }
+        ";
+
+        let content_bad = r"
+            interface IVault {
+                function totalAssets() external view returns (uint256);
+
+                /// @notice Deposits `amount`.
+                /// @param assets The amount to deposit.
+                function deposit(uint256 amount, address receiver) external returns (uint256 shares);
+
+                // @notice Not NatSpec, since it's a regular comment.
+                function withdraw() external;
+
+                /// @dev Only a dev comment.
+                /// @return The first value.
+                function split() external returns (uint256, uint256);
+            }
+        ";
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq_with_config(content_good, &file_config, &validate);
+        // Not enabled, so nothing is flagged.
+        expected_findings_good.assert_eq(content_bad, &validate);
+
+        let expected_findings_bad = ExpectedFindings { src: 4, ..ExpectedFindings::default() };
+        expected_findings_bad.assert_eq_with_config(content_bad, &file_config, &validate);
+
+        // Only the configured tags are required.
+        let file_config =
+            FileConfig::from_toml("[rules.natspec]\nenabled = true\ntags = [\"notice\"]").unwrap();
+        let expected_findings_bad = ExpectedFindings { src: 3, ..ExpectedFindings::default() };
+        expected_findings_bad.assert_eq_with_config(content_bad, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_missing_tags() {
+        let content = r"
+            contract Vault {
+                /// @notice Deposits `amount`.
+                /// @param assets The amount to deposit.
+                function deposit(uint256 amount, address) external returns (uint256 shares, bool) {}
+            }
+        ";
+        let mut parsed = parse_source(Path::new("./src/Vault.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.natspec]\nenabled = true").unwrap();
+        let texts: Vec<String> = validate(&parsed).into_iter().map(|item| item.text).collect();
+        assert_eq!(
+            texts,
+            ["Function 'deposit' is missing NatSpec @param amount, @return shares, @return"]
+        );
+    }
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(NatspecTag::parse("notice"), Some(NatspecTag::Notice));
+        assert_eq!(NatspecTag::parse("@param"), None);
+        assert_eq!(NatspecTag::parse(NatspecTag::Return.name()), Some(NatspecTag::Return));
+    }
+}
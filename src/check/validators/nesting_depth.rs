@@ -0,0 +1,173 @@
+use crate::check::{
+    utils::{function_definitions, FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::pt::{CatchClause, FunctionDefinition, Loc, Statement};
+
+/// The deepest nesting allowed when `[rules.nesting] max_depth` is not set.
+pub const DEFAULT_MAX_DEPTH: usize = 4;
+
+fn is_matching_file(parsed: &Parsed) -> bool {
+    parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
+}
+
+#[must_use]
+/// Validates that function bodies in src files don't nest blocks, conditionals, loops, and
+/// try/catch statements deeper than the configured maximum.
+///
+/// Each function is reported once, at its most deeply nested statement. An `else if` is at the
+/// same depth as the `if` it continues.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !is_matching_file(parsed) {
+        return Vec::new();
+    }
+    let max_depth = parsed.file_config.max_nesting_depth();
+
+    function_definitions(&parsed.pt)
+        .filter_map(|f| {
+            let (depth, loc) = deepest_statement(f)?;
+            (depth > max_depth).then(|| {
+                InvalidItem::new(
+                    ValidatorKind::Nesting,
+                    parsed,
+                    loc,
+                    format!(
+                        "{} nests {depth} levels deep, more than {max_depth}; return early or extract a function",
+                        function_name(f)
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Returns the depth and location of the most deeply nested statement in the body of `f`, if it
+/// has any nested statements.
+fn deepest_statement(f: &FunctionDefinition) -> Option<(usize, Loc)> {
+    let mut deepest = None;
+    visit_body(f.body.as_ref()?, 0, &mut deepest);
+    deepest
+}
+
+/// Visits the statements of a body at `depth`, without counting its braces as another level.
+fn visit_body(body: &Statement, depth: usize, deepest: &mut Option<(usize, Loc)>) {
+    match body {
+        Statement::Block { statements, .. } => {
+            for stmt in statements {
+                visit(stmt, depth, deepest);
+            }
+        }
+        stmt => visit(stmt, depth, deepest),
+    }
+}
+
+/// Visits a statement inside `depth` nested statements, recording it in `deepest` if it nests.
+fn visit(stmt: &Statement, depth: usize, deepest: &mut Option<(usize, Loc)>) {
+    let nested = depth + 1;
+    let loc = match stmt {
+        Statement::If(loc, _, then_stmt, else_stmt) => {
+            visit_body(then_stmt, nested, deepest);
+            match else_stmt.as_deref() {
+                Some(else_if @ Statement::If(..)) => visit(else_if, depth, deepest),
+                Some(else_stmt) => visit_body(else_stmt, nested, deepest),
+                None => {}
+            }
+            *loc
+        }
+        Statement::While(loc, _, body) | Statement::DoWhile(loc, body, _) => {
+            visit_body(body, nested, deepest);
+            *loc
+        }
+        Statement::For(loc, _, _, _, body) => {
+            if let Some(body) = body {
+                visit_body(body, nested, deepest);
+            }
+            *loc
+        }
+        Statement::Try(loc, _, returns, catch_clauses) => {
+            if let Some((_, body)) = returns {
+                visit_body(body, nested, deepest);
+            }
+            for clause in catch_clauses {
+                let (CatchClause::Simple(_, _, body) | CatchClause::Named(_, _, _, body)) = clause;
+                visit_body(body, nested, deepest);
+            }
+            *loc
+        }
+        Statement::Block { loc, .. } => {
+            visit_body(stmt, nested, deepest);
+            *loc
+        }
+        _ => return,
+    };
+    if deepest.is_none_or(|(max, _)| nested > max) {
+        *deepest = Some((nested, loc));
+    }
+}
+
+/// Returns how findings refer to `f`, e.g. `Function 'deposit'` or `Constructor`.
+fn function_name(f: &FunctionDefinition) -> String {
+    f.name.as_ref().map_or_else(
+        || {
+            let mut ty = f.ty.to_string();
+            ty[..1].make_ascii_uppercase();
+            ty
+        },
+        |name| format!("Function '{name}'"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            contract MyContract {
+                function shallow(uint256 x) external {
+                    if (x > 0) {
+                        for (uint256 i = 0; i < x; i++) {
+                            if (i == 1) {
+                                x--;
+                            } else if (i == 2) {
+                                x++;
+                            } else {
+                                while (x > 10) {
+                                    x--;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                function deep(uint256 x) external {
+                    if (x > 0) {
+                        for (uint256 i = 0; i < x; i++) {
+                            if (i == 1) {
+                                while (x > 10) {
+                                    unchecked {
+                                        x--;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                constructor(uint256 x) {
+                    if (x > 0) { if (x > 1) { if (x > 2) { if (x > 3) { if (x > 4) {} } } } }
+                }
+            }
+        ";
+
+        let expected_findings = ExpectedFindings { src: 2, ..ExpectedFindings::default() };
+        expected_findings.assert_eq(content, &validate);
+
+        // Each level is an offense with a lower maximum.
+        let file_config = FileConfig::from_toml("[rules.nesting]\nmax_depth = 3").unwrap();
+        let expected_findings = ExpectedFindings { src: 3, ..ExpectedFindings::default() };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::check::{
+    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::pt::{ContractTy, SourceUnitPart};
+
+#[must_use]
+/// Validates that src files declare at most one non-abstract contract, since files with several
+/// are harder to verify on block explorers and to diff.
+///
+/// Libraries and interfaces next to the contract are flagged too, unless `[rules.onecontract]
+/// allow_companions = true` is set. Abstract contracts, and files without a non-abstract
+/// contract, such as files of interfaces, are never flagged. This rule is opt-in and only runs
+/// when `[rules.onecontract] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(allow_companions) = parsed.file_config.one_contract_companions() else {
+        return Vec::new();
+    };
+    if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+        return Vec::new();
+    }
+    let file_name = parsed.file.file_name().map_or_else(
+        || parsed.file.display().to_string(),
+        |name| name.to_string_lossy().to_string(),
+    );
+
+    let definitions: Vec<_> = parsed
+        .pt
+        .0
+        .iter()
+        .filter_map(|element| match element {
+            SourceUnitPart::ContractDefinition(c) => Some((&c.ty, c.name.as_ref()?)),
+            _ => None,
+        })
+        .collect();
+    let Some((_, main)) = definitions.iter().find(|(ty, _)| matches!(ty, ContractTy::Contract(_)))
+    else {
+        return Vec::new();
+    };
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for (ty, name) in &definitions {
+        let text = match ty {
+            ContractTy::Contract(_) if name.loc != main.loc => format!(
+                "Contract '{}' is declared next to contract '{}' in {file_name}, move it to its own file",
+                name.name, main.name
+            ),
+            ContractTy::Library(_) | ContractTy::Interface(_) if !allow_companions => format!(
+                "{} '{}' is declared next to contract '{}' in {file_name}, move it to its own file or set [rules.onecontract] allow_companions",
+                if matches!(ty, ContractTy::Library(_)) { "Library" } else { "Interface" },
+                name.name,
+                main.name
+            ),
+            _ => continue,
+        };
+        invalid_items.push(InvalidItem::new(ValidatorKind::OneContract, parsed, name.loc, text));
+    }
+    invalid_items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml("[rules.onecontract]\nenabled = true").unwrap();
+        let content = r"
+            interface IVault {}
+            library VaultLib {}
+            abstract contract VaultBase {}
+            contract Vault is VaultBase {}
+            contract VaultFactory {}
+        ";
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+        let expected_findings = ExpectedFindings { src: 3, ..ExpectedFindings::default() };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+
+        let file_config =
+            FileConfig::from_toml("[rules.onecontract]\nenabled = true\nallow_companions = true")
+                .unwrap();
+        let expected_findings = ExpectedFindings { src: 1, ..ExpectedFindings::default() };
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+
+        // Files without a non-abstract contract are skipped.
+        let content = r"
+            interface IVault {}
+            interface IVaultEvents {}
+            abstract contract VaultBase {}
+        ";
+        ExpectedFindings::default().assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_messages() {
+        let content = "library Math {}\ncontract Vault {}\ncontract Factory {}";
+        let mut parsed = parse_source(Path::new("./src/Vault.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.onecontract]\nenabled = true").unwrap();
+        let texts: Vec<String> = validate(&parsed).into_iter().map(|item| item.text).collect();
+        assert_eq!(
+            texts,
+            [
+                "Library 'Math' is declared next to contract 'Vault' in Vault.sol, move it to its own file or set [rules.onecontract] allow_companions",
+                "Contract 'Factory' is declared next to contract 'Vault' in Vault.sol, move it to its own file",
+            ]
+        );
+    }
+}
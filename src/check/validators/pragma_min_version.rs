@@ -0,0 +1,247 @@
+use crate::check::{
+    utils::{InvalidItem, ValidatorKind},
+    Parsed,
+};
+use regex::{Captures, Regex};
+use solang_parser::{helpers::CodeLocation, pt::SourceUnitPart};
+use std::sync::LazyLock;
+
+/// A compiler version, as `(major, minor, patch)`.
+pub type SolcVersion = (u64, u64, u64);
+
+static RE_PRAGMA_SOLIDITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^pragma\s+solidity\s+([^;]+)").unwrap());
+static RE_COMPARATOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\^|~|>=|<=|>|<|=)?\s*v?(\d+)(?:\.(\d+|[xX*]))?(?:\.(\d+|[xX*]))?").unwrap()
+});
+
+#[must_use]
+/// Validates that `pragma solidity` does not admit compilers older than the configured minimum.
+///
+/// This rule is opt-in and only runs when `[rules.pragma] min_solc` is configured in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(min_solc) = parsed.file_config.min_solc() else {
+        return Vec::new();
+    };
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for element in &parsed.pt.0 {
+        if !matches!(element, SourceUnitPart::PragmaDirective(..)) {
+            continue;
+        }
+        let loc = element.loc();
+        let Some(captures) = RE_PRAGMA_SOLIDITY.captures(&parsed.src[loc.start()..loc.end()])
+        else {
+            continue;
+        };
+        let requirement = captures[1].trim();
+
+        if let Some(lowest) = lowest_admitted_version(requirement) {
+            if lowest < min_solc {
+                invalid_items.push(InvalidItem::new(
+                    ValidatorKind::Pragma,
+                    parsed,
+                    loc,
+                    format!(
+                        "`pragma solidity {requirement}` admits solc {}, below the minimum {}",
+                        format_version(lowest),
+                        format_version(min_solc)
+                    ),
+                ));
+            }
+        }
+    }
+    invalid_items
+}
+
+/// Parses a plain version such as `0.8.20` or `0.8`. Missing components default to zero.
+#[must_use]
+pub fn parse_version(version: &str) -> Option<SolcVersion> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Formats a version as `major.minor.patch`, as written in `[rules.pragma] min_solc`.
+#[must_use]
+pub fn format_version((major, minor, patch): SolcVersion) -> String {
+    format!("{major}.{minor}.{patch}")
+}
+
+/// Returns the lowest compiler version admitted by a version requirement such as `^0.8.0` or
+/// `>=0.7.0 <0.9.0 || 0.6.12`. Returns `None` if the requirement can't be parsed.
+#[must_use]
+pub fn lowest_admitted_version(requirement: &str) -> Option<SolcVersion> {
+    requirement.split("||").map(lowest_admitted_by_range).min()?
+}
+
+/// Returns the lower bound of a single range, i.e. a set of comparators that must all hold.
+fn lowest_admitted_by_range(range: &str) -> Option<SolcVersion> {
+    // In a hyphen range such as `0.8.0 - 0.8.20`, only the first version is a lower bound.
+    let range = range.split(" - ").next()?;
+
+    let mut lowest: SolcVersion = (0, 0, 0);
+    let mut parsed_any = false;
+    for captures in RE_COMPARATOR.captures_iter(range) {
+        parsed_any = true;
+        // Wildcards such as `0.8.x` and missing components admit zero, and `>` excludes every
+        // version the comparator's version matches, e.g. `>0.8` admits `0.9.0` at the lowest.
+        let (lower, upper) = comparator_bounds(&captures);
+        let bound = match captures.get(1).map(|m| m.as_str()) {
+            // Upper bounds don't raise the lowest admitted version.
+            Some("<" | "<=") => continue,
+            Some(">") => upper.unwrap_or(lower),
+            _ => lower,
+        };
+        lowest = lowest.max(bound);
+    }
+    parsed_any.then_some(lowest)
+}
+
+/// Returns whether a version requirement such as `^0.8.0` or `>=0.7.0 <0.9.0 || 0.6.12` admits
+/// `version`. Returns `None` if the requirement can't be parsed.
+#[must_use]
+pub fn admits(requirement: &str, version: SolcVersion) -> Option<bool> {
+    let mut admitted = None;
+    for range in requirement.split("||") {
+        let range_admits = range_admits(range, version)?;
+        admitted = Some(admitted.unwrap_or(false) || range_admits);
+    }
+    admitted
+}
+
+/// Returns whether a single range, i.e. a set of comparators that must all hold, admits
+/// `version`.
+fn range_admits(range: &str, version: SolcVersion) -> Option<bool> {
+    // In a hyphen range such as `0.8.0 - 0.8.20`, both ends are admitted.
+    if let Some((from, to)) = range.split_once(" - ") {
+        let (lower, _) = comparator_bounds(&RE_COMPARATOR.captures(from)?);
+        let (_, upper) = comparator_bounds(&RE_COMPARATOR.captures(to)?);
+        return Some(lower <= version && upper.is_none_or(|upper| version < upper));
+    }
+
+    let mut parsed_any = false;
+    for captures in RE_COMPARATOR.captures_iter(range) {
+        parsed_any = true;
+        let (lower, upper) = match captures.get(1).map(|m| m.as_str()) {
+            Some(">=") => (comparator_bounds(&captures).0, None),
+            Some(">") => (comparator_bounds(&captures).1.unwrap_or_default(), None),
+            Some("<") => ((0, 0, 0), Some(comparator_bounds(&captures).0)),
+            Some("<=") => ((0, 0, 0), comparator_bounds(&captures).1),
+            Some("^" | "~") => caret_or_tilde_bounds(&captures),
+            _ => comparator_bounds(&captures),
+        };
+        if version < lower || upper.is_some_and(|upper| version >= upper) {
+            return Some(false);
+        }
+    }
+    parsed_any.then_some(true)
+}
+
+/// Returns the versions matched by a comparator's version as written, e.g. `0.8` matches from
+/// `0.8.0` up to but excluding `0.9.0`, and `0.8.20` matches only itself.
+fn comparator_bounds(captures: &Captures) -> (SolcVersion, Option<SolcVersion>) {
+    // Wildcards such as `0.8.x` count as missing components.
+    let component = |i: usize| captures.get(i).and_then(|m| m.as_str().parse::<u64>().ok());
+    let major = component(2).unwrap_or(0);
+    let upper = match (component(3), component(4)) {
+        (None, _) => (major + 1, 0, 0),
+        (Some(minor), None) => (major, minor + 1, 0),
+        (Some(minor), Some(patch)) => (major, minor, patch + 1),
+    };
+    ((major, component(3).unwrap_or(0), component(4).unwrap_or(0)), Some(upper))
+}
+
+/// Returns the versions matched by a `^` or `~` comparator. `^` admits changes that leave the
+/// first non-zero component alone, `~` admits patch changes if the minor version is given.
+fn caret_or_tilde_bounds(captures: &Captures) -> (SolcVersion, Option<SolcVersion>) {
+    let (lower, exact_upper) = comparator_bounds(captures);
+    let (major, minor, _) = lower;
+    let minor_given = captures.get(3).is_some_and(|m| m.as_str().parse::<u64>().is_ok());
+    let upper = match captures.get(1).map(|m| m.as_str()) {
+        Some("~") if minor_given => (major, minor + 1, 0),
+        Some("~") => (major + 1, 0, 0),
+        _ if major > 0 || !minor_given => (major + 1, 0, 0),
+        _ if minor > 0 => (0, minor + 1, 0),
+        _ => exact_upper.unwrap_or((0, 1, 0)),
+    };
+    (lower, Some(upper))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml("[rules.pragma]\nmin_solc = \"0.8.20\"").unwrap();
+
+        let content_good = r"
+            pragma solidity ^0.8.20;
+            pragma solidity >=0.8.20 <0.9.0;
+            pragma solidity 0.8.23;
+            pragma solidity >0.8.19;
+            pragma abicoder v2;
+            contract MyContract {}
+        ";
+
+        let content_bad = r"
+            pragma solidity ^0.8.0;
+            pragma solidity >=0.7.0 <0.9.0;
+            pragma solidity <0.9.0;
+            pragma solidity 0.8.24 || ^0.8.19;
+            contract MyContract {}
+        ";
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq_with_config(content_good, &file_config, &validate);
+        // Not configured, so nothing is flagged.
+        expected_findings_good.assert_eq(content_bad, &validate);
+
+        ExpectedFindings::new(4).assert_eq_with_config(content_bad, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_lowest_admitted_version() {
+        assert_eq!(lowest_admitted_version("^0.8.0"), Some((0, 8, 0)));
+        assert_eq!(lowest_admitted_version("~0.8"), Some((0, 8, 0)));
+        assert_eq!(lowest_admitted_version(">= 0.8.4 < 0.9.0"), Some((0, 8, 4)));
+        assert_eq!(lowest_admitted_version(">0.8.4"), Some((0, 8, 5)));
+        assert_eq!(lowest_admitted_version(">0.8"), Some((0, 9, 0)));
+        assert_eq!(lowest_admitted_version(">0.8.x"), Some((0, 9, 0)));
+        assert_eq!(lowest_admitted_version(">0"), Some((1, 0, 0)));
+        assert_eq!(lowest_admitted_version("0.8.0 - 0.8.20"), Some((0, 8, 0)));
+        assert_eq!(lowest_admitted_version("0.8.x"), Some((0, 8, 0)));
+        assert_eq!(lowest_admitted_version("^0.8.19 || 0.7.6"), Some((0, 7, 6)));
+        assert_eq!(lowest_admitted_version("<=0.8.20"), Some((0, 0, 0)));
+        assert_eq!(lowest_admitted_version("latest"), None);
+    }
+
+    #[test]
+    fn test_admits() {
+        let pinned = (0, 8, 28);
+        assert_eq!(admits("0.8.28", pinned), Some(true));
+        assert_eq!(admits("=0.8.28", pinned), Some(true));
+        assert_eq!(admits("0.8.20", pinned), Some(false));
+        assert_eq!(admits("^0.8.20", pinned), Some(true));
+        assert_eq!(admits("^0.7.6", pinned), Some(false));
+        assert_eq!(admits("~0.8.20", pinned), Some(true));
+        assert_eq!(admits("~0.8", pinned), Some(true));
+        assert_eq!(admits("0.8", pinned), Some(true));
+        assert_eq!(admits("0.8.x", pinned), Some(true));
+        assert_eq!(admits(">=0.8.20 <0.8.28", pinned), Some(false));
+        assert_eq!(admits(">=0.8.20 <=0.8.28", pinned), Some(true));
+        assert_eq!(admits(">0.8.28", pinned), Some(false));
+        assert_eq!(admits("0.8.0 - 0.8.27", pinned), Some(false));
+        assert_eq!(admits("0.8.0 - 0.8", pinned), Some(true));
+        assert_eq!(admits("0.7.6 || ^0.8.24", pinned), Some(true));
+        assert_eq!(admits("^0.0.3", (0, 0, 4)), Some(false));
+        assert_eq!(admits("latest", pinned), None);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    check::{
+        file_config::FileConfig,
+        utils::{import_paths, offset_to_line, InvalidItem, ValidatorKind},
+        Parsed,
+    },
+    foundry_config::{self, DEFAULT_PROFILE},
+};
+use solang_parser::pt::Loc;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs,
+    ops::Range,
+    path::Path,
+};
+use toml_edit::{ImDocument, Item};
+
+/// The path of `remappings.txt`, relative to the project root.
+const REMAPPINGS_TXT: &str = "./remappings.txt";
+
+/// The path of `foundry.toml`, relative to the project root.
+const FOUNDRY_TOML: &str = "./foundry.toml";
+
+/// A remapping as written in `remappings.txt` or `foundry.toml`, with where it was written.
+struct Remapping<'a> {
+    file: &'a Path,
+    src: &'a str,
+    span: Range<usize>,
+    text: &'a str,
+}
+
+#[must_use]
+/// Validates the remappings of `remappings.txt` and the `remappings` of the selected profile in
+/// `foundry.toml`.
+///
+/// Each remapping must point to an existing directory, must not remap a prefix another remapping
+/// remaps to a different target, and must be used by an import of a file in `files`, i.e. the
+/// src, test, and script directories. Remappings scoped to a context, e.g. `lib/a:b/=c/`, usually
+/// serve dependencies and aren't checked for use. This rule is opt-in and only runs when
+/// `[rules.remappings] enabled = true` is set in `.scopelint`.
+pub fn validate(file_config: &FileConfig, files: &[Parsed]) -> Vec<InvalidItem> {
+    if !file_config.remappings_rule_enabled() {
+        return Vec::new();
+    }
+    let remappings_txt = fs::read_to_string(REMAPPINGS_TXT).ok();
+    let foundry_toml = fs::read_to_string(FOUNDRY_TOML).ok();
+    let imports: Vec<String> = files
+        .iter()
+        .flat_map(|parsed| import_paths(parsed).into_iter().map(|(_, path)| path))
+        .collect();
+    validate_sources(
+        remappings_txt.as_deref(),
+        foundry_toml.as_deref(),
+        &foundry_config::profile(),
+        &imports,
+        &|target| Path::new(target).is_dir(),
+        file_config,
+    )
+}
+
+/// Validates the remappings of the given `remappings.txt` and `foundry.toml` sources against the
+/// `imports` of the project. `is_dir` tells whether a target exists.
+fn validate_sources(
+    remappings_txt: Option<&str>,
+    foundry_toml: Option<&str>,
+    profile: &str,
+    imports: &[String],
+    is_dir: &dyn Fn(&str) -> bool,
+    file_config: &FileConfig,
+) -> Vec<InvalidItem> {
+    let mut remappings = Vec::new();
+    if let Some(src) = remappings_txt {
+        remappings.extend(remappings_txt_entries(src));
+    }
+    let doc = foundry_toml.and_then(|src| Some((src, ImDocument::parse(src).ok()?)));
+    if let Some((src, doc)) = &doc {
+        remappings.extend(foundry_toml_entries(src, doc, profile));
+    }
+
+    // (context, prefix) -> the target of its first remapping, and where that is.
+    let mut targets: HashMap<(Option<&str>, &str), (&str, String)> = HashMap::new();
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for entry in remappings {
+        let finding = |text: String| {
+            let loc = Loc::File(0, entry.span.start, entry.span.end);
+            InvalidItem::in_file(
+                ValidatorKind::Remappings,
+                entry.file,
+                entry.src,
+                loc,
+                text,
+                file_config,
+            )
+        };
+        let Some((key, target)) = entry.text.split_once('=') else {
+            invalid_items.push(finding(format!(
+                "Remapping '{}' is not of the form `prefix=target`",
+                entry.text
+            )));
+            continue;
+        };
+        let (context, prefix) =
+            key.split_once(':').map_or((None, key), |(context, prefix)| (Some(context), prefix));
+
+        if !is_dir(target.trim_end_matches('/')) {
+            invalid_items.push(finding(format!(
+                "Remapping '{}' points to {target}, which is not a directory",
+                entry.text
+            )));
+        }
+
+        let location =
+            format!("{}:{}", entry.file.display(), offset_to_line(entry.src, entry.span.start));
+        match targets.entry((context, prefix.trim_end_matches('/'))) {
+            Entry::Vacant(vacant) => {
+                vacant.insert((target, location));
+            }
+            Entry::Occupied(occupied) => {
+                let (first_target, first_location) = occupied.get();
+                if first_target.trim_end_matches('/') != target.trim_end_matches('/') {
+                    invalid_items.push(finding(format!(
+                        "Remapping '{}' conflicts with the one to {first_target} in {first_location}",
+                        entry.text
+                    )));
+                }
+            }
+        }
+
+        if context.is_none() && !imports.iter().any(|import| import.starts_with(prefix)) {
+            invalid_items.push(finding(format!(
+                "Remapping '{}' is not used by any import in the src, test, or script directories",
+                entry.text
+            )));
+        }
+    }
+    invalid_items
+}
+
+/// Returns the remappings of `remappings.txt`, one per line. Empty lines and `#` comments are
+/// skipped.
+fn remappings_txt_entries(src: &str) -> Vec<Remapping<'_>> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let text = line.trim();
+        if !text.is_empty() && !text.starts_with('#') {
+            let start = offset + line.len() - line.trim_start().len();
+            entries.push(Remapping {
+                file: Path::new(REMAPPINGS_TXT),
+                src,
+                span: start..start + text.len(),
+                text,
+            });
+        }
+        offset += line.len();
+    }
+    entries
+}
+
+/// Returns the `remappings` of `profile` in `foundry.toml`, or of `[profile.default]` if the
+/// profile doesn't set any, like Forge.
+fn foundry_toml_entries<'a>(
+    src: &'a str,
+    doc: &'a ImDocument<&'a str>,
+    profile: &str,
+) -> Vec<Remapping<'a>> {
+    let profiles = doc.get("profile").and_then(Item::as_table_like);
+    let remappings = [profile, DEFAULT_PROFILE]
+        .into_iter()
+        .find_map(|profile| profiles?.get(profile)?.as_table_like()?.get("remappings")?.as_array());
+    remappings
+        .into_iter()
+        .flatten()
+        .filter_map(|value| {
+            Some(Remapping {
+                file: Path::new(FOUNDRY_TOML),
+                src,
+                span: value.span()?,
+                text: value.as_str()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(remappings_txt: Option<&str>, foundry_toml: Option<&str>) -> Vec<String> {
+        let imports = ["forge-std/Test.sol", "@openzeppelin/contracts/token/ERC20/ERC20.sol"]
+            .map(ToString::to_string);
+        validate_sources(
+            remappings_txt,
+            foundry_toml,
+            "ci",
+            &imports,
+            &|target| target.starts_with("lib/"),
+            &FileConfig::default(),
+        )
+        .into_iter()
+        .map(|item| format!("{}:{}: {}", item.file, item.line, item.text))
+        .collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let good = "# Dependencies\n\
+                    forge-std/=lib/forge-std/src/\n\
+                    \n\
+                    @openzeppelin/=lib/openzeppelin-contracts/\n\
+                    lib/solady:src/=lib/solady/src/\n";
+        assert!(findings(Some(good), None).is_empty());
+
+        let bad = "forge-std/=lib/forge-std/src/\n\
+                   @openzeppelin/=node_modules/@openzeppelin/\n\
+                   solmate/=lib/solmate/src/\n\
+                   forge-std=lib/forge-std/\n\
+                   ds-test\n";
+        assert_eq!(
+            findings(Some(bad), None),
+            [
+                "./remappings.txt:2: Remapping '@openzeppelin/=node_modules/@openzeppelin/' points to node_modules/@openzeppelin/, which is not a directory",
+                "./remappings.txt:3: Remapping 'solmate/=lib/solmate/src/' is not used by any import in the src, test, or script directories",
+                "./remappings.txt:4: Remapping 'forge-std=lib/forge-std/' conflicts with the one to lib/forge-std/src/ in ./remappings.txt:1",
+                "./remappings.txt:5: Remapping 'ds-test' is not of the form `prefix=target`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_foundry_toml() {
+        let foundry_toml = r#"
+[profile.default]
+  remappings = ["solmate/=lib/solmate/src/"]
+
+[profile.ci]
+  remappings = [
+    "forge-std/=lib/forge-std/src/",
+    "@openzeppelin/=lib/oz/",
+  ]
+"#;
+        // The `ci` profile is selected, so its remappings replace the default ones.
+        assert!(findings(None, Some(foundry_toml)).is_empty());
+
+        // Remappings in both files are compared.
+        assert_eq!(
+            findings(Some("@openzeppelin/=lib/openzeppelin-contracts/\n"), Some(foundry_toml)),
+            ["./foundry.toml:8: Remapping '@openzeppelin/=lib/oz/' conflicts with the one to lib/openzeppelin-contracts/ in ./remappings.txt:1"]
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use crate::check::{
+    utils::{
+        visit_expressions, visit_statement_expressions, visit_statements, FileKind, InvalidItem,
+        IsFileKind, ValidatorKind,
+    },
+    Parsed,
+};
+use solang_parser::pt::{ContractPart, Expression, SourceUnitPart, Statement};
+use std::collections::HashSet;
+
+fn is_matching_file(parsed: &Parsed) -> bool {
+    parsed.file.is_file_kind(FileKind::Script, &parsed.path_config)
+}
+
+#[must_use]
+/// Validates that deployment scripts produce the same deployments on every run.
+///
+/// Flags CREATE2 salts derived from `block.timestamp`, `block.number`, `msg.sender` or `tx.origin`
+/// (directly or through local variables), use of `block.prevrandao`/`block.difficulty`, and
+/// `vm.random*` cheatcodes in scripts that never call `vm.setSeed`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !is_matching_file(parsed) {
+        return Vec::new();
+    }
+
+    let bodies: Vec<&Statement> = parsed
+        .pt
+        .0
+        .iter()
+        .filter_map(|element| match element {
+            SourceUnitPart::ContractDefinition(c) => Some(&c.parts),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|part| match part {
+            ContractPart::FunctionDefinition(f) => f.body.as_ref(),
+            _ => None,
+        })
+        .collect();
+
+    let is_seeded = bodies.iter().any(|body| {
+        let mut found = false;
+        visit_statement_expressions(body, &mut |expr| {
+            found |= is_vm_call(expr, |name| name == "setSeed");
+        });
+        found
+    });
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for body in bodies {
+        let tainted = tainted_locals(body);
+
+        visit_statement_expressions(body, &mut |expr| match expr {
+            Expression::FunctionCallBlock(_, _, block) => {
+                let Statement::Args(_, args) = block.as_ref() else { return };
+                for arg in args.iter().filter(|arg| arg.name.name == "salt") {
+                    if let Some(source) = nondeterministic_source(&arg.expr, &tainted) {
+                        invalid_items.push(InvalidItem::new(
+                            ValidatorKind::Determinism,
+                            parsed,
+                            arg.loc,
+                            format!("CREATE2 salt depends on `{source}`"),
+                        ));
+                    }
+                }
+            }
+            Expression::MemberAccess(loc, inner, member) if is_randomness(inner, &member.name) => {
+                invalid_items.push(InvalidItem::new(
+                    ValidatorKind::Determinism,
+                    parsed,
+                    *loc,
+                    format!("`block.{}` is not reproducible across runs", member.name),
+                ));
+            }
+            Expression::FunctionCall(loc, ..)
+                if !is_seeded && is_vm_call(expr, |name| name.starts_with("random")) =>
+            {
+                invalid_items.push(InvalidItem::new(
+                    ValidatorKind::Determinism,
+                    parsed,
+                    *loc,
+                    "unseeded randomness cheatcode, call `vm.setSeed` first".to_string(),
+                ));
+            }
+            _ => {}
+        });
+    }
+    invalid_items
+}
+
+/// Returns the names of local variables in `body` whose value is derived from a
+/// non-deterministic source, following assignments until no new variables are found.
+fn tainted_locals(body: &Statement) -> HashSet<String> {
+    let mut tainted: HashSet<String> = HashSet::new();
+    loop {
+        let mut newly_tainted: Vec<String> = Vec::new();
+        visit_statements(body, &mut |stmt| {
+            if let Statement::VariableDefinition(_, decl, Some(init)) = stmt {
+                if let Some(name) = &decl.name {
+                    if nondeterministic_source(init, &tainted).is_some() {
+                        newly_tainted.push(name.name.clone());
+                    }
+                }
+            }
+        });
+        visit_statement_expressions(body, &mut |expr| {
+            if let Expression::Assign(_, left, right) = expr {
+                if let Expression::Variable(name) = left.as_ref() {
+                    if nondeterministic_source(right, &tainted).is_some() {
+                        newly_tainted.push(name.name.clone());
+                    }
+                }
+            }
+        });
+
+        let count = tainted.len();
+        tainted.extend(newly_tainted);
+        if tainted.len() == count {
+            return tainted;
+        }
+    }
+}
+
+/// Returns the first non-deterministic value `expr` depends on, e.g. `block.timestamp` or the name
+/// of a tainted local variable.
+fn nondeterministic_source(expr: &Expression, tainted: &HashSet<String>) -> Option<String> {
+    let mut source: Option<String> = None;
+    visit_expressions(expr, &mut |inner| {
+        if source.is_some() {
+            return;
+        }
+        source = match inner {
+            Expression::MemberAccess(_, base, member) => match (base.as_ref(), &*member.name) {
+                (Expression::Variable(base), "timestamp" | "number") if base.name == "block" => {
+                    Some(format!("block.{}", member.name))
+                }
+                (Expression::Variable(base), "sender") if base.name == "msg" => {
+                    Some("msg.sender".to_string())
+                }
+                (Expression::Variable(base), "origin") if base.name == "tx" => {
+                    Some("tx.origin".to_string())
+                }
+                _ => None,
+            },
+            Expression::Variable(name) if tainted.contains(&name.name) => Some(name.name.clone()),
+            _ => None,
+        };
+    });
+    source
+}
+
+fn is_randomness(base: &Expression, member: &str) -> bool {
+    matches!(base, Expression::Variable(base) if base.name == "block") &&
+        matches!(member, "prevrandao" | "difficulty")
+}
+
+/// Returns true if `expr` is a call to a `vm` cheatcode whose name satisfies `matches_name`.
+fn is_vm_call(expr: &Expression, matches_name: impl Fn(&str) -> bool) -> bool {
+    let Expression::FunctionCall(_, callee, _) = expr else { return false };
+    matches!(
+        callee.as_ref(),
+        Expression::MemberAccess(_, base, member)
+            if matches!(base.as_ref(), Expression::Variable(base) if base.name == "vm") &&
+                matches_name(&member.name)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::utils::ExpectedFindings;
+
+    #[test]
+    fn test_validate() {
+        let content_good = r"
+            contract MyContract {
+                function run() public {
+                    bytes32 salt = keccak256('my-salt');
+                    new Foo{salt: salt}();
+                    new Foo{salt: bytes32(uint256(1)), value: block.number}();
+                    uint256 start = block.timestamp;
+                }
+            }
+        ";
+
+        let content_seeded = r"
+            contract MyContract {
+                function setUp() public {
+                    vm.setSeed(42);
+                }
+
+                function run() public {
+                    uint256 amount = vm.randomUint();
+                }
+            }
+        ";
+
+        let content_bad_salts = r"
+            contract MyContract {
+                function run() public {
+                    new Foo{salt: bytes32(block.timestamp)}();
+                    new Foo{salt: keccak256(abi.encode(msg.sender))}();
+                    bytes32 salt = bytes32(block.number);
+                    new Foo{salt: salt}();
+                }
+            }
+        ";
+
+        let content_bad_indirect_salt = r"
+            contract MyContract {
+                function run() public {
+                    address deployer = tx.origin;
+                    bytes32 salt;
+                    salt = keccak256(abi.encode(deployer, 1));
+                    if (true) {
+                        new Foo{salt: salt}(1);
+                    }
+                }
+            }
+        ";
+
+        let content_bad_randomness = r"
+            contract MyContract {
+                function run() public {
+                    uint256 a = block.prevrandao;
+                    uint256 b = block.difficulty;
+                    address c = vm.randomAddress();
+                }
+            }
+        ";
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq(content_good, &validate);
+        expected_findings_good.assert_eq(content_seeded, &validate);
+
+        ExpectedFindings { script: 3, ..Default::default() }
+            .assert_eq(content_bad_salts, &validate);
+        ExpectedFindings { script: 1, ..Default::default() }
+            .assert_eq(content_bad_indirect_salt, &validate);
+        ExpectedFindings { script: 3, ..Default::default() }
+            .assert_eq(content_bad_randomness, &validate);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::check::{
+    utils::{visit_contract_expressions, FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{
+    helpers::CodeLocation,
+    pt::{Expression, Loc},
+};
+
+fn is_matching_file(parsed: &Parsed) -> bool {
+    parsed.file.is_file_kind(FileKind::Script, &parsed.path_config)
+}
+
+#[must_use]
+/// Validates that every env var a script reads is declared in `[scripts.env] required`.
+///
+/// This rule is opt-in and only runs when `[scripts.env]` is configured in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(required) = parsed.file_config.required_env_vars() else {
+        return Vec::new();
+    };
+    if !is_matching_file(parsed) {
+        return Vec::new();
+    }
+
+    env_var_names(parsed)
+        .into_iter()
+        .filter(|(_, name)| !required.contains(name))
+        .map(|(loc, name)| {
+            InvalidItem::new(
+                ValidatorKind::Env,
+                parsed,
+                loc,
+                format!(
+                    "'{name}' is not listed in `[scripts.env] required`, run `scopelint check --write` to update it"
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Returns the names of env vars read through `vm.env*` cheatcodes in a script, with the location
+/// of each call. Calls whose name is not a string literal are skipped.
+#[must_use]
+pub fn env_var_names(parsed: &Parsed) -> Vec<(Loc, String)> {
+    if !is_matching_file(parsed) {
+        return Vec::new();
+    }
+
+    let mut names: Vec<(Loc, String)> = Vec::new();
+    visit_contract_expressions(&parsed.pt, &mut |expr| {
+        if let Some(name) = env_var_name(expr) {
+            names.push((expr.loc(), name));
+        }
+    });
+    names
+}
+
+/// Returns the env var name if `expr` is a `vm.env*("NAME", ...)` call.
+fn env_var_name(expr: &Expression) -> Option<String> {
+    let Expression::FunctionCall(_, callee, args) = expr else { return None };
+    let Expression::MemberAccess(_, base, member) = callee.as_ref() else { return None };
+    let Expression::Variable(base) = base.as_ref() else { return None };
+    if base.name != "vm" || !member.name.starts_with("env") {
+        return None;
+    }
+
+    match args.first()? {
+        Expression::StringLiteral(parts) => Some(parts.iter().map(|part| &*part.string).collect()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml(
+            r#"
+[scripts.env]
+required = ["DEPLOYER", "RPC_URL", "ADMINS"]
+"#,
+        )
+        .unwrap();
+
+        let content_good = r#"
+            contract MyContract {
+                address deployer = vm.envAddress("DEPLOYER");
+
+                function run() public {
+                    string memory rpcUrl = vm.envString("RPC_URL");
+                    address[] memory admins = vm.envAddress("ADMINS", ",");
+                    string memory key = string.concat("KEY_", "1");
+                    vm.envUint(key);
+                }
+            }
+        "#;
+
+        let content_bad = r#"
+            contract MyContract {
+                uint256 amount = vm.envUint("AMOUNT");
+
+                function run() public {
+                    bool dryRun = vm.envOr("DRY_RUN", false);
+                    if (vm.envBool("VERBOSE")) {}
+                    address deployer = vm.envAddress("DEPLOYER");
+                }
+            }
+        "#;
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq_with_config(content_good, &file_config, &validate);
+        // Not configured, so nothing is flagged.
+        expected_findings_good.assert_eq(content_bad, &validate);
+
+        let expected_findings_bad = ExpectedFindings { script: 3, ..Default::default() };
+        expected_findings_bad.assert_eq_with_config(content_bad, &file_config, &validate);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::check::{
+    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::pt::{ContractDefinition, ContractTy, SourceUnitPart};
+
+/// The suffix script contract names must end with when `[rules.scriptfile] suffix` is not set.
+pub const DEFAULT_SUFFIX: &str = "Script";
+
+#[must_use]
+/// Validates the naming and structure of files in the script directory.
+///
+/// A contract inheriting forge-std's `Script` must be in a `.s.sol` file, and each `.s.sol` file
+/// must contain exactly one deployable contract, whose name ends with the configured suffix
+/// (`Script` by default). Interfaces, libraries, and abstract contracts don't count, and helper
+/// files without the `.s.sol` suffix may contain any number of contracts that aren't scripts.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.path_config.in_script(&parsed.file) {
+        return Vec::new();
+    }
+    let is_script_file = parsed.file.is_file_kind(FileKind::Script, &parsed.path_config);
+    let suffix = parsed.file_config.script_contract_suffix();
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    let mut script_contracts = 0;
+    for element in &parsed.pt.0 {
+        let SourceUnitPart::ContractDefinition(c) = element else { continue };
+        let Some(name) = &c.name else { continue };
+        if !matches!(c.ty, ContractTy::Contract(_)) {
+            continue;
+        }
+
+        if !is_script_file {
+            if inherits_script(c) {
+                invalid_items.push(InvalidItem::new(
+                    ValidatorKind::ScriptFile,
+                    parsed,
+                    name.loc,
+                    format!("{name} inherits `Script`, so its file name should end in `.s.sol`"),
+                ));
+            }
+            continue;
+        }
+
+        script_contracts += 1;
+        if script_contracts > 1 {
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::ScriptFile,
+                parsed,
+                name.loc,
+                format!("{name} should be in its own file, scripts contain one contract per file"),
+            ));
+        }
+        if !name.name.ends_with(suffix) {
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::ScriptFile,
+                parsed,
+                name.loc,
+                format!("{name} should end with `{suffix}`"),
+            ));
+        }
+    }
+    invalid_items
+}
+
+fn inherits_script(c: &ContractDefinition) -> bool {
+    c.base.iter().any(|base| base.name.identifiers.last().is_some_and(|id| id.name == "Script"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let content_good = r"
+            interface IMultiSend {}
+            library Addresses {}
+            abstract contract BaseScript is Script {}
+            contract DeployScript is BaseScript {
+                function run() public {}
+            }
+        ";
+
+        let content_bad = r"
+            contract Deploy is Script {
+                function run() public {}
+            }
+            contract UpgradeScript is Script {
+                function run() public {}
+            }
+        ";
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq(content_good, &validate);
+
+        // In `.s.sol` files, `Deploy` is misnamed and `UpgradeScript` shares the file. In helper
+        // files, both inherit `Script` without the `.s.sol` suffix.
+        let expected_findings_bad =
+            ExpectedFindings { script: 2, script_helper: 2, ..ExpectedFindings::default() };
+        expected_findings_bad.assert_eq(content_bad, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.scriptfile]\nsuffix = \"\"").unwrap();
+        let expected_findings_no_suffix =
+            ExpectedFindings { script: 1, script_helper: 2, ..ExpectedFindings::default() };
+        expected_findings_no_suffix.assert_eq_with_config(content_bad, &file_config, &validate);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::check::{
+    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{
+    helpers::CodeLocation,
+    pt::{ContractDefinition, ContractTy, SourceUnitPart},
+};
+
+const TAG: &str = "@custom:security-contact";
+
+#[must_use]
+/// Validates that deployable src contracts have a `@custom:security-contact` tag.
+///
+/// This rule is opt-in and only runs when `[rules.contact] enabled = true` is set in `.scopelint`.
+/// Abstract contracts, interfaces, libraries, and contracts listed in `[rules.contact] exempt` are
+/// skipped.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.file_config.contact_rule_enabled() ||
+        !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
+    {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    // NatSpec of a contract is the doc comments between the previous top-level item and the
+    // contract itself.
+    let mut previous_end = 0;
+    for element in &parsed.pt.0 {
+        if let SourceUnitPart::ContractDefinition(c) = element {
+            if let Some(text) = check_contract(parsed, c, previous_end) {
+                invalid_items.push(InvalidItem::new(
+                    ValidatorKind::SecurityContact,
+                    parsed,
+                    c.name.as_ref().map_or(c.loc, |name| name.loc),
+                    text,
+                ));
+            }
+        }
+        previous_end = element.loc().end();
+    }
+    invalid_items
+}
+
+fn check_contract(parsed: &Parsed, c: &ContractDefinition, natspec_start: usize) -> Option<String> {
+    if !matches!(c.ty, ContractTy::Contract(_)) {
+        return None;
+    }
+    let name = &c.name.as_ref()?.name;
+    if parsed.file_config.is_contact_exempt(name) {
+        return None;
+    }
+
+    let contact =
+        parsed.comments.doc_comments_in(natspec_start, c.loc.start()).find_map(|comment| {
+            let (_, rest) = comment.contents().split_once(TAG)?;
+            Some(rest.lines().next().unwrap_or_default().trim_end_matches("*/").trim().to_string())
+        });
+
+    match contact {
+        None => Some(format!("{name} has no `{TAG}` NatSpec tag")),
+        Some(contact) if contact.is_empty() => Some(format!("{name} has an empty `{TAG}` tag")),
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let file_config =
+            FileConfig::from_toml("[rules.contact]\nenabled = true\nexempt = [\"MockToken\"]")
+                .unwrap();
+
+        let content_good = r"
+            /// @title Token
+            /// @custom:security-contact security@example.com
+            contract Token {}
+
+            /**
+             * @notice A vault.
+             * @custom:security-contact https://example.com/security
+             */
+            contract Vault is Token {}
+
+            abstract contract Base {}
+            interface IToken {}
+            library Math {}
+            contract MockToken {}
+        ";
+
+        let content_bad = r"
+            contract Token {}
+
+            // @custom:security-contact security@example.com
+            contract Vault {}
+
+            /// @custom:security-contact
+            contract Staking {}
+
+            /// @custom:security-contact security@example.com
+            contract Registry {}
+            contract Router {}
+        ";
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq_with_config(content_good, &file_config, &validate);
+        // Not enabled, so nothing is flagged.
+        expected_findings_good.assert_eq(content_bad, &validate);
+
+        let expected_findings_bad = ExpectedFindings { src: 4, ..ExpectedFindings::default() };
+        expected_findings_bad.assert_eq_with_config(content_bad, &file_config, &validate);
+    }
+}
@@ -0,0 +1,361 @@
+use crate::check::{
+    utils::{AbiTypes, FileKind, InvalidItem, IsFileKind, ValidatorKind, VisibilitySummary},
+    Parsed,
+};
+use solang_parser::pt::{
+    ContractDefinition, ContractPart, ContractTy, Expression, FunctionTy, Loc, SourceUnitPart,
+    Type, VariableAttribute, Visibility,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use tiny_keccak::{Hasher, Keccak};
+
+/// A 4-byte function selector.
+pub type Selector = u32;
+
+#[must_use]
+/// Validates that no two external or public functions of a src contract, including inherited
+/// functions and public state variable getters, share a 4-byte selector.
+///
+/// When `[rules.selector] reserved` is configured in `.scopelint`, functions whose selector matches
+/// a reserved selector are flagged too, e.g. the admin functions of a transparent proxy. Base
+/// contracts are resolved by name among the checked files, and bases that can't be found are
+/// skipped. Parameter types are resolved as described in [`AbiTypes::canonical_type`].
+pub fn validate(files: &[Parsed]) -> Vec<InvalidItem> {
+    let index = Index::new(files);
+    let reserved = files.first().map_or(&[][..], |parsed| parsed.file_config.reserved_selectors());
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for parsed in files {
+        if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+            continue;
+        }
+        for element in &parsed.pt.0 {
+            let SourceUnitPart::ContractDefinition(c) = element else { continue };
+            // Library functions are called with a different selector scheme and never collide.
+            if !matches!(c.ty, ContractTy::Library(_)) {
+                invalid_items.extend(validate_contract(parsed, c, &index, reserved));
+            }
+        }
+    }
+    invalid_items
+}
+
+fn validate_contract(
+    parsed: &Parsed,
+    c: &ContractDefinition,
+    index: &Index,
+    reserved: &[(String, Selector)],
+) -> Vec<InvalidItem> {
+    let own = index.own_functions(parsed, c);
+    let mut by_selector: BTreeMap<Selector, BTreeSet<String>> = BTreeMap::new();
+    for signature in index.signatures(parsed, c) {
+        by_selector.entry(selector(&signature)).or_default().insert(signature);
+    }
+
+    // A finding that a base contract already has is reported on the base instead.
+    let base_signatures: Vec<BTreeSet<String>> = c
+        .base
+        .iter()
+        .filter_map(|base| index.contracts.get(base.name.identifiers.last()?.name.as_str()))
+        .map(|(base_parsed, base)| index.signatures(base_parsed, base))
+        .collect();
+    let is_inherited = |signatures: &[&String]| {
+        base_signatures.iter().any(|base| signatures.iter().all(|s| base.contains(*s)))
+    };
+    // Report on the first matching function declared in this contract, if any.
+    let loc_of = |signatures: &[&String]| {
+        own.iter().find(|(s, _)| signatures.contains(&s)).map_or(c.loc, |(_, loc)| *loc)
+    };
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for (selector, signatures) in &by_selector {
+        let signatures: Vec<&String> = signatures.iter().collect();
+        if signatures.len() > 1 && !is_inherited(&signatures) {
+            let names = signatures.iter().map(|s| format!("'{s}'")).collect::<Vec<_>>().join(", ");
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::Selector,
+                parsed,
+                loc_of(&signatures),
+                format!("functions {names} share the selector {selector:#010x}"),
+            ));
+        }
+
+        let Some((entry, _)) = reserved.iter().find(|(_, reserved)| reserved == selector) else {
+            continue;
+        };
+        for signature in signatures.iter().filter(|s| !is_inherited(&[s])) {
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::Selector,
+                parsed,
+                loc_of(&[signature]),
+                format!(
+                    "function '{signature}' has the selector {selector:#010x}, which is reserved by '{entry}'"
+                ),
+            ));
+        }
+    }
+    invalid_items
+}
+
+/// Returns the keccak256 hash of `data`, e.g. the topic of an event signature.
+#[must_use]
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(data);
+    keccak.finalize(&mut hash);
+    hash
+}
+
+/// Returns the selector of a canonical function signature such as `transfer(address,uint256)`.
+#[must_use]
+pub fn selector(signature: &str) -> Selector {
+    let hash = keccak256(signature.as_bytes());
+    Selector::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Parses a reserved selector, given either as hex such as `0x3659cfe6` or as a function
+/// signature such as `upgradeTo(address)`.
+#[must_use]
+pub fn parse_reserved(entry: &str) -> Option<Selector> {
+    let entry = entry.trim();
+    if let Some(hex) = entry.strip_prefix("0x") {
+        return (hex.len() == 8).then(|| Selector::from_str_radix(hex, 16).ok())?;
+    }
+    let signature: String = entry.chars().filter(|c| !c.is_whitespace()).collect();
+    (signature.contains('(') && signature.ends_with(')')).then(|| selector(&signature))
+}
+
+/// The contracts and user-defined types declared in a set of files, used to compute the
+/// signatures of each contract's functions, including inherited ones.
+pub struct Index<'a> {
+    contracts: HashMap<&'a str, (&'a Parsed, &'a ContractDefinition)>,
+    types: AbiTypes<'a>,
+}
+
+impl<'a> Index<'a> {
+    /// Indexes the contracts and user-defined types declared in `files`. If several contracts
+    /// share a name, the first one wins.
+    #[must_use]
+    pub fn new(files: &'a [Parsed]) -> Self {
+        let mut contracts = HashMap::new();
+        for parsed in files {
+            for element in &parsed.pt.0 {
+                let SourceUnitPart::ContractDefinition(c) = element else { continue };
+                if let Some(name) = &c.name {
+                    contracts.entry(name.name.as_str()).or_insert_with(|| (parsed, c.as_ref()));
+                }
+            }
+        }
+        Self { contracts, types: AbiTypes::new(files) }
+    }
+
+    /// Returns the types used to resolve parameter types to canonical ABI types.
+    #[must_use]
+    pub const fn types(&self) -> &AbiTypes<'a> {
+        &self.types
+    }
+
+    /// Returns `c` followed by its base contracts, nearest first, each with the file that declares
+    /// it. Bases that aren't in the index are skipped.
+    #[must_use]
+    pub fn linearized(
+        &self,
+        parsed: &'a Parsed,
+        c: &'a ContractDefinition,
+    ) -> Vec<(&'a Parsed, &'a ContractDefinition)> {
+        let mut contracts = vec![(parsed, c)];
+        let mut visited = HashSet::from([c.loc]);
+        let mut i = 0;
+        while let Some((_, contract)) = contracts.get(i) {
+            for base in &contract.base {
+                let Some(name) = base.name.identifiers.last() else { continue };
+                if let Some(&(base_parsed, base)) = self.contracts.get(name.name.as_str()) {
+                    if visited.insert(base.loc) {
+                        contracts.push((base_parsed, base));
+                    }
+                }
+            }
+            i += 1;
+        }
+        contracts
+    }
+
+    /// Returns the signatures of `c`'s external and public functions, including inherited ones.
+    #[must_use]
+    pub fn signatures(&self, parsed: &'a Parsed, c: &'a ContractDefinition) -> BTreeSet<String> {
+        self.linearized(parsed, c)
+            .into_iter()
+            .flat_map(|(parsed, c)| self.own_functions(parsed, c))
+            .map(|(signature, _)| signature)
+            .collect()
+    }
+
+    /// Returns the signatures of the external and public functions declared in `c`, including
+    /// getters of public state variables, with the location of each declaration.
+    fn own_functions(&self, parsed: &Parsed, c: &ContractDefinition) -> Vec<(String, Loc)> {
+        let mut functions = Vec::new();
+        for part in &c.parts {
+            match part {
+                ContractPart::FunctionDefinition(f)
+                    if f.ty == FunctionTy::Function && f.is_public_or_external() =>
+                {
+                    let Some(name) = &f.name else { continue };
+                    let params = f
+                        .params
+                        .iter()
+                        .filter_map(|(_, param)| param.as_ref())
+                        .map(|param| self.types.canonical_type(parsed, &param.ty))
+                        .collect::<Vec<_>>();
+                    functions.push((format!("{}({})", name.name, params.join(",")), f.loc));
+                }
+                ContractPart::VariableDefinition(v)
+                    if v.attrs.iter().any(|a| {
+                        matches!(a, VariableAttribute::Visibility(Visibility::Public(_)))
+                    }) =>
+                {
+                    let Some(name) = &v.name else { continue };
+                    // Getters take one argument per mapping key and array index.
+                    let mut params = Vec::new();
+                    let mut ty = &v.ty;
+                    loop {
+                        match ty {
+                            Expression::Type(_, Type::Mapping { key, value, .. }) => {
+                                params.push(self.types.canonical_type(parsed, key));
+                                ty = value;
+                            }
+                            Expression::ArraySubscript(_, inner, _) => {
+                                params.push("uint256".to_string());
+                                ty = inner;
+                            }
+                            _ => break,
+                        }
+                    }
+                    functions.push((format!("{}({})", name.name, params.join(",")), v.loc));
+                }
+                _ => {}
+            }
+        }
+        functions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source};
+    use std::path::Path;
+
+    fn findings(files: &[(&str, &str)], file_config: &FileConfig) -> Vec<String> {
+        let parsed = files
+            .iter()
+            .map(|(path, src)| {
+                let mut parsed = parse_source(Path::new(path), src).unwrap();
+                parsed.file_config = file_config.clone();
+                parsed
+            })
+            .collect::<Vec<_>>();
+        validate(&parsed).into_iter().map(|item| item.text).collect()
+    }
+
+    #[test]
+    fn test_selector() {
+        assert_eq!(selector("transfer(address,uint256)"), 0xa905_9cbb);
+        assert_eq!(parse_reserved("upgradeTo(address)"), Some(0x3659_cfe6));
+        assert_eq!(parse_reserved(" upgradeTo( address ) "), Some(0x3659_cfe6));
+        assert_eq!(parse_reserved("0x3659cfe6"), Some(0x3659_cfe6));
+        assert_eq!(parse_reserved("0x3659"), None);
+        assert_eq!(parse_reserved("upgradeTo"), None);
+    }
+
+    #[test]
+    fn test_canonical_types() {
+        let src = r"
+            struct Order { address maker; uint[] amounts; Side side; }
+            enum Side { Buy, Sell }
+            type Price is uint128;
+            contract Book {
+                mapping(address => mapping(uint256 => Order)) public orders;
+                function fill(Order calldata _order, Price _price, IERC20 _token, bytes32[2] memory _sigs) external {}
+            }
+        ";
+        let parsed = parse_source(Path::new("./src/Book.sol"), src).unwrap();
+        let files = [parsed];
+        let index = Index::new(&files);
+        let (parsed, book) = index.contracts["Book"];
+        assert_eq!(
+            index.own_functions(parsed, book).into_iter().map(|(s, _)| s).collect::<Vec<_>>(),
+            [
+                "orders(address,uint256)",
+                "fill((address,uint256[],uint8),uint128,address,bytes32[2])",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        // `burn(uint256)` and `collate_propagate_storage(bytes16)` share the selector 0x42966c68.
+        let base = r"
+            contract Base {
+                function burn(uint256 _amount) external {}
+                function _burn(uint256 _amount) internal {}
+            }
+        ";
+        let good = r"
+            contract Token is Base {
+                function burn(uint256 _amount) external override {}
+                function mint(uint256 _amount) public {}
+            }
+        ";
+        assert!(findings(
+            &[("./src/Base.sol", base), ("./src/Token.sol", good)],
+            &FileConfig::default()
+        )
+        .is_empty());
+
+        let bad = r"
+            contract Token is Base {
+                function collate_propagate_storage(bytes16 _data) external {}
+            }
+        ";
+        assert_eq!(
+            findings(&[("./src/Base.sol", base), ("./src/Token.sol", bad)], &FileConfig::default()),
+            ["functions 'burn(uint256)', 'collate_propagate_storage(bytes16)' share the selector 0x42966c68"]
+        );
+
+        // Collisions in a base contract are only reported on the base.
+        let both = r"
+            contract Base {
+                function burn(uint256 _amount) external {}
+                function collate_propagate_storage(bytes16 _data) external {}
+            }
+            contract Token is Base {}
+        ";
+        assert_eq!(findings(&[("./src/Token.sol", both)], &FileConfig::default()).len(), 1);
+        // Files outside src are not checked.
+        assert!(findings(&[("./test/Token.t.sol", both)], &FileConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reserved() {
+        let file_config = FileConfig::from_toml(
+            "[rules.selector]\nreserved = [\"upgradeTo(address)\", \"0xf851a440\"]",
+        )
+        .unwrap();
+        let src = r"
+            contract Implementation {
+                address public admin;
+                function upgradeTo(address _implementation) external {}
+                function initialize() external {}
+            }
+        ";
+        assert_eq!(
+            findings(&[("./src/Implementation.sol", src)], &file_config),
+            [
+                "function 'upgradeTo(address)' has the selector 0x3659cfe6, which is reserved by 'upgradeTo(address)'",
+                "function 'admin()' has the selector 0xf851a440, which is reserved by '0xf851a440'",
+            ]
+        );
+        assert!(findings(&[("./src/Implementation.sol", src)], &FileConfig::default()).is_empty());
+    }
+}
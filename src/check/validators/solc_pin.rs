@@ -0,0 +1,153 @@
+use crate::{
+    check::{
+        utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+        validators::pragma_min_version::{admits, format_version, parse_version, SolcVersion},
+        Parsed,
+    },
+    foundry_config::{self, DEFAULT_PROFILE},
+};
+use regex::Regex;
+use solang_parser::{helpers::CodeLocation, pt::SourceUnitPart};
+use std::{fs, sync::LazyLock};
+
+/// The path of `foundry.toml`, relative to the project root.
+const FOUNDRY_TOML: &str = "./foundry.toml";
+
+/// The keys Foundry reads the compiler version from.
+const SOLC_KEYS: [&str; 2] = ["solc", "solc_version"];
+
+static RE_PRAGMA_SOLIDITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^pragma\s+solidity\s+([^;]+)").unwrap());
+
+/// The compiler version `foundry.toml` pins, and the profile that pins it.
+#[derive(Debug, PartialEq, Eq)]
+struct SolcPin {
+    version: SolcVersion,
+    profile: String,
+}
+
+#[must_use]
+/// Validates that `pragma solidity` in src files admits the compiler version `foundry.toml` pins
+/// with `solc`, since Forge builds with that version regardless of what the pragma suggests.
+///
+/// The pin is read from the selected profile, or from `[profile.default]` if the profile doesn't
+/// set one. Pins to a path of a solc binary are skipped. This rule is opt-in and only runs when
+/// `[rules.solc] enabled = true` is set in `.scopelint`.
+pub fn validate(files: &[Parsed]) -> Vec<InvalidItem> {
+    let Some(file_config) = files.first().map(|parsed| &parsed.file_config) else {
+        return Vec::new();
+    };
+    if !file_config.solc_rule_enabled() {
+        return Vec::new();
+    }
+    let Some(pin) = fs::read_to_string(FOUNDRY_TOML)
+        .ok()
+        .and_then(|src| solc_pin(&src, &foundry_config::profile()))
+    else {
+        return Vec::new();
+    };
+    files
+        .iter()
+        .filter(|parsed| parsed.file.is_file_kind(FileKind::Src, &parsed.path_config))
+        .flat_map(|parsed| validate_file(parsed, &pin))
+        .collect()
+}
+
+/// Returns the compiler version the `foundry.toml` source `src` pins for `profile`, if any.
+fn solc_pin(src: &str, profile: &str) -> Option<SolcPin> {
+    let toml: toml::Value = toml::from_str(src).ok()?;
+    let (profile, value) = [profile, DEFAULT_PROFILE].into_iter().find_map(|profile| {
+        let settings = toml.get("profile")?.get(profile)?;
+        SOLC_KEYS.iter().find_map(|key| settings.get(key)).map(|value| (profile, value))
+    })?;
+    let version = value.as_str()?.trim();
+    let version = version.strip_prefix('=').unwrap_or(version).trim();
+    if version.split('.').count() != 3 {
+        return None;
+    }
+    Some(SolcPin { version: parse_version(version)?, profile: profile.to_string() })
+}
+
+/// Returns a finding for each `pragma solidity` of `parsed` that excludes the pinned version.
+fn validate_file(parsed: &Parsed, pin: &SolcPin) -> Vec<InvalidItem> {
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for element in &parsed.pt.0 {
+        if !matches!(element, SourceUnitPart::PragmaDirective(..)) {
+            continue;
+        }
+        let loc = element.loc();
+        let Some(captures) = RE_PRAGMA_SOLIDITY.captures(&parsed.src[loc.start()..loc.end()])
+        else {
+            continue;
+        };
+        let requirement = captures[1].trim();
+        if admits(requirement, pin.version) == Some(false) {
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::SolcPin,
+                parsed,
+                loc,
+                format!(
+                    "`pragma solidity {requirement}` excludes solc {}, which [profile.{}] in foundry.toml pins",
+                    format_version(pin.version),
+                    pin.profile
+                ),
+            ));
+        }
+    }
+    invalid_items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::parse_source;
+    use std::path::Path;
+
+    #[test]
+    fn test_solc_pin() {
+        let pin = |version: (u64, u64, u64), profile: &str| SolcPin {
+            version,
+            profile: profile.to_string(),
+        };
+        let foundry_toml = r#"
+[profile.default]
+  solc = "0.8.28"
+
+[profile.legacy]
+  solc_version = "=0.7.6"
+
+[profile.local]
+  solc = "/usr/local/bin/solc"
+"#;
+        assert_eq!(solc_pin(foundry_toml, "default"), Some(pin((0, 8, 28), "default")));
+        assert_eq!(solc_pin(foundry_toml, "legacy"), Some(pin((0, 7, 6), "legacy")));
+        // Profiles without a pin inherit the default one.
+        assert_eq!(solc_pin(foundry_toml, "ci"), Some(pin((0, 8, 28), "default")));
+        assert_eq!(solc_pin(foundry_toml, "local"), None);
+        assert_eq!(solc_pin("[profile.default]\n  solc = \"0.8\"\n", "default"), None);
+        assert_eq!(solc_pin("[profile.default]\n  optimizer = true\n", "default"), None);
+    }
+
+    #[test]
+    fn test_validate_file() {
+        let content = r"
+            pragma solidity ^0.8.20;
+            pragma solidity 0.8.28;
+            pragma solidity ^0.7.6;
+            pragma solidity >=0.8.0 <0.8.28;
+            pragma abicoder v2;
+            contract Vault {}
+        ";
+        let parsed = parse_source(Path::new("./src/Vault.sol"), content).unwrap();
+        let pin = SolcPin { version: (0, 8, 28), profile: "default".to_string() };
+        let texts: Vec<String> =
+            validate_file(&parsed, &pin).into_iter().map(|item| item.text).collect();
+        assert_eq!(
+            texts,
+            [
+                "`pragma solidity ^0.7.6` excludes solc 0.8.28, which [profile.default] in foundry.toml pins",
+                "`pragma solidity >=0.8.0 <0.8.28` excludes solc 0.8.28, which [profile.default] in foundry.toml pins",
+            ]
+        );
+    }
+}
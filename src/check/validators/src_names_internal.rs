@@ -2,7 +2,10 @@ use crate::check::{
     utils::{FileKind, InvalidItem, IsFileKind, Name, ValidatorKind, VisibilitySummary},
     Parsed,
 };
-use solang_parser::pt::{ContractPart, ContractTy, FunctionDefinition, SourceUnitPart};
+use solang_parser::pt::{
+    ContractDefinition, ContractPart, ContractTy, FunctionAttribute, FunctionDefinition,
+    SourceUnitPart, Visibility,
+};
 
 fn is_matching_file(parsed: &Parsed) -> bool {
     parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
@@ -19,17 +22,15 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     for element in &parsed.pt.0 {
         match element {
             SourceUnitPart::FunctionDefinition(f) => {
-                if let Some(invalid_item) = validate_name(parsed, f) {
+                if let Some(invalid_item) = validate_name(parsed, f, None) {
                     invalid_items.push(invalid_item);
                 }
             }
-            SourceUnitPart::ContractDefinition(c) => {
-                if !matches!(c.ty, ContractTy::Library(_)) {
-                    for el in &c.parts {
-                        if let ContractPart::FunctionDefinition(f) = el {
-                            if let Some(invalid_item) = validate_name(parsed, f) {
-                                invalid_items.push(invalid_item);
-                            }
+            SourceUnitPart::ContractDefinition(c) if !matches!(c.ty, ContractTy::Library(_)) => {
+                for el in &c.parts {
+                    if let ContractPart::FunctionDefinition(f) = el {
+                        if let Some(invalid_item) = validate_name(parsed, f, Some(c)) {
+                            invalid_items.push(invalid_item);
                         }
                     }
                 }
@@ -44,13 +45,27 @@ fn is_valid_internal_or_private_name(name: &str) -> bool {
     name.starts_with('_')
 }
 
-fn validate_name(parsed: &Parsed, f: &FunctionDefinition) -> Option<InvalidItem> {
+fn is_private(f: &FunctionDefinition) -> bool {
+    f.attributes.iter().any(|a| matches!(a, FunctionAttribute::Visibility(Visibility::Private(_))))
+}
+
+fn validate_name(
+    parsed: &Parsed,
+    f: &FunctionDefinition,
+    contract: Option<&ContractDefinition>,
+) -> Option<InvalidItem> {
     let name = f.name();
-    if f.is_internal_or_private() && !is_valid_internal_or_private_name(&name) {
-        Some(InvalidItem::new(ValidatorKind::Src, parsed, f.name_loc, name))
-    } else {
-        None
+    if !f.is_internal_or_private() || is_valid_internal_or_private_name(&name) {
+        return None;
     }
+    let renamed = format!("_{name}");
+    let item = InvalidItem::new(ValidatorKind::Src, parsed, f.name_loc, name);
+    // Private functions can only be called from their own contract, so every call can be renamed
+    // with the declaration. Internal functions may be called from other files.
+    Some(match contract.filter(|_| is_private(f)) {
+        Some(c) => item.with_rename(parsed, f.name_loc, &renamed, c.loc.start()..c.loc.end()),
+        None => item.with_suggested_rename(parsed, f.name_loc, renamed),
+    })
 }
 
 #[cfg(test)]
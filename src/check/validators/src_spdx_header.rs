@@ -20,12 +20,16 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     if find_spdx_header(&parsed.src).is_none() {
         // Create a simple location for file-level issues
         let loc = solang_parser::pt::Loc::File(0, 0, 0);
-        invalid_items.push(InvalidItem::new(
-            ValidatorKind::Src,
-            parsed,
-            loc,
-            "Missing SPDX-License-Identifier header".to_string(),
-        ));
+        let header = format!("// SPDX-License-Identifier: {}", parsed.file_config.spdx_license());
+        invalid_items.push(
+            InvalidItem::new(
+                ValidatorKind::Spdx,
+                parsed,
+                loc,
+                "Missing SPDX-License-Identifier header".to_string(),
+            )
+            .with_inserted_line(0, header),
+        );
     }
 
     invalid_items
@@ -98,6 +102,22 @@ mod tests {
         expected_findings.assert_eq(content, &validate);
     }
 
+    #[test]
+    fn test_validate_ignored_separately_from_src() {
+        let content = r"
+            // scopelint: ignore-spdx-file
+            pragma solidity ^0.8.17;
+
+            contract Test {
+                function helper() internal {}
+            }
+        ";
+
+        ExpectedFindings::new(0).assert_eq(content, &validate);
+        let src_findings = ExpectedFindings { src: 1, ..ExpectedFindings::default() };
+        src_findings.assert_eq(content, &crate::check::validators::src_names_internal::validate);
+    }
+
     #[test]
     fn test_validate_comment_then_spdx() {
         let content = r"
@@ -0,0 +1,106 @@
+use crate::check::{
+    utils::{InvalidItem, ValidatorKind},
+    Parsed,
+};
+use solang_parser::{
+    helpers::CodeLocation,
+    pt::{ContractPart, SourceUnitPart, VariableAttribute},
+};
+
+#[must_use]
+/// Validates that state variables declare their visibility, instead of relying on the implicit
+/// `internal`.
+///
+/// Findings suggest `internal`, which keeps the current behavior, as a fix. This rule is opt-in
+/// and only runs when `[rules.visibility] enabled = true` is set in `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !parsed.file_config.visibility_rule_enabled() {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for element in &parsed.pt.0 {
+        let SourceUnitPart::ContractDefinition(c) = element else { continue };
+        for part in &c.parts {
+            let ContractPart::VariableDefinition(v) = part else { continue };
+            let Some(name) = &v.name else { continue };
+            if v.attrs.iter().any(|attr| matches!(attr, VariableAttribute::Visibility(_))) {
+                continue;
+            }
+            // Visibility goes right after the type, e.g. `uint256 internal constant X`.
+            let end = v.ty.loc().end();
+            invalid_items.push(
+                InvalidItem::new(
+                    ValidatorKind::StateVisibility,
+                    parsed,
+                    name.loc,
+                    format!(
+                        "State variable '{}' has no explicit visibility, declare it `internal`",
+                        name.name
+                    ),
+                )
+                .with_replacement(
+                    parsed,
+                    end..end,
+                    " internal".to_string(),
+                    "add `internal`".to_string(),
+                ),
+            );
+        }
+    }
+    invalid_items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, parse_source, utils::ExpectedFindings};
+    use std::path::Path;
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            uint256 constant FILE_LEVEL = 1;
+
+            contract Vault {
+                uint256 totalShares;
+                mapping(address => uint256) balances;
+                uint256 immutable CAP;
+                uint256 internal count;
+                address public owner;
+                bytes32 private constant SALT = bytes32(0);
+
+                function f() external {
+                    uint256 local = 1;
+                }
+            }
+
+            library Math {
+                uint256 constant WAD = 1e18;
+            }
+        ";
+
+        // The rule is opt-in.
+        ExpectedFindings::default().assert_eq(content, &validate);
+
+        let file_config = FileConfig::from_toml("[rules.visibility]\nenabled = true").unwrap();
+        let expected_findings = ExpectedFindings::new(4);
+        expected_findings.assert_eq_with_config(content, &file_config, &validate);
+    }
+
+    #[test]
+    fn test_fix() {
+        let content = "contract Vault {\n    uint256 constant WAD = 1e18;\n}\n";
+        let mut parsed = parse_source(Path::new("./src/Vault.sol"), content).unwrap();
+        parsed.file_config = FileConfig::from_toml("[rules.visibility]\nenabled = true").unwrap();
+        let items = validate(&parsed);
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].text,
+            "State variable 'WAD' has no explicit visibility, declare it `internal`"
+        );
+        let fix = items[0].fix.as_ref().unwrap();
+        assert!(fix.automatic);
+        assert_eq!(fix.after, "    uint256 internal constant WAD = 1e18;");
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    check::{
+        utils::{InvalidItem, ValidatorKind},
+        Parsed,
+    },
+    storage::{contract_layouts, first_incompatibility, Snapshot},
+};
+use itertools::Itertools;
+
+#[must_use]
+/// Validates that src contracts only append to the storage layout recorded in the snapshot.
+///
+/// Reordering, removing, or changing the type of a recorded storage variable is flagged, since it
+/// corrupts the storage of deployed upgradeable contracts. Renaming a variable in place is allowed.
+/// Only the first difference in each contract is reported, because every later variable shifts
+/// with it. This rule is opt-in and only runs when a snapshot has been written with
+/// `scopelint storage --write`.
+pub fn validate(files: &[Parsed]) -> Vec<InvalidItem> {
+    let snapshot = match Snapshot::load() {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => return Vec::new(),
+        Err(err) => {
+            eprintln!("Warning: {err}. Skipping storage layout checks.");
+            return Vec::new();
+        }
+    };
+    validate_against(files, &snapshot)
+}
+
+/// Compares the storage layouts of `files` to `snapshot`.
+fn validate_against(files: &[Parsed], snapshot: &Snapshot) -> Vec<InvalidItem> {
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for parsed in files {
+        for layout in contract_layouts(parsed) {
+            let Some(recorded) = snapshot.contracts.get(&layout.id) else { continue };
+            let current =
+                layout.variables.iter().map(|(variable, _)| variable.clone()).collect_vec();
+            if let Some((i, text)) = first_incompatibility(&current, recorded) {
+                // Removals past the end of the current layout are reported on the contract.
+                let loc = layout.variables.get(i).map_or(layout.loc, |(_, loc)| *loc);
+                invalid_items.push(InvalidItem::new(ValidatorKind::Storage, parsed, loc, text));
+            }
+        }
+    }
+    invalid_items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        check::{comments::Comments, inline_config::InlineConfig},
+        foundry_config::CheckPaths,
+        storage::StorageVariable,
+    };
+    use std::path::PathBuf;
+
+    fn parsed_file(src: &str) -> Parsed {
+        let (pt, comments) = crate::parser::parse_solidity(src, 0).expect("parse");
+        let comments = Comments::new(comments, src);
+        let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
+            comments.parse_inline_config_items().partition_result();
+        let inline_config = InlineConfig::new(inline_config_items, src, &comments, &pt);
+        Parsed {
+            file: PathBuf::from("./src/Vault.sol"),
+            src: src.to_string(),
+            pt,
+            comments,
+            inline_config,
+            invalid_inline_config_items,
+            file_config: crate::check::file_config::FileConfig::default(),
+            path_config: CheckPaths::default(),
+        }
+    }
+
+    fn snapshot() -> Snapshot {
+        let variable =
+            |name: &str, ty: &str| StorageVariable { name: name.to_string(), ty: ty.to_string() };
+        let mut snapshot = Snapshot::default();
+        snapshot.contracts.insert(
+            "src/Vault.sol:Vault".to_string(),
+            vec![
+                variable("owner", "address"),
+                variable("balances", "mapping(address => uint256)"),
+                variable("total", "uint256"),
+            ],
+        );
+        snapshot
+    }
+
+    fn findings(src: &str) -> Vec<String> {
+        validate_against(&[parsed_file(src)], &snapshot()).into_iter().map(|i| i.text).collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        // Appending, renaming in place, and adding constants or immutables is fine.
+        assert!(findings(
+            r"
+            contract Vault {
+                uint256 constant FEE = 1;
+                address public admin;
+                mapping(address  =>  uint256) balances;
+                uint256 immutable START;
+                uint256 total;
+                uint256 newValue;
+            }
+            contract Other { uint256 x; }
+        "
+        )
+        .is_empty());
+
+        assert_eq!(
+            findings(
+                "contract Vault { address owner; uint256 total; mapping(address => uint256) balances; }"
+            ),
+            ["storage variable 'balances' moved from position 1 to 2".to_string()]
+        );
+        assert_eq!(
+            findings("contract Vault { address owner; uint256 total; }"),
+            ["storage variable 'balances' (mapping(address => uint256)) was removed".to_string()]
+        );
+        assert_eq!(
+            findings(
+                "contract Vault { address owner; mapping(address => uint256) balances; int256 total; }"
+            ),
+            ["type of storage variable 'total' changed from 'uint256' to 'int256'".to_string()]
+        );
+        assert_eq!(
+            findings("contract Vault { address owner; mapping(address => uint256) balances; }"),
+            ["storage variable 'total' (uint256) was removed".to_string()]
+        );
+        assert_eq!(
+            findings("contract Vault { address owner; bool paused; uint256 total; }"),
+            ["storage variable 'balances' (mapping(address => uint256)) was replaced by 'paused' (bool)"
+                .to_string()]
+        );
+    }
+}
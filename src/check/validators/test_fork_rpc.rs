@@ -0,0 +1,99 @@
+use crate::check::{
+    utils::{visit_contract_expressions, visit_expressions, InvalidItem, ValidatorKind},
+    Parsed,
+};
+use solang_parser::pt::Expression;
+
+fn is_matching_file(parsed: &Parsed) -> bool {
+    // Fork setup often lives in shared base contracts, so check every file in the test directory.
+    parsed.path_config.in_test(&parsed.file)
+}
+
+#[must_use]
+/// Validates that tests create forks from RPC aliases or env vars rather than inline URLs.
+///
+/// Inline URLs tend to leak API keys into version control and tie the test suite to a single
+/// provider. Use `vm.rpcUrl("alias")` with an `[rpc_endpoints]` entry in `foundry.toml` instead.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !is_matching_file(parsed) {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    visit_contract_expressions(&parsed.pt, &mut |expr| {
+        let Expression::FunctionCall(loc, callee, args) = expr else { return };
+        let Expression::MemberAccess(_, base, member) = callee.as_ref() else { return };
+        if !matches!(base.as_ref(), Expression::Variable(base) if base.name == "vm") ||
+            !matches!(member.name.as_str(), "createFork" | "createSelectFork")
+        {
+            return;
+        }
+
+        if args.first().is_some_and(contains_url_literal) {
+            // The URL itself is not included in the message since it may contain an API key.
+            invalid_items.push(InvalidItem::new(
+                ValidatorKind::Rpc,
+                parsed,
+                *loc,
+                format!(
+                    "`vm.{}` is called with an inline RPC URL, use `vm.rpcUrl(\"<alias>\")` or an env var instead",
+                    member.name
+                ),
+            ));
+        }
+    });
+    invalid_items
+}
+
+/// Returns `true` if `expr` contains a string literal that looks like a URL.
+fn contains_url_literal(expr: &Expression) -> bool {
+    let mut found = false;
+    visit_expressions(expr, &mut |inner| {
+        if let Expression::StringLiteral(parts) = inner {
+            found |= parts.first().is_some_and(|part| {
+                ["http://", "https://", "ws://", "wss://"]
+                    .iter()
+                    .any(|scheme| part.string.starts_with(scheme))
+            });
+        }
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::utils::ExpectedFindings;
+
+    #[test]
+    fn test_validate() {
+        let content_good = r#"
+            contract MyContract {
+                uint256 mainnetFork = vm.createFork(vm.rpcUrl("mainnet"));
+
+                function setUp() public {
+                    vm.createSelectFork(vm.envString("OPTIMISM_RPC_URL"), 1234);
+                    vm.createSelectFork("arbitrum");
+                }
+            }
+        "#;
+
+        let content_bad = r#"
+            contract MyContract {
+                uint256 mainnetFork = vm.createFork("https://eth-mainnet.g.alchemy.com/v2/abc123");
+
+                function setUp() public {
+                    vm.createSelectFork(string.concat("https://rpc.ankr.com/eth/", vm.envString("KEY")));
+                    vm.createFork("wss://localhost:8546", 1234);
+                }
+            }
+        "#;
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq(content_good, &validate);
+
+        let expected_findings_bad =
+            ExpectedFindings { test_helper: 3, test: 3, handler: 3, ..Default::default() };
+        expected_findings_bad.assert_eq(content_bad, &validate);
+    }
+}
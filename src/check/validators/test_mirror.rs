@@ -0,0 +1,138 @@
+use crate::{
+    check::{
+        utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+        Parsed,
+    },
+    foundry_config::CheckPaths,
+};
+use solang_parser::pt::{ContractTy, SourceUnitPart};
+use std::path::{Path, PathBuf};
+
+/// The test file pattern used when `[rules.mirror] patterns` is not set.
+pub const DEFAULT_PATTERN: &str = "{dir}/{name}.t.sol";
+
+#[must_use]
+/// Validates that each src file has a test file at the mirrored path in the test directory, e.g.
+/// `src/foo/Bar.sol` is tested by `test/foo/Bar.t.sol`.
+///
+/// This is a coarse completeness check: a file passes as long as any configured pattern matches
+/// an existing file or directory. Files that only declare interfaces have nothing to test and are
+/// skipped. This rule is opt-in and only runs when `[rules.mirror] enabled = true` is set in
+/// `.scopelint`.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let Some(patterns) = parsed.file_config.mirror_patterns() else {
+        return Vec::new();
+    };
+    if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+        return Vec::new();
+    }
+
+    // Report on the first contract or library, since there is no single offending line.
+    let Some(loc) = parsed.pt.0.iter().find_map(|element| match element {
+        SourceUnitPart::ContractDefinition(c) if !matches!(c.ty, ContractTy::Interface(_)) => {
+            Some(c.name.as_ref().map_or(c.loc, |name| name.loc))
+        }
+        _ => None,
+    }) else {
+        return Vec::new();
+    };
+
+    let expected = expected_test_paths(&parsed.file, &parsed.path_config, patterns);
+    if expected.iter().any(|path| path.exists()) {
+        return Vec::new();
+    }
+    let expected = expected.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+    vec![InvalidItem::new(
+        ValidatorKind::TestMirror,
+        parsed,
+        loc,
+        format!("No test file found, expected {}", expected.join(" or ")),
+    )]
+}
+
+/// Returns the paths where the tests of `src_file` may live, one per pattern and test directory,
+/// with `{dir}` and `{name}` substituted. `{dir}` is relative to the src directory the file is in.
+#[must_use]
+pub fn expected_test_paths(
+    src_file: &Path,
+    paths: &CheckPaths,
+    patterns: &[String],
+) -> Vec<PathBuf> {
+    let relative = paths
+        .src_paths
+        .iter()
+        .find_map(|root| src_file.strip_prefix(root).ok())
+        .unwrap_or(src_file);
+    let src_dir = relative.parent().map(Path::to_string_lossy).unwrap_or_default();
+    let src_name = relative.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    let (src_dir, src_name) = (&src_dir, &src_name);
+
+    paths
+        .test_paths
+        .iter()
+        .flat_map(|test_path| {
+            patterns.iter().map(move |pattern| {
+                let path = pattern.replace("{dir}", src_dir).replace("{name}", src_name);
+                // An empty `{dir}` leaves empty path segments behind.
+                let segments = path.split('/').filter(|segment| !segment.is_empty());
+                segments.fold(PathBuf::from(test_path), |path, segment| path.join(segment))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{file_config::FileConfig, utils::ExpectedFindings};
+
+    #[test]
+    fn test_validate() {
+        let file_config = FileConfig::from_toml(
+            "[rules.mirror]\nenabled = true\npatterns = [\"{dir}/{name}.t.sol\"]",
+        )
+        .unwrap();
+
+        // There is no `./test` directory, so every src file is missing its test file.
+        let content_bad = r"
+            contract MyContract {}
+        ";
+        let content_good = r"
+            interface IMyContract {}
+        ";
+
+        let expected_findings_bad = ExpectedFindings { src: 1, ..ExpectedFindings::default() };
+        expected_findings_bad.assert_eq_with_config(content_bad, &file_config, &validate);
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq_with_config(content_good, &file_config, &validate);
+        // Not enabled, so nothing is flagged.
+        expected_findings_good.assert_eq(content_bad, &validate);
+    }
+
+    #[test]
+    fn test_expected_test_paths() {
+        let paths = CheckPaths::default();
+        let patterns = [DEFAULT_PATTERN.to_string(), "unit/{dir}/{name}/".to_string()];
+
+        assert_eq!(
+            expected_test_paths(Path::new("./src/foo/Bar.sol"), &paths, &patterns),
+            [PathBuf::from("./test/foo/Bar.t.sol"), PathBuf::from("./test/unit/foo/Bar")]
+        );
+        assert_eq!(
+            expected_test_paths(Path::new("./src/Bar.sol"), &paths, &patterns),
+            [PathBuf::from("./test/Bar.t.sol"), PathBuf::from("./test/unit/Bar")]
+        );
+
+        // Every test directory is searched, relative to the src directory the file is in.
+        let paths = CheckPaths {
+            src_paths: vec!["./src".to_string(), "./periphery".to_string()],
+            test_paths: vec!["./test".to_string(), "./integration-tests".to_string()],
+            ..CheckPaths::default()
+        };
+        assert_eq!(
+            expected_test_paths(Path::new("./periphery/Bar.sol"), &paths, &patterns[..1]),
+            [PathBuf::from("./test/Bar.t.sol"), PathBuf::from("./integration-tests/Bar.t.sol")]
+        );
+    }
+}
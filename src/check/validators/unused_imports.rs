@@ -1,4 +1,5 @@
 use crate::check::{
+    scan::ScanBudget,
     utils::{InvalidItem, ValidatorKind},
     Parsed,
 };
@@ -68,9 +69,11 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     }
 
     // Second pass: check if imported symbols are used (excluding the import statements themselves)
+    let mut budget = ScanBudget::new(&parsed.file, ValidatorKind::Import);
     for (symbol_name, import_start, import_end) in imported_symbols {
         // Check if symbol is used outside of import statements
-        let is_used = is_symbol_used_excluding_imports(&parsed.src, &symbol_name, &import_ranges);
+        let is_used =
+            is_symbol_used_excluding_imports(parsed, &symbol_name, &import_ranges, &mut budget);
         if !is_used {
             // Find the symbol within the import statement to get exact location
             let import_text = &parsed.src[import_start..import_end];
@@ -93,23 +96,29 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
 /// Checks if a symbol is used in the source code, excluding import statements and comments.
 /// This prevents false positives where the symbol appears only in the import line or comments.
 /// However, symbols used in `@inheritdoc` `NatSpec` directives are considered as used.
+/// Symbols that can't be checked within the scan `budget` are considered as used too.
 fn is_symbol_used_excluding_imports(
-    source: &str,
+    parsed: &Parsed,
     symbol: &str,
     import_ranges: &[(usize, usize)],
+    budget: &mut ScanBudget<'_>,
 ) -> bool {
+    let source = parsed.src.as_str();
     // First, check if symbol is used in @inheritdoc directives (even in comments)
     // Pattern: @inheritdoc followed by optional whitespace and the symbol name
     let inheritdoc_pattern = format!(r"@inheritdoc\s+{}\b", regex::escape(symbol));
-    let inheritdoc_re = regex::Regex::new(&inheritdoc_pattern).unwrap();
-    if inheritdoc_re.is_match(source) {
+    let Some(inheritdoc_re) = budget.regex(&inheritdoc_pattern) else { return true };
+    if !budget.scan(source) || inheritdoc_re.is_match(source) {
         return true; // Symbol is used in @inheritdoc
     }
 
     // Create a regex pattern that matches the symbol as a whole word
     // This prevents false positives (e.g., "ERC20" matching in "ERC20Token")
     let pattern = format!(r"\b{}\b", regex::escape(symbol));
-    let re = regex::Regex::new(&pattern).unwrap();
+    let Some(re) = budget.regex(&pattern) else { return true };
+    if !budget.scan(source) {
+        return true;
+    }
 
     // Check all matches and see if any are outside import ranges and comments
     for cap in re.find_iter(source) {
@@ -124,20 +133,8 @@ fn is_symbol_used_excluding_imports(
             continue; // Skip matches in import statements
         }
 
-        // Check if this match is in a comment
-        // Find the line containing this match
-        let line_start = source[..match_start].rfind('\n').map_or(0, |i| i + 1);
-        let line_end = source[match_start..].find('\n').map_or(source.len(), |i| match_start + i);
-        let line = &source[line_start..line_end.min(source.len())];
-
-        // Check if the line is a comment (starts with // or contains /* before the match)
-        let line_before_match = &line[..(match_start - line_start).min(line.len())];
-        let is_in_comment = line.trim_start().starts_with("//") ||
-            line_before_match.contains("/*") ||
-            line_before_match.contains("//");
-
         // If we found a match outside import statements and comments, the symbol is used
-        if !is_in_comment {
+        if !parsed.comments.is_in_comment(match_start) {
             return true;
         }
     }
@@ -167,6 +164,7 @@ pub fn fix_source(parsed: &Parsed, only_remove: Option<&HashSet<String>>) -> Opt
     }
 
     let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let mut budget = ScanBudget::new(&parsed.file, ValidatorKind::Import);
 
     // Named imports: `import { A, B } from "path";`
     for cap in RE_IMPORT_SYMBOL_LIST_WITH_PATH.captures_iter(&parsed.src) {
@@ -182,7 +180,7 @@ pub fn fix_source(parsed: &Parsed, only_remove: Option<&HashSet<String>>) -> Opt
             let name =
                 symbol_part.split_once(" as ").map_or(symbol_part, |(_, alias)| alias.trim());
             let should_remove = only_remove.map_or_else(
-                || !is_symbol_used_excluding_imports(&parsed.src, name, &import_ranges),
+                || !is_symbol_used_excluding_imports(parsed, name, &import_ranges, &mut budget),
                 |set| set.contains(name),
             );
             if !should_remove {
@@ -205,7 +203,7 @@ pub fn fix_source(parsed: &Parsed, only_remove: Option<&HashSet<String>>) -> Opt
         let end = m.end();
         let alias = cap.get(1).expect("capture 1 always present").as_str();
         let should_remove = only_remove.map_or_else(
-            || !is_symbol_used_excluding_imports(&parsed.src, alias, &import_ranges),
+            || !is_symbol_used_excluding_imports(parsed, alias, &import_ranges, &mut budget),
             |set| set.contains(alias),
         );
         if should_remove {
@@ -233,8 +231,9 @@ fn symbol_part_count(symbols_str: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::check::utils::ExpectedFindings;
+    use crate::check::{parse_source, utils::ExpectedFindings};
     use itertools::Itertools;
+    use std::path::Path;
 
     #[test]
     fn test_no_unused_imports() {
@@ -254,6 +253,19 @@ mod tests {
         expected_findings.assert_eq(content, &validate);
     }
 
+    #[test]
+    fn test_scan_budget() {
+        let content = "import {ERC20} from \"ERC20.sol\";\ncontract C {}\n";
+        let parsed = parse_source(Path::new("./src/C.sol"), content).unwrap();
+        let mut budget = ScanBudget::new(&parsed.file, ValidatorKind::Import);
+        assert!(!is_symbol_used_excluding_imports(&parsed, "ERC20", &[(0, 32)], &mut budget));
+
+        // Symbols that can't be scanned within the budget aren't reported.
+        let mut budget = ScanBudget::with_limit(&parsed.file, ValidatorKind::Import, 10);
+        assert!(is_symbol_used_excluding_imports(&parsed, "ERC20", &[(0, 32)], &mut budget));
+        assert!(budget.is_exhausted());
+    }
+
     #[test]
     fn test_unused_import() {
         let content = r#"
@@ -276,6 +288,21 @@ mod tests {
         expected_findings.assert_eq(content, &validate);
     }
 
+    #[test]
+    fn test_used_after_comment_like_string() {
+        let content = r#"
+            import {ERC20, IERC20} from "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+
+            contract MyContract {
+                string constant URL = "https://example.com"; ERC20 public token;
+                /* ERC20 */ IERC20 public other;
+            }
+        "#;
+
+        let expected_findings = ExpectedFindings::new(0);
+        expected_findings.assert_eq(content, &validate);
+    }
+
     #[test]
     fn test_unused_aliased_import() {
         let content = r#"
@@ -361,7 +388,7 @@ mod tests {
         let comments = Comments::new(comments, content);
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
-        let inline_config = InlineConfig::new(inline_config_items, content);
+        let inline_config = InlineConfig::new(inline_config_items, content, &comments, &pt);
         crate::check::Parsed {
             file: PathBuf::from("./src/Contract.sol"),
             src: content.to_string(),
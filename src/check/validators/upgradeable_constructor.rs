@@ -0,0 +1,218 @@
+use crate::check::{
+    utils::{
+        visit_statement_expressions, visit_statements, FileKind, InvalidItem, IsFileKind,
+        ValidatorKind,
+    },
+    Parsed,
+};
+use solang_parser::pt::{
+    ContractDefinition, ContractPart, Expression, FunctionAttribute, FunctionTy, SourceUnitPart,
+    Statement, VariableAttribute,
+};
+use std::collections::HashSet;
+
+fn is_matching_file(parsed: &Parsed) -> bool {
+    parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
+}
+
+#[must_use]
+/// Validates that constructors of upgradeable contracts don't emit events or write storage.
+///
+/// A proxy never runs the implementation's constructor, so that work must happen in an
+/// initializer instead. Contracts are treated as upgradeable if they inherit from `Initializable`
+/// or an `*Upgradeable` contract, or have a function with an `initializer` or `reinitializer`
+/// modifier. Assigning immutables is fine, since they live in the implementation's bytecode.
+pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    if !is_matching_file(parsed) {
+        return Vec::new();
+    }
+
+    let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    for element in &parsed.pt.0 {
+        let SourceUnitPart::ContractDefinition(c) = element else { continue };
+        if !is_upgradeable(c) {
+            continue;
+        }
+        let storage_vars = storage_variables(c);
+
+        for el in &c.parts {
+            let ContractPart::FunctionDefinition(f) = el else { continue };
+            if f.ty != FunctionTy::Constructor {
+                continue;
+            }
+            let Some(body) = &f.body else { continue };
+
+            // Parameters and locals shadow storage variables of the same name.
+            let mut shadowed: HashSet<&str> = f
+                .params
+                .iter()
+                .filter_map(|(_, param)| param.as_ref()?.name.as_ref())
+                .map(|name| name.name.as_str())
+                .collect();
+            visit_statements(body, &mut |stmt| {
+                if let Statement::VariableDefinition(_, decl, _) = stmt {
+                    if let Some(name) = &decl.name {
+                        shadowed.insert(&name.name);
+                    }
+                }
+            });
+
+            visit_statements(body, &mut |stmt| {
+                if let Statement::Emit(loc, _) = stmt {
+                    invalid_items.push(InvalidItem::new(
+                        ValidatorKind::Upgradeable,
+                        parsed,
+                        *loc,
+                        "event emitted in the constructor of an upgradeable contract, emit it in the initializer instead".to_string(),
+                    ));
+                }
+            });
+            visit_statement_expressions(body, &mut |expr| {
+                let Some((loc, target)) = assignment_target(expr) else { return };
+                if storage_vars.contains(target) && !shadowed.contains(target) {
+                    invalid_items.push(InvalidItem::new(
+                        ValidatorKind::Upgradeable,
+                        parsed,
+                        loc,
+                        format!("storage variable '{target}' is set in the constructor of an upgradeable contract, set it in the initializer instead"),
+                    ));
+                }
+            });
+        }
+    }
+    invalid_items
+}
+
+fn is_upgradeable(c: &ContractDefinition) -> bool {
+    let has_upgradeable_base = c.base.iter().any(|base| {
+        base.name
+            .identifiers
+            .last()
+            .is_some_and(|name| name.name == "Initializable" || name.name.ends_with("Upgradeable"))
+    });
+    let has_initializer = c.parts.iter().any(|part| {
+        let ContractPart::FunctionDefinition(f) = part else { return false };
+        f.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::BaseOrModifier(_, base)
+                    if base.name.identifiers.last().is_some_and(|name| {
+                        name.name == "initializer" || name.name == "reinitializer"
+                    })
+            )
+        })
+    });
+    has_upgradeable_base || has_initializer
+}
+
+/// Returns the names of the contract's state variables that live in storage, i.e. excluding
+/// constants and immutables.
+fn storage_variables(c: &ContractDefinition) -> HashSet<&str> {
+    c.parts
+        .iter()
+        .filter_map(|part| match part {
+            ContractPart::VariableDefinition(v)
+                if !v.attrs.iter().any(|a| {
+                    matches!(a, VariableAttribute::Constant(_) | VariableAttribute::Immutable(_))
+                }) =>
+            {
+                v.name.as_ref().map(|name| name.name.as_str())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// If `expr` is an assignment, returns its location and the name of the variable being written,
+/// e.g. `balances` for `balances[owner] += 1`.
+fn assignment_target(expr: &Expression) -> Option<(solang_parser::pt::Loc, &str)> {
+    let (loc, mut target) = match expr {
+        Expression::Assign(loc, left, _) |
+        Expression::AssignOr(loc, left, _) |
+        Expression::AssignAnd(loc, left, _) |
+        Expression::AssignXor(loc, left, _) |
+        Expression::AssignShiftLeft(loc, left, _) |
+        Expression::AssignShiftRight(loc, left, _) |
+        Expression::AssignAdd(loc, left, _) |
+        Expression::AssignSubtract(loc, left, _) |
+        Expression::AssignMultiply(loc, left, _) |
+        Expression::AssignDivide(loc, left, _) |
+        Expression::AssignModulo(loc, left, _) => (*loc, left.as_ref()),
+        _ => return None,
+    };
+    loop {
+        match target {
+            Expression::Variable(name) => return Some((loc, &name.name)),
+            Expression::ArraySubscript(_, inner, _) | Expression::MemberAccess(_, inner, _) => {
+                target = inner;
+            }
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::utils::ExpectedFindings;
+
+    #[test]
+    fn test_validate() {
+        let content_good = r"
+            contract MyContract is Initializable, UUPSUpgradeable {
+                address public immutable FACTORY;
+                uint256 public value;
+
+                constructor(address _factory) {
+                    FACTORY = _factory;
+                    _disableInitializers();
+                }
+
+                function initialize(uint256 _value) external initializer {
+                    value = _value;
+                    emit Initialized(_value);
+                }
+            }
+
+            contract NotUpgradeable is Ownable {
+                uint256 public value;
+
+                constructor(uint256 _value) {
+                    value = _value;
+                    emit Initialized(_value);
+                }
+            }
+        ";
+
+        let content_bad = r"
+            contract MyContract is OwnableUpgradeable {
+                uint256 public value;
+                mapping(address => uint256) public balances;
+
+                constructor(uint256 _value) {
+                    value = _value;
+                    balances[msg.sender] += _value;
+                    emit Initialized(_value);
+                }
+            }
+
+            contract MyOtherContract {
+                struct Config { uint256 fee; }
+                Config public config;
+
+                constructor() {
+                    uint256 value = 1;
+                    config.fee = value;
+                }
+
+                function initialize() external reinitializer(2) {}
+            }
+        ";
+
+        let expected_findings_good = ExpectedFindings::new(0);
+        expected_findings_good.assert_eq(content_good, &validate);
+
+        let expected_findings_bad = ExpectedFindings { src: 4, ..Default::default() };
+        expected_findings_bad.assert_eq(content_bad, &validate);
+    }
+}
@@ -2,10 +2,14 @@ use crate::check::{
     utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
     Parsed,
 };
-use solang_parser::pt::{
-    ContractPart, FunctionDefinition, Parameter, SourceUnitPart, Statement, VariableDeclaration,
-    VariableDefinition,
+use solang_parser::{
+    helpers::CodeLocation,
+    pt::{
+        ContractPart, FunctionDefinition, Identifier, Parameter, SourceUnitPart, Statement,
+        VariableDeclaration, VariableDefinition,
+    },
 };
+use std::ops::Range;
 fn is_matching_file(parsed: &Parsed) -> bool {
     let file = &parsed.file;
     file.is_file_kind(FileKind::Src, &parsed.path_config) ||
@@ -53,6 +57,9 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
 
 fn validate_function(parsed: &Parsed, f: &FunctionDefinition) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
+    // Parameters can only be referred to from the function's signature, e.g. in modifier
+    // arguments, and its body.
+    let scope = f.loc.start()..f.body.as_ref().map_or_else(|| f.loc.end(), |body| body.loc().end());
 
     // Validate function parameters
     for (_, param) in &f.params {
@@ -68,11 +75,12 @@ fn validate_function(parsed: &Parsed, f: &FunctionDefinition) -> Vec<InvalidItem
                     } else {
                         format!("Parameter '{}' should have underscore prefix", &name.name)
                     };
-                    invalid_items.push(InvalidItem::new(
-                        ValidatorKind::Variable,
+                    let item = InvalidItem::new(ValidatorKind::Variable, parsed, p.loc, message);
+                    invalid_items.push(with_toggled_prefix(
+                        item,
                         parsed,
-                        p.loc,
-                        message,
+                        name,
+                        Some(scope.clone()),
                     ));
                 }
             }
@@ -81,7 +89,7 @@ fn validate_function(parsed: &Parsed, f: &FunctionDefinition) -> Vec<InvalidItem
 
     // Validate local variables in function body
     if let Some(body) = &f.body {
-        invalid_items.extend(validate_statement(parsed, body));
+        invalid_items.extend(validate_statement(parsed, body, scope.end));
     }
 
     invalid_items
@@ -93,17 +101,18 @@ fn validate_state_variable(parsed: &Parsed, v: &VariableDefinition) -> Option<In
         if is_valid_state_variable_name(name_str) {
             None
         } else {
-            Some(InvalidItem::new(
-                ValidatorKind::Variable,
-                parsed,
-                name.loc,
-                format!("State variable '{name_str}' should NOT have underscore prefix"),
-            ))
+            let message = format!("State variable '{name_str}' should NOT have underscore prefix");
+            let item = InvalidItem::new(ValidatorKind::Variable, parsed, name.loc, message);
+            // State variables can be referred to from other contracts, so this is only a
+            // suggestion.
+            Some(with_toggled_prefix(item, parsed, name, None))
         }
     })
 }
 
-fn validate_statement(parsed: &Parsed, stmt: &Statement) -> Vec<InvalidItem> {
+// `scope_end` is the end of the innermost block containing `stmt`, which is where the scope of a
+// local variable declared by `stmt` ends.
+fn validate_statement(parsed: &Parsed, stmt: &Statement, scope_end: usize) -> Vec<InvalidItem> {
     let mut invalid_items = Vec::new();
 
     match stmt {
@@ -122,34 +131,31 @@ fn validate_statement(parsed: &Parsed, stmt: &Statement) -> Vec<InvalidItem> {
                 } else {
                     format!("Local variable '{}' should have underscore prefix", &name.name)
                 };
-                invalid_items.push(InvalidItem::new(
-                    ValidatorKind::Variable,
-                    parsed,
-                    *loc,
-                    message,
-                ));
+                let item = InvalidItem::new(ValidatorKind::Variable, parsed, *loc, message);
+                let scope = loc.start()..scope_end;
+                invalid_items.push(with_toggled_prefix(item, parsed, name, Some(scope)));
             }
         }
-        Statement::Block { statements, .. } => {
+        Statement::Block { loc, statements, .. } => {
             for s in statements {
-                invalid_items.extend(validate_statement(parsed, s));
+                invalid_items.extend(validate_statement(parsed, s, loc.end()));
             }
         }
         Statement::If(_, _, then_stmt, else_stmt) => {
-            invalid_items.extend(validate_statement(parsed, then_stmt));
+            invalid_items.extend(validate_statement(parsed, then_stmt, scope_end));
             if let Some(else_s) = else_stmt {
-                invalid_items.extend(validate_statement(parsed, else_s));
+                invalid_items.extend(validate_statement(parsed, else_s, scope_end));
             }
         }
         Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => {
-            invalid_items.extend(validate_statement(parsed, body));
+            invalid_items.extend(validate_statement(parsed, body, scope_end));
         }
-        Statement::For(_, init, _, _, body) => {
+        Statement::For(loc, init, _, _, body) => {
             if let Some(init_stmt) = init {
-                invalid_items.extend(validate_statement(parsed, init_stmt));
+                invalid_items.extend(validate_statement(parsed, init_stmt, loc.end()));
             }
             if let Some(body_stmt) = body {
-                invalid_items.extend(validate_statement(parsed, body_stmt));
+                invalid_items.extend(validate_statement(parsed, body_stmt, loc.end()));
             }
         }
         _ => {}
@@ -158,6 +164,29 @@ fn validate_statement(parsed: &Parsed, stmt: &Statement) -> Vec<InvalidItem> {
     invalid_items
 }
 
+// Every finding of this rule is a name with the wrong underscore prefix, so the fix is to add the
+// prefix if it is missing or strip it if it is present. No fix is suggested when stripping would
+// leave an invalid identifier, such as `_` or `_1`. With a `scope` outside of which the name can't
+// be used, the fix renames every reference too, so `--fix` can apply it.
+fn with_toggled_prefix(
+    item: InvalidItem,
+    parsed: &Parsed,
+    name: &Identifier,
+    scope: Option<Range<usize>>,
+) -> InvalidItem {
+    let renamed = match name.name.strip_prefix('_') {
+        Some(_) => name.name.trim_start_matches('_').to_string(),
+        None => format!("_{}", name.name),
+    };
+    if renamed.is_empty() || renamed.starts_with(|c: char| c.is_ascii_digit()) {
+        return item;
+    }
+    match scope {
+        Some(scope) => item.with_rename(parsed, name.loc, &renamed, scope),
+        None => item.with_suggested_rename(parsed, name.loc, renamed),
+    }
+}
+
 const fn is_storage_parameter(param: &Parameter) -> bool {
     // Check if the parameter has storage location set to Storage
     // This is the proper way to detect storage parameters
@@ -1,4 +1,13 @@
-use clap::{Parser, Subcommand};
+use crate::{
+    check::utils::{Severity, ValidatorKind},
+    explain,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::{
+    env,
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
 
 /// Returns version information with appropriate suffix
 fn version_info() -> &'static str {
@@ -24,6 +33,77 @@ pub struct Opts {
     #[clap(subcommand)]
     /// The mode to run scopelint in.
     pub subcommand: Subcommands,
+    #[clap(
+        long,
+        global = true,
+        help = "The foundry.toml profile to read src, test, and script paths from [env: FOUNDRY_PROFILE]"
+    )]
+    /// The `foundry.toml` profile to read paths from, also passed on to Forge. Overrides the
+    /// `FOUNDRY_PROFILE` env var, and defaults to `default`.
+    pub profile: Option<String>,
+    #[clap(
+        long,
+        global = true,
+        help = "Read scopelint settings from this file instead of .scopelint [env: SCOPELINT_CONFIG]"
+    )]
+    /// The config file to read instead of the `.scopelint` found in the current directory or its
+    /// parents, e.g. a stricter config for CI. Overrides the `SCOPELINT_CONFIG` env var.
+    pub config: Option<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Also ignore files matching the gitignore-style patterns in this file [env: SCOPELINT_IGNORE_PATH]"
+    )]
+    /// A file of gitignore-style patterns, e.g. `.scopelintignore`, whose matching files are
+    /// ignored like those of `[ignore] files` in `.scopelint`. Overrides the
+    /// `SCOPELINT_IGNORE_PATH` env var.
+    pub ignore_path: Option<PathBuf>,
+    #[clap(long, global = true, help = "Run as if scopelint was started in this directory")]
+    /// The project root to run in instead of the current directory, e.g. a package of a monorepo.
+    /// `foundry.toml`, `.scopelint`, and relative paths given on the command line are resolved
+    /// relative to it.
+    pub root: Option<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = ColorChoice::Auto,
+        help = "When to color output and emit terminal hyperlinks"
+    )]
+    /// When to color output and emit terminal hyperlinks.
+    pub color: ColorChoice,
+    #[clap(
+        short,
+        long,
+        global = true,
+        help = "Print nothing on success, and only findings, diffs, warnings, and errors on failure"
+    )]
+    /// Don't print progress and success messages such as "Checking formatting..." or "Wrote
+    /// .scopelint", so wrapper scripts see output only when something is wrong. Findings, diffs,
+    /// warnings, errors, and the output of commands that print data are still printed.
+    pub quiet: bool,
+    #[clap(
+        long,
+        global = true,
+        value_enum,
+        help = "Output format: `check` supports all, `fmt`, `selectors`, and `config` human and json, `spec` and `stats` human, json, and markdown"
+    )]
+    /// The output format, shared by every command that prints machine-readable output. Each
+    /// command supports some of the formats, see [`Subcommands::formats`]. Defaults to `human`.
+    pub format: Option<OutputFormat>,
+    #[clap(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "text",
+        help = "Print the help of every command and every rule with its settings, or a man page with `man`"
+    )]
+    /// Print the help of every command, then every rule with its `.scopelint` settings and their
+    /// defaults, as text or as a man page. Needs no command, see [`crate::help::requested`].
+    pub help_all: Option<HelpFormat>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -31,13 +111,20 @@ pub struct Opts {
 pub enum Subcommands {
     #[clap(about = "Checks code to verify all conventions are being followed.")]
     /// Checks code to verify all conventions are being followed.
-    Check,
+    Check(Box<CheckArgs>),
     #[clap(about = "Formats Solidity and TOML files in the codebase.")]
     /// Formats Solidity and TOML files in the codebase.
     Fmt {
         #[clap(long, help = "Show changes without modifying files")]
         /// Show changes without modifying files.
         check: bool,
+        #[clap(long, help = "Fail if any file was reformatted, to format and verify in one run")]
+        /// Fail if any file was reformatted, so CI can format and verify in a single run.
+        fail_on_change: bool,
+        #[clap(help = "Only format these files, directories, or glob patterns")]
+        /// Only format these files, directories, or glob patterns, e.g. the file being edited,
+        /// instead of the whole project.
+        paths: Vec<String>,
     },
     #[clap(about = "Applies safe fixes (e.g. remove unused imports), then runs check.")]
     /// Applies safe fixes (e.g. remove unused imports), then runs check.
@@ -48,5 +135,472 @@ pub enum Subcommands {
         #[clap(long, help = "Show internal functions in the specification.")]
         /// Show internal functions in the specification.
         show_internal: bool,
+        #[clap(long, help = "Write the output of `--format json` or `markdown` to this file")]
+        /// Write the output of `--format json` or `markdown` to this file instead of stdout, e.g.
+        /// in a release script.
+        out: Option<PathBuf>,
     },
+    #[clap(about = "Prints the storage layout of src contracts, or snapshots it with --write.")]
+    /// Prints the storage layout of src contracts, or snapshots it with `--write`.
+    Storage {
+        #[clap(subcommand)]
+        /// Compare layouts between git refs instead of printing the current layout.
+        command: Option<StorageCommand>,
+        #[clap(long, help = "Write the storage layout snapshot checked by `scopelint check`")]
+        /// Write the storage layout snapshot checked by `scopelint check`.
+        write: bool,
+    },
+    #[clap(
+        about = "Lists function selectors, event topics, and error selectors of src contracts."
+    )]
+    /// Lists function selectors, event topics, and error selectors of src contracts.
+    Selectors {
+        #[clap(long, hide = true)]
+        /// Deprecated alias of `--format json`, kept so existing scripts keep working.
+        json: bool,
+    },
+    #[clap(
+        about = "Explains a rule by code or name, e.g. `SL001` or `constant`, or lists all rules."
+    )]
+    /// Explains a rule by code or name, e.g. `SL001` or `constant`, or lists all rules.
+    Explain {
+        #[clap(help = "The rule code or name to explain")]
+        /// The rule code or name to explain.
+        rule: Option<String>,
+    },
+    #[clap(about = "Writes a starter .scopelint for the Foundry layout in foundry.toml.")]
+    /// Writes a starter `.scopelint` for the Foundry layout in `foundry.toml`.
+    Init {
+        #[clap(long, help = "Also install the git pre-commit hook, see `scopelint hook install`")]
+        /// Also install the git pre-commit hook, like `scopelint hook install`.
+        hook: bool,
+    },
+    #[clap(
+        about = "Shows the effective configuration, to debug which files and rules are checked."
+    )]
+    /// Shows the effective configuration, to debug which files and rules are checked.
+    Config {
+        #[clap(subcommand)]
+        /// The config command to run.
+        command: ConfigCommand,
+    },
+    #[clap(about = "Manages the git pre-commit hook that checks and format-checks staged files.")]
+    /// Manages the git pre-commit hook that checks and format-checks staged files.
+    Hook {
+        #[clap(subcommand)]
+        /// The hook command to run.
+        command: HookCommand,
+    },
+    #[clap(about = "Writes a .scopelint equivalent to a solhint configuration.")]
+    /// Writes a `.scopelint` equivalent to a solhint configuration: rules with a scopelint
+    /// equivalent, their options, and `.solhintignore` patterns.
+    MigrateSolhint {
+        #[clap(default_value = ".solhint.json", help = "The solhint configuration to convert")]
+        /// The solhint configuration to convert. The `.solhintignore` next to it is read too.
+        path: PathBuf,
+    },
+    #[clap(about = "Rewrites renamed or removed rule names in .scopelint and inline directives.")]
+    /// Rewrites renamed rule names in `.scopelint` overrides and inline `scopelint:` directives to
+    /// their new names, and drops those of removed rules.
+    MigrateConfig {
+        #[clap(long, help = "Only report deprecated rule names, failing if there are any")]
+        /// Only report deprecated rule names without rewriting them, failing if there are any.
+        check: bool,
+    },
+    #[clap(about = "Generates a Foundry project seeded with violations of the given rules.")]
+    /// Generates a Foundry project seeded with violations of the given rules, to validate a
+    /// configuration end-to-end.
+    NewFixture {
+        #[clap(help = "The directory to write the project to, which must be empty or not exist")]
+        /// The directory to write the project to, which must be empty or not exist.
+        dir: PathBuf,
+        #[clap(
+            long,
+            value_delimiter = ',',
+            value_parser = parse_rule,
+            help = "Only seed these rules, given as comma-separated names or codes (default: all)"
+        )]
+        /// Only seed these rules, given by name or code. All rules are seeded when empty.
+        rules: Vec<ValidatorKind>,
+    },
+    #[clap(about = "Records current findings in a baseline, so check only fails on new ones.")]
+    /// Records all current findings in `.scopelint-baseline.json`, so `scopelint check` only fails
+    /// on findings that aren't in it.
+    Baseline,
+    #[clap(about = "Keeps parsed files in memory to serve `scopelint check --use-daemon`.")]
+    /// Runs in the foreground and serves `scopelint check --use-daemon` over a local socket,
+    /// reparsing only the files that changed since the previous check.
+    Daemon {
+        #[clap(long, help = "Stop the daemon running for this project")]
+        /// Stop the daemon running for this project instead of starting one.
+        stop: bool,
+    },
+    #[clap(about = "Prints counts of contracts, functions, tests, errors and events, and SLOC.")]
+    /// Prints metrics of the project for audit scoping: contracts by kind, functions by
+    /// visibility, tests by prefix, errors and events per contract, and SLOC per directory.
+    Stats,
+    #[clap(about = "Checks that the configuration parses and forge is installed.")]
+    /// Checks that `foundry.toml` and `.scopelint` parse, that `forge` is installed, and that the
+    /// configured paths contain Solidity files.
+    Doctor,
+}
+
+impl Subcommands {
+    /// Returns the name of the command and the output formats it supports, the first of which is
+    /// the default.
+    #[must_use]
+    pub const fn formats(&self) -> (&'static str, &'static [OutputFormat]) {
+        use OutputFormat::{Checkstyle, Html, Human, Json, Jsonl, Junit, Markdown, Sarif};
+        match self {
+            Self::Check(_) => {
+                ("check", &[Human, Json, Jsonl, Sarif, Junit, Checkstyle, Markdown, Html])
+            }
+            Self::Fix => ("fix", &[Human]),
+            Self::Fmt { .. } => ("fmt", &[Human, Json]),
+            Self::Spec { .. } => ("spec", &[Human, Json, Markdown]),
+            Self::Selectors { .. } => ("selectors", &[Human, Json]),
+            Self::Storage { .. } => ("storage", &[Human]),
+            Self::Explain { .. } => ("explain", &[Human]),
+            Self::Init { .. } => ("init", &[Human]),
+            Self::Hook { .. } => ("hook", &[Human]),
+            Self::Config { .. } => ("config", &[Human, Json]),
+            Self::MigrateSolhint { .. } => ("migrate-solhint", &[Human]),
+            Self::MigrateConfig { .. } => ("migrate-config", &[Human]),
+            Self::NewFixture { .. } => ("new-fixture", &[Human]),
+            Self::Baseline => ("baseline", &[Human]),
+            Self::Daemon { .. } => ("daemon", &[Human]),
+            Self::Stats => ("stats", &[Human, Json, Markdown]),
+            Self::Doctor => ("doctor", &[Human]),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Args)]
+#[allow(clippy::struct_excessive_bools)] // Command line flags.
+/// Options for `scopelint check`.
+pub struct CheckArgs {
+    #[clap(help = "Only check these files and directories instead of the whole project")]
+    /// Only check these files and directories instead of the whole project. Files outside the
+    /// src, test, and script directories are never checked.
+    pub paths: Vec<PathBuf>,
+    #[clap(
+        long,
+        conflicts_with_all = ["paths", "staged"],
+        help = "Only check Solidity files and foundry.toml changed since the last commit"
+    )]
+    /// Only check Solidity files and `foundry.toml` changed since the last commit, staged or not.
+    pub diff: bool,
+    #[clap(
+        long,
+        conflicts_with = "paths",
+        help = "Only check Solidity files and foundry.toml with staged changes"
+    )]
+    /// Only check Solidity files and `foundry.toml` with staged changes, e.g. in a pre-commit
+    /// hook. The files are checked and format-checked as staged in the index, not as on disk.
+    pub staged: bool,
+    #[clap(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["paths", "diff", "staged"],
+        help = "Only check the files defining the named contract and the files importing them"
+    )]
+    /// Only check the files defining the contract, interface, or library with this name, and the
+    /// files that import them, e.g. to iterate on one contract in a large repo.
+    pub contract: Option<String>,
+    #[clap(
+        long,
+        requires = "stdin_filepath",
+        conflicts_with_all = ["paths", "diff", "staged", "contract", "write", "fix", "bug_report"],
+        help = "Check Solidity source read from stdin instead of files, e.g. an unsaved editor buffer"
+    )]
+    /// Check Solidity source read from stdin as the contents of `stdin_filepath`, e.g. an unsaved
+    /// editor buffer. Only that file is checked, and formatting is not checked.
+    pub stdin: bool,
+    #[clap(
+        long,
+        value_name = "FILE",
+        requires = "stdin",
+        help = "The path of the file read with --stdin, e.g. `src/Token.sol`"
+    )]
+    /// The path of the file read with `--stdin`, which decides the rules that apply to it. The
+    /// file doesn't need to exist.
+    pub stdin_filepath: Option<PathBuf>,
+    #[clap(skip)]
+    /// Output format for findings, from the global `--format`.
+    pub format: OutputFormat,
+    #[clap(
+        long,
+        help = "Write the env vars read by scripts to `[scripts.env] required` in .scopelint before checking"
+    )]
+    /// Write the env vars read by scripts to `[scripts.env] required` in `.scopelint` before
+    /// checking.
+    pub write: bool,
+    #[clap(
+        long,
+        help = "Apply mechanical fixes (SPDX headers, unused imports, underscore prefixes) before checking"
+    )]
+    /// Apply mechanical fixes, such as missing SPDX headers, unused imports, and underscore
+    /// prefixes, before checking. Findings that can't be fixed mechanically are still reported.
+    /// Only the fixes of the rules selected with `--only` and `--skip` are applied.
+    pub fix: bool,
+    #[clap(
+        long,
+        requires = "fix",
+        conflicts_with = "write",
+        help = "With --fix, print a diff of the fixes instead of applying them"
+    )]
+    /// With `--fix`, print a unified diff of the fixes per file and the number of findings that
+    /// can't be fixed, instead of writing anything.
+    pub dry_run: bool,
+    #[clap(
+        long,
+        conflicts_with = "manifest",
+        help = "Stop at the first file with findings, without checking the rest or formatting"
+    )]
+    /// Stop at the first file or project-level check with findings at or above `min_severity`,
+    /// and report only its findings, without checking the remaining files or formatting. For
+    /// hooks that only need to know whether the project is clean.
+    pub fail_fast: bool,
+    #[clap(
+        short,
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Check at most N files at once, defaults to the number of CPUs"
+    )]
+    /// Check at most this many files at once, e.g. to leave cores free for other CI jobs. Defaults
+    /// to the `SCOPELINT_JOBS` env var if set, or else to the number of available CPUs.
+    pub jobs: Option<u16>,
+    #[clap(
+        long,
+        help = "Print files checked, findings per rule, suppressed findings, and elapsed time"
+    )]
+    /// Print files checked, findings per rule, suppressed findings, and elapsed time.
+    pub summary: bool,
+    #[clap(
+        long,
+        conflicts_with_all = ["format", "suggest_ignores", "group_by_file"],
+        help = "Print only the summary and whether the check passed, without individual findings"
+    )]
+    /// Print only the summary of `summary` and whether the check passed, without the individual
+    /// findings, e.g. for status dashboards and scheduled jobs that fetch details separately.
+    pub summary_only: bool,
+    #[clap(
+        long,
+        conflicts_with = "format",
+        help = "Print inline directives and .scopelint overrides that suppress each finding, instead of the findings"
+    )]
+    /// Print ready-to-paste inline directives and `.scopelint` overrides that suppress each
+    /// finding, grouped per file, instead of the findings.
+    pub suggest_ignores: bool,
+    #[clap(
+        short,
+        long,
+        action = clap::ArgAction::Count,
+        help = "Print the files walked and the time spent on each, -vv also per parse and rule"
+    )]
+    /// Print the files walked, the rules run, and the time spent on each file and project-level
+    /// check. Given twice, also print the time spent parsing each file and in each of its checks.
+    pub verbose: u8,
+    #[clap(long, help = "Group human-readable findings under a header per file, sorted by line")]
+    /// Group human-readable findings under a header per file, sorted by line.
+    pub group_by_file: bool,
+    #[clap(
+        long,
+        value_enum,
+        default_value_t = Severity::Info,
+        help = "Only report findings at or above this severity"
+    )]
+    /// Only report findings at or above this severity.
+    pub min_severity: Severity,
+    #[clap(
+        long,
+        value_name = "N",
+        help = "Pass when there are no errors and at most this many findings below error severity"
+    )]
+    /// Pass when there are no errors and at most this many findings below error severity, which
+    /// are still reported. Overrides `[check] max_warnings` in `.scopelint`.
+    pub max_warnings: Option<usize>,
+    #[clap(
+        long,
+        visible_alias = "deny-warnings",
+        conflicts_with = "max_warnings",
+        help = "Promote findings below error severity to errors, and don't color output by default"
+    )]
+    /// Promote findings below error severity to errors, so they fail the check regardless of
+    /// `[check] max_warnings`, and don't color output unless `--color always` is given. Lets the
+    /// same config be lenient locally and strict in CI.
+    pub ci: bool,
+    #[clap(
+        long,
+        help = "URL template for finding hyperlinks, e.g. `https://github.com/org/repo/blob/main/{path}#L{line}`"
+    )]
+    /// URL template for finding hyperlinks, with `{path}`, `{line}`, and `{column}` placeholders.
+    /// Overrides `[output] link_template` in `.scopelint`.
+    pub link_template: Option<String>,
+    #[clap(
+        long,
+        help = "Write a manifest of the version, config and file hashes, and findings of the run"
+    )]
+    /// Write a reproducibility manifest of the run to this file, with the scopelint version, the
+    /// hashes of the config and checked files, and a summary of the findings, e.g. as audit
+    /// evidence.
+    pub manifest: Option<PathBuf>,
+    #[clap(long, help = "Write the output of a machine-readable `--format` to this file")]
+    /// Write the output of a machine-readable `--format` to this file instead of stdout.
+    pub out: Option<PathBuf>,
+    #[clap(
+        long,
+        value_delimiter = ',',
+        value_parser = parse_rule,
+        help = "Only run these rules, given as comma-separated names or codes, e.g. `error,test,eip712`"
+    )]
+    /// Only run these rules, given by name or code. All rules run when empty.
+    pub only: Vec<ValidatorKind>,
+    #[clap(
+        long,
+        value_delimiter = ',',
+        value_parser = parse_rule,
+        help = "Don't run these rules, given as comma-separated names or codes, e.g. `variable,import`"
+    )]
+    /// Don't run these rules, given by name or code. Applied after `only`.
+    pub skip: Vec<ValidatorKind>,
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Check only this file, then write a redacted diagnostic bundle to attach to a bug report"
+    )]
+    /// Check only this file, then write a redacted diagnostic bundle with the scopelint version,
+    /// effective config, and file name and size to a temp path, to attach to a bug report.
+    pub bug_report: Option<PathBuf>,
+    #[clap(
+        long,
+        conflicts_with_all = ["stdin", "fix", "bug_report"],
+        help = "Check through the `scopelint daemon` of this project, if one is running"
+    )]
+    /// Check through the `scopelint daemon` running for this project, which reuses the files it
+    /// parsed in earlier runs. Checks in-process when no daemon is running.
+    pub use_daemon: bool,
+}
+
+// Parses a rule name or code given on the command line.
+fn parse_rule(rule: &str) -> Result<ValidatorKind, String> {
+    explain::find_rule(rule)
+        .cloned()
+        .ok_or_else(|| format!("unknown rule `{rule}`, run `scopelint explain` to list all rules"))
+}
+
+#[derive(Debug, Subcommand)]
+/// Storage layout commands.
+pub enum StorageCommand {
+    #[clap(
+        about = "Compares src storage layouts between two git refs and fails if the change is not append-only."
+    )]
+    /// Compares src storage layouts between two git refs and fails if the change is not
+    /// append-only.
+    Diff {
+        #[clap(help = "The git ref with the deployed layout, e.g. `main`")]
+        /// The git ref with the deployed layout, e.g. `main`.
+        base: String,
+        #[clap(default_value = "HEAD", help = "The git ref with the upgraded layout")]
+        /// The git ref with the upgraded layout.
+        head: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+/// Configuration commands.
+pub enum ConfigCommand {
+    #[clap(
+        about = "Prints the foundry.toml paths, .scopelint settings, and run flags in effect, as TOML or JSON."
+    )]
+    /// Prints the paths from `foundry.toml`, the `.scopelint` settings with defaults filled in,
+    /// and how scopelint was run (version, root, profile, config file, jobs), as TOML, or JSON
+    /// with `--format json`.
+    Show {
+        #[clap(
+            help = "Also explain how these files are checked: their kind, ignores, and overrides"
+        )]
+        /// Also explain how these files are checked: their kind, whether they are checked or
+        /// ignored, and the rules `[ignore.overrides]` turns off for them.
+        files: Vec<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+/// Git hook commands.
+pub enum HookCommand {
+    #[clap(
+        about = "Adds a section running `scopelint check --staged` and `scopelint fmt --check` on staged files to the pre-commit hook."
+    )]
+    /// Adds a section that runs `scopelint check --staged` and `scopelint fmt --check` on the
+    /// staged Solidity and TOML files to the git pre-commit hook, creating the hook if needed.
+    Install {
+        #[clap(long, help = "Remove the scopelint section from the pre-commit hook instead")]
+        /// Remove the scopelint section from the pre-commit hook instead, and the hook itself if
+        /// nothing else is left in it.
+        uninstall: bool,
+    },
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+/// The format used to print the output of a command, e.g. findings or the specification.
+pub enum OutputFormat {
+    /// Human-readable text, one finding per line on stderr.
+    #[default]
+    Human,
+    /// A JSON document on stdout, for consumption by CI scripts and other tools.
+    Json,
+    /// One JSON object per finding on stdout, streamed as each file is validated, for very large
+    /// repos.
+    Jsonl,
+    /// A SARIF 2.1.0 log on stdout, for code scanning tools such as GitHub code scanning.
+    Sarif,
+    /// A `JUnit` XML report on stdout, for CI test report UIs such as Jenkins and GitLab.
+    Junit,
+    /// A checkstyle XML report on stdout, for tools such as reviewdog and Jenkins warnings-ng.
+    Checkstyle,
+    /// Markdown on stdout: tables of findings grouped by rule, for PR descriptions and bots, or
+    /// the specification with a section per contract, e.g. to commit as `SPEC.md`.
+    Markdown,
+    /// A self-contained, filterable HTML page on stdout, for sharing with reviewers and auditors.
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+/// The format of `--help-all`.
+pub enum HelpFormat {
+    /// Plain text.
+    Text,
+    /// A roff man page, e.g. to install as `man1/scopelint.1`.
+    Man,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+/// When to color output.
+pub enum ColorChoice {
+    /// Color output when stdout and stderr are terminals and the `NO_COLOR` env var is not set.
+    #[default]
+    Auto,
+    /// Always color output, e.g. for CI logs that render ANSI codes.
+    Always,
+    /// Never color output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns whether output should be colored.
+    #[must_use]
+    pub fn should_colorize(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                // See https://no-color.org: any non-empty value disables color.
+                let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                let dumb_term = env::var("TERM").is_ok_and(|term| term == "dumb");
+                !no_color && !dumb_term && io::stdout().is_terminal() && io::stderr().is_terminal()
+            }
+        }
+    }
 }
@@ -0,0 +1,173 @@
+use crate::{
+    check::file_config::FileConfig,
+    failure::Failure,
+    foundry_config::{self, CheckPaths},
+    progress,
+};
+use colored::Colorize;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use walkdir::WalkDir;
+
+/// Counts the problems found while checking the setup.
+#[derive(Default)]
+struct Diagnosis {
+    /// Problems that make scopelint fail or check the wrong files.
+    errors: usize,
+    /// Problems that scopelint works around, but are likely mistakes.
+    warnings: usize,
+}
+
+impl Diagnosis {
+    fn ok(message: &str) {
+        println!("{} {message}", "ok".green());
+    }
+
+    fn warn(&mut self, message: &str) {
+        self.warnings += 1;
+        println!("{} {message}", "warning".yellow());
+    }
+
+    fn error(&mut self, message: &str) {
+        self.errors += 1;
+        println!("{} {message}", "error".red());
+    }
+}
+
+/// Checks that `foundry.toml` and `.scopelint` parse, `forge` is installed, and the configured
+/// paths contain Solidity files.
+///
+/// Setup problems are reported up front instead of surfacing as a `scopelint check` that silently
+/// checks nothing.
+///
+/// # Errors
+///
+/// Returns [`Failure::Findings`] if any check failed. Warnings, e.g. for unknown config keys, don't
+/// fail the run.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let mut diagnosis = Diagnosis::default();
+    check_foundry_toml(&mut diagnosis);
+    check_config(&mut diagnosis);
+    check_forge(&mut diagnosis);
+    check_paths(&mut diagnosis);
+
+    if diagnosis.errors > 0 {
+        eprintln!(
+            "{}: Found {} problem(s) and {} warning(s), see details above",
+            "error".bold().red(),
+            diagnosis.errors,
+            diagnosis.warnings
+        );
+        return Err(Failure::Findings.into());
+    }
+    progress::info(format_args!("No problems found, {} warning(s)", diagnosis.warnings));
+    Ok(())
+}
+
+/// Checks that `foundry.toml` parses and its `[check]` section has only known keys.
+fn check_foundry_toml(diagnosis: &mut Diagnosis) {
+    let Some(path) = CheckPaths::find_foundry_toml().map(|path| relative(&path)) else {
+        diagnosis.warn("No foundry.toml found, using the default src, test, and script paths");
+        return;
+    };
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) => return diagnosis.error(&format!("Failed to read {}: {err}", path.display())),
+    };
+    if let Err(err) = CheckPaths::from_toml(&content, &foundry_config::profile()) {
+        return diagnosis.error(&format!("{}: {err}", path.display()));
+    }
+    Diagnosis::ok(&format!("{} parses", path.display()));
+
+    let check = toml::from_str::<toml::Table>(&content)
+        .ok()
+        .and_then(|toml| toml.get("check").and_then(toml::Value::as_table).cloned())
+        .unwrap_or_default();
+    for key in check.keys().filter(|key| !foundry_config::CHECK_KEYS.contains(&key.as_str())) {
+        diagnosis.warn(&format!("{}: Unknown key `check.{key}` is ignored", path.display()));
+    }
+}
+
+/// Checks that `.scopelint` parses and has only known keys.
+fn check_config(diagnosis: &mut Diagnosis) {
+    let Some(path) = FileConfig::path().map(|path| relative(&path)) else {
+        Diagnosis::ok("No .scopelint found, using the default configuration");
+        return;
+    };
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) => return diagnosis.error(&format!("Failed to read {}: {err}", path.display())),
+    };
+    if let Err(err) = FileConfig::from_toml(&content) {
+        return diagnosis.error(&format!("{}: {err}", path.display()));
+    }
+    Diagnosis::ok(&format!("{} parses", path.display()));
+
+    for key in FileConfig::unknown_keys(&content).unwrap_or_default() {
+        diagnosis.warn(&format!("{}: Unknown key `{key}` is ignored", path.display()));
+    }
+}
+
+/// Checks that `forge` is on the `PATH`, which `scopelint fmt` and the formatting check need.
+fn check_forge(diagnosis: &mut Diagnosis) {
+    match Command::new("forge").arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let version = stdout.lines().next().unwrap_or_default().trim();
+            Diagnosis::ok(&format!("Found {version}"));
+        }
+        Ok(output) => diagnosis.error(&format!(
+            "`forge --version` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Err(err) => diagnosis.error(&format!(
+            "forge not found on PATH, which formatting needs ({err}), see https://book.getfoundry.sh/getting-started/installation"
+        )),
+    }
+}
+
+/// Returns `path` relative to the current directory if it's inside it, to keep messages short.
+fn relative(path: &Path) -> PathBuf {
+    std::env::current_dir()
+        .ok()
+        .and_then(|dir| path.strip_prefix(dir).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Checks that every configured src, test, and script path exists and that there are Solidity
+/// files to check.
+fn check_paths(diagnosis: &mut Diagnosis) {
+    let paths = CheckPaths::load();
+    let kinds =
+        [("src", &paths.src_paths), ("test", &paths.test_paths), ("script", &paths.script_paths)];
+    let mut total = 0;
+    for (kind, roots) in kinds {
+        for root in roots {
+            if !Path::new(root).is_dir() {
+                let message = format!("The {kind} path {root} does not exist");
+                // Projects without tests or scripts are fine, but without src nothing is checked.
+                if kind == "src" {
+                    diagnosis.error(&message);
+                } else {
+                    diagnosis.warn(&message);
+                }
+                continue;
+            }
+            let count = WalkDir::new(root)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|dent| dent.path().extension().is_some_and(|ext| ext == "sol"))
+                .count();
+            total += count;
+            Diagnosis::ok(&format!("The {kind} path {root} has {count} Solidity file(s)"));
+        }
+    }
+    if total == 0 {
+        diagnosis
+            .error("No Solidity files found in the configured paths, nothing would be checked");
+    }
+}
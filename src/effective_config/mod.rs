@@ -0,0 +1,130 @@
+use crate::{
+    check::{
+        self,
+        file_config::FileConfig,
+        utils::{FileKind, IsFileKind, ValidatorKind},
+    },
+    config::OutputFormat,
+    foundry_config::{self, CheckPaths},
+};
+use globset::GlobMatcher;
+use serde_json::{json, Value};
+use std::{
+    env,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// Prints the effective configuration: how scopelint was run, the paths read from
+/// `foundry.toml`, and the settings of `.scopelint` with defaults filled in.
+///
+/// Each of `files` is also explained: its kind, whether it's checked, and the rules that don't
+/// run on it. The configuration is printed as TOML, or as JSON with `OutputFormat::Json`.
+/// # Errors
+/// Errors if the configuration can't be serialized.
+pub fn run(files: &[PathBuf], format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    let path_config = CheckPaths::load();
+    let file_config = FileConfig::load();
+    let mut config = json!({
+        "run": run_info(),
+        "paths": paths(&path_config),
+        "scopelint": file_config.to_json(),
+    });
+    if !files.is_empty() {
+        let files: Vec<Value> =
+            files.iter().map(|file| explain_file(file, &path_config, &file_config)).collect();
+        config["files"] = json!(files);
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&config)?),
+        _ => print!("{}", toml::to_string_pretty(&config)?),
+    }
+    Ok(())
+}
+
+// How scopelint was run: its version, the working directory, the selected Foundry profile, and
+// the config files that were read.
+fn run_info() -> Value {
+    let mut run = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "profile": foundry_config::profile(),
+    });
+    if let Ok(root) = env::current_dir() {
+        run["root"] = json!(root.display().to_string());
+    }
+    if let Some(foundry_toml) = CheckPaths::find_foundry_toml() {
+        run["foundry_toml"] = json!(foundry_toml.display().to_string());
+    }
+    if let Some(config) = FileConfig::path() {
+        run["config"] = json!(config.display().to_string());
+    }
+    if let Some(ignore_path) = FileConfig::ignore_path() {
+        run["ignore_path"] = json!(ignore_path.display().to_string());
+    }
+    if let Ok(jobs) = check::jobs(None) {
+        run["jobs"] = json!(jobs);
+    }
+    run
+}
+
+// The directories and globs that decide the kind of each file.
+fn paths(path_config: &CheckPaths) -> Value {
+    let globs = |matchers: &[GlobMatcher]| -> Vec<String> {
+        matchers.iter().map(|matcher| matcher.glob().glob().to_string()).collect()
+    };
+    json!({
+        "src": path_config.src_paths,
+        "test": path_config.test_paths,
+        "script": path_config.script_paths,
+        "handlers": globs(&path_config.handler_globs),
+        "test_helpers": globs(&path_config.test_helper_globs),
+        "script_helpers": globs(&path_config.script_helper_globs),
+    })
+}
+
+// Explains how `file` is checked: its kind, whether it's checked or formatted at all, and the
+// rules `[ignore.overrides]` turns off for it.
+fn explain_file(file: &Path, path_config: &CheckPaths, file_config: &FileConfig) -> Value {
+    // Walked files start with `./`, like the directories in `foundry.toml`.
+    let path = if file.is_relative() && !file.starts_with(".") {
+        Path::new(".").join(file)
+    } else {
+        file.to_path_buf()
+    };
+    let kind = [
+        FileKind::Src,
+        FileKind::Script,
+        FileKind::ScriptHelper,
+        FileKind::Test,
+        FileKind::Handler,
+        FileKind::TestHelper,
+    ]
+    .into_iter()
+    .find(|kind| path.is_file_kind(*kind, path_config));
+    let ignored = file_config.is_file_ignored(&path);
+    let ignored_rules: Vec<&str> =
+        file_config.get_ignored_rules(&path).iter().map(ValidatorKind::rule_name).collect();
+
+    json!({
+        "path": file.display().to_string(),
+        "exists": path.is_file(),
+        "kind": kind.map_or("none", kind_name),
+        "checked": path.is_file() && kind.is_some() && !ignored,
+        "ignored": ignored,
+        "fmt_ignored": file_config.is_fmt_ignored(&path),
+        "ignored_rules": ignored_rules,
+    })
+}
+
+// The name of a file kind, as used in `foundry.toml` and the docs.
+const fn kind_name(kind: FileKind) -> &'static str {
+    match kind {
+        FileKind::Src => "src",
+        FileKind::Script => "script",
+        FileKind::ScriptHelper => "script_helper",
+        FileKind::Test => "test",
+        FileKind::TestHelper => "test_helper",
+        FileKind::Handler => "handler",
+    }
+}
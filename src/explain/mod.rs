@@ -0,0 +1,88 @@
+use crate::check::utils::ValidatorKind;
+use colored::Colorize;
+use std::error::Error;
+
+/// Prints the description, rationale, and examples of a rule, or lists every rule if no rule is
+/// given.
+///
+/// # Errors
+///
+/// Returns an error if `rule` is neither a known rule code nor a rule name.
+pub fn run(rule: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let Some(rule) = rule else {
+        for kind in &ValidatorKind::ALL {
+            println!("{}  {:<12} {}", kind.code().bold(), kind.rule_name(), kind.summary());
+        }
+        return Ok(());
+    };
+
+    let Some(kind) = find_rule(rule) else {
+        eprintln!(
+            "{}: Unknown rule '{rule}', run `scopelint explain` to list all rules",
+            "error".bold().red()
+        );
+        return Err("Unknown rule".into());
+    };
+    print!("{}", explanation(kind));
+    Ok(())
+}
+
+/// Finds the rule with the given code (e.g. `SL001`) or rule name (e.g. `constant`), ignoring case.
+/// The old name of a renamed rule finds the rule that replaced it.
+#[must_use]
+pub fn find_rule(rule: &str) -> Option<&'static ValidatorKind> {
+    let renamed = ValidatorKind::from_rule_name(&rule.to_ascii_lowercase());
+    ValidatorKind::ALL.iter().find(|kind| {
+        kind.code().eq_ignore_ascii_case(rule) ||
+            kind.rule_name().eq_ignore_ascii_case(rule) ||
+            renamed.as_ref() == Some(*kind)
+    })
+}
+
+/// Returns the full explanation of a rule: a heading with its code and name, the summary, and the
+/// embedded rationale and examples.
+#[must_use]
+pub fn explanation(kind: &ValidatorKind) -> String {
+    format!(
+        "# {} `{}`\n\n{}\n\nSeverity: {}\n\n{}",
+        kind.code(),
+        kind.rule_name(),
+        kind.summary(),
+        kind.severity().name(),
+        kind.explanation()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_rule() {
+        assert_eq!(find_rule("SL001"), Some(&ValidatorKind::Constant));
+        assert_eq!(find_rule("sl006"), Some(&ValidatorKind::Variable));
+        assert_eq!(find_rule("Storage"), Some(&ValidatorKind::Storage));
+        assert_eq!(find_rule("SL999"), None);
+        assert_eq!(find_rule("unknown"), None);
+    }
+
+    #[test]
+    fn test_codes_are_unique_and_explained() {
+        for (i, kind) in ValidatorKind::ALL.iter().enumerate() {
+            assert_eq!(kind.code(), format!("SL{:03}", i + 1));
+            assert!(kind.explanation().contains("## Rationale"), "{}", kind.rule_name());
+            assert!(kind.explanation().contains("## Examples"), "{}", kind.rule_name());
+        }
+    }
+
+    #[test]
+    fn test_rule_names_round_trip() {
+        for kind in &ValidatorKind::ALL {
+            let name = kind.rule_name();
+            assert_eq!(ValidatorKind::from_rule_name(name).as_ref(), Some(kind), "{name}");
+            // Inline directives split the rule name from the scope on `-`.
+            assert!(!name.contains('-'), "{name}");
+        }
+        assert_eq!(ValidatorKind::from_rule_name("unknown"), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! Failure classes of a run, which decide the exit code so CI can tell a violated convention apart
+//! from a file that couldn't be parsed.
+
+use std::{error::Error, fmt};
+
+/// The exit code when every check passed.
+pub const SUCCESS: i32 = 0;
+
+/// The exit code when the run couldn't complete, e.g. because a file couldn't be parsed or read,
+/// a command line argument was invalid, or scopelint crashed.
+pub const INTERNAL_ERROR: i32 = 3;
+
+/// A run that completed but didn't pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// Findings were reported, e.g. a violated naming convention or a breaking storage layout
+    /// change.
+    Findings,
+    /// Files are not formatted.
+    Formatting,
+}
+
+impl Failure {
+    /// Returns the exit code for this failure: 1 for findings, 2 for formatting.
+    #[must_use]
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Findings => 1,
+            Self::Formatting => 2,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Findings => write!(f, "findings were reported"),
+            Self::Formatting => write!(f, "files are not formatted"),
+        }
+    }
+}
+
+impl Error for Failure {}
+
+/// Returns the exit code for an error returned by [`crate::run`]: the code of a [`Failure`], or
+/// [`INTERNAL_ERROR`] for any other error.
+#[must_use]
+pub fn exit_code(err: &(dyn Error + 'static)) -> i32 {
+    err.downcast_ref::<Failure>().map_or(INTERNAL_ERROR, |failure| failure.exit_code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(&Failure::Findings), 1);
+        assert_eq!(exit_code(&Failure::Formatting), 2);
+        let err: Box<dyn Error> = "Failed to parse file".into();
+        assert_eq!(exit_code(err.as_ref()), INTERNAL_ERROR);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{check::utils::ValidatorKind, progress, storage::SNAPSHOT_FILE};
+use colored::Colorize;
+use itertools::Itertools;
+use std::{error::Error, fmt::Write as _, fs, path::Path};
+
+/// Files and configuration that make one rule report a finding.
+struct Seed {
+    /// Files to write, relative to the project root, and their contents.
+    files: &'static [(&'static str, &'static str)],
+    /// Settings to add to `.scopelint`, e.g. to enable an opt-in rule.
+    config: &'static str,
+}
+
+// Expands to the path of a seed file and its contents from the `seeds` directory, which mirrors
+// the layout of the generated project.
+macro_rules! seed_file {
+    ($path:literal) => {
+        ($path, include_str!(concat!("seeds/", $path)))
+    };
+}
+
+/// Writes a minimal Foundry project to `dir` that is seeded with a violation of each of the given
+/// `rules`, or of every rule if none are given, to validate a configuration end-to-end.
+///
+/// # Errors
+///
+/// Returns an error if `dir` exists and is not empty, or if the project can't be written.
+pub fn run(dir: &Path, rules: &[ValidatorKind]) -> Result<(), Box<dyn Error>> {
+    if dir.read_dir().is_ok_and(|mut entries| entries.next().is_some()) {
+        eprintln!("{}: {} is not empty", "error".bold().red(), dir.display());
+        return Err(format!("{} is not empty", dir.display()).into());
+    }
+    let rules = if rules.is_empty() { ValidatorKind::ALL.as_slice() } else { rules };
+
+    let files = project(rules);
+    for (path, content) in &files {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+    progress::info(format_args!(
+        "Wrote a fixture with {} rule(s) to {}, run `scopelint check` in it to see findings",
+        rules.len(),
+        dir.display()
+    ));
+    Ok(())
+}
+
+/// Returns the files of a project seeded with a violation of each of `rules`.
+fn project(rules: &[ValidatorKind]) -> Vec<(String, String)> {
+    let mut files = Vec::new();
+    let mut config = String::new();
+    let mut readme =
+        "# scopelint fixture\n\nEach rule is seeded with a violation in these files:\n\n"
+            .to_string();
+    for kind in rules {
+        let seed = seed(kind);
+        let paths = seed.files.iter().map(|(path, _)| format!("`{path}`")).join(", ");
+        let _ = writeln!(readme, "- {} `{}`: {paths}", kind.code(), kind.rule_name());
+        files.extend(
+            seed.files.iter().map(|(path, content)| (path.to_string(), content.to_string())),
+        );
+        if !seed.config.is_empty() {
+            let _ = writeln!(config, "{}", seed.config);
+        }
+    }
+
+    // Every src file needs a test file once the `mirror` rule is enabled, so only its own seed is
+    // flagged.
+    if rules.contains(&ValidatorKind::TestMirror) {
+        let mirrors: Vec<(String, String)> = files
+            .iter()
+            .filter_map(|(path, _)| {
+                let name = path.strip_prefix("src/")?.strip_suffix(".sol")?;
+                (name != "Unmirrored")
+                    .then(|| (format!("test/{name}.t.sol"), format!("contract {name}Test {{}}\n")))
+            })
+            .collect();
+        files.extend(mirrors);
+    }
+    // The `foundry` rule seeds its own `foundry.toml`. Both pin the solc that the `solc` rule's
+    // seed excludes.
+    if !rules.contains(&ValidatorKind::FoundryConfig) {
+        files.insert(
+            0,
+            (
+                "foundry.toml".to_string(),
+                "[profile.default]\n  script = \"script\"\n  solc = \"0.8.28\"\n  src = \"src\"\n  \
+                 test = \"test\"\n"
+                    .to_string(),
+            ),
+        );
+    }
+    if !config.is_empty() {
+        files.push((".scopelint".to_string(), config));
+    }
+    files.push(("README.md".to_string(), readme));
+    files
+}
+
+/// Returns the files and configuration that make `kind` report a finding. Seeded src files have a
+/// license, a pragma, and a security contact, so they aren't flagged by other rules.
+const fn seed(kind: &ValidatorKind) -> Seed {
+    let (files, config): (&[_], _) = match kind {
+        ValidatorKind::Constant => (&[seed_file!("src/Constant.sol")], ""),
+        ValidatorKind::Script => (&[seed_file!("script/Deploy.s.sol")], ""),
+        ValidatorKind::Src => (&[seed_file!("src/Src.sol")], ""),
+        ValidatorKind::Test => (&[seed_file!("test/Naming.t.sol")], ""),
+        ValidatorKind::Directive => (&[seed_file!("src/Directive.sol")], ""),
+        ValidatorKind::Variable => (&[seed_file!("src/Variable.sol")], ""),
+        ValidatorKind::Error => (&[seed_file!("src/Errors.sol")], ""),
+        ValidatorKind::Eip712 => (&[seed_file!("src/Permit.sol")], ""),
+        ValidatorKind::Import => (&[seed_file!("src/Import.sol")], ""),
+        ValidatorKind::Console => (&[seed_file!("src/Logging.sol")], ""),
+        ValidatorKind::Chain => (
+            &[
+                seed_file!("script/deploy/mainnet/Addresses.sol"),
+                seed_file!("script/deploy/optimism/Deploy.s.sol"),
+            ],
+            "[rules.chain.dirs]\n\
+             mainnet = \"script/deploy/mainnet\"\n\
+             optimism = \"script/deploy/optimism\"\n",
+        ),
+        ValidatorKind::Determinism => (&[seed_file!("script/Random.s.sol")], ""),
+        ValidatorKind::Env => {
+            (&[seed_file!("script/Env.s.sol")], "[scripts.env]\nrequired = [\"RPC_URL\"]\n")
+        }
+        ValidatorKind::Rpc => (&[seed_file!("test/Fork.t.sol")], ""),
+        ValidatorKind::Pragma => {
+            (&[seed_file!("src/Pragma.sol")], "[rules.pragma]\nmin_solc = \"0.8.20\"\n")
+        }
+        ValidatorKind::Upgradeable => (&[seed_file!("src/Upgradeable.sol")], ""),
+        ValidatorKind::Storage => (
+            &[seed_file!("src/Layout.sol"), (SNAPSHOT_FILE, include_str!("seeds/storage.json"))],
+            "",
+        ),
+        ValidatorKind::Selector => (&[seed_file!("src/Selector.sol")], ""),
+        ValidatorKind::Event => (
+            &[seed_file!("src/Deposits.sol"), seed_file!("src/Stakes.sol")],
+            "[rules.event]\nenabled = true\n",
+        ),
+        ValidatorKind::SecurityContact => {
+            (&[seed_file!("src/Contact.sol")], "[rules.contact]\nenabled = true\n")
+        }
+        ValidatorKind::TestMirror => (
+            &[seed_file!("src/Unmirrored.sol")],
+            "[rules.mirror]\nenabled = true\npatterns = [\"{dir}/{name}.t.sol\"]\n",
+        ),
+        ValidatorKind::ScriptFile => (&[seed_file!("script/Upgrade.s.sol")], ""),
+        ValidatorKind::Spdx => (&[seed_file!("src/License.sol")], ""),
+        ValidatorKind::Nesting => (&[seed_file!("src/Nesting.sol")], ""),
+        ValidatorKind::ElseAfterReturn => {
+            (&[seed_file!("src/Early.sol")], "[rules.else]\nenabled = true\n")
+        }
+        ValidatorKind::BoolComparison => {
+            (&[seed_file!("src/Flags.sol")], "[rules.bool]\nenabled = true\n")
+        }
+        ValidatorKind::IntType => (&[seed_file!("src/Width.sol")], ""),
+        ValidatorKind::EtherUnits => {
+            (&[seed_file!("src/Units.sol")], "[rules.units]\nenabled = true\n")
+        }
+        ValidatorKind::NamedReturns => {
+            (&[seed_file!("src/Returns.sol")], "[rules.returns]\nenabled = true\n")
+        }
+        ValidatorKind::ChainConstant => {
+            (&[seed_file!("src/Router.sol")], "[rules.immutable]\nenabled = true\n")
+        }
+        ValidatorKind::Natspec => {
+            (&[seed_file!("src/Docs.sol")], "[rules.natspec]\nenabled = true\n")
+        }
+        ValidatorKind::FileName => {
+            (&[seed_file!("src/FileName.sol")], "[rules.filename]\nenabled = true\n")
+        }
+        ValidatorKind::StateVisibility => {
+            (&[seed_file!("src/Visibility.sol")], "[rules.visibility]\nenabled = true\n")
+        }
+        ValidatorKind::OneContract => {
+            (&[seed_file!("src/Pair.sol")], "[rules.onecontract]\nenabled = true\n")
+        }
+        ValidatorKind::FloatingPragma => {
+            (&[seed_file!("src/Floating.sol")], "[rules.floating]\nenabled = true\n")
+        }
+        ValidatorKind::SolcPin => {
+            (&[seed_file!("src/Outdated.sol")], "[rules.solc]\nenabled = true\n")
+        }
+        ValidatorKind::Remappings => {
+            (&[seed_file!("remappings.txt")], "[rules.remappings]\nenabled = true\n")
+        }
+        ValidatorKind::FoundryConfig => {
+            (&[seed_file!("foundry.toml")], "[rules.foundry]\nenabled = true\n")
+        }
+    };
+    Seed { files, config }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_paths_are_unique() {
+        let files = project(&ValidatorKind::ALL);
+        let mut paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        let count = paths.len();
+        paths.sort_unstable();
+        paths.dedup();
+        assert_eq!(paths.len(), count);
+        assert!(paths.contains(&".scopelint"));
+        assert!(paths.contains(&"foundry.toml"));
+
+        // Opt-in rules are only enabled when they are seeded.
+        let files = project(&[ValidatorKind::Constant]);
+        assert!(files.iter().all(|(path, _)| path != ".scopelint"));
+    }
+}
@@ -0,0 +1,5 @@
+contract DeployScript {
+    function deployToken() public {}
+
+    function deployVault() public {}
+}
@@ -0,0 +1,5 @@
+contract EnvScript {
+    function run() public {
+        address _deployer = vm.envAddress("DEPLOYER");
+    }
+}
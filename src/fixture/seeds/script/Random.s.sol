@@ -0,0 +1,5 @@
+contract RandomScript {
+    function run() public {
+        address _admin = vm.randomAddress();
+    }
+}
@@ -0,0 +1,3 @@
+contract Upgrade {
+    function run() public {}
+}
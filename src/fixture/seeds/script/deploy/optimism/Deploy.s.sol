@@ -0,0 +1,5 @@
+import {Addresses} from "../mainnet/Addresses.sol";
+
+contract DeployScript is Addresses {
+    function run() public {}
+}
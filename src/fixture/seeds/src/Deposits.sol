@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Deposits {
+    event Deposited(address indexed user, uint256 amount);
+}
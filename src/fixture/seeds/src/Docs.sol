@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Docs {
+    /// @notice Returns half of `_amount`.
+    function half(uint256 _amount) external pure returns (uint256) {
+        return _amount / 2;
+    }
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Early {
+    uint256 public total;
+
+    function add(uint256 _x) external {
+        if (_x == 0) {
+            revert();
+        } else {
+            total += _x;
+        }
+    }
+}
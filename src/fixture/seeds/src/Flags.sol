@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Flags {
+    bool public paused;
+
+    function unpause() external {
+        require(paused == true);
+        paused = false;
+    }
+}
@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {Unused} from "./Unused.sol";
+
+/// @custom:security-contact security@example.com
+contract Import {}
@@ -0,0 +1,4 @@
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract License {}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {console} from "forge-std/console.sol";
+
+/// @custom:security-contact security@example.com
+contract Logging {
+  function ping() external pure {
+    console.log("ping");
+  }
+}
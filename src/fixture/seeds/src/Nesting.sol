@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Nesting {
+    uint256 public total;
+
+    function add(uint256 _x) external {
+        if (_x > 0) {
+            for (uint256 _i = 0; _i < _x; _i++) {
+                if (_i % 2 == 0) {
+                    while (total < _i) {
+                        if (total % 3 == 0) total += 2;
+                        else total++;
+                    }
+                }
+            }
+        }
+    }
+}
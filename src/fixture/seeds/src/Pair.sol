@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Pair {
+    uint256 public reserve;
+}
+
+/// @custom:security-contact security@example.com
+contract PairFactory {
+    uint256 public count;
+}
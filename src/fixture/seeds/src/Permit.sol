@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Permit {
+    bytes32 constant PERMIT_TYPEHASH = keccak256("Permit(address owner,uint256 value)");
+
+    function _hash(address _owner, uint256 _value, uint256 _nonce) internal pure returns (bytes32) {
+        return keccak256(abi.encode(PERMIT_TYPEHASH, _owner, _value, _nonce));
+    }
+}
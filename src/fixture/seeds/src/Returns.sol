@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Returns {
+    function half(uint256 _amount) external pure returns (uint256 result) {
+        if (_amount == 0) return 0;
+        result = _amount / 2;
+    }
+}
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Selector {
+    function burn(uint256 _amount) external {}
+
+    function collate_propagate_storage(bytes16 _data) external {}
+}
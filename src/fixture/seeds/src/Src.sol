@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Src {
+    function computeShares(uint256 _assets) internal pure returns (uint256) {
+        return _assets;
+    }
+}
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Units {
+    function toWad(uint256 _amount) external pure returns (uint256) {
+        return _amount * 10 ** 18;
+    }
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @custom:security-contact security@example.com
+contract Upgradeable is UUPSUpgradeable {
+    address owner;
+
+    constructor(address _owner) {
+        owner = _owner;
+    }
+}
@@ -0,0 +1,5 @@
+contract ForkTest {
+    function setUp() public {
+        vm.createSelectFork("https://eth-mainnet.example.com/v2/key");
+    }
+}
@@ -0,0 +1,3 @@
+contract NamingTest {
+    function testTransfer() public {}
+}
@@ -1,120 +1,358 @@
+use crate::{check, config::OutputFormat, failure::Failure, foundry_config::CheckPaths, progress};
 use colored::Colorize;
-use std::{error::Error, fs, process};
+use globset::Glob;
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+use walkdir::WalkDir;
 
-/// Check formatting without modifying files.
-/// # Errors
-/// Errors if `forge fmt` fails, or if `taplo` fails to format `foundry.toml`.
-fn check_formatting(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    println!("Checking formatting...");
+// The TOML file formatted when no paths are given.
+const FOUNDRY_TOML: &str = "./foundry.toml";
 
-    let forge_status = process::Command::new("forge").args(["fmt", "--check"]).output()?;
+/// Check formatting of `paths`, or of the whole project if empty, without modifying files. With
+/// `OutputFormat::Json`, the files that would be reformatted are printed as JSON instead of diffs.
+/// # Errors
+/// Errors if `forge fmt` fails, or if `taplo` fails to format a TOML file.
+fn check_formatting(
+    taplo_opts: &taplo::formatter::Options,
+    paths: &[PathBuf],
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    let human = format == OutputFormat::Human;
+    if human && !progress::is_quiet() {
+        println!("Checking formatting...");
+    }
 
     let mut has_changes = false;
+    let mut unformatted: Vec<String> = Vec::new();
+    let (sol_paths, toml_paths) = targets(paths);
 
-    // Print any warnings/errors from `forge fmt --check`.
-    if !forge_status.stderr.is_empty() {
-        print!("{}", String::from_utf8(forge_status.stderr)?);
-    }
-
-    // Print the diff output from forge fmt --check with colors
-    if !forge_status.stdout.is_empty() {
-        println!("Solidity files that would be reformatted:");
-        let forge_output = String::from_utf8(forge_status.stdout)?;
+    // Files excluded from formatting are left out, and if every file is, forge isn't run at all.
+    if let Some(paths) = sol_paths {
+        let forge_status =
+            process::Command::new("forge").args(["fmt", "--check"]).args(paths).output()?;
 
-        for line in forge_output.lines() {
-            if line.starts_with("Diff in ") {
-                println!("{line}");
-            } else if line.contains("|-") {
-                // Red for removed lines
-                let parts: Vec<&str> = line.split("|-").collect();
-                if parts.len() == 2 {
-                    println!("{}{}{}", parts[0], "|-".red(), parts[1].red());
-                } else {
-                    println!("{line}");
-                }
-            } else if line.contains("|+") {
-                // Green for added lines
-                let parts: Vec<&str> = line.split("|+").collect();
-                if parts.len() == 2 {
-                    println!("{}{}{}", parts[0], "|+".green(), parts[1].green());
-                } else {
-                    println!("{line}");
-                }
+        // Print any warnings/errors from `forge fmt --check`, keeping stdout machine-readable.
+        if !forge_status.stderr.is_empty() {
+            let stderr = String::from_utf8(forge_status.stderr)?;
+            if human {
+                print!("{stderr}");
             } else {
-                println!("{line}");
+                eprint!("{stderr}");
             }
         }
-        has_changes = true;
+
+        let forge_output = String::from_utf8(forge_status.stdout)?;
+        unformatted.extend(
+            forge_output
+                .lines()
+                .filter_map(|line| line.strip_prefix("Diff in "))
+                .map(|file| file.trim_end_matches(':').to_string()),
+        );
+        if !forge_output.is_empty() {
+            if human {
+                println!("Solidity files that would be reformatted:");
+                print_forge_diff(&forge_output);
+            }
+            has_changes = true;
+        }
+
+        // Check if forge fmt found any issues
+        if !forge_status.status.success() {
+            has_changes = true;
+        }
     }
 
-    // Check if forge fmt found any issues
-    if !forge_status.status.success() {
+    // Check TOML formatting
+    for path in &toml_paths {
+        let config_orig = fs::read_to_string(path)?;
+        let config_fmt = taplo::formatter::format(&config_orig, taplo_opts.clone());
+        if config_orig == config_fmt {
+            continue;
+        }
+        let path = path.strip_prefix(".").unwrap_or(path).display();
+        unformatted.push(path.to_string());
+        if human {
+            println!("{path} would be reformatted:");
+            println!("Diff in {path}:");
+            print_toml_diff(&config_orig, &config_fmt);
+        }
         has_changes = true;
     }
 
-    // Check foundry.toml formatting
-    let config_orig = fs::read_to_string("./foundry.toml")?;
-    let config_fmt = taplo::formatter::format(&config_orig, taplo_opts);
+    if !human {
+        println!("{}", serde_json::to_string_pretty(&json!({ "unformatted": unformatted }))?);
+    } else if has_changes {
+        println!("\nRun 'scopelint fmt' to apply these changes.");
+    } else if !progress::is_quiet() {
+        println!("All files are properly formatted!");
+    }
+    if has_changes {
+        return Err(Failure::Formatting.into());
+    }
+    Ok(())
+}
 
-    if config_orig != config_fmt {
-        println!("foundry.toml would be reformatted:");
-        println!("Diff in foundry.toml:");
+// Prints a line-by-line diff of a TOML file before and after formatting, with removed lines in red
+// and added lines in green.
+fn print_toml_diff(config_orig: &str, config_fmt: &str) {
+    // Simple diff output with colors
+    let orig_lines: Vec<&str> = config_orig.lines().collect();
+    let fmt_lines: Vec<&str> = config_fmt.lines().collect();
 
-        // Simple diff output with colors
-        let orig_lines: Vec<&str> = config_orig.lines().collect();
-        let fmt_lines: Vec<&str> = config_fmt.lines().collect();
+    for (i, line) in fmt_lines.iter().enumerate() {
+        if i < orig_lines.len() && orig_lines[i] != *line {
+            // Red for removed lines
+            println!("{}    |{}{}", i + 1, "-".red(), orig_lines[i].red());
+            // Green for added lines
+            println!("{}    |{}{}", i + 1, "+".green(), line.green());
+        } else if i >= orig_lines.len() {
+            // Green for new lines
+            println!("{}    |{}{}", i + 1, "+".green(), line.green());
+        }
+    }
+}
 
-        for (i, line) in fmt_lines.iter().enumerate() {
-            if i < orig_lines.len() && orig_lines[i] != *line {
-                // Red for removed lines
-                println!("{}    |{}{}", i + 1, "-".red(), orig_lines[i].red());
-                // Green for added lines
-                println!("{}    |{}{}", i + 1, "+".green(), line.green());
-            } else if i >= orig_lines.len() {
-                // Green for new lines
-                println!("{}    |{}{}", i + 1, "+".green(), line.green());
+// Prints the diff output of `forge fmt --check`, with removed lines in red and added lines in
+// green.
+fn print_forge_diff(forge_output: &str) {
+    for line in forge_output.lines() {
+        if line.starts_with("Diff in ") {
+            println!("{line}");
+        } else if line.contains("|-") {
+            // Red for removed lines
+            let parts: Vec<&str> = line.split("|-").collect();
+            if parts.len() == 2 {
+                println!("{}{}{}", parts[0], "|-".red(), parts[1].red());
+            } else {
+                println!("{line}");
+            }
+        } else if line.contains("|+") {
+            // Green for added lines
+            let parts: Vec<&str> = line.split("|+").collect();
+            if parts.len() == 2 {
+                println!("{}{}{}", parts[0], "|+".green(), parts[1].green());
+            } else {
+                println!("{line}");
             }
+        } else {
+            println!("{line}");
         }
+    }
+}
 
-        has_changes = true;
+/// Apply formatting to `paths`, or to the whole project if empty, then print the files that
+/// changed, as JSON with `OutputFormat::Json`. With `fail_on_change`, it's an error if any file
+/// changed, so CI can format and verify in a single run.
+/// # Errors
+/// Errors if `forge fmt` fails, if `taplo` fails to format a TOML file, or if `fail_on_change` is
+/// set and any file was reformatted.
+fn apply_formatting(
+    taplo_opts: &taplo::formatter::Options,
+    fail_on_change: bool,
+    paths: &[PathBuf],
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    let human = format == OutputFormat::Human;
+    let before = snapshot(paths);
+    let (sol_paths, toml_paths) = targets(paths);
+    if let Some(paths) = sol_paths {
+        let forge_status = process::Command::new("forge").arg("fmt").args(paths).output()?;
+
+        // Print any warnings/errors from `forge fmt`, keeping stdout machine-readable.
+        if !forge_status.stderr.is_empty() {
+            let stderr = String::from_utf8(forge_status.stderr)?;
+            if human {
+                print!("{stderr}");
+            } else {
+                eprint!("{stderr}");
+            }
+        }
     }
 
-    // Exit with error code if any files would be changed
-    if has_changes {
-        println!("\nRun 'scopelint fmt' to apply these changes.");
-        process::exit(1);
-    } else {
-        println!("All files are properly formatted!");
+    // Format TOML files with taplo.
+    for path in &toml_paths {
+        let config_orig = fs::read_to_string(path)?;
+        fs::write(path, taplo::formatter::format(&config_orig, taplo_opts.clone()))?;
     }
 
+    let after = snapshot(paths);
+    let changed = changed_files(&before, &after);
+    if !human {
+        let files: Vec<String> = changed.iter().map(|file| file.display().to_string()).collect();
+        println!("{}", serde_json::to_string_pretty(&json!({ "formatted": files }))?);
+    } else if progress::is_quiet() && (!fail_on_change || changed.is_empty()) {
+        // Reformatted files are only worth printing when they fail the run.
+    } else if changed.is_empty() {
+        println!("All files are properly formatted!");
+    } else {
+        println!("Formatted {} file(s):", changed.len());
+        for file in &changed {
+            println!("  {}", file.display());
+        }
+    }
+    if fail_on_change && !changed.is_empty() {
+        eprintln!(
+            "{}: {} file(s) were not formatted, run 'scopelint fmt' and commit the changes",
+            "error".bold().red(),
+            changed.len()
+        );
+        return Err(Failure::Formatting.into());
+    }
     Ok(())
 }
 
-/// Apply formatting to files.
-/// # Errors
-/// Errors if `forge fmt` fails, or if `taplo` fails to format `foundry.toml`.
-fn apply_formatting(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    let forge_status = process::Command::new("forge").arg("fmt").output()?;
+// Returns the Solidity paths to pass to `forge fmt`, or `None` if it shouldn't run, and the TOML
+// files to format with taplo. Without `paths`, that's the whole project and `foundry.toml`.
+fn targets(paths: &[PathBuf]) -> (Option<Vec<PathBuf>>, Vec<PathBuf>) {
+    let (toml_paths, sol_paths): (Vec<PathBuf>, Vec<PathBuf>) =
+        paths.iter().cloned().partition(|path| path.extension() == Some(OsStr::new("toml")));
+    let file_config = check::file_config::FileConfig::load();
+    if paths.is_empty() {
+        return (
+            check::validators::formatting::fmt_paths(&[], &file_config),
+            vec![PathBuf::from(FOUNDRY_TOML)],
+        );
+    }
+    let sol_paths = if sol_paths.is_empty() {
+        None
+    } else {
+        check::validators::formatting::fmt_paths(&sol_paths, &file_config)
+    };
+    (sol_paths, toml_paths)
+}
 
-    // Print any warnings/errors from `forge fmt`.
-    if !forge_status.stderr.is_empty() {
-        print!("{}", String::from_utf8(forge_status.stderr)?);
+// Resolves the files, directories, and glob patterns given on the command line. Patterns that
+// aren't existing paths, e.g. a quoted `src/*Vault*.sol`, are matched against the Solidity files
+// in the src, test, and script directories and the TOML files in the project root.
+fn resolve_paths(args: &[String]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    let mut candidates: Option<Vec<PathBuf>> = None;
+    for arg in args {
+        if Path::new(arg).exists() {
+            paths.push(PathBuf::from(arg));
+            continue;
+        }
+        let glob = Glob::new(arg)
+            .map_err(|err| format!("Invalid glob pattern '{arg}': {err}"))?
+            .compile_matcher();
+        let candidates = candidates.get_or_insert_with(project_files);
+        let matched: Vec<PathBuf> = candidates
+            .iter()
+            .filter(|file| glob.is_match(file.strip_prefix(".").unwrap_or(file)))
+            .cloned()
+            .collect();
+        if matched.is_empty() {
+            eprintln!("{}: {arg} does not exist or match any file", "error".bold().red());
+            return Err(format!("{arg} does not exist or match any file").into());
+        }
+        paths.extend(matched);
     }
+    Ok(paths)
+}
 
-    // Format `foundry.toml` with taplo.
-    let config_orig = fs::read_to_string("./foundry.toml")?;
-    let config_fmt = taplo::formatter::format(&config_orig, taplo_opts);
-    fs::write("./foundry.toml", config_fmt)?;
-    Ok(())
+// The Solidity files in the src, test, and script directories and the TOML files in the project
+// root, which are the files `scopelint fmt` formats.
+fn project_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(".")
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| Path::new(".").join(entry.file_name()))
+        .filter(|file| file.is_file() && file.extension() == Some(OsStr::new("toml")))
+        .collect();
+    for path in CheckPaths::load().all() {
+        for dent in WalkDir::new(path).into_iter().filter_map(Result::ok) {
+            let file = dent.path();
+            if dent.file_type().is_file() && file.extension() == Some(OsStr::new("sol")) {
+                files.push(file.to_path_buf());
+            }
+        }
+    }
+    files
+}
+
+// Reads the Solidity and TOML files in `paths`, or `foundry.toml` and the Solidity files in the
+// src, script, and test directories if empty, to find the files changed by formatting.
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, String> {
+    let roots: Vec<PathBuf> = if paths.is_empty() {
+        let path_config = CheckPaths::load();
+        let roots = path_config.all().into_iter().map(PathBuf::from);
+        std::iter::once(PathBuf::from(FOUNDRY_TOML)).chain(roots).collect()
+    } else {
+        paths.to_vec()
+    };
+    let mut files = BTreeMap::new();
+    for root in roots {
+        for dent in WalkDir::new(root).into_iter().filter_map(Result::ok) {
+            let file = dent.path();
+            let is_formatted =
+                matches!(file.extension().and_then(OsStr::to_str), Some("sol" | "toml"));
+            if dent.file_type().is_file() && is_formatted {
+                if let Ok(content) = fs::read_to_string(file) {
+                    files.insert(file.to_path_buf(), content);
+                }
+            }
+        }
+    }
+    files
+}
+
+// Files that are new in `after` or whose content differs from `before`.
+fn changed_files<'a>(
+    before: &BTreeMap<PathBuf, String>,
+    after: &'a BTreeMap<PathBuf, String>,
+) -> Vec<&'a Path> {
+    after
+        .iter()
+        .filter(|(file, content)| before.get(*file) != Some(content))
+        .map(|(file, _)| file.as_path())
+        .collect()
 }
 
-/// Format the code.
+/// Format the code. If `paths` is not empty, only those files, directories, and files matching
+/// those glob patterns are formatted, with `forge fmt` for Solidity and taplo for TOML.
+///
+/// With `OutputFormat::Json`, the files that were or would be reformatted are printed as JSON.
 /// # Errors
-/// Errors if `forge fmt` fails, or if `taplo` fails to format `foundry.toml`.
-pub fn run(taplo_opts: taplo::formatter::Options, check: bool) -> Result<(), Box<dyn Error>> {
+/// Errors if a path doesn't exist or a pattern matches no file, if `forge fmt` fails, if `taplo`
+/// fails to format a TOML file, or if `fail_on_change` is set and any file was reformatted.
+pub fn run(
+    taplo_opts: &taplo::formatter::Options,
+    check: bool,
+    fail_on_change: bool,
+    paths: &[String],
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    let paths = resolve_paths(paths)?;
     if check {
-        check_formatting(taplo_opts)
+        check_formatting(taplo_opts, &paths, format)
     } else {
-        apply_formatting(taplo_opts)
+        apply_formatting(taplo_opts, fail_on_change, &paths, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_files() {
+        let files = |entries: &[(&str, &str)]| -> BTreeMap<PathBuf, String> {
+            entries
+                .iter()
+                .map(|(file, content)| (PathBuf::from(file), content.to_string()))
+                .collect()
+        };
+        let before = files(&[("a.sol", "a"), ("b.sol", "b"), ("c.sol", "c")]);
+        let after = files(&[("a.sol", "a"), ("b.sol", "b2"), ("d.sol", "d")]);
+        assert_eq!(changed_files(&before, &after), [Path::new("b.sol"), Path::new("d.sol")]);
+        assert!(changed_files(&before, &before).is_empty());
     }
 }
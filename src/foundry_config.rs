@@ -2,42 +2,156 @@
 //!
 //! Reads the existing Foundry config so scopelint works with non-default layouts
 //! (e.g. `contracts/` instead of `src/`). Paths can be overridden with a
-//! scopelint-specific `[check]` section.
+//! scopelint-specific `[check]` section, which can also set globs for invariant test handlers and
+//! test and script helpers that don't follow the file name conventions. Like Forge, the profile is
+//! selected with the `FOUNDRY_PROFILE` env var, and paths it doesn't set are inherited from
+//! `[profile.default]`.
 
-use std::path::PathBuf;
+use colored::Colorize;
+use globset::{Glob, GlobMatcher};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// The env var that selects the Foundry profile, shared with Forge.
+pub const PROFILE_ENV: &str = "FOUNDRY_PROFILE";
+
+/// The profile used when [`PROFILE_ENV`] is not set.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Keys of the scopelint-specific `[check]` section of `foundry.toml`.
+pub const CHECK_KEYS: &[&str] =
+    &["src_path", "script_path", "test_path", "handlers", "test_helpers", "script_helpers"];
+
+/// Returns the selected Foundry profile, from [`PROFILE_ENV`] or [`DEFAULT_PROFILE`].
+#[must_use]
+pub fn profile() -> String {
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .filter(|profile| !profile.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Checks that the selected profile exists in `foundry.toml`, so a typo doesn't silently lint the
+/// default paths.
+///
+/// # Errors
+///
+/// Returns an error if a profile other than the default is selected and `foundry.toml` has no
+/// `[profile.<name>]` section for it.
+pub fn validate_profile() -> Result<(), Box<dyn Error>> {
+    let profile = profile();
+    if profile == DEFAULT_PROFILE {
+        return Ok(());
+    }
+    let Some(content) =
+        CheckPaths::find_foundry_toml().and_then(|path| std::fs::read_to_string(path).ok())
+    else {
+        return Ok(());
+    };
+    let toml: toml::Value = toml::from_str(&content).unwrap_or(toml::Value::Boolean(false));
+    if toml.get("profile").and_then(|p| p.get(&profile)).is_none() {
+        eprintln!(
+            "{}: Profile `{profile}` is not defined in foundry.toml, add a `[profile.{profile}]` section",
+            "error".bold().red()
+        );
+        return Err(format!("profile `{profile}` not found").into());
+    }
+    Ok(())
+}
 
 /// Paths for source, script, and test directories (relative to project root).
+///
+/// Each kind can have several roots, e.g. `test = ["test", "integration-tests"]`.
 /// Normalized to start with `./` for consistent use with walking and path checks.
 #[derive(Debug, Clone)]
 pub struct CheckPaths {
-    /// Source contracts directory (e.g. `./src` or `./contracts`).
-    pub src_path: String,
-    /// Scripts directory (e.g. `./script`).
-    pub script_path: String,
-    /// Test directory (e.g. `./test`).
-    pub test_path: String,
+    /// Source contracts directories (e.g. `./src` or `./contracts`).
+    pub src_paths: Vec<String>,
+    /// Scripts directories (e.g. `./script`).
+    pub script_paths: Vec<String>,
+    /// Test directories (e.g. `./test`).
+    pub test_paths: Vec<String>,
+    /// Globs for invariant test handlers, in addition to `.handler.sol` files in a test directory.
+    pub handler_globs: Vec<GlobMatcher>,
+    /// Globs for test helpers, which are never test files, even if they end with `.t.sol`.
+    pub test_helper_globs: Vec<GlobMatcher>,
+    /// Globs for script helpers, which are never scripts, even if they end with `.s.sol`.
+    pub script_helper_globs: Vec<GlobMatcher>,
 }
 
 impl Default for CheckPaths {
     fn default() -> Self {
         Self {
-            src_path: "./src".to_string(),
-            script_path: "./script".to_string(),
-            test_path: "./test".to_string(),
+            src_paths: vec!["./src".to_string()],
+            script_paths: vec!["./script".to_string()],
+            test_paths: vec!["./test".to_string()],
+            handler_globs: Vec::new(),
+            test_helper_globs: Vec::new(),
+            script_helper_globs: Vec::new(),
         }
     }
 }
 
 impl CheckPaths {
-    /// Paths as a 3-element array for iterating (src, script, test).
+    /// All paths for iterating, src roots first, then script and test roots.
     #[must_use]
-    pub const fn as_array(&self) -> [&str; 3] {
-        [self.src_path.as_str(), self.script_path.as_str(), self.test_path.as_str()]
+    pub fn all(&self) -> Vec<&str> {
+        self.src_paths
+            .iter()
+            .chain(&self.script_paths)
+            .chain(&self.test_paths)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The first src directory, e.g. for examples and messages.
+    #[must_use]
+    pub fn src_path(&self) -> &str {
+        self.src_paths.first().map_or("./src", String::as_str)
+    }
+
+    /// The first script directory, e.g. for examples and messages.
+    #[must_use]
+    pub fn script_path(&self) -> &str {
+        self.script_paths.first().map_or("./script", String::as_str)
+    }
+
+    /// The first test directory, e.g. for examples and messages.
+    #[must_use]
+    pub fn test_path(&self) -> &str {
+        self.test_paths.first().map_or("./test", String::as_str)
+    }
+
+    /// Returns true if `path` is in one of the src directories.
+    #[must_use]
+    pub fn in_src(&self, path: &Path) -> bool {
+        self.src_paths.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Returns true if `path` is in one of the script directories.
+    #[must_use]
+    pub fn in_script(&self, path: &Path) -> bool {
+        self.script_paths.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Returns true if `path` is in one of the test directories.
+    #[must_use]
+    pub fn in_test(&self, path: &Path) -> bool {
+        self.test_paths.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Returns true if `path` matches one of `globs`, relative to the project root.
+    #[must_use]
+    pub fn matches(globs: &[GlobMatcher], path: &Path) -> bool {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        globs.iter().any(|glob| glob.is_match(path))
     }
 
     /// Load paths from `foundry.toml`: use `[check]` overrides if present,
-    /// otherwise `[profile.default]` (or root-level) `src`, `test`, `script`.
-    /// Returns default paths if no config is found or parsing fails.
+    /// otherwise the selected profile's, `[profile.default]`'s, or root-level `src`, `test`,
+    /// `script`. Returns default paths if no config is found or parsing fails.
     #[must_use]
     pub fn load() -> Self {
         let Some(config_path) = Self::find_foundry_toml() else {
@@ -48,10 +162,11 @@ impl CheckPaths {
             return Self::default();
         };
 
-        Self::from_toml(&content).unwrap_or_default()
+        Self::from_toml(&content, &profile()).unwrap_or_default()
     }
 
-    fn find_foundry_toml() -> Option<PathBuf> {
+    /// Searches up the directory tree from the current working directory for `foundry.toml`.
+    pub(crate) fn find_foundry_toml() -> Option<PathBuf> {
         let mut current_dir = std::env::current_dir().ok()?;
 
         loop {
@@ -70,52 +185,91 @@ impl CheckPaths {
     }
 
     /// Parse paths from TOML. Uses `[check]` section if present, else Foundry's
-    /// `[profile.default]` (or root) `src`, `test`, `script`.
-    pub(crate) fn from_toml(content: &str) -> Result<Self, String> {
+    /// `[profile.<profile>]`, `[profile.default]` (or root) `src`, `test`, `script`. Each path may
+    /// be a string or an array of strings.
+    pub(crate) fn from_toml(content: &str, profile: &str) -> Result<Self, String> {
         let toml: toml::Value =
             toml::from_str(content).map_err(|e| format!("Invalid TOML: {e}"))?;
 
         // Optional scopelint [check] overrides (src_path, script_path, test_path)
         let check_section = toml.get("check").and_then(|v| v.as_table());
+        let paths = |check_key: &str, key: &str| -> Result<Vec<String>, String> {
+            check_section.and_then(|check| check.get(check_key)).map_or_else(
+                || from_foundry_profile(&toml, profile, key),
+                |value| {
+                    parse_paths(value).ok_or_else(|| {
+                        format!("[check] {check_key} must be a string or an array of strings")
+                    })
+                },
+            )
+        };
 
-        let (src_path, script_path, test_path) = check_section.map_or_else(
-            || {
-                (
-                    from_foundry_profile(&toml, "src"),
-                    from_foundry_profile(&toml, "script"),
-                    from_foundry_profile(&toml, "test"),
-                )
-            },
-            |check| {
-                let src = check.get("src_path").and_then(|v| v.as_str()).map(normalize_path);
-                let script = check.get("script_path").and_then(|v| v.as_str()).map(normalize_path);
-                let test = check.get("test_path").and_then(|v| v.as_str()).map(normalize_path);
-                (
-                    src.unwrap_or_else(|| from_foundry_profile(&toml, "src")),
-                    script.unwrap_or_else(|| from_foundry_profile(&toml, "script")),
-                    test.unwrap_or_else(|| from_foundry_profile(&toml, "test")),
-                )
-            },
-        );
+        let globs = |key: &str| -> Result<Vec<GlobMatcher>, String> {
+            let Some(value) = check_section.and_then(|check| check.get(key)) else {
+                return Ok(Vec::new());
+            };
+            let patterns = value
+                .as_array()
+                .and_then(|patterns| {
+                    patterns.iter().map(toml::Value::as_str).collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| format!("[check] {key} must be an array of strings"))?;
+            patterns
+                .into_iter()
+                .map(|pattern| {
+                    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+                    Glob::new(pattern).map(|glob| glob.compile_matcher()).map_err(|e| {
+                        format!("Invalid glob pattern '{pattern}' in [check] {key}: {e}")
+                    })
+                })
+                .collect()
+        };
 
-        Ok(Self { src_path, script_path, test_path })
+        Ok(Self {
+            src_paths: paths("src_path", "src")?,
+            script_paths: paths("script_path", "script")?,
+            test_paths: paths("test_path", "test")?,
+            handler_globs: globs("handlers")?,
+            test_helper_globs: globs("test_helpers")?,
+            script_helper_globs: globs("script_helpers")?,
+        })
     }
 }
 
-/// Read a path from [profile.default] or root level (Foundry allows both).
-fn from_foundry_profile(toml: &toml::Value, key: &str) -> String {
-    let profile = toml
-        .get("profile")
-        .and_then(|p| p.get("default"))
-        .and_then(|d| d.get(key))
-        .and_then(|v| v.as_str());
-    let root = toml.get(key).and_then(|v| v.as_str());
-    let raw = profile.or(root).unwrap_or(match key {
-        "script" => "script",
-        "test" => "test",
-        _ => "src",
-    });
-    normalize_path(raw)
+/// Read a path from `[profile.<profile>]`, falling back to [profile.default] or root level (Foundry
+/// allows both).
+fn from_foundry_profile(
+    toml: &toml::Value,
+    profile: &str,
+    key: &str,
+) -> Result<Vec<String>, String> {
+    let from_profile =
+        |name: &str| toml.get("profile").and_then(|p| p.get(name)).and_then(|d| d.get(key));
+    let Some(value) =
+        from_profile(profile).or_else(|| from_profile(DEFAULT_PROFILE)).or_else(|| toml.get(key))
+    else {
+        let default = match key {
+            "script" => "script",
+            "test" => "test",
+            _ => "src",
+        };
+        return Ok(vec![normalize_path(default)]);
+    };
+    parse_paths(value).ok_or_else(|| format!("{key} must be a string or an array of strings"))
+}
+
+/// Parses a path or an array of paths, normalized. Returns `None` if `value` is neither, or the
+/// array is empty.
+fn parse_paths(value: &toml::Value) -> Option<Vec<String>> {
+    if let Some(path) = value.as_str() {
+        return Some(vec![normalize_path(path)]);
+    }
+    let paths = value
+        .as_array()?
+        .iter()
+        .map(|path| path.as_str().map(normalize_path))
+        .collect::<Option<Vec<_>>>()?;
+    (!paths.is_empty()).then_some(paths)
 }
 
 /// Ensure path has a `./` prefix for consistent comparison and walking.
@@ -133,15 +287,16 @@ fn normalize_path(p: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::CheckPaths;
+    use super::{CheckPaths, DEFAULT_PROFILE};
+    use std::path::Path;
 
     #[test]
     fn from_toml_defaults_when_no_paths() {
         // No src/test/script in config -> use Foundry defaults
-        let p = CheckPaths::from_toml("[fmt]\nline_length = 100").unwrap();
-        assert_eq!(p.src_path, "./src");
-        assert_eq!(p.script_path, "./script");
-        assert_eq!(p.test_path, "./test");
+        let p = CheckPaths::from_toml("[fmt]\nline_length = 100", DEFAULT_PROFILE).unwrap();
+        assert_eq!(p.src_paths, ["./src"]);
+        assert_eq!(p.script_paths, ["./script"]);
+        assert_eq!(p.test_paths, ["./test"]);
     }
 
     #[test]
@@ -153,11 +308,79 @@ src = "contracts"
 test = "test"
 script = "script"
 "#,
+            DEFAULT_PROFILE,
+        )
+        .unwrap();
+        assert_eq!(p.src_paths, ["./contracts"]);
+        assert_eq!(p.script_paths, ["./script"]);
+        assert_eq!(p.test_paths, ["./test"]);
+    }
+
+    #[test]
+    fn from_toml_selected_profile() {
+        let content = r#"
+[profile.default]
+src = "src"
+test = "test"
+
+[profile.ci]
+src = "contracts"
+"#;
+        let p = CheckPaths::from_toml(content, "ci").unwrap();
+        assert_eq!(p.src_paths, ["./contracts"]);
+        // Paths the profile doesn't set are inherited from the default profile.
+        assert_eq!(p.test_paths, ["./test"]);
+        assert_eq!(p.script_paths, ["./script"]);
+
+        let p = CheckPaths::from_toml(content, DEFAULT_PROFILE).unwrap();
+        assert_eq!(p.src_paths, ["./src"]);
+    }
+
+    #[test]
+    fn from_toml_multiple_roots() {
+        let p = CheckPaths::from_toml(
+            r#"
+[profile.default]
+src = ["src", "periphery"]
+test = ["test", "integration-tests"]
+
+[check]
+script_path = ["./script", "./deploy"]
+"#,
+            DEFAULT_PROFILE,
         )
         .unwrap();
-        assert_eq!(p.src_path, "./contracts");
-        assert_eq!(p.script_path, "./script");
-        assert_eq!(p.test_path, "./test");
+        assert_eq!(p.src_paths, ["./src", "./periphery"]);
+        assert_eq!(p.script_paths, ["./script", "./deploy"]);
+        assert_eq!(p.test_paths, ["./test", "./integration-tests"]);
+        assert!(p.in_test(Path::new("./integration-tests/Vault.t.sol")));
+        assert!(!p.in_src(Path::new("./src2/Vault.sol")));
+        assert_eq!(p.all().len(), 6);
+
+        assert!(CheckPaths::from_toml("src = []", DEFAULT_PROFILE).is_err());
+        assert!(CheckPaths::from_toml("[check]\nsrc_path = [1]", DEFAULT_PROFILE).is_err());
+    }
+
+    #[test]
+    fn from_toml_kind_globs() {
+        let p = CheckPaths::from_toml(
+            r#"
+[check]
+handlers = ["test/invariant/handlers/**"]
+test_helpers = ["./test/utils/*.sol"]
+"#,
+            DEFAULT_PROFILE,
+        )
+        .unwrap();
+        assert!(CheckPaths::matches(
+            &p.handler_globs,
+            Path::new("./test/invariant/handlers/Vault.sol")
+        ));
+        assert!(CheckPaths::matches(&p.test_helper_globs, Path::new("test/utils/Base.t.sol")));
+        assert!(p.script_helper_globs.is_empty());
+
+        assert!(CheckPaths::from_toml("[check]\nhandlers = \"test/**\"", DEFAULT_PROFILE).is_err());
+        assert!(CheckPaths::from_toml("[check]\nhandlers = [\"test/[\"]", DEFAULT_PROFILE).is_err());
     }
 
     #[test]
@@ -174,11 +397,12 @@ src_path = "./contracts"
 script_path = "./scripts"
 test_path = "./tests"
 "#,
+            DEFAULT_PROFILE,
         )
         .unwrap();
-        assert_eq!(p.src_path, "./contracts");
-        assert_eq!(p.script_path, "./scripts");
-        assert_eq!(p.test_path, "./tests");
+        assert_eq!(p.src_paths, ["./contracts"]);
+        assert_eq!(p.script_paths, ["./scripts"]);
+        assert_eq!(p.test_paths, ["./tests"]);
     }
 
     #[test]
@@ -193,10 +417,11 @@ script = "script"
 [check]
 src_path = "./contracts"
 "#,
+            DEFAULT_PROFILE,
         )
         .unwrap();
-        assert_eq!(p.src_path, "./contracts");
-        assert_eq!(p.script_path, "./script");
-        assert_eq!(p.test_path, "./test");
+        assert_eq!(p.src_paths, ["./contracts"]);
+        assert_eq!(p.script_paths, ["./script"]);
+        assert_eq!(p.test_paths, ["./test"]);
     }
 }
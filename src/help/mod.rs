@@ -0,0 +1,239 @@
+use crate::{
+    check::{file_config::FileConfig, utils::ValidatorKind},
+    config::{HelpFormat, Opts},
+};
+use clap::{builder::PossibleValue, Arg, Command, CommandFactory};
+use itertools::Itertools;
+use std::{ffi::OsString, fmt::Write};
+
+/// Returns the format `--help-all` was given with, if it was given.
+///
+/// Every other invocation needs a command, so the arguments are parsed leniently, without
+/// requiring one, before they are parsed for real.
+#[must_use]
+pub fn requested() -> Option<HelpFormat> {
+    requested_from(std::env::args_os())
+}
+
+// Same as `requested`, for the given arguments, starting with the binary name.
+fn requested_from(
+    args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
+) -> Option<HelpFormat> {
+    let matches = Opts::command()
+        .subcommand_required(false)
+        .ignore_errors(true)
+        .try_get_matches_from(args)
+        .ok()?;
+    matches.get_one::<HelpFormat>("help_all").copied()
+}
+
+/// Renders the help of every command, then every rule with its settings.
+///
+/// Each rule is listed with its summary, severity, and `.scopelint` settings with their defaults.
+/// Everything is generated from the command-line definition and the rule registry, so it can't
+/// drift from the code.
+#[must_use]
+pub fn render(format: HelpFormat) -> String {
+    let mut cmd = Opts::command();
+    cmd.build();
+    match format {
+        HelpFormat::Text => text(&cmd),
+        HelpFormat::Man => man(&cmd),
+    }
+}
+
+/// Returns every command below `cmd`, depth first, with its full name, e.g. `scopelint hook
+/// install`. The `help` commands are skipped.
+fn commands(cmd: &Command) -> Vec<(String, &Command)> {
+    let mut commands = Vec::new();
+    for sub in cmd.get_subcommands().filter(|sub| sub.get_name() != "help") {
+        let name =
+            format!("{} {}", cmd.get_bin_name().unwrap_or_else(|| cmd.get_name()), sub.get_name());
+        commands.push((name, sub));
+        commands.extend(self::commands(sub));
+    }
+    commands
+}
+
+/// Renders the settings of `kind`, e.g. `rules.nesting.max_depth = 4`, one per line.
+fn settings(kind: &ValidatorKind) -> Vec<String> {
+    FileConfig::rule_settings(kind)
+        .into_iter()
+        .map(|(key, default)| {
+            if default.is_null() {
+                format!("{key} (unset)")
+            } else {
+                format!("{key} = {default}")
+            }
+        })
+        .collect()
+}
+
+// Renders the help as plain text, with a heading per command.
+fn text(cmd: &Command) -> String {
+    let mut text = cmd.clone().render_long_help().to_string();
+    for (name, sub) in commands(cmd) {
+        let help = sub.clone().render_long_help().to_string();
+        let _ = write!(text, "\n{name}\n{}\n\n{help}", "=".repeat(name.len()));
+    }
+    let _ = write!(text, "\nRules\n=====\n");
+    for kind in &ValidatorKind::ALL {
+        let _ = write!(
+            text,
+            "\n{} {} ({})\n  {}\n",
+            kind.code(),
+            kind.rule_name(),
+            kind.severity().name(),
+            kind.summary()
+        );
+        let settings = settings(kind);
+        if !settings.is_empty() {
+            let _ = writeln!(text, "  Settings in .scopelint:");
+            for setting in settings {
+                let _ = writeln!(text, "    {setting}");
+            }
+        }
+    }
+    text
+}
+
+// Renders the help as a roff man page.
+fn man(cmd: &Command) -> String {
+    let name = cmd.get_name();
+    let about = cmd.get_about().map(ToString::to_string).unwrap_or_default();
+    let mut man = format!(
+        ".TH {} 1 \"\" \"{name} {}\" \"User Commands\"\n.SH NAME\n{} \\- {}\n",
+        roff(&name.to_uppercase()),
+        cmd.get_version().unwrap_or_default(),
+        roff(name),
+        roff(&about)
+    );
+    let _ = write!(man, ".SH SYNOPSIS\n\\fB{}\\fR [OPTIONS] <COMMAND>\n.SH OPTIONS\n", roff(name));
+    arguments(&mut man, cmd);
+    man.push_str(".SH COMMANDS\n");
+    for (name, sub) in commands(cmd) {
+        let about = sub.get_long_about().or_else(|| sub.get_about()).map(ToString::to_string);
+        let _ = writeln!(man, ".SS \"{}\"\n{}", roff(&name), roff(&about.unwrap_or_default()));
+        arguments(&mut man, sub);
+    }
+    man.push_str(".SH RULES\n");
+    for kind in &ValidatorKind::ALL {
+        let _ = writeln!(
+            man,
+            ".SS \"{} {}\"\n{}\n.br\nSeverity: {}",
+            kind.code(),
+            roff(kind.rule_name()),
+            roff(kind.summary()),
+            kind.severity().name()
+        );
+        for setting in settings(kind) {
+            let _ = writeln!(man, ".TP\n\\fB{}\\fR", roff(&setting));
+        }
+    }
+    man
+}
+
+// Appends the arguments of `cmd` to `man`, each as a tagged paragraph.
+fn arguments(man: &mut String, cmd: &Command) {
+    for arg in cmd.get_arguments().filter(|arg| !arg.is_hide_set()) {
+        let help = arg.get_long_help().or_else(|| arg.get_help()).map(ToString::to_string);
+        let _ = writeln!(
+            man,
+            ".TP\n\\fB{}\\fR\n{}",
+            roff(&usage(arg)),
+            roff(&help.unwrap_or_default())
+        );
+        let values = arg.get_possible_values();
+        if !values.is_empty() {
+            let values = values.iter().map(PossibleValue::get_name).join(", ");
+            let _ = writeln!(man, ".br\nValues: {}", roff(&values));
+        }
+        let defaults = arg.get_default_values();
+        if !defaults.is_empty() {
+            let defaults = defaults.iter().map(|value| value.to_string_lossy()).join(", ");
+            let _ = writeln!(man, ".br\nDefault: {}", roff(&defaults));
+        }
+    }
+}
+
+// Returns how `arg` is written, e.g. `-j, --jobs <N>` or `<PATHS>`.
+fn usage(arg: &Arg) -> String {
+    let names =
+        arg.get_value_names().map(|names| names.iter().map(|name| format!("<{name}>")).join(" "));
+    let value = names.unwrap_or_else(|| format!("<{}>", arg.get_id().as_str().to_uppercase()));
+    if arg.is_positional() {
+        return value;
+    }
+    let flags = arg
+        .get_short()
+        .map(|short| format!("-{short}"))
+        .into_iter()
+        .chain(arg.get_long().map(|long| format!("--{long}")))
+        .join(", ");
+    if arg.get_num_args().is_some_and(|range| range.takes_values()) {
+        format!("{flags} {value}")
+    } else {
+        flags
+    }
+}
+
+/// Escapes `text` for roff: backslashes and dashes, and lines that would start a request.
+fn roff(text: &str) -> String {
+    text.replace('\\', "\\e")
+        .replace('-', "\\-")
+        .lines()
+        .map(|line| {
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requested() {
+        assert_eq!(requested_from(["scopelint", "--help-all"]), Some(HelpFormat::Text));
+        assert_eq!(requested_from(["scopelint", "--help-all", "man"]), Some(HelpFormat::Man));
+        assert_eq!(requested_from(["scopelint", "check", "--help-all"]), Some(HelpFormat::Text));
+        assert_eq!(requested_from(["scopelint", "check"]), None);
+        assert_eq!(requested_from(["scopelint"]), None);
+    }
+
+    #[test]
+    fn test_render_text() {
+        let text = render(HelpFormat::Text);
+        // Every command and every rule is listed.
+        assert!(text.contains("\ndev-scopelint check\n===================\n"));
+        assert!(text.contains("\ndev-scopelint hook install\n"));
+        assert!(text.contains("--use-daemon"));
+        for kind in &ValidatorKind::ALL {
+            assert!(text.contains(&format!("\n{} {} (", kind.code(), kind.rule_name())));
+        }
+        assert!(text.contains("    rules.nesting.max_depth = 4\n"));
+        assert!(text.contains("    rules.chain.dirs.* (unset)\n"));
+    }
+
+    #[test]
+    fn test_render_man() {
+        let man = render(HelpFormat::Man);
+        assert!(man.starts_with(".TH DEV\\-SCOPELINT 1 "));
+        assert!(man.contains(".SS \"dev\\-scopelint check\"\n"));
+        assert!(man.contains("\\fB\\-j, \\-\\-jobs <N>\\fR\n"));
+        assert!(man.contains(".SS \"SL023 nesting\"\n"));
+        assert!(man.contains("\\fBrules.nesting.max_depth = 4\\fR\n"));
+        // No line starts a roff request by accident.
+        assert!(man.lines().all(|line| !line.starts_with('.') || line.len() > 1));
+    }
+
+    #[test]
+    fn test_roff() {
+        assert_eq!(roff("--fix a\\b"), "\\-\\-fix a\\eb");
+        assert_eq!(roff("first\n.scopelint"), "first\n\\&.scopelint");
+    }
+}
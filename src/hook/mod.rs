@@ -0,0 +1,180 @@
+use crate::progress;
+use colored::Colorize;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The line that starts the scopelint section of the pre-commit hook.
+const SECTION_START: &str = "# >>> scopelint >>>";
+
+/// The line that ends the scopelint section of the pre-commit hook.
+const SECTION_END: &str = "# <<< scopelint <<<";
+
+/// The commands run by the scopelint section of the pre-commit hook: `scopelint check --staged`,
+/// then `scopelint fmt --check` on the staged Solidity and TOML files.
+const SECTION_BODY: &str = r#"# Installed by `scopelint hook install`, remove with `scopelint hook install --uninstall`.
+scopelint check --staged || exit 1
+# The staged files are passed NUL-separated, so names with spaces stay intact.
+if [ -n "$(git diff --cached --name-only --diff-filter=ACMR -- '*.sol' '*.toml')" ]; then
+  git diff --cached -z --name-only --diff-filter=ACMR -- '*.sol' '*.toml' |
+    xargs -0 scopelint fmt --check || exit 1
+fi"#;
+
+/// Installs the scopelint section of the git pre-commit hook, or removes it if `uninstall` is
+/// set.
+///
+/// # Errors
+///
+/// Returns an error if the current directory is not in a git repository, or if the hook can't be
+/// read or written.
+pub fn run(uninstall: bool) -> Result<(), Box<dyn Error>> {
+    if uninstall {
+        self::uninstall()
+    } else {
+        install()
+    }
+}
+
+/// Writes the scopelint section to the git pre-commit hook of the current repository.
+///
+/// A hook that doesn't exist yet is created, an existing hook gets the section right after its
+/// shebang, and a section installed before is replaced.
+///
+/// # Errors
+///
+/// Returns an error if the current directory is not in a git repository, or if the hook can't be
+/// read or written.
+pub fn install() -> Result<(), Box<dyn Error>> {
+    let hook_path = hook_path()?;
+    let existing = if hook_path.exists() { Some(fs::read_to_string(&hook_path)?) } else { None };
+    let hook = with_section(existing.as_deref());
+    if existing.as_deref() == Some(hook.as_str()) {
+        progress::info(format_args!("Pre-commit hook at {} is up to date", hook_path.display()));
+        return Ok(());
+    }
+
+    if let Some(hooks_dir) = hook_path.parent() {
+        fs::create_dir_all(hooks_dir)?;
+    }
+    fs::write(&hook_path, hook)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+    }
+    let action = if existing.is_some() { "Updated" } else { "Installed" };
+    progress::info(format_args!("{action} pre-commit hook at {}", hook_path.display()));
+    Ok(())
+}
+
+/// Removes the scopelint section from the git pre-commit hook of the current repository, and
+/// deletes the hook if nothing else is left in it.
+fn uninstall() -> Result<(), Box<dyn Error>> {
+    let hook_path = hook_path()?;
+    let existing = if hook_path.exists() { Some(fs::read_to_string(&hook_path)?) } else { None };
+    let Some(hook) = existing.as_deref().and_then(without_section) else {
+        progress::info(format_args!(
+            "No scopelint section in the pre-commit hook at {}",
+            hook_path.display()
+        ));
+        return Ok(());
+    };
+
+    if hook.lines().all(|line| line.trim().is_empty() || line.starts_with("#!")) {
+        fs::remove_file(&hook_path)?;
+        progress::info(format_args!("Removed pre-commit hook at {}", hook_path.display()));
+    } else {
+        fs::write(&hook_path, hook)?;
+        progress::info(format_args!(
+            "Removed the scopelint section from the pre-commit hook at {}",
+            hook_path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the path of the pre-commit hook of the current repository, which honors
+/// `core.hooksPath`.
+fn hook_path() -> Result<PathBuf, Box<dyn Error>> {
+    let output =
+        Command::new("git").args(["rev-parse", "--git-path", "hooks/pre-commit"]).output()?;
+    if !output.status.success() {
+        eprintln!("{}: Not a git repository, can't install the hook", "error".bold().red());
+        return Err("not a git repository".into());
+    }
+    Ok(Path::new(String::from_utf8(output.stdout)?.trim()).to_path_buf())
+}
+
+/// Returns the scopelint section, including the lines that delimit it.
+fn section() -> String {
+    format!("{SECTION_START}\n{SECTION_BODY}\n{SECTION_END}\n")
+}
+
+/// Returns `hook` with the scopelint section installed: a new hook if there is none, the section
+/// replaced if it was installed before, or else inserted after the shebang so an `exec` at the
+/// end of the hook can't skip it.
+fn with_section(hook: Option<&str>) -> String {
+    let Some(hook) = hook else {
+        return format!("#!/bin/sh\n{}", section());
+    };
+    if let (Some(start), Some(end)) = (hook.find(SECTION_START), hook.find(SECTION_END)) {
+        let end = hook[end..].find('\n').map_or(hook.len(), |idx| end + idx + 1);
+        return format!("{}{}{}", &hook[..start], section(), &hook[end..]);
+    }
+    let body_start =
+        if hook.starts_with("#!") { hook.find('\n').map_or(hook.len(), |idx| idx + 1) } else { 0 };
+    let (shebang, rest) = hook.split_at(body_start);
+    let shebang = if shebang.is_empty() || shebang.ends_with('\n') {
+        shebang.to_string()
+    } else {
+        format!("{shebang}\n")
+    };
+    format!("{shebang}{}{rest}", section())
+}
+
+/// Returns `hook` without the scopelint section, or `None` if it isn't installed.
+fn without_section(hook: &str) -> Option<String> {
+    let start = hook.find(SECTION_START)?;
+    let end = hook.find(SECTION_END)?;
+    let end = hook[end..].find('\n').map_or(hook.len(), |idx| end + idx + 1);
+    Some(format!("{}{}", &hook[..start], &hook[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_section() {
+        let new = with_section(None);
+        assert!(new.starts_with("#!/bin/sh\n# >>> scopelint >>>\n"));
+        assert!(new.contains("scopelint check --staged"));
+        assert!(new.contains("xargs -0 scopelint fmt --check"));
+        // Installing again changes nothing.
+        assert_eq!(with_section(Some(&new)), new);
+
+        // An existing hook runs the section first.
+        let existing = "#!/bin/bash\nexec npx lint-staged\n";
+        let augmented = with_section(Some(existing));
+        assert_eq!(augmented, format!("#!/bin/bash\n{}exec npx lint-staged\n", section()));
+        assert_eq!(with_section(Some(&augmented)), augmented);
+
+        // An outdated section is replaced in place.
+        let outdated = "#!/bin/sh\necho hi\n# >>> scopelint >>>\nscopelint check\n# <<< scopelint <<<\nexit 0\n";
+        assert_eq!(
+            with_section(Some(outdated)),
+            format!("#!/bin/sh\necho hi\n{}exit 0\n", section())
+        );
+    }
+
+    #[test]
+    fn test_without_section() {
+        let existing = "#!/bin/bash\nexec npx lint-staged\n";
+        assert_eq!(without_section(&with_section(Some(existing))).unwrap(), existing);
+        assert_eq!(without_section(&with_section(None)).unwrap(), "#!/bin/sh\n");
+        assert_eq!(without_section(existing), None);
+    }
+}
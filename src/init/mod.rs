@@ -0,0 +1,207 @@
+use crate::{foundry_config::CheckPaths, hook, progress};
+use colored::Colorize;
+use std::{error::Error, fs, path::Path};
+
+/// Writes a starter `.scopelint` for the Foundry layout in `foundry.toml`, with every section
+/// commented out, and optionally installs the git pre-commit hook of `scopelint hook install`.
+///
+/// # Errors
+///
+/// Returns an error if `.scopelint` already exists, or if it or the pre-commit hook can't be
+/// written.
+pub fn run(hook: bool) -> Result<(), Box<dyn Error>> {
+    let config_path = Path::new(".scopelint");
+    if config_path.exists() {
+        eprintln!("{}: .scopelint already exists, not overwriting it", "error".bold().red());
+        return Err(".scopelint already exists".into());
+    }
+
+    let path_config = CheckPaths::load();
+    progress::info(format_args!(
+        "Detected src = {}, test = {}, script = {}",
+        path_config.src_paths.join(", "),
+        path_config.test_paths.join(", "),
+        path_config.script_paths.join(", ")
+    ));
+    fs::write(config_path, template(&path_config))?;
+    progress::info(format_args!("Wrote .scopelint"));
+
+    if hook {
+        hook::install()?;
+    }
+    Ok(())
+}
+
+/// Returns the starter `.scopelint`, with example paths in the first detected `src`, `test`, and
+/// `script` directories.
+#[allow(clippy::too_many_lines)] // One section per setting.
+fn template(path_config: &CheckPaths) -> String {
+    let src = path_config.src_path().trim_start_matches("./");
+    let test = path_config.test_path().trim_start_matches("./");
+    let script = path_config.script_path().trim_start_matches("./");
+    format!(
+        r#"# scopelint configuration, see https://github.com/thelostone-mc/scopelint-dev#scopelint-check
+# Every section is optional, uncomment the ones you need.
+
+# Ignore entire files.
+# [ignore]
+# files = ["{src}/legacy/*.sol", "{test}/integration/*.sol"]
+
+# Ignore specific rules for specific files, by rule name (see `scopelint explain`).
+# [ignore.overrides]
+# "{src}/legacy/**/*.sol" = ["src", "error"]
+
+# Opt-in: lowest compiler version that `pragma solidity` may admit.
+# [rules.pragma]
+# min_solc = "0.8.20"
+
+# Opt-in: `pragma solidity` must pin an exact compiler version in files of these directories.
+# [rules.floating]
+# enabled = true
+# dirs = ["src"]
+
+# Opt-in: per-chain directories that must not import from each other.
+# [rules.chain.dirs]
+# mainnet = "{script}/deploy/mainnet"
+# optimism = "{script}/deploy/optimism"
+
+# Opt-in: selectors that contract functions must not use, e.g. a proxy's admin functions.
+# [rules.selector]
+# reserved = ["upgradeTo(address)"]
+
+# Opt-in: events that share a signature must be declared the same way.
+# [rules.event]
+# enabled = true
+
+# Opt-in: no `else` after an `if` branch that ends in `return` or `revert`.
+# [rules.else]
+# enabled = true
+
+# Opt-in: no comparisons of booleans to `true` or `false`.
+# [rules.bool]
+# enabled = true
+
+# Opt-in: no scaling by literals like `10**18` or `1e18` where `1 ether` would do.
+# [rules.units]
+# enabled = true
+
+# Opt-in: functions with named returns don't mix `return x;` with implicit returns.
+# [rules.returns]
+# enabled = true
+
+# Opt-in: state variables declare their visibility instead of relying on the implicit `internal`.
+# [rules.visibility]
+# enabled = true
+
+# Opt-in: constants of src contracts holding addresses, or named like these patterns, must be
+# `immutable` and set in the constructor.
+# [rules.immutable]
+# enabled = true
+# patterns = ["*CHAIN_ID*", "*CHAINID*"]
+
+# Opt-in: src files must be named after the contract, interface, or library they define. Other
+# definitions in the file must match one of `helpers`.
+# [rules.filename]
+# enabled = true
+# helpers = []
+
+# Opt-in: remappings in remappings.txt and foundry.toml must point to existing directories, must
+# not conflict, and must be used by an import.
+# [rules.remappings]
+# enabled = true
+
+# Opt-in: `pragma solidity` in src files must admit the compiler version foundry.toml pins.
+# [rules.solc]
+# enabled = true
+
+# Opt-in: `[profile.default]` in foundry.toml must set the `required` keys, no profile but the
+# `exempt_profiles` may enable a `forbidden` key, and profiles must set the `consistent` keys to the
+# value of `[profile.default]`.
+# [rules.foundry]
+# enabled = true
+# required = ["solc", "optimizer_runs"]
+# forbidden = ["ffi"]
+# exempt_profiles = []
+# consistent = ["solc", "evm_version"]
+
+# Opt-in: src files must declare at most one non-abstract contract. Libraries and interfaces next
+# to it are flagged too, unless `allow_companions` is set.
+# [rules.onecontract]
+# enabled = true
+# allow_companions = false
+
+# Opt-in: public and external functions of src contracts must have these NatSpec tags.
+# [rules.natspec]
+# enabled = true
+# tags = ["notice", "param", "return"]
+
+# Opt-in: deployable src contracts must have a `@custom:security-contact` tag.
+# [rules.contact]
+# enabled = true
+# exempt = []
+
+# Opt-in: each src file must have a test file at one of these paths, relative to {test}.
+# [rules.mirror]
+# enabled = true
+# patterns = ["{{dir}}/{{name}}.t.sol", "{{dir}}/{{name}}/"]
+
+# Suffix that script contract names must end with.
+# [rules.scriptfile]
+# suffix = "Script"
+
+# Deepest nesting of blocks, conditionals, and loops in src function bodies.
+# [rules.nesting]
+# max_depth = 4
+
+# How 256-bit integer types are written: "explicit" for `uint256`, "short" for `uint`.
+# [rules.uint]
+# style = "explicit"
+
+# Opt-in: env vars that deployment scripts may read, see `scopelint check --write`.
+# [scripts.env]
+# required = []
+
+# Findings below error severity that don't fail `scopelint check`, for gradual adoption.
+# [check]
+# max_warnings = 0
+
+# License written to missing SPDX headers by `scopelint check --fix`.
+# [fix]
+# spdx_license = "UNLICENSED"
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::file_config::FileConfig;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_template_parses_when_uncommented() {
+        let path_config = CheckPaths {
+            src_paths: vec!["./contracts".to_string()],
+            script_paths: vec!["./scripts".to_string()],
+            test_paths: vec!["./tests".to_string()],
+            ..CheckPaths::default()
+        };
+        let template = template(&path_config);
+        assert!(
+            template.contains(r#"files = ["contracts/legacy/*.sol", "tests/integration/*.sol"]"#)
+        );
+        assert!(template.contains(r#"mainnet = "scripts/deploy/mainnet""#));
+
+        // As written, nothing is configured.
+        assert!(FileConfig::from_toml(&template).is_ok());
+        // Every commented-out setting is valid once uncommented.
+        let uncommented = template
+            .lines()
+            .filter_map(|line| line.strip_prefix("# "))
+            .filter(|line| line.starts_with('[') || line.contains(" = "))
+            .join("\n");
+        let config = FileConfig::from_toml(&uncommented).unwrap();
+        assert!(config.contact_rule_enabled());
+        assert!(config.is_file_ignored(Path::new("./contracts/legacy/Old.sol")));
+    }
+}
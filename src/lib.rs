@@ -2,26 +2,71 @@
 #![warn(unreachable_pub, unused, rust_2021_compatibility)]
 #![warn(clippy::all, clippy::pedantic, clippy::cargo, clippy::nursery)]
 #![allow(clippy::multiple_crate_versions)]
+use clap::ValueEnum;
+use colored::Colorize;
 use std::error::Error;
 
+/// Writes diagnostic bundles for bug reports when scopelint crashes.
+pub mod bug_report;
+
 /// Runs validators on Solidity files.
 pub mod check;
 
 /// Parses library configuration.
 pub mod config;
 
+/// Checks the configuration and environment for setup problems.
+pub mod doctor;
+
+/// Prints the effective configuration of a run.
+pub mod effective_config;
+
 /// Path configuration from foundry.toml.
 pub mod foundry_config;
 
 /// Parses Solidity source files.
 pub mod parser;
 
+/// Generates test-fixture projects seeded with rule violations.
+pub mod fixture;
+
 /// Formats Solidity and TOML files.
 pub mod fmt;
 
+/// Classifies failed runs to pick the exit code.
+pub mod failure;
+
+/// Explains what each rule checks and why.
+pub mod explain;
+
+/// Renders the help of every command and rule, for `--help-all`.
+pub mod help;
+
+/// Installs the git pre-commit hook that checks staged files.
+pub mod hook;
+
+/// Scaffolds a starter `.scopelint` configuration.
+pub mod init;
+
+/// Converts configurations of other linters to `.scopelint`, and deprecated rule names to current
+/// ones.
+pub mod migrate;
+
+/// Lists the function selectors, event topics, and error selectors of src contracts.
+pub mod selectors;
+
+/// Prints progress and success messages, unless `--quiet` is given.
+pub mod progress;
+
 /// Generates a specification for the current project from test names.
 pub mod spec;
 
+/// Reports codebase metrics such as contract, function, and test counts and SLOC.
+pub mod stats;
+
+/// Snapshots the storage layout of src contracts.
+pub mod storage;
+
 // ===========================
 // ======== Execution ========
 // ===========================
@@ -38,11 +83,95 @@ pub fn run(opts: &config::Opts) -> Result<(), Box<dyn Error>> {
         ..Default::default()
     };
 
+    // CI logs aren't interactive, so `--ci` only colors output when asked to.
+    let ci = matches!(&opts.subcommand, config::Subcommands::Check(args) if args.ci);
+    let colorize = match opts.color {
+        config::ColorChoice::Auto if ci => false,
+        color => color.should_colorize(),
+    };
+    colored::control::set_override(colorize);
+    progress::set_quiet(opts.quiet);
+
+    // Run from the project root, so `foundry.toml`, `.scopelint`, and every relative path given on
+    // the command line are resolved relative to it.
+    if let Some(root) = &opts.root {
+        std::env::set_current_dir(root).inspect_err(|err| {
+            eprintln!("{}: Can't run in {}: {err}", "error".bold().red(), root.display());
+        })?;
+    }
+
+    // Select the Foundry profile for `CheckPaths` and any Forge command we run.
+    if let Some(profile) = &opts.profile {
+        std::env::set_var(foundry_config::PROFILE_ENV, profile);
+    }
+    foundry_config::validate_profile()?;
+
+    // Select the config file, and any extra ignore file, read by `FileConfig::load`.
+    if let Some(config) = &opts.config {
+        std::env::set_var(check::file_config::CONFIG_ENV, config);
+    }
+    if let Some(ignore_path) = &opts.ignore_path {
+        std::env::set_var(check::file_config::IGNORE_PATH_ENV, ignore_path);
+    }
+    check::file_config::FileConfig::validate_override()?;
+
+    // `--format` is accepted by every command, but each only supports some formats.
+    let (command, formats) = opts.subcommand.formats();
+    let format = match &opts.subcommand {
+        // `selectors --json` predates `--format`, and is kept as a hidden alias of it.
+        config::Subcommands::Selectors { json: true } => {
+            eprintln!("Warning: `scopelint selectors --json` is deprecated, use `--format json`");
+            opts.format.unwrap_or(config::OutputFormat::Json)
+        }
+        _ => opts.format.unwrap_or(formats[0]),
+    };
+    if !formats.contains(&format) {
+        let name = |format: &config::OutputFormat| {
+            format.to_possible_value().map(|value| value.get_name().to_string()).unwrap_or_default()
+        };
+        eprintln!(
+            "{}: `scopelint {command}` doesn't support `--format {}`, use one of: {}",
+            "error".bold().red(),
+            name(&format),
+            formats.iter().map(name).collect::<Vec<_>>().join(", ")
+        );
+        return Err("unsupported --format".into());
+    }
+
     // Execute commands.
     match &opts.subcommand {
-        config::Subcommands::Check => check::run(taplo_opts),
-        config::Subcommands::Fmt { check } => fmt::run(taplo_opts, *check),
-        config::Subcommands::Fix => check::run_fix(taplo_opts),
-        config::Subcommands::Spec { show_internal } => spec::run(*show_internal),
+        config::Subcommands::Check(args) => {
+            check::run(&taplo_opts, &config::CheckArgs { format, ..(**args).clone() })
+        }
+        config::Subcommands::Fmt { check, fail_on_change, paths } => {
+            fmt::run(&taplo_opts, *check, *fail_on_change, paths, format)
+        }
+        config::Subcommands::Fix => check::run_fix(&taplo_opts),
+        config::Subcommands::Spec { show_internal, out } => {
+            spec::run(*show_internal, format, out.as_deref())
+        }
+        config::Subcommands::Storage {
+            command: Some(config::StorageCommand::Diff { base, head }),
+            ..
+        } => storage::diff(base, head),
+        config::Subcommands::Storage { command: None, write } => storage::run(*write),
+        config::Subcommands::Selectors { .. } => {
+            selectors::run(format == config::OutputFormat::Json)
+        }
+        config::Subcommands::Stats => stats::run(format),
+        config::Subcommands::Explain { rule } => explain::run(rule.as_deref()),
+        config::Subcommands::Init { hook } => init::run(*hook),
+        config::Subcommands::Config { command: config::ConfigCommand::Show { files } } => {
+            effective_config::run(files, format)
+        }
+        config::Subcommands::Hook { command: config::HookCommand::Install { uninstall } } => {
+            hook::run(*uninstall)
+        }
+        config::Subcommands::MigrateSolhint { path } => migrate::run(path),
+        config::Subcommands::MigrateConfig { check } => migrate::rules::run(*check),
+        config::Subcommands::NewFixture { dir, rules } => fixture::run(dir, rules),
+        config::Subcommands::Baseline => check::baseline::run(),
+        config::Subcommands::Daemon { stop } => check::daemon::run(*stop),
+        config::Subcommands::Doctor => doctor::run(),
     }
 }
@@ -3,14 +3,34 @@
 #![warn(clippy::all, clippy::pedantic, clippy::cargo, clippy::nursery)]
 #![allow(clippy::multiple_crate_versions)]
 use clap::Parser;
-use dev_scopelint::config::Opts;
-use std::process;
+use dev_scopelint::{config::Opts, failure};
+use std::{panic, process};
 
 fn main() {
-    let opts = Opts::parse();
+    // `--help-all` needs no command, so it's handled before the arguments are parsed.
+    if let Some(format) = dev_scopelint::help::requested() {
+        print!("{}", dev_scopelint::help::render(format));
+        return;
+    }
+    let opts = Opts::try_parse().unwrap_or_else(|err| {
+        // Invalid arguments exit with the same code as other errors that stop a run, instead of
+        // clap's default of 2, which means unformatted files.
+        let _ = err.print();
+        process::exit(if err.use_stderr() { failure::INTERNAL_ERROR } else { failure::SUCCESS });
+    });
+    dev_scopelint::bug_report::install_panic_hook();
 
-    if let Err(_err) = dev_scopelint::run(&opts) {
+    // A panic has already been reported by the panic hook once it unwinds to here.
+    match panic::catch_unwind(|| dev_scopelint::run(&opts)) {
+        Ok(Ok(())) => {}
         // All warnings/errors have already been logged.
-        process::exit(1);
+        Ok(Err(err)) => {
+            let code = failure::exit_code(err.as_ref());
+            if code == failure::INTERNAL_ERROR {
+                dev_scopelint::bug_report::report_error(err.as_ref());
+            }
+            process::exit(code)
+        }
+        Err(_) => process::exit(failure::INTERNAL_ERROR),
     }
 }
@@ -0,0 +1,250 @@
+use crate::{check::validators::pragma_min_version, progress};
+use colored::Colorize;
+use serde_json::Value;
+use std::{error::Error, fmt::Write, fs, path::Path};
+
+/// Rewrites deprecated rule names in `.scopelint` and inline directives, for `scopelint
+/// migrate-config`.
+pub mod rules;
+
+/// solhint rules that scopelint always enforces, and the scopelint rule that does.
+const ENFORCED: &[(&str, &str)] = &[
+    ("const-name-snakecase", "constant"),
+    ("private-vars-leading-underscore", "variable"),
+    ("no-unused-import", "import"),
+    ("foundry-test-functions", "test"),
+    ("explicit-types", "uint"),
+    ("no-console", "console"),
+];
+
+/// solhint rules about layout, which `scopelint fmt` takes care of.
+const FORMATTING: &[&str] = &[
+    "max-line-length",
+    "quotes",
+    "indent",
+    "bracket-align",
+    "array-bracket-spacing",
+    "no-trailing-whitespace",
+    "space-after-comma",
+    "no-mix-tabs-and-spaces",
+];
+
+/// Converts the solhint configuration at `path`, and the `.solhintignore` next to it if any, to
+/// a `.scopelint` in the current directory, and reports how each rule was migrated.
+///
+/// # Errors
+///
+/// Returns an error if `.scopelint` already exists, or if the solhint configuration can't be read
+/// or parsed.
+pub fn run(path: &Path) -> Result<(), Box<dyn Error>> {
+    let config_path = Path::new(".scopelint");
+    if config_path.exists() {
+        eprintln!("{}: .scopelint already exists, not overwriting it", "error".bold().red());
+        return Err(".scopelint already exists".into());
+    }
+    let solhint = fs::read_to_string(path).map_err(|err| {
+        eprintln!("{}: Failed to read {}: {err}", "error".bold().red(), path.display());
+        format!("failed to read {}", path.display())
+    })?;
+    let solhint: Value = serde_json::from_str(&solhint).map_err(|err| {
+        eprintln!("{}: Failed to parse {}: {err}", "error".bold().red(), path.display());
+        format!("failed to parse {}", path.display())
+    })?;
+    let ignore_path = path.with_file_name(".solhintignore");
+    let ignore = fs::read_to_string(&ignore_path).ok();
+
+    let migration = convert(&solhint, ignore.as_deref());
+    fs::write(config_path, &migration.config)?;
+    for note in &migration.notes {
+        progress::info(format_args!("{note}"));
+    }
+    progress::info(format_args!("Wrote .scopelint from {}", path.display()));
+    Ok(())
+}
+
+/// The result of converting a solhint configuration.
+struct Migration {
+    /// The contents of the `.scopelint` to write.
+    config: String,
+    /// How each solhint setting was migrated, one line per setting.
+    notes: Vec<String>,
+}
+
+/// Converts a parsed `.solhint.json`, and the contents of `.solhintignore` if any, to the
+/// contents of a `.scopelint`.
+fn convert(solhint: &Value, ignore: Option<&str>) -> Migration {
+    let mut table = toml::Table::new();
+    let mut notes = Vec::new();
+    let mut comments = Vec::new();
+
+    if let Some(extends) = solhint.get("extends") {
+        notes.push(format!("`extends: {extends}` is not migrated, scopelint has its own defaults"));
+    }
+
+    let files: Vec<String> = ignore.map(ignore_patterns).unwrap_or_default();
+    if !files.is_empty() {
+        notes.push(format!("Migrated {} .solhintignore pattern(s) to [ignore] files", files.len()));
+        let mut ignore = toml::Table::new();
+        ignore.insert("files".into(), files.into());
+        table.insert("ignore".into(), ignore.into());
+    }
+
+    let mut rules = toml::Table::new();
+    let empty = serde_json::Map::new();
+    let solhint_rules = solhint.get("rules").and_then(Value::as_object).unwrap_or(&empty);
+    for (name, setting) in solhint_rules {
+        let (enabled, options) = severity(setting);
+        if let Some((_, rule)) = ENFORCED.iter().find(|(solhint, _)| solhint == name) {
+            if *name == "explicit-types" && enabled {
+                let style = match options.first().and_then(Value::as_str) {
+                    Some("implicit") => "short",
+                    _ => "explicit",
+                };
+                rules.insert(
+                    "uint".into(),
+                    toml::Table::from_iter([("style".into(), style.into())]).into(),
+                );
+            }
+            let note = if enabled {
+                format!("`{name}` is enforced by the `{rule}` rule")
+            } else {
+                format!(
+                    "`{name}` is off, but the `{rule}` rule is always enforced, ignore it per file in [ignore.overrides]"
+                )
+            };
+            comments.push(format!("{name} -> {rule}"));
+            notes.push(note);
+        } else if name == "compiler-version" {
+            let requirement = options.first().and_then(Value::as_str);
+            let lowest = requirement.and_then(pragma_min_version::lowest_admitted_version);
+            match lowest.filter(|_| enabled) {
+                Some(lowest) => {
+                    let min_solc = pragma_min_version::format_version(lowest);
+                    rules.insert(
+                        "pragma".into(),
+                        toml::Table::from_iter([("min_solc".into(), min_solc.clone().into())])
+                            .into(),
+                    );
+                    comments.push(format!("{name} -> pragma"));
+                    notes.push(format!(
+                        "`{name}` is enforced by the `pragma` rule, min_solc = {min_solc}"
+                    ));
+                }
+                None if enabled => {
+                    notes.push(format!("`{name}` has no version requirement, it's not migrated"));
+                }
+                None => {}
+            }
+        } else if FORMATTING.contains(&name.as_str()) {
+            comments.push(format!("{name} -> scopelint fmt"));
+            notes.push(format!(
+                "`{name}` is handled by `scopelint fmt`, configure it in foundry.toml [fmt]"
+            ));
+        } else if enabled {
+            comments.push(format!("{name} -> not migrated"));
+            notes.push(format!("`{name}` has no scopelint equivalent, it's not migrated"));
+        }
+    }
+    if !rules.is_empty() {
+        table.insert("rules".into(), rules.into());
+    }
+
+    let mut config = "# Migrated from solhint by `scopelint migrate-solhint`.\n".to_string();
+    if !comments.is_empty() {
+        config.push_str("#\n# solhint rule -> scopelint\n");
+        for comment in &comments {
+            let _ = writeln!(config, "#   {comment}");
+        }
+    }
+    if !table.is_empty() {
+        config.push('\n');
+        config.push_str(&toml::to_string(&table).unwrap_or_default());
+    }
+    Migration { config, notes }
+}
+
+/// Returns whether a solhint rule setting enables the rule, and its options. Settings are a
+/// severity such as `"warn"`, or an array of a severity followed by options.
+fn severity(setting: &Value) -> (bool, &[Value]) {
+    let (severity, options) = match setting {
+        Value::Array(values) => match values.split_first() {
+            Some((severity, options)) => (severity, options),
+            None => return (false, &[]),
+        },
+        severity => (severity, &[][..]),
+    };
+    let enabled = match severity {
+        Value::String(severity) => severity != "off",
+        Value::Number(level) => level.as_u64() != Some(0),
+        Value::Bool(enabled) => *enabled,
+        _ => false,
+    };
+    (enabled, options)
+}
+
+/// Converts the lines of a `.solhintignore` to `[ignore] files` globs. Directories, written with
+/// a trailing `/`, ignore everything in them.
+fn ignore_patterns(ignore: &str) -> Vec<String> {
+    ignore
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let line = line.trim_start_matches("./").trim_start_matches('/');
+            line.strip_suffix('/').map_or_else(|| line.to_string(), |dir| format!("{dir}/**"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::file_config::FileConfig;
+    use serde_json::json;
+
+    #[test]
+    fn test_convert() {
+        let solhint = json!({
+            "extends": "solhint:recommended",
+            "rules": {
+                "compiler-version": ["error", "^0.8.20"],
+                "const-name-snakecase": "warn",
+                "private-vars-leading-underscore": "off",
+                "explicit-types": ["warn", "implicit"],
+                "max-line-length": ["warn", 100],
+                "reentrancy": "error",
+                "no-empty-blocks": "off"
+            }
+        });
+        let migration = convert(&solhint, Some("# deps\nlib/\n./src/legacy/*.sol\n\n"));
+
+        let config = FileConfig::from_toml(&migration.config).unwrap();
+        assert_eq!(config.min_solc(), Some((0, 8, 20)));
+        assert_eq!(config.int_style().name(), "short");
+        assert!(config.is_file_ignored(Path::new("./src/legacy/Old.sol")));
+        assert!(migration.config.contains("#   reentrancy -> not migrated\n"));
+        assert!(!migration.config.contains("no-empty-blocks"));
+        assert!(migration
+            .notes
+            .iter()
+            .any(|note| note.contains("`private-vars-leading-underscore` is off")));
+        assert_eq!(migration.notes.len(), 8);
+    }
+
+    #[test]
+    fn test_ignore_patterns() {
+        assert_eq!(
+            ignore_patterns("node_modules/\n# comment\n/test/mocks/*.sol\n"),
+            ["node_modules/**", "test/mocks/*.sol"]
+        );
+    }
+
+    #[test]
+    fn test_severity() {
+        assert!(severity(&json!("warn")).0);
+        assert!(!severity(&json!("off")).0);
+        assert!(!severity(&json!(0)).0);
+        let setting = json!(["error", "^0.8.0"]);
+        assert_eq!(severity(&setting), (true, &[json!("^0.8.0")][..]));
+    }
+}
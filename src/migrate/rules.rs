@@ -0,0 +1,285 @@
+use crate::{
+    check::{
+        self,
+        comments::Comments,
+        file_config::FileConfig,
+        utils::{DeprecatedRule, DEPRECATED_RULES},
+    },
+    failure::Failure,
+    foundry_config::CheckPaths,
+    progress,
+};
+use colored::Colorize;
+use std::{error::Error, fs};
+
+/// Rewrites renamed rule names in `.scopelint` overrides and inline `scopelint: ignore-<rule>`
+/// directives to their new names, and drops the ones of removed rules, printing each change.
+///
+/// # Errors
+///
+/// Returns an error if `.scopelint` can't be read, parsed, or written, or a Solidity file can't be
+/// written. With `check`, nothing is written and [`Failure::Findings`] is returned if anything
+/// would change.
+pub fn run(check: bool) -> Result<(), Box<dyn Error>> {
+    let mut changes = 0;
+    let mut changed_files = 0;
+
+    if let Some(path) = FileConfig::path() {
+        let content = fs::read_to_string(&path).inspect_err(|err| {
+            eprintln!("{}: Failed to read {}: {err}", "error".bold().red(), path.display());
+        })?;
+        let (migrated, notes) = migrate_overrides(&content, DEPRECATED_RULES).map_err(|err| {
+            eprintln!("{}: Failed to parse {}: {err}", "error".bold().red(), path.display());
+            format!("failed to parse {}", path.display())
+        })?;
+        for note in &notes {
+            println!("{}: {note}", path.display());
+        }
+        if !notes.is_empty() {
+            changes += notes.len();
+            changed_files += 1;
+            if !check {
+                fs::write(&path, migrated)?;
+            }
+        }
+    }
+
+    let mut files = check::walk(&CheckPaths::load(), &[], 0);
+    files.sort();
+    files.dedup();
+    for file in files {
+        let Ok(src) = fs::read_to_string(&file) else { continue };
+        // Only files with directives need to be parsed.
+        if !src.contains("scopelint:") {
+            continue;
+        }
+        let Some((migrated, notes)) = migrate_directives(&src, DEPRECATED_RULES) else {
+            eprintln!("Warning: Failed to parse {}, skipping it", file.display());
+            continue;
+        };
+        for (line, note) in &notes {
+            println!("{}:{line}: {note}", file.display());
+        }
+        if !notes.is_empty() {
+            changes += notes.len();
+            changed_files += 1;
+            if !check {
+                fs::write(&file, migrated)?;
+            }
+        }
+    }
+
+    if changes == 0 {
+        progress::info(format_args!("No deprecated rule names found"));
+    } else if check {
+        eprintln!(
+            "{}: Found {changes} deprecated rule name(s) in {changed_files} file(s), run `scopelint migrate-config` to update them",
+            "error".bold().red()
+        );
+        return Err(Failure::Findings.into());
+    } else {
+        progress::info(format_args!(
+            "Migrated {changes} deprecated rule name(s) in {changed_files} file(s)"
+        ));
+    }
+    Ok(())
+}
+
+/// Rewrites the deprecated rule names in `rules` found in the `[ignore.overrides]` of the
+/// `.scopelint` `content`, keeping its formatting and comments. Returns the new content and a note
+/// for each change. Patterns left without rules are dropped.
+fn migrate_overrides(
+    content: &str,
+    rules: &[DeprecatedRule],
+) -> Result<(String, Vec<String>), String> {
+    let mut doc: toml_edit::DocumentMut = content.parse().map_err(|err| format!("{err}"))?;
+    let mut notes = Vec::new();
+    let overrides = doc
+        .get_mut("ignore")
+        .and_then(|ignore| ignore.get_mut("overrides"))
+        .and_then(toml_edit::Item::as_table_like_mut);
+    let Some(overrides) = overrides else { return Ok((content.to_string(), notes)) };
+
+    let mut emptied = Vec::new();
+    for (pattern, item) in overrides.iter_mut() {
+        let Some(names) = item.as_array_mut() else { continue };
+        for value in names.iter_mut() {
+            let Some(deprecated) =
+                value.as_str().and_then(|name| DeprecatedRule::find(rules, name))
+            else {
+                continue;
+            };
+            notes.push(format!(
+                "[ignore.overrides] \"{}\": `{}` {}",
+                pattern.get(),
+                deprecated.name,
+                deprecated.status()
+            ));
+            if let Some(replacement) = deprecated.replacement {
+                let decor = value.decor().clone();
+                *value = replacement.into();
+                *value.decor_mut() = decor;
+            }
+        }
+        let count = names.len();
+        // Renamed entries were rewritten above, so only removed ones are still found.
+        names.retain(|value| {
+            value.as_str().and_then(|name| DeprecatedRule::find(rules, name)).is_none()
+        });
+        if names.is_empty() && count > 0 {
+            emptied.push(pattern.get().to_string());
+        }
+    }
+    for pattern in emptied {
+        overrides.remove(&pattern);
+    }
+    Ok((doc.to_string(), notes))
+}
+
+/// Rewrites the `scopelint: ignore-<rule>` directives of the deprecated rules in `rules` in the
+/// Solidity source `src`. Directives of renamed rules get the new name, and those of removed rules
+/// are deleted, with their line if nothing else is on it. Returns the new source and a note for
+/// each change with its line, or `None` if `src` can't be parsed.
+fn migrate_directives(
+    src: &str,
+    rules: &[DeprecatedRule],
+) -> Option<(String, Vec<(usize, String)>)> {
+    let (_, comments) = crate::parser::parse_solidity(src, 0).ok()?;
+    let comments = Comments::new(comments, src);
+
+    // Replacements of byte ranges of `src`, in order.
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let mut notes = Vec::new();
+    for comment in comments.iter() {
+        let Some(item) = comment.contents().trim_start().strip_prefix("scopelint:") else {
+            continue;
+        };
+        let Some(rest) = item.trim().strip_prefix("ignore-") else { continue };
+        // Rule names never contain `-`, so the name ends at the scope, e.g. `-line`.
+        let name = rest.split('-').next().unwrap_or(rest);
+        let Some(deprecated) = DeprecatedRule::find(rules, name) else { continue };
+
+        let (start, end) = (comment.loc.start(), comment.loc.end());
+        notes.push((
+            src[..start].matches('\n').count() + 1,
+            format!("`{}` {}", deprecated.name, deprecated.status()),
+        ));
+        if let Some(replacement) = deprecated.replacement {
+            let text = src[start..end].replacen(
+                &format!("ignore-{name}"),
+                &format!("ignore-{replacement}"),
+                1,
+            );
+            edits.push((start, end, text));
+            continue;
+        }
+        let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = src[end..].find('\n').map_or(src.len(), |i| end + i + 1);
+        let before = &src[line_start..start];
+        if before.trim().is_empty() && src[end..line_end].trim().is_empty() {
+            edits.push((line_start, line_end, String::new()));
+        } else {
+            edits.push((line_start + before.trim_end().len(), end, String::new()));
+        }
+    }
+
+    let mut migrated = src.to_string();
+    for (start, end, text) in edits.into_iter().rev() {
+        migrated.replace_range(start..end, &text);
+    }
+    Some((migrated, notes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::utils::ValidatorKind;
+
+    const RULES: [DeprecatedRule; 2] = [
+        DeprecatedRule { name: "old", replacement: Some("error") },
+        DeprecatedRule { name: "gone", replacement: None },
+    ];
+
+    #[test]
+    fn test_migrate_overrides() {
+        let content = r#"# Project config
+[ignore.overrides]
+"src/Legacy.sol" = ["old", "src"] # legacy code
+"src/Gone.sol" = ["gone"]
+"test/**/*.sol" = [
+    "gone",
+    "test",
+]
+"#;
+        let (migrated, notes) = migrate_overrides(content, &RULES).unwrap();
+        assert_eq!(
+            migrated,
+            r#"# Project config
+[ignore.overrides]
+"src/Legacy.sol" = ["error", "src"] # legacy code
+"test/**/*.sol" = [
+    "test",
+]
+"#
+        );
+        assert_eq!(
+            notes,
+            [
+                "[ignore.overrides] \"src/Legacy.sol\": `old` was renamed to `error`",
+                "[ignore.overrides] \"src/Gone.sol\": `gone` was removed",
+                "[ignore.overrides] \"test/**/*.sol\": `gone` was removed",
+            ]
+        );
+
+        // Nothing to migrate leaves the content as is.
+        let content = "[ignore]\nfiles = [\"src/Old.sol\"]\n";
+        assert_eq!(migrate_overrides(content, &RULES).unwrap(), (content.to_string(), vec![]));
+        assert!(migrate_overrides("[ignore", &RULES).is_err());
+    }
+
+    #[test]
+    fn test_migrate_directives() {
+        let src = r"contract Legacy {
+    // scopelint: ignore-old-next-line
+    error Failed();
+    /* scopelint: ignore-gone-file */
+    uint256 x; // scopelint: ignore-gone-line
+    // scopelint: ignore-variable-line old
+    // scopelint: disable-next-line
+}
+";
+        let (migrated, notes) = migrate_directives(src, &RULES).unwrap();
+        assert_eq!(
+            migrated,
+            r"contract Legacy {
+    // scopelint: ignore-error-next-line
+    error Failed();
+    uint256 x;
+    // scopelint: ignore-variable-line old
+    // scopelint: disable-next-line
+}
+"
+        );
+        assert_eq!(
+            notes,
+            [
+                (2, "`old` was renamed to `error`".to_string()),
+                (4, "`gone` was removed".to_string()),
+                (5, "`gone` was removed".to_string()),
+            ]
+        );
+        assert!(migrate_directives("contract {", &RULES).is_none());
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(DeprecatedRule::find(&RULES, "gone"), Some(&RULES[1]));
+        assert_eq!(DeprecatedRule::find(&RULES, "error"), None);
+        // Deprecated names aren't reused, and renamed rules point at current ones.
+        for deprecated in DEPRECATED_RULES {
+            let current = |name| ValidatorKind::ALL.iter().any(|kind| kind.rule_name() == name);
+            assert!(!current(deprecated.name), "{}", deprecated.name);
+            assert!(deprecated.replacement.is_none_or(current), "{}", deprecated.name);
+        }
+    }
+}
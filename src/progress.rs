@@ -0,0 +1,29 @@
+//! Progress and success messages, e.g. "Checking formatting..." or "Wrote .scopelint".
+//!
+//! `--quiet` turns them off, so wrapper scripts only see findings, diffs, warnings, and errors.
+
+use colored::Colorize;
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Turns progress and success messages off, for `--quiet`.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether progress and success messages are turned off.
+#[must_use]
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Prints `message` as info on stderr, unless `--quiet` was given.
+pub fn info(message: fmt::Arguments<'_>) {
+    if !is_quiet() {
+        eprintln!("{}: {message}", "info".bold().green());
+    }
+}
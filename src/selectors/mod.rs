@@ -0,0 +1,205 @@
+use crate::{
+    check::{
+        self,
+        utils::{AbiTypes, FileKind, IsFileKind},
+        validators::selector_collisions::{keccak256, selector, Index},
+        Parsed,
+    },
+    foundry_config::CheckPaths,
+};
+use colored::Colorize;
+use serde_json::json;
+use solang_parser::pt::{ContractPart, ContractTy, Expression, SourceUnitPart};
+use std::{collections::BTreeMap, error::Error, ffi::OsStr, fmt::Write, path::Path};
+use walkdir::WalkDir;
+
+/// Prints the function selectors, event topics, and error selectors of every src contract, as
+/// text or as JSON.
+///
+/// # Errors
+///
+/// Returns an error if a source file cannot be parsed.
+pub fn run(json: bool) -> Result<(), Box<dyn Error>> {
+    let path_config = CheckPaths::load();
+    let mut files = Vec::new();
+
+    for src_path in path_config.src_paths.iter().filter(|path| Path::new(path).is_dir()) {
+        for dent in WalkDir::new(src_path).sort_by_file_name().into_iter().filter_map(Result::ok) {
+            if !dent.file_type().is_file() || dent.path().extension() != Some(OsStr::new("sol")) {
+                continue;
+            }
+            let mut parsed = check::parse(dent.path())?;
+            parsed.path_config = path_config.clone();
+            files.push(parsed);
+        }
+    }
+
+    let contracts = contract_selectors(&files);
+    if json {
+        println!("{}", to_json(&contracts));
+    } else {
+        for (id, selectors) in &contracts {
+            println!("{}", id.bold());
+            for (title, entries) in [
+                ("functions", &selectors.functions),
+                ("events", &selectors.events),
+                ("errors", &selectors.errors),
+            ] {
+                if entries.is_empty() {
+                    continue;
+                }
+                println!("  {title}");
+                for (signature, hash) in entries {
+                    println!("    {hash}  {signature}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The selectors of a contract's functions, events, and errors, including inherited ones, keyed
+/// by signature.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ContractSelectors {
+    /// Selectors of external and public functions, including public state variable getters.
+    pub functions: BTreeMap<String, String>,
+    /// Topics of non-anonymous events.
+    pub events: BTreeMap<String, String>,
+    /// Selectors of custom errors.
+    pub errors: BTreeMap<String, String>,
+}
+
+/// Returns the selectors of each contract and interface in the src files among `files`, keyed by
+/// `<path>:<name>`. Libraries are skipped, since their functions aren't called through the ABI.
+#[must_use]
+pub fn contract_selectors(files: &[Parsed]) -> BTreeMap<String, ContractSelectors> {
+    let index = Index::new(files);
+    let mut contracts = BTreeMap::new();
+    for parsed in files {
+        if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+            continue;
+        }
+        let file = parsed.file.display().to_string();
+        let file = file.trim_start_matches("./");
+
+        for element in &parsed.pt.0 {
+            let SourceUnitPart::ContractDefinition(c) = element else { continue };
+            let Some(name) = &c.name else { continue };
+            if matches!(c.ty, ContractTy::Library(_)) {
+                continue;
+            }
+
+            let mut selectors = ContractSelectors::default();
+            for signature in index.signatures(parsed, c) {
+                let hash = format!("{:#010x}", selector(&signature));
+                selectors.functions.insert(signature, hash);
+            }
+            for (parsed, c) in index.linearized(parsed, c) {
+                for part in &c.parts {
+                    match part {
+                        ContractPart::EventDefinition(event) if !event.anonymous => {
+                            let Some(name) = &event.name else { continue };
+                            let types = event.fields.iter().map(|field| &field.ty);
+                            let signature = signature(index.types(), parsed, &name.name, types);
+                            let hash = keccak256(signature.as_bytes());
+                            selectors.events.insert(signature, format!("0x{}", hex(&hash)));
+                        }
+                        ContractPart::ErrorDefinition(error) => {
+                            let Some(name) = &error.name else { continue };
+                            let types = error.fields.iter().map(|field| &field.ty);
+                            let signature = signature(index.types(), parsed, &name.name, types);
+                            let hash = format!("{:#010x}", selector(&signature));
+                            selectors.errors.insert(signature, hash);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            contracts.insert(format!("{file}:{}", name.name), selectors);
+        }
+    }
+    contracts
+}
+
+/// Serializes the selectors as pretty-printed JSON.
+#[must_use]
+pub fn to_json(contracts: &BTreeMap<String, ContractSelectors>) -> String {
+    let contracts: serde_json::Map<String, serde_json::Value> = contracts
+        .iter()
+        .map(|(id, selectors)| {
+            let value = json!({
+                "functions": selectors.functions,
+                "events": selectors.events,
+                "errors": selectors.errors,
+            });
+            (id.clone(), value)
+        })
+        .collect();
+    format!("{:#}", json!({ "contracts": contracts }))
+}
+
+fn signature<'a>(
+    types: &AbiTypes,
+    parsed: &Parsed,
+    name: &str,
+    params: impl Iterator<Item = &'a Expression>,
+) -> String {
+    let params = params.map(|ty| types.canonical_type(parsed, ty)).collect::<Vec<_>>();
+    format!("{name}({})", params.join(","))
+}
+
+/// Returns `bytes` as lowercase hex, without a `0x` prefix.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::parse_source;
+
+    #[test]
+    fn test_contract_selectors() {
+        let src = r"
+            interface IERC20 {
+                event Transfer(address indexed from, address indexed to, uint256 value);
+                function transfer(address to, uint256 amount) external returns (bool);
+            }
+            contract Token is IERC20 {
+                error Token_InsufficientBalance(uint256 balance);
+                event Anonymous() anonymous;
+                mapping(address => uint256) public balanceOf;
+                function transfer(address _to, uint256 _amount) external returns (bool) {}
+                function _move() internal {}
+            }
+            library Math {
+                function max(uint256 _a, uint256 _b) external pure returns (uint256) {}
+            }
+        ";
+        let files = [parse_source(Path::new("./src/Token.sol"), src).unwrap()];
+        let contracts = contract_selectors(&files);
+
+        assert_eq!(
+            contracts.keys().collect::<Vec<_>>(),
+            ["src/Token.sol:IERC20", "src/Token.sol:Token"]
+        );
+        let token = &contracts["src/Token.sol:Token"];
+        assert_eq!(token.functions["transfer(address,uint256)"], "0xa9059cbb");
+        assert_eq!(token.functions["balanceOf(address)"], "0x70a08231");
+        assert_eq!(token.functions.len(), 2);
+        assert_eq!(
+            token.events["Transfer(address,address,uint256)"],
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+        assert_eq!(token.events.len(), 1);
+        assert_eq!(token.errors.len(), 1);
+        assert!(token.errors.contains_key("Token_InsufficientBalance(uint256)"));
+
+        let json = to_json(&contracts);
+        assert!(json.contains(r#""transfer(address,uint256)": "0xa9059cbb""#), "{json}");
+    }
+}
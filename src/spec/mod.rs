@@ -5,34 +5,81 @@
 
 use crate::{
     check::utils::{Name, VisibilitySummary},
+    config::OutputFormat,
     foundry_config::CheckPaths,
+    progress,
 };
 use colored::Colorize;
+use serde_json::json;
 use solang_parser::pt::{
     ContractDefinition, ContractPart, ContractTy, FunctionDefinition, SourceUnitPart,
 };
 use std::{
     error::Error,
+    fmt::{self, Write as _},
     fs,
     path::{Path, PathBuf},
 };
 use walkdir::WalkDir;
 
-/// Generates a specification for the current project from test names.
+/// Generates a specification for the current project from test names, and prints it as a tree,
+/// as JSON, or as Markdown. With `out`, the JSON or Markdown is written to that file instead.
 /// # Errors
-/// Returns an error if the specification could not be generated from the Solidity code.
-/// # Panics
-/// Panics when a file path could not be unwrapped.
-pub fn run(show_internal: bool) -> Result<(), Box<dyn Error>> {
+/// Returns an error if the specification could not be generated from the Solidity code, or if
+/// `out` is given for the human-readable format or can't be written.
+pub fn run(
+    show_internal: bool,
+    format: OutputFormat,
+    out: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    if out.is_some() && format == OutputFormat::Human {
+        eprintln!("{}: `--out` requires `--format json` or `markdown`", "error".bold().red());
+        return Err("--out requires a machine-readable --format".into());
+    }
+    let specification = specification(show_internal).inspect_err(|err| {
+        eprintln!("{}: {err}", "error".bold().red());
+    })?;
+    let output = match format {
+        OutputFormat::Json => specification.to_json(),
+        OutputFormat::Markdown => specification.to_markdown(),
+        // Other formats are rejected before the command runs.
+        _ => specification.to_string(),
+    };
+    match out {
+        Some(path) => {
+            fs::write(path, output).inspect_err(|err| {
+                eprintln!("{}: Failed to write {}: {err}", "error".bold().red(), path.display());
+            })?;
+            progress::info(format_args!("Wrote specification to {}", path.display()));
+        }
+        None => print!("{output}"),
+    }
+    Ok(())
+}
+
+/// Builds the specification of the current project from the names of its tests, so tools such as
+/// documentation generators can build on scopelint's test name parsing.
+///
+/// Only public and external functions of src contracts are included unless `show_internal` is set,
+/// and contracts without functions are skipped, as are free functions.
+/// # Errors
+/// Returns an error if a source or test file can't be read or parsed.
+pub fn specification(show_internal: bool) -> Result<Specification, Box<dyn Error>> {
     // =================================
     // ======== Parse contracts ========
     // =================================
 
-    // First, parse all source and test files to collect the contracts and their methods. All free
-    // functions are added under a special contract called `FreeFunctions`.
+    // First, parse all source and test files to collect the contracts and their methods. Free
+    // functions are skipped, since tests are matched to functions through their contract.
     let path_config = CheckPaths::load();
-    let src_contracts = get_contracts_for_dir(&path_config.src_path, ".sol", show_internal);
-    let test_contracts = get_contracts_for_dir(&path_config.test_path, ".t.sol", show_internal);
+    let mut src_contracts: Vec<ParsedContract> = Vec::new();
+    for dir in &path_config.src_paths {
+        src_contracts.extend(get_contracts_for_dir(dir, ".sol", show_internal)?);
+    }
+    let mut test_contracts: Vec<ParsedContract> = Vec::new();
+    for dir in &path_config.test_paths {
+        test_contracts.extend(get_contracts_for_dir(dir, ".t.sol", show_internal)?);
+    }
 
     // ========================================
     // ======== Generate Specification ========
@@ -43,49 +90,219 @@ pub fn run(show_internal: bool) -> Result<(), Box<dyn Error>> {
     //   - The name of a test contract file matches the name of the contract it tests.
     //   - If the name of a test contract matches a function name in the source contract, that test
     //     contract contains that source method's tests/specification.
-    let mut protocol_spec = ProtocolSpecification::new();
+    let mut contracts = Vec::new();
     for src_contract in src_contracts {
         // Skip contracts with no functions - they have nothing to specify
         if src_contract.functions.is_empty() {
             continue;
         }
 
-        let mut contract_specification = ContractSpecification::new(src_contract.clone());
-        let src_contract_name = src_contract.contract.unwrap().name.unwrap().name;
+        let name = src_contract.contract_name();
+        let test_contracts: Vec<&ParsedContract> =
+            test_contracts.iter().filter(|tc| tc.contract_name_from_file() == name).collect();
+        let functions = src_contract
+            .functions
+            .iter()
+            .map(|src_fn| {
+                // Find the test contract with the same name
+                let test_contract = test_contracts
+                    .iter()
+                    .find(|tc| tc.contract_name().eq_ignore_ascii_case(&src_fn.name()));
+                FunctionSpecification {
+                    name: src_fn.name(),
+                    tested: test_contract.is_some(),
+                    scenarios: test_contract.map_or_else(Vec::new, |tc| scenarios(tc)),
+                }
+            })
+            .collect();
+        contracts.push(ContractSpecification { name, path: src_contract.path, functions });
+    }
+    Ok(Specification { contracts })
+}
+
+/// The specification of a project, derived from its test names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Specification {
+    /// The src contracts with at least one function, in the order they were found.
+    pub contracts: Vec<ContractSpecification>,
+}
+
+/// The specification of a src contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractSpecification {
+    /// The name of the contract.
+    pub name: String,
+    /// The path of the file the contract is defined in.
+    pub path: PathBuf,
+    /// The contract's functions, in order of appearance in the source code.
+    pub functions: Vec<FunctionSpecification>,
+}
+
+/// The specification of a src function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSpecification {
+    /// The name of the function.
+    pub name: String,
+    /// Whether a test contract named after the function exists. Untested functions have no
+    /// requirements.
+    pub tested: bool,
+    /// The requirements parsed from the tests of the function.
+    pub scenarios: Vec<Scenario>,
+}
 
-        for test_contract in &test_contracts {
-            if src_contract_name == test_contract.contract_name_from_file() {
-                contract_specification.push_test_contract(test_contract.clone());
+/// A requirement parsed from the name of a test, e.g. `Revert If: Spender Has Insufficient
+/// Balance` for `test_RevertIf_SpenderHasInsufficientBalance`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scenario {
+    /// The name of the test function.
+    pub test: String,
+    /// The path of the file the test is defined in.
+    pub path: PathBuf,
+    /// The requirement, in words.
+    pub requirement: String,
+}
+
+impl Specification {
+    /// Returns the specification as a JSON document, with contracts, their functions, and the
+    /// scenarios of each function.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let contracts: Vec<serde_json::Value> = self
+            .contracts
+            .iter()
+            .map(|contract| {
+                let functions: Vec<serde_json::Value> = contract
+                    .functions
+                    .iter()
+                    .map(|function| {
+                        let scenarios: Vec<serde_json::Value> = function
+                            .scenarios
+                            .iter()
+                            .map(|scenario| {
+                                json!({
+                                    "test": scenario.test,
+                                    "path": scenario.path.display().to_string(),
+                                    "requirement": scenario.requirement,
+                                })
+                            })
+                            .collect();
+                        json!({
+                            "name": function.name,
+                            "tested": function.tested,
+                            "scenarios": scenarios,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "name": contract.name,
+                    "path": contract.path.display().to_string(),
+                    "functions": functions,
+                })
+            })
+            .collect();
+        format!("{:#}\n", json!({ "contracts": contracts }))
+    }
+
+    /// Returns the specification as a Markdown document, e.g. to commit as `SPEC.md`, with a
+    /// section per contract and a list of requirements per function.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Specification\n");
+        for contract in &self.contracts {
+            let _ = write!(
+                markdown,
+                "\n## {}\n\nDefined in `{}`.\n",
+                contract.name,
+                contract.path.display()
+            );
+            for function in &contract.functions {
+                let _ = write!(markdown, "\n### `{}`\n\n", function.name);
+                if function.scenarios.is_empty() {
+                    markdown.push_str("_No requirements, this function has no matching tests._\n");
+                }
+                for scenario in &function.scenarios {
+                    let _ = writeln!(markdown, "- {}", scenario.requirement);
+                }
             }
         }
-        protocol_spec.push_contract_specification(contract_specification);
+        markdown
     }
-    protocol_spec.print_summary();
+}
 
-    Ok(())
+impl fmt::Display for Specification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for contract in &self.contracts {
+            let prefix = format!("\n{}", "Contract Specification:".bold());
+            writeln!(f, "{prefix} {}", contract.name.bold())?;
+
+            let num_src_fns = contract.functions.len();
+            for (i, function) in contract.functions.iter().enumerate() {
+                let is_last_fn = i == num_src_fns - 1;
+                let src_fn_name_prefix = if is_last_fn { "└── " } else { "├── " };
+
+                // Print the names of functions without tests in red to indicate to the user that
+                // they are missing tests to define their requirements.
+                if function.tested {
+                    writeln!(f, "{src_fn_name_prefix}{}", function.name)?;
+                } else {
+                    writeln!(f, "{src_fn_name_prefix}{}", function.name.red())?;
+                }
+
+                let num_scenarios = function.scenarios.len();
+                for (j, scenario) in function.scenarios.iter().enumerate() {
+                    let test_fn_name_prefix = match (is_last_fn, j == num_scenarios - 1) {
+                        (false, true) => "│   └── ",
+                        (false, false) => "│   ├── ",
+                        (true, true) => "    └── ",
+                        (true, false) => "    ├── ",
+                    };
+                    writeln!(f, "{test_fn_name_prefix} {}", scenario.requirement)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Parses the requirements from the names of the tests in `test_contract`.
+fn scenarios(test_contract: &ParsedContract) -> Vec<Scenario> {
+    test_contract
+        .functions
+        .iter()
+        .filter(|f| f.is_public_or_external() && f.name().starts_with("test"))
+        .filter_map(|f| {
+            // Remove everything before, and including, the first underscore. If there were no
+            // underscores present this is an invalid test name, so it's skipped. The user should
+            // use `scopelint check` to make sure all test names are valid.
+            let test = f.name();
+            let requirement = trimmed_fn_name_to_requirement(test.split_once('_')?.1);
+            Some(Scenario {
+                requirement: requirement.trim_start().to_string(),
+                path: test_contract.path.clone(),
+                test,
+            })
+        })
+        .collect()
 }
 
 #[derive(Clone)]
 struct ParsedContract {
     // Path to the contract file.
     path: PathBuf,
-    // The contract item, or `None` for free functions.
-    contract: Option<ContractDefinition>,
+    // The contract item.
+    contract: ContractDefinition,
     // All functions present in the contract.
     functions: Vec<FunctionDefinition>,
 }
 
 impl ParsedContract {
-    fn new(path: PathBuf, contract: Option<ContractDefinition>, show_internal: bool) -> Self {
-        let functions =
-            contract.as_ref().map_or(Vec::new(), |c| get_functions_from_contract(c, show_internal));
+    fn new(path: PathBuf, contract: ContractDefinition, show_internal: bool) -> Self {
+        let functions = get_functions_from_contract(&contract, show_internal);
         Self { path, contract, functions }
     }
 
     fn contract_name(&self) -> String {
-        self.contract
-            .as_ref()
-            .map_or_else(|| "FreeFunctions".to_string(), |c| c.name.as_ref().unwrap().name.clone())
+        self.contract.name.as_ref().map_or_else(String::new, |name| name.name.clone())
     }
 
     fn contract_name_from_file(&self) -> String {
@@ -101,107 +318,6 @@ impl ParsedContract {
     }
 }
 
-struct ContractSpecification {
-    src_contract: ParsedContract,
-    test_contracts: Vec<ParsedContract>,
-}
-
-impl ContractSpecification {
-    const fn new(src_contract: ParsedContract) -> Self {
-        Self { src_contract, test_contracts: Vec::new() }
-    }
-
-    fn push_test_contract(&mut self, test_contract: ParsedContract) {
-        self.test_contracts.push(test_contract);
-    }
-
-    fn print_specification(&self) {
-        let prefix = format!("\n{}", "Contract Specification:".bold());
-        let contract_name = format!("{}", self.src_contract.contract_name().bold());
-        println!("{prefix} {contract_name}");
-
-        // Vectors of functions are already sorted by their order of appearance in the source code,
-        // which is the order we want to print in.
-        let src_fns = &self.src_contract.functions;
-        let num_src_fns = src_fns.len();
-
-        for (i, src_fn) in src_fns.iter().enumerate() {
-            let src_fn_name_prefix = if i == num_src_fns - 1 { "└── " } else { "├── " };
-
-            self.test_contracts
-                .iter()
-                .find(|tc| {
-                    // Find the test contract with the same name
-                    tc.contract_name().eq_ignore_ascii_case(&src_fn.name())
-                })
-                .map_or_else(
-                    // If there's no matching test contract, print the name of the source function
-                    // in red to indicate to the user that it is missing tests
-                    // to define it's requirements. Otherwise, parse the test
-                    // names into a specification and print it.
-                    || println!("{src_fn_name_prefix}{}", src_fn.name().red()),
-                    |test_contract| {
-                        println!("{src_fn_name_prefix}{}", src_fn.name());
-
-                        let test_fns = &test_contract.functions;
-                        let num_test_fns = test_fns.len();
-                        for (j, f) in test_fns.iter().enumerate() {
-                            let is_test_fn =
-                                f.is_public_or_external() && f.name().starts_with("test");
-                            if !is_test_fn {
-                                continue;
-                            }
-
-                            let test_fn_name_prefix =
-                                if i < num_src_fns - 1 && j == num_test_fns - 1 {
-                                    "│   └── "
-                                } else if i < num_src_fns - 1 {
-                                    "│   ├── "
-                                } else if j == num_test_fns - 1 {
-                                    "    └── "
-                                } else {
-                                    "    ├── "
-                                };
-
-                            // Remove everything before, and including, the first underscore.
-                            let fn_name = f.name();
-                            let trimmed_fn_name_opt = fn_name.split_once('_').map(|x| x.1);
-
-                            // If there were no underscores present this is an invalid test name, so
-                            // we print nothing. The user should use `scopelint check` to make sure
-                            // all test names are valid. Otherwise, parse and print the
-                            // requirement.
-                            if let Some(trimmed_fn_name) = trimmed_fn_name_opt {
-                                let requirement = trimmed_fn_name_to_requirement(trimmed_fn_name);
-                                println!("{test_fn_name_prefix}{requirement}");
-                            }
-                        }
-                    },
-                );
-        }
-    }
-}
-
-struct ProtocolSpecification {
-    contract_specifications: Vec<ContractSpecification>,
-}
-
-impl ProtocolSpecification {
-    const fn new() -> Self {
-        Self { contract_specifications: Vec::new() }
-    }
-
-    fn push_contract_specification(&mut self, contract_specification: ContractSpecification) {
-        self.contract_specifications.push(contract_specification);
-    }
-
-    fn print_summary(&self) {
-        for contract_specification in &self.contract_specifications {
-            contract_specification.print_specification();
-        }
-    }
-}
-
 // ==================================
 // ======== Helper functions ========
 // ==================================
@@ -210,7 +326,7 @@ fn get_contracts_for_dir<P: AsRef<Path>>(
     dir: P,
     extension: &str,
     show_internal: bool,
-) -> Vec<ParsedContract> {
+) -> Result<Vec<ParsedContract>, Box<dyn Error>> {
     let mut contracts: Vec<ParsedContract> = Vec::new();
     for result in WalkDir::new(dir) {
         let dent = match result {
@@ -226,38 +342,30 @@ fn get_contracts_for_dir<P: AsRef<Path>>(
             continue;
         }
 
-        let new_contracts = parse_contracts(file, show_internal);
+        let new_contracts = parse_contracts(file, show_internal)?;
         contracts.extend(new_contracts);
     }
-    contracts
+    Ok(contracts)
 }
 
-fn parse_contracts(file: &Path, show_internal: bool) -> Vec<ParsedContract> {
-    let content = fs::read_to_string(file).unwrap();
-    let (pt, _comments) = crate::parser::parse_solidity(&content, 0).expect("Parsing failed");
+fn parse_contracts(file: &Path, show_internal: bool) -> Result<Vec<ParsedContract>, String> {
+    let content = fs::read_to_string(file)
+        .map_err(|err| format!("Failed to read {}: {err}", file.display()))?;
+    let (pt, _comments) = crate::parser::parse_solidity(&content, 0)
+        .map_err(|_| format!("Failed to parse {}", file.display()))?;
     let mut contracts: Vec<ParsedContract> = Vec::new();
 
+    // Free functions, which don't belong to any contract, are skipped, as are interfaces.
     for element in &pt.0 {
-        match element {
-            SourceUnitPart::FunctionDefinition(_f) => {
-                // These are free functions not belonging to any contract.
-                todo!("Free functions not yet supported.");
+        if let SourceUnitPart::ContractDefinition(c) = element {
+            if let ContractTy::Interface(_) = c.ty {
+                continue;
             }
-            SourceUnitPart::ContractDefinition(c) => {
-                if let ContractTy::Interface(_) = c.ty {
-                    continue;
-                }
 
-                contracts.push(ParsedContract::new(
-                    file.to_path_buf(),
-                    Some(*c.clone()),
-                    show_internal,
-                ));
-            }
-            _ => (),
+            contracts.push(ParsedContract::new(file.to_path_buf(), *c.clone(), show_internal));
         }
     }
-    contracts
+    Ok(contracts)
 }
 
 fn get_functions_from_contract(
@@ -0,0 +1,420 @@
+use crate::{
+    check::{
+        self,
+        file_config::FileConfig,
+        utils::{FileKind, IsFileKind},
+        Parsed,
+    },
+    config::OutputFormat,
+    foundry_config::CheckPaths,
+};
+use colored::Colorize;
+use serde_json::json;
+use solang_parser::{
+    lexer::Lexer,
+    pt::{
+        ContractPart, ContractTy, FunctionAttribute, FunctionDefinition, FunctionTy,
+        SourceUnitPart, Visibility,
+    },
+};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    ffi::OsStr,
+    fmt::{self, Write as _},
+    path::Path,
+};
+use walkdir::WalkDir;
+
+/// The kinds of files, by the directory they are in.
+const FILE_KINDS: [&str; 3] = ["src", "test", "script"];
+
+/// The kinds of contracts.
+const CONTRACT_KINDS: [&str; 4] = ["contract", "abstract", "interface", "library"];
+
+/// The visibilities of functions.
+const VISIBILITIES: [&str; 4] = ["external", "public", "internal", "private"];
+
+/// The prefixes of test names before the first `_`, plus `other` for tests that don't follow the
+/// convention.
+const TEST_PREFIXES: [&str; 6] =
+    ["test", "testFuzz", "testFork", "testForkFuzz", "invariant", "other"];
+
+/// Prints metrics of the Solidity files of the current project, e.g. for audit scoping reports, as
+/// text, JSON, or Markdown. Files ignored in `.scopelint` are skipped.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be parsed.
+pub fn run(format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    let path_config = CheckPaths::load();
+    let file_config = FileConfig::load();
+    let mut files = Vec::new();
+    for path in path_config.all().into_iter().filter(|path| Path::new(path).is_dir()) {
+        for dent in WalkDir::new(path).sort_by_file_name().into_iter().filter_map(Result::ok) {
+            if !dent.file_type().is_file() ||
+                dent.path().extension() != Some(OsStr::new("sol")) ||
+                file_config.is_file_ignored(dent.path())
+            {
+                continue;
+            }
+            let mut parsed = check::parse(dent.path()).inspect_err(|err| {
+                eprintln!("{}: {}: {err}", "error".bold().red(), dent.path().display());
+            })?;
+            parsed.path_config = path_config.clone();
+            files.push(parsed);
+        }
+    }
+
+    let stats = Stats::new(&files);
+    match format {
+        OutputFormat::Json => println!("{}", stats.to_json()),
+        OutputFormat::Markdown => print!("{}", stats.to_markdown()),
+        // Other formats are rejected before the command runs.
+        _ => print!("{stats}"),
+    }
+    Ok(())
+}
+
+/// Counts by key, with their title, the header of their keys, and the keys in order.
+type Counts<'a> =
+    (&'static str, &'static str, &'static [&'static str], &'a BTreeMap<&'static str, usize>);
+
+/// Metrics of the Solidity files of a project. Contracts, functions, errors, and events are only
+/// counted in src files, and tests in test files.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of files of each kind in [`FILE_KINDS`].
+    pub files: BTreeMap<&'static str, usize>,
+    /// The number of contracts of each kind in [`CONTRACT_KINDS`].
+    pub contracts: BTreeMap<&'static str, usize>,
+    /// The number of functions, including free functions, of each visibility in
+    /// [`VISIBILITIES`]. Constructors, modifiers, `fallback`, and `receive` aren't counted.
+    pub functions: BTreeMap<&'static str, usize>,
+    /// The number of tests with each prefix in [`TEST_PREFIXES`].
+    pub tests: BTreeMap<&'static str, usize>,
+    /// The number of errors and events each contract declares, keyed by `<path>:<name>`.
+    pub declarations: BTreeMap<String, Declarations>,
+    /// The source lines of code, i.e. lines with anything but whitespace and comments, in each
+    /// directory.
+    pub sloc: BTreeMap<String, usize>,
+}
+
+/// The number of errors and events a contract declares.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Declarations {
+    /// The number of custom errors.
+    pub errors: usize,
+    /// The number of events.
+    pub events: usize,
+}
+
+impl Stats {
+    /// Collects the metrics of `files`.
+    #[must_use]
+    pub fn new(files: &[Parsed]) -> Self {
+        let zeros = |keys: &[&'static str]| keys.iter().map(|key| (*key, 0)).collect();
+        let mut stats = Self {
+            files: zeros(&FILE_KINDS),
+            contracts: zeros(&CONTRACT_KINDS),
+            functions: zeros(&VISIBILITIES),
+            tests: zeros(&TEST_PREFIXES),
+            declarations: BTreeMap::new(),
+            sloc: BTreeMap::new(),
+        };
+        for parsed in files {
+            let file = parsed.file.display().to_string();
+            let file = file.trim_start_matches("./");
+            let dir =
+                Path::new(file).parent().map_or(String::new(), |dir| dir.display().to_string());
+            *stats.sloc.entry(dir).or_default() += sloc(&parsed.src);
+
+            let paths = &parsed.path_config;
+            let is_src = parsed.file.is_file_kind(FileKind::Src, paths);
+            let is_script = parsed.file.is_file_kind(FileKind::Script, paths) ||
+                parsed.file.is_file_kind(FileKind::ScriptHelper, paths);
+            let kind = if is_src {
+                "src"
+            } else if is_script {
+                "script"
+            } else {
+                "test"
+            };
+            *stats.files.entry(kind).or_default() += 1;
+            if is_src {
+                stats.add_src(file, parsed);
+            } else if parsed.file.is_file_kind(FileKind::Test, paths) {
+                stats.add_tests(parsed);
+            }
+        }
+        stats
+    }
+
+    // Counts the contracts, functions, errors, and events of the src file `parsed`.
+    fn add_src(&mut self, file: &str, parsed: &Parsed) {
+        for element in &parsed.pt.0 {
+            match element {
+                SourceUnitPart::FunctionDefinition(f) => self.add_function(f, "internal"),
+                SourceUnitPart::ContractDefinition(c) => {
+                    let (kind, default_visibility) = match c.ty {
+                        ContractTy::Abstract(_) => ("abstract", "internal"),
+                        ContractTy::Contract(_) => ("contract", "internal"),
+                        ContractTy::Interface(_) => ("interface", "external"),
+                        ContractTy::Library(_) => ("library", "internal"),
+                    };
+                    *self.contracts.entry(kind).or_default() += 1;
+                    let mut declarations = Declarations::default();
+                    for part in &c.parts {
+                        match part {
+                            ContractPart::FunctionDefinition(f) => {
+                                self.add_function(f, default_visibility);
+                            }
+                            ContractPart::ErrorDefinition(_) => declarations.errors += 1,
+                            ContractPart::EventDefinition(_) => declarations.events += 1,
+                            _ => {}
+                        }
+                    }
+                    if let Some(name) = &c.name {
+                        self.declarations.insert(format!("{file}:{}", name.name), declarations);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Counts `f` under its visibility, or `default_visibility` if it has none.
+    fn add_function(&mut self, f: &FunctionDefinition, default_visibility: &'static str) {
+        if f.ty != FunctionTy::Function {
+            return;
+        }
+        let visibility = f
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                FunctionAttribute::Visibility(Visibility::External(_)) => Some("external"),
+                FunctionAttribute::Visibility(Visibility::Public(_)) => Some("public"),
+                FunctionAttribute::Visibility(Visibility::Internal(_)) => Some("internal"),
+                FunctionAttribute::Visibility(Visibility::Private(_)) => Some("private"),
+                _ => None,
+            })
+            .unwrap_or(default_visibility);
+        *self.functions.entry(visibility).or_default() += 1;
+    }
+
+    // Counts the tests of the test file `parsed` by prefix: public and external functions whose
+    // names start with `test` or `invariant`.
+    fn add_tests(&mut self, parsed: &Parsed) {
+        for element in &parsed.pt.0 {
+            let SourceUnitPart::ContractDefinition(c) = element else { continue };
+            for part in &c.parts {
+                let ContractPart::FunctionDefinition(f) = part else { continue };
+                let Some(name) = f.name.as_ref().map(|name| name.name.as_str()) else { continue };
+                let is_public = f.attributes.iter().any(|attribute| {
+                    matches!(
+                        attribute,
+                        FunctionAttribute::Visibility(
+                            Visibility::External(_) | Visibility::Public(_)
+                        )
+                    )
+                });
+                if !is_public || !(name.starts_with("test") || name.starts_with("invariant")) {
+                    continue;
+                }
+                let prefix = name.split_once('_').map(|(prefix, _)| prefix);
+                let prefix = TEST_PREFIXES
+                    .into_iter()
+                    .find(|known| prefix == Some(*known) && *known != "other")
+                    .unwrap_or("other");
+                *self.tests.entry(prefix).or_default() += 1;
+            }
+        }
+    }
+
+    /// Returns the source lines of code of all directories.
+    #[must_use]
+    pub fn total_sloc(&self) -> usize {
+        self.sloc.values().sum()
+    }
+
+    /// Serializes the metrics as pretty-printed JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let declarations: serde_json::Map<String, serde_json::Value> = self
+            .declarations
+            .iter()
+            .map(|(contract, declarations)| {
+                let value = json!({ "errors": declarations.errors, "events": declarations.events });
+                (contract.clone(), value)
+            })
+            .collect();
+        let stats = json!({
+            "files": self.files,
+            "contracts": self.contracts,
+            "functions": self.functions,
+            "tests": self.tests,
+            "declarations": declarations,
+            "sloc": { "directories": self.sloc, "total": self.total_sloc() },
+        });
+        format!("{stats:#}")
+    }
+
+    /// Renders the metrics as Markdown tables, e.g. for an audit scoping report.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Codebase metrics\n");
+        for (title, header, keys, counts) in self.counts() {
+            let _ = write!(markdown, "\n## {title}\n\n| {header} | Count |\n| --- | --- |\n");
+            for key in keys {
+                let _ = writeln!(markdown, "| {key} | {} |", counts[key]);
+            }
+        }
+        markdown.push_str(
+            "\n## Errors and events\n\n| Contract | Errors | Events |\n| --- | --- | --- |\n",
+        );
+        for (contract, declarations) in &self.declarations {
+            let _ = writeln!(
+                markdown,
+                "| `{contract}` | {} | {} |",
+                declarations.errors, declarations.events
+            );
+        }
+        markdown.push_str("\n## SLOC\n\n| Directory | SLOC |\n| --- | --- |\n");
+        for (dir, sloc) in &self.sloc {
+            let _ = writeln!(markdown, "| `{dir}` | {sloc} |");
+        }
+        let _ = writeln!(markdown, "| **Total** | {} |", self.total_sloc());
+        markdown
+    }
+
+    // The counts by key, with their title, the header of their keys, and the keys in order.
+    const fn counts(&self) -> [Counts<'_>; 4] {
+        [
+            ("Files", "Kind", &FILE_KINDS, &self.files),
+            ("Contracts", "Kind", &CONTRACT_KINDS, &self.contracts),
+            ("Functions", "Visibility", &VISIBILITIES, &self.functions),
+            ("Tests", "Prefix", &TEST_PREFIXES, &self.tests),
+        ]
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (title, _, keys, counts) in self.counts() {
+            let counts: Vec<String> =
+                keys.iter().map(|key| format!("{} {key}", counts[key])).collect();
+            writeln!(f, "{} {}", format!("{title}:").bold(), counts.join(", "))?;
+        }
+        writeln!(f, "{}", "Errors and events:".bold())?;
+        let width = self.declarations.keys().map(String::len).max().unwrap_or_default();
+        for (contract, declarations) in &self.declarations {
+            writeln!(
+                f,
+                "  {contract:width$}  {} error(s), {} event(s)",
+                declarations.errors, declarations.events
+            )?;
+        }
+        writeln!(f, "{}", "SLOC:".bold())?;
+        let width = self.sloc.keys().map(String::len).max().unwrap_or_default().max("total".len());
+        for (dir, sloc) in &self.sloc {
+            writeln!(f, "  {dir:width$}  {sloc}")?;
+        }
+        writeln!(f, "  {:width$}  {}", "total", self.total_sloc())
+    }
+}
+
+/// Returns the number of lines of `src` with anything but whitespace and comments.
+fn sloc(src: &str) -> usize {
+    let line_starts: Vec<usize> =
+        std::iter::once(0).chain(src.match_indices('\n').map(|(idx, _)| idx + 1)).collect();
+    let line = |offset: usize| line_starts.partition_point(|start| *start <= offset) - 1;
+    let mut has_code = vec![false; line_starts.len()];
+    let (mut comments, mut errors) = (Vec::new(), Vec::new());
+    for (start, _, end) in Lexer::new(src, 0, &mut comments, &mut errors) {
+        // A token, e.g. a string, may span several lines.
+        for has_code in &mut has_code[line(start)..=line(end.saturating_sub(1).max(start))] {
+            *has_code = true;
+        }
+    }
+    has_code.into_iter().filter(|has_code| *has_code).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::parse_source;
+
+    #[test]
+    fn test_stats() {
+        let src = r"// SPDX-License-Identifier: MIT
+pragma solidity 0.8.20;
+
+/// A counter.
+contract Counter {
+    error Counter_Overflow();
+    event Incremented(uint256 value);
+    event Reset();
+
+    /* Multi-line
+       comment */
+    function increment() external {}
+    function number() public view returns (uint256) {}
+    function _bump() internal {}
+}
+
+interface ICounter {
+    function increment() external;
+}
+
+abstract contract Base {
+    function _hook() private {}
+}
+
+library Math {}
+
+function free() pure {}
+";
+        let test = r"contract CounterTest {
+    function setUp() public {}
+    function test_Increment() public {}
+    function testFuzz_Increment(uint256 x) external {}
+    function testFork_RevertIf_Paused() public {}
+    function invariant_TotalSupply() public {}
+    function testIncrementBadName() public {}
+    function test_helper() internal {}
+}
+";
+        let files = [
+            parse_source(Path::new("./src/Counter.sol"), src).unwrap(),
+            parse_source(Path::new("./test/Counter.t.sol"), test).unwrap(),
+        ];
+        let stats = Stats::new(&files);
+
+        assert_eq!(stats.files["src"], 1);
+        assert_eq!(stats.files["test"], 1);
+        assert_eq!(stats.files["script"], 0);
+        let contracts: Vec<usize> =
+            CONTRACT_KINDS.iter().map(|kind| stats.contracts[kind]).collect();
+        assert_eq!(contracts, [1, 1, 1, 1]);
+        let functions: Vec<usize> = VISIBILITIES.iter().map(|kind| stats.functions[kind]).collect();
+        assert_eq!(functions, [2, 1, 2, 1]);
+        let tests: Vec<usize> = TEST_PREFIXES.iter().map(|prefix| stats.tests[prefix]).collect();
+        assert_eq!(tests, [1, 1, 1, 0, 1, 1]);
+        assert_eq!(
+            stats.declarations["src/Counter.sol:Counter"],
+            Declarations { errors: 1, events: 2 }
+        );
+        assert_eq!(stats.declarations["src/Counter.sol:Math"], Declarations::default());
+        assert_eq!(stats.sloc["src"], 17);
+        assert_eq!(stats.sloc["test"], 9);
+        assert_eq!(stats.total_sloc(), 26);
+    }
+
+    #[test]
+    fn test_sloc() {
+        assert_eq!(sloc(""), 0);
+        assert_eq!(sloc("// only a comment\n\n"), 0);
+        assert_eq!(sloc("contract C {\n  /* a\n  b */ uint256 x;\n}"), 3);
+        assert_eq!(sloc("uint256 constant X = 1; /* a\n b */\n"), 1);
+    }
+}
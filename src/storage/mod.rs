@@ -0,0 +1,372 @@
+use crate::{
+    check::{
+        self,
+        utils::{FileKind, IsFileKind},
+        Parsed,
+    },
+    failure::Failure,
+    foundry_config::CheckPaths,
+    progress,
+};
+use colored::Colorize;
+use serde_json::json;
+use solang_parser::{
+    helpers::CodeLocation,
+    pt::{ContractTy, Loc, SourceUnitPart, VariableAttribute},
+};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    ffi::OsStr,
+    fs,
+    path::Path,
+    process::{Command, Output},
+};
+use walkdir::WalkDir;
+
+/// The file, relative to the project root, that the storage layout snapshot is written to.
+pub const SNAPSHOT_FILE: &str = ".scopelint-storage.json";
+
+/// Prints the storage layout of every src contract, or writes it to the snapshot file.
+///
+/// # Errors
+///
+/// Returns an error if a source file cannot be parsed or the snapshot cannot be written.
+pub fn run(write: bool) -> Result<(), Box<dyn Error>> {
+    let path_config = CheckPaths::load();
+    let mut snapshot = Snapshot::default();
+
+    for src_path in path_config.src_paths.iter().filter(|path| Path::new(path).is_dir()) {
+        for dent in WalkDir::new(src_path).into_iter().filter_map(Result::ok) {
+            if !dent.file_type().is_file() || dent.path().extension() != Some(OsStr::new("sol")) {
+                continue;
+            }
+            let mut parsed = check::parse(dent.path())?;
+            parsed.path_config = path_config.clone();
+            snapshot.add_layouts(&parsed);
+        }
+    }
+
+    if write {
+        fs::write(SNAPSHOT_FILE, snapshot.to_json())?;
+        progress::info(format_args!(
+            "Wrote storage layout of {} contract(s) to {SNAPSHOT_FILE}",
+            snapshot.contracts.len()
+        ));
+    } else {
+        print!("{snapshot}");
+    }
+    Ok(())
+}
+
+/// Compares the storage layouts of src contracts at two git refs and prints whether `head` only
+/// appends to the layouts at `base`.
+///
+/// # Errors
+///
+/// Returns an error if a ref cannot be read, or if any contract's layout changed incompatibly, so
+/// the command can gate upgrade PRs in CI.
+pub fn diff(base: &str, head: &str) -> Result<(), Box<dyn Error>> {
+    let path_config = CheckPaths::load();
+    let before = snapshot_at(base, &path_config)?;
+    let after = snapshot_at(head, &path_config)?;
+
+    let mut breaking = 0;
+    for (id, recorded) in &before.contracts {
+        let Some(current) = after.contracts.get(id) else {
+            println!("{} {id}: contract removed, not compared", "skipped".yellow());
+            continue;
+        };
+        if let Some((_, text)) = first_incompatibility(current, recorded) {
+            breaking += 1;
+            println!("{} {id}: {text}", "breaking".red());
+        } else if current.len() > recorded.len() {
+            let appended = current[recorded.len()..]
+                .iter()
+                .map(|variable| format!("{} {}", variable.ty, variable.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("{} {id}: appended {appended}", "append-only".green());
+        }
+    }
+    for id in after.contracts.keys().filter(|id| !before.contracts.contains_key(*id)) {
+        println!("{} {id}: contract added", "new".green());
+    }
+
+    if breaking > 0 {
+        eprintln!(
+            "{}: Storage layout changes between {base} and {head} are breaking in {breaking} contract(s)",
+            "error".bold().red()
+        );
+        return Err(Failure::Findings.into());
+    }
+    progress::info(format_args!(
+        "Storage layout changes between {base} and {head} are append-only"
+    ));
+    Ok(())
+}
+
+/// Returns the storage layouts of the src contracts committed at `git_ref`.
+fn snapshot_at(git_ref: &str, path_config: &CheckPaths) -> Result<Snapshot, Box<dyn Error>> {
+    // Paths are listed relative to the current directory, like the configured src paths.
+    let mut args = vec!["ls-tree", "-r", "-z", "--name-only", git_ref, "--"];
+    args.extend(path_config.src_paths.iter().map(String::as_str));
+    let listing = git(&args)?;
+    let mut snapshot = Snapshot::default();
+    for file in String::from_utf8_lossy(&listing.stdout).split('\0') {
+        if Path::new(file).extension() != Some(OsStr::new("sol")) {
+            continue;
+        }
+        let src = git(&["show", &format!("{git_ref}:./{file}")])?;
+        let mut parsed =
+            check::parse_source(&Path::new(".").join(file), &String::from_utf8_lossy(&src.stdout))?;
+        parsed.path_config = path_config.clone();
+        snapshot.add_layouts(&parsed);
+    }
+    Ok(snapshot)
+}
+
+/// Runs a git command, returning an error with git's stderr if it fails.
+fn git(args: &[&str]) -> Result<Output, Box<dyn Error>> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}: git {}: {}", "error".bold().red(), args.join(" "), stderr.trim());
+        return Err("git command failed".into());
+    }
+    Ok(output)
+}
+
+/// A state variable that occupies storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageVariable {
+    /// The variable name.
+    pub name: String,
+    /// The declared type, as written in the source with whitespace collapsed.
+    pub ty: String,
+}
+
+/// The declared storage variables of a single contract, in declaration order.
+pub struct ContractLayout {
+    /// Identifies the contract as `<path>:<name>`, e.g. `src/Counter.sol:Counter`.
+    pub id: String,
+    /// Location of the contract definition.
+    pub loc: Loc,
+    /// The storage variables, with the location of each declaration.
+    pub variables: Vec<(StorageVariable, Loc)>,
+}
+
+/// The storage layouts of all src contracts, keyed by contract id.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Storage variables of each contract, in declaration order.
+    pub contracts: BTreeMap<String, Vec<StorageVariable>>,
+}
+
+impl Snapshot {
+    /// Loads the snapshot from [`SNAPSHOT_FILE`] in the current directory, returning `None` if
+    /// it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load() -> Result<Option<Self>, String> {
+        let path = Path::new(SNAPSHOT_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {SNAPSHOT_FILE}: {e}"))?;
+        Self::from_json(&content).map(Some)
+    }
+
+    /// Parses a snapshot from its JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or doesn't match the snapshot format.
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let json: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| format!("Invalid storage snapshot: {e}"))?;
+        let contracts = json
+            .get("contracts")
+            .and_then(serde_json::Value::as_object)
+            .ok_or_else(|| "Storage snapshot must have a `contracts` object".to_string())?;
+
+        let mut snapshot = Self::default();
+        for (id, variables) in contracts {
+            let variables = variables
+                .as_array()
+                .ok_or_else(|| format!("Storage layout of '{id}' must be an array"))?
+                .iter()
+                .map(|variable| {
+                    let field = |key: &str| {
+                        variable.get(key).and_then(serde_json::Value::as_str).map(str::to_string)
+                    };
+                    Some(StorageVariable { name: field("name")?, ty: field("type")? })
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| {
+                    format!("Storage variables of '{id}' must have a `name` and `type`")
+                })?;
+            snapshot.contracts.insert(id.clone(), variables);
+        }
+        Ok(snapshot)
+    }
+
+    /// Records the storage layout of each contract in a src file.
+    pub fn add_layouts(&mut self, parsed: &Parsed) {
+        for layout in contract_layouts(parsed) {
+            self.contracts.insert(
+                layout.id,
+                layout.variables.into_iter().map(|(variable, _)| variable).collect(),
+            );
+        }
+    }
+
+    /// Serializes the snapshot as pretty-printed JSON, with contracts sorted by id so the file
+    /// diffs cleanly.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let contracts: serde_json::Map<String, serde_json::Value> = self
+            .contracts
+            .iter()
+            .map(|(id, variables)| {
+                let variables = variables
+                    .iter()
+                    .map(|variable| json!({ "name": variable.name, "type": variable.ty }))
+                    .collect();
+                (id.clone(), serde_json::Value::Array(variables))
+            })
+            .collect();
+        format!("{:#}\n", json!({ "contracts": contracts }))
+    }
+}
+
+impl std::fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (id, variables) in &self.contracts {
+            writeln!(f, "{}", id.bold())?;
+            for (i, variable) in variables.iter().enumerate() {
+                writeln!(f, "  {i}: {} {}", variable.ty, variable.name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the storage layout of each contract in a src file. Interfaces and libraries have no
+/// storage and are skipped, as are constants and immutables.
+#[must_use]
+pub fn contract_layouts(parsed: &Parsed) -> Vec<ContractLayout> {
+    if !parsed.file.is_file_kind(FileKind::Src, &parsed.path_config) {
+        return Vec::new();
+    }
+    let file = parsed.file.display().to_string();
+    let file = file.trim_start_matches("./");
+
+    let mut layouts = Vec::new();
+    for element in &parsed.pt.0 {
+        let SourceUnitPart::ContractDefinition(c) = element else { continue };
+        if matches!(c.ty, ContractTy::Interface(_) | ContractTy::Library(_)) {
+            continue;
+        }
+        let Some(name) = &c.name else { continue };
+
+        let variables = c
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                solang_parser::pt::ContractPart::VariableDefinition(v) => Some(v),
+                _ => None,
+            })
+            .filter(|v| {
+                !v.attrs.iter().any(|a| {
+                    matches!(a, VariableAttribute::Constant(_) | VariableAttribute::Immutable(_))
+                })
+            })
+            .filter_map(|v| {
+                let name = v.name.as_ref()?.name.clone();
+                let ty_loc = v.ty.loc();
+                let ty = parsed.src[ty_loc.start()..ty_loc.end()]
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                Some((StorageVariable { name, ty }, v.loc))
+            })
+            .collect();
+
+        layouts.push(ContractLayout { id: format!("{file}:{}", name.name), loc: c.loc, variables });
+    }
+    layouts
+}
+
+/// Returns the position in `current` and a description of the first `recorded` variable that is no
+/// longer at the same position with the same type. Renaming a variable in place is compatible.
+///
+/// The position is past the end of `current` if a variable was removed from the end of the layout.
+#[must_use]
+pub fn first_incompatibility(
+    current: &[StorageVariable],
+    recorded: &[StorageVariable],
+) -> Option<(usize, String)> {
+    for (i, old) in recorded.iter().enumerate() {
+        let Some(new) = current.get(i) else {
+            return Some((i, format!("storage variable '{}' ({}) was removed", old.name, old.ty)));
+        };
+
+        if new.name == old.name {
+            if new.ty != old.ty {
+                return Some((
+                    i,
+                    format!(
+                        "type of storage variable '{}' changed from '{}' to '{}'",
+                        old.name, old.ty, new.ty
+                    ),
+                ));
+            }
+        } else if let Some(j) = current.iter().position(|v| v.name == old.name) {
+            return Some((
+                i,
+                format!("storage variable '{}' moved from position {i} to {j}", old.name),
+            ));
+        } else if recorded.iter().any(|v| v.name == new.name) {
+            // A later recorded variable now sits here, so this one was removed.
+            return Some((i, format!("storage variable '{}' ({}) was removed", old.name, old.ty)));
+        } else if new.ty != old.ty {
+            return Some((
+                i,
+                format!(
+                    "storage variable '{}' ({}) was replaced by '{}' ({})",
+                    old.name, old.ty, new.name, new.ty
+                ),
+            ));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_json_roundtrip() {
+        let mut snapshot = Snapshot::default();
+        snapshot.contracts.insert(
+            "src/Counter.sol:Counter".to_string(),
+            vec![
+                StorageVariable { name: "number".to_string(), ty: "uint256".to_string() },
+                StorageVariable {
+                    name: "balances".to_string(),
+                    ty: "mapping(address => uint256)".to_string(),
+                },
+            ],
+        );
+
+        let json = snapshot.to_json();
+        assert!(json.contains(r#""name": "number","#));
+        assert_eq!(Snapshot::from_json(&json).unwrap(), snapshot);
+        assert!(Snapshot::from_json(r#"{"contracts": {"A": [{"name": "x"}]}}"#).is_err());
+    }
+}
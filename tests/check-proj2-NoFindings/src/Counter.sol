@@ -3,7 +3,7 @@ pragma solidity ^0.8.17;
 
 contract Counter {
   uint256 public immutable GOOD_IMMUTABLE;
-  uint256 public constant GOOD_CONSTANT__ = 1;
+  uint256 public constant GOOD_CONSTANT = 1;
 
   uint256 public number;
 
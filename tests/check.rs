@@ -2,10 +2,77 @@
 /// terminal. Therefore, most testing is done by running the binary against a sample forge
 /// project and checking the output.
 use std::{
-    env,
-    process::{Command, Output},
+    env, fs,
+    path::PathBuf,
+    process::{self, Command, Output},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
+/// A project in its own temp directory, which is removed when the project is dropped, even if the
+/// test fails.
+struct TempProject {
+    path: PathBuf,
+}
+
+impl TempProject {
+    /// Creates an empty project directory named after `name`. The name also gets the process id,
+    /// the time, and a counter, so it's unique across tests and concurrent runs of the tests.
+    fn new(name: &str) -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let path =
+            env::temp_dir().join(format!("scopelint-{name}-{}-{nanos}-{count}", process::id()));
+        // Fails rather than reusing a directory that already exists.
+        fs::create_dir(&path).expect("Failed to create project directory");
+        Self { path }
+    }
+
+    /// Returns the path of `file`, relative to the project root.
+    fn join(&self, file: &str) -> PathBuf {
+        self.path.join(file)
+    }
+
+    /// Writes `content` to `file`, creating the directories it's in.
+    fn write(&self, file: &str, content: impl AsRef<[u8]>) {
+        let path = self.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Returns the content of `file`.
+    fn read(&self, file: &str) -> String {
+        fs::read_to_string(self.join(file)).unwrap_or_else(|err| panic!("{file}: {err}"))
+    }
+
+    /// Runs scopelint with `args` in the project.
+    fn run(&self, args: &[&str]) -> Output {
+        Command::new(env::current_dir().unwrap().join("target/debug/dev-scopelint"))
+            .current_dir(&self.path)
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    }
+
+    /// Runs git with `args` in the project, as a fixed author, and asserts that it succeeds.
+    fn git(&self, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(&self.path)
+            .args(["-c", "user.name=scopelint", "-c", "user.email=scopelint@example.com"])
+            .args(args)
+            .status()
+            .expect("Failed to execute git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+}
+
+impl Drop for TempProject {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
 fn run_scopelint(test_folder: &str) -> Output {
     let cwd = env::current_dir().unwrap();
     let project_path = cwd.join("tests").join(test_folder);
@@ -30,50 +97,65 @@ fn run_scopelint_fix(test_folder: &str) -> Output {
         .expect("Failed to execute command")
 }
 
+fn run_scopelint_with_args(test_folder: &str, args: &[&str]) -> Output {
+    let cwd = env::current_dir().unwrap();
+    let project_path = cwd.join("tests").join(test_folder);
+    let binary_path = cwd.join("target/debug/dev-scopelint");
+
+    Command::new(binary_path)
+        .current_dir(project_path)
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
 #[test]
 fn test_check_proj1_all_findings() {
     let output = run_scopelint("check-proj1-AllFindings");
     let stderr = String::from_utf8(output.stderr).unwrap();
-    let findings: Vec<&str> = stderr.split("\n").collect();
+    // Source snippets are indented, so skip them to compare the findings themselves.
+    let findings: Vec<&str> = stderr.split("\n").filter(|line| !line.starts_with(' ')).collect();
 
     let expected_findings = [
-        "Invalid constant or immutable name in ./script/Counter.s.sol on line 7: VERY_bad_constant",
-        "Invalid constant or immutable name in ./script/Counter.s.sol on line 6: bad_constant",
-        "Invalid constant or immutable name in ./script/Counter.s.sol on line 8: sorryBadName",
-        "Invalid constant or immutable name in ./script/ScriptHelpers.sol on line 4: stillNeedGoodNames",
-        "Invalid constant or immutable name in ./src/Counter.sol on line 7: badImmutable",
-        "Invalid constant or immutable name in ./src/Counter.sol on line 8: bad_constant",
-        "Invalid constant or immutable name in ./test/Counter.t.sol on line 7: testVal",
-        "Invalid src method name in ./src/Counter.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/Counter.sol on line 27: internalShouldHaveLeadingUnderscore",
-        "Invalid src method name in ./src/Counter.sol on line 29: privateShouldHaveLeadingUnderscore",
-        "Invalid src method name in ./src/CounterIgnored1.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored2.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored3.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored4.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored4.sol on line 29: missingLeadingUnderscoreAndNotIgnored",
-        "Invalid test name in ./test/Counter.t.sol on line 16: testIncrementBadName",
-        "Invalid directive in ./src/Counter.sol: Invalid inline config item: this directive is invalid",
-        "Invalid variable name in ./script/Counter.s.sol on line 25: Local variable 'x' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 19: Parameter 'newNumber' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 34: Parameter 'owner' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 34: Parameter 'spender' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 34: Parameter 'value' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 6: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 20: Parameter 'newNumber' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 41: Parameter 'someImportantData' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 50: Parameter 'someImportantData' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 40: Parameter 'someImportantNumber' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 49: Parameter 'someImportantNumber' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 39: Parameter 'someImportantUser' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 48: Parameter 'someImportantUser' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 7: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
-        "Invalid variable name in ./test/Counter.t.sol on line 31: Local variable 'x' should have underscore prefix",
-        "Invalid variable name in ./test/Counter.t.sol on line 21: Parameter 'x' should have underscore prefix",
-        "Invalid error name in ./src/Counter.sol on line 40: Error 'AnotherInvalidError' should be prefixed with 'Counter_'",
-        "Invalid error name in ./src/Counter.sol on line 39: Error 'InvalidError' should be prefixed with 'Counter_'",
-        "Invalid EIP712 typehash in ./src/Counter.sol: EIP712 typehash 'PERMIT_TYPEHASH' parameter mismatch: typehash defines 5 parameters but abi.encode usage uses 3 parameters",
-        "Unused import in ./src/Counter.sol on line 3: Unused import: 'ERC20'",
+        "[SL001] Invalid constant or immutable name in ./script/Counter.s.sol on line 7: VERY_bad_constant",
+        "[SL001] Invalid constant or immutable name in ./script/Counter.s.sol on line 6: bad_constant",
+        "[SL001] Invalid constant or immutable name in ./script/Counter.s.sol on line 8: sorryBadName",
+        "[SL001] Invalid constant or immutable name in ./script/ScriptHelpers.sol on line 4: stillNeedGoodNames",
+        "[SL001] Invalid constant or immutable name in ./src/Counter.sol on line 6: _GOOD__IMMUTABLE_",
+        "[SL001] Invalid constant or immutable name in ./src/Counter.sol on line 7: badImmutable",
+        "[SL001] Invalid constant or immutable name in ./src/Counter.sol on line 8: bad_constant",
+        "[SL001] Invalid constant or immutable name in ./src/CounterIgnored3.sol on line 7: _GOOD__IMMUTABLE_",
+        "[SL001] Invalid constant or immutable name in ./test/Counter.t.sol on line 7: testVal",
+        "[SL003] Invalid src method name in ./src/Counter.sol on line 27: internalShouldHaveLeadingUnderscore",
+        "[SL003] Invalid src method name in ./src/Counter.sol on line 29: privateShouldHaveLeadingUnderscore",
+        "[SL003] Invalid src method name in ./src/CounterIgnored4.sol on line 29: missingLeadingUnderscoreAndNotIgnored",
+        "[SL004] Invalid test name in ./test/Counter.t.sol on line 16: testIncrementBadName",
+        "[SL005] Invalid directive in ./src/Counter.sol: Invalid inline config item: this directive is invalid",
+        "[SL006] Invalid variable name in ./script/Counter.s.sol on line 25: Local variable 'x' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/Counter.sol on line 19: Parameter 'newNumber' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/Counter.sol on line 34: Parameter 'owner' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/Counter.sol on line 34: Parameter 'spender' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/Counter.sol on line 34: Parameter 'value' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/Counter.sol on line 6: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 20: Parameter 'newNumber' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 41: Parameter 'someImportantData' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 50: Parameter 'someImportantData' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 40: Parameter 'someImportantNumber' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 49: Parameter 'someImportantNumber' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 39: Parameter 'someImportantUser' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 48: Parameter 'someImportantUser' should have underscore prefix",
+        "[SL006] Invalid variable name in ./src/CounterIgnored3.sol on line 7: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
+        "[SL006] Invalid variable name in ./test/Counter.t.sol on line 31: Local variable 'x' should have underscore prefix",
+        "[SL006] Invalid variable name in ./test/Counter.t.sol on line 21: Parameter 'x' should have underscore prefix",
+        "[SL007] Invalid error name in ./src/Counter.sol on line 40: Error 'AnotherInvalidError' should be prefixed with 'Counter_'",
+        "[SL007] Invalid error name in ./src/Counter.sol on line 39: Error 'InvalidError' should be prefixed with 'Counter_'",
+        "[SL008] Invalid EIP712 typehash in ./src/Counter.sol: EIP712 typehash 'PERMIT_TYPEHASH' parameter mismatch: typehash defines 5 parameters but abi.encode usage uses 3 parameters",
+        "[SL009] Unused import in ./src/Counter.sol on line 3: Unused import: 'ERC20'",
+        "[SL022] Invalid license header in ./src/Counter.sol: Missing SPDX-License-Identifier header",
+        "[SL022] Invalid license header in ./src/CounterIgnored1.sol: Missing SPDX-License-Identifier header",
+        "[SL022] Invalid license header in ./src/CounterIgnored2.sol: Missing SPDX-License-Identifier header",
+        "[SL022] Invalid license header in ./src/CounterIgnored3.sol: Missing SPDX-License-Identifier header",
+        "[SL022] Invalid license header in ./src/CounterIgnored4.sol: Missing SPDX-License-Identifier header",
         "error: Convention checks failed, see details above",
         "error: Formatting validation failed, run `scopelint fmt` to fix",
         "",
@@ -85,6 +167,140 @@ fn test_check_proj1_all_findings() {
     assert_eq!(findings.len(), expected_findings.len());
 }
 
+#[test]
+fn test_check_proj1_finding_snippets() {
+    let output = run_scopelint("check-proj1-AllFindings");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let snippet = [
+        "[SL001] Invalid constant or immutable name in ./src/Counter.sol on line 7: badImmutable",
+        " 7 |   uint256 public immutable badImmutable;",
+        "   |                            ^^^^^^^^^^^^",
+    ]
+    .join("\n");
+    assert!(stderr.contains(&snippet), "stderr:\n{stderr}");
+}
+
+#[test]
+fn test_check_proj1_suggested_fixes() {
+    let output = run_scopelint("check-proj1-AllFindings");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let suggestions = [
+        [
+            "   |                            ^^^^^^^^^^^^",
+            "   = help: rename to 'BAD_IMMUTABLE'",
+            "   -   uint256 public immutable badImmutable;",
+            "   +   uint256 public immutable BAD_IMMUTABLE;",
+        ]
+        .join("\n"),
+        [
+            "   = help: rename to 'GOOD_IMMUTABLE'",
+            "   -   uint256 public immutable _GOOD__IMMUTABLE_;",
+            "   +   uint256 public immutable GOOD_IMMUTABLE;",
+        ]
+        .join("\n"),
+    ];
+    for suggestion in suggestions {
+        assert!(stderr.contains(&suggestion), "stderr:\n{stderr}");
+    }
+}
+
+#[test]
+fn test_check_proj1_explicit_paths() {
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "src/Counter.sol", "test/"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let files: std::collections::BTreeSet<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with('['))
+        .filter_map(|line| line.split(" in ").nth(1)?.split([' ', ':']).next())
+        .collect();
+    assert_eq!(
+        files.into_iter().collect::<Vec<_>>(),
+        ["./src/Counter.sol", "./test/Counter.t.sol"],
+        "stderr:\n{stderr}"
+    );
+    assert!(!output.status.success());
+
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "src/Missing.sol"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("src/Missing.sol does not exist"), "stderr:\n{stderr}");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj1_group_by_file() {
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "--group-by-file"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let counter = [
+        "./src/Counter.sol",
+        "  1:1  [SL022] Missing SPDX-License-Identifier header",
+        "  3:9  [SL009] Unused import: 'ERC20'",
+        "  6:28  [SL001] _GOOD__IMMUTABLE_",
+        "  6:28  [SL006] State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
+        "  7:28  [SL001] badImmutable",
+        "  8:27  [SL001] bad_constant",
+    ]
+    .join("\n");
+    assert!(stderr.contains(&counter), "stderr:\n{stderr}");
+    assert_eq!(stderr.matches("./src/Counter.sol\n").count(), 1);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj1_link_template() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--link-template", "https://example.com/blob/main/{path}#L{line}"],
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let linked = "\x1b]8;;https://example.com/blob/main/src/Counter.sol#L19\x1b\\./src/Counter.sol\x1b]8;;\x1b\\";
+    assert!(
+        stderr.contains(&format!(
+            "[SL006] Invalid variable name in {linked} on line 19: Parameter 'newNumber' should have underscore prefix"
+        )),
+        "stderr:\n{stderr}"
+    );
+
+    // Without a template, links are only emitted to terminals.
+    let output = run_scopelint("check-proj1-AllFindings");
+    assert!(!String::from_utf8(output.stderr).unwrap().contains("\x1b]8;;"));
+}
+
+#[test]
+fn test_check_proj1_summary() {
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "--summary"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("\nSummary\n  Files checked: "), "stderr:\n{stderr}");
+    assert!(stderr.contains("  Findings: 39 ("));
+    assert!(stderr.contains("\n    SL006 variable: 16\n"));
+    assert!(stderr.contains("  Elapsed: "));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_summary_only() {
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "--summary-only"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.starts_with("Summary\n  Files checked: "), "stderr:\n{stderr}");
+    assert!(stderr.contains("\n    SL006 variable: 16\n"));
+    assert!(stderr.contains("\n  Result: failed\n"), "stderr:\n{stderr}");
+    // Individual findings aren't printed.
+    assert!(!stderr.contains("./src/"), "stderr:\n{stderr}");
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--summary-only"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("\n  Result: passed\n"), "stderr:\n{stderr}");
+    assert!(output.status.success());
+}
+
 #[test]
 fn test_check_proj2_no_findings() {
     let output = run_scopelint("check-proj2-NoFindings");
@@ -99,6 +315,258 @@ fn test_check_proj2_no_findings() {
     assert_eq!(findings.len(), expected_findings.len());
 }
 
+#[test]
+fn test_check_proj1_json_format() {
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "--format", "json"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let findings = json["findings"].as_array().unwrap();
+
+    assert_eq!(findings.len(), 39);
+    let finding = findings
+        .iter()
+        .find(|f| f["message"] == "Parameter 'newNumber' should have underscore prefix")
+        .unwrap();
+    assert_eq!(finding["file"], "./src/Counter.sol");
+    assert_eq!(finding["line"], 19);
+    assert_eq!(finding["column"], 22);
+    assert_eq!(finding["rule"], "variable");
+    assert_eq!(finding["code"], "SL006");
+    assert_eq!(finding["severity"], "error");
+    assert_eq!(finding["fix"]["help"], "rename to '_newNumber'");
+    assert_eq!(finding["fix"]["automatic"], true);
+    assert_eq!(finding["fix"]["edits"][0]["replacement"], "_newNumber");
+    assert!(!output.status.success());
+
+    // The summary tells how the run was configured and what it found.
+    let summary = &json["summary"];
+    assert_eq!(summary["version"], env!("CARGO_PKG_VERSION"));
+    let rules = summary["rules"].as_array().unwrap();
+    assert!(rules
+        .contains(&serde_json::json!({ "rule": "import", "code": "SL009", "severity": "warn" })));
+    assert_eq!(summary["severities"], serde_json::json!(["error", "warn", "info"]));
+    assert_eq!(summary["counts"], serde_json::json!({ "error": 38, "warn": 1, "info": 0 }));
+    assert_eq!(summary["passed"], false);
+}
+
+#[test]
+fn test_check_proj1_min_severity() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--format", "json", "--min-severity", "error"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let findings = json["findings"].as_array().unwrap();
+
+    // The unused import is a warning, so it's hidden.
+    assert_eq!(findings.len(), 38);
+    assert!(findings.iter().all(|f| f["severity"] == "error"));
+    assert!(findings.iter().all(|f| f["rule"] != "import"));
+    assert_eq!(json["summary"]["severities"], serde_json::json!(["error"]));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj1_only_rules() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--format", "json", "--only", "error,SL003"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let findings = json["findings"].as_array().unwrap();
+
+    assert!(!findings.is_empty());
+    assert!(findings.iter().all(|f| f["rule"] == "error" || f["code"] == "SL003"), "{stdout}");
+    let rules: Vec<&serde_json::Value> =
+        json["summary"]["rules"].as_array().unwrap().iter().map(|rule| &rule["rule"]).collect();
+    assert_eq!(rules, ["error", "src"]);
+    assert!(!output.status.success());
+
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "--only", "nope"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("unknown rule `nope`"), "stderr:\n{stderr}");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj1_skip_rules() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--format", "json", "--skip", "variable,import"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let findings = json["findings"].as_array().unwrap();
+
+    assert!(!findings.is_empty());
+    assert!(findings.iter().all(|f| f["rule"] != "variable" && f["rule"] != "import"), "{stdout}");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj2_json_format_no_findings() {
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--format", "json"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    assert_eq!(json["findings"], serde_json::json!([]));
+    assert_eq!(json["summary"]["counts"], serde_json::json!({ "error": 0, "warn": 0, "info": 0 }));
+    assert_eq!(json["summary"]["suppressed"], 0);
+    assert_eq!(json["summary"]["passed"], true);
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_check_proj1_jsonl_format() {
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--format", "jsonl"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let findings: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line should be JSON"))
+        .collect();
+
+    assert_eq!(findings.len(), 39);
+    let finding = findings
+        .iter()
+        .find(|f| f["message"] == "Parameter 'newNumber' should have underscore prefix")
+        .unwrap();
+    assert_eq!(finding["file"], "./src/Counter.sol");
+    assert_eq!(finding["line"], 19);
+    assert_eq!(finding["code"], "SL006");
+    assert!(!output.status.success());
+
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--format", "jsonl"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "");
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_check_proj1_sarif_format() {
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--format", "sarif"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let sarif: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "scopelint");
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 39);
+
+    let result = results
+        .iter()
+        .find(|r| r["message"]["text"] == "Parameter 'newNumber' should have underscore prefix")
+        .unwrap();
+    assert_eq!(result["ruleId"], "SL006");
+    assert_eq!(result["level"], "error");
+    let rule_index = usize::try_from(result["ruleIndex"].as_u64().unwrap()).unwrap();
+    assert_eq!(rules[rule_index]["id"], "SL006");
+    assert_eq!(rules[rule_index]["name"], "variable");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/Counter.sol");
+    assert_eq!(location["region"]["startLine"], 19);
+    assert_eq!(location["region"]["startColumn"], 22);
+    let fix = &result["fixes"][0];
+    assert_eq!(fix["description"]["text"], "rename to '_newNumber'");
+    let change = &fix["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"], "src/Counter.sol");
+    assert_eq!(change["replacements"][0]["insertedContent"]["text"], "_newNumber");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj1_junit_format() {
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--format", "junit"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with("<?xml"), "stdout:\n{stdout}");
+    assert!(stdout.contains(r#"<testsuite name="./src/Counter.sol" tests=""#));
+    assert!(stdout.contains(r#"<testcase name="variable" classname="./src/Counter.sol">"#));
+    assert!(stdout.contains(
+        "./src/Counter.sol:19:22: Parameter &apos;newNumber&apos; should have underscore prefix"
+    ));
+    // Rules without findings are reported as passing test cases.
+    assert!(stdout.contains(r#"<testcase name="chain" classname="./src/Counter.sol"/>"#));
+    assert!(stdout.trim_end().ends_with("</testsuites>"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj2_junit_format_no_findings() {
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--format", "junit"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains(r#"<testsuites name="scopelint" tests=""#), "stdout:\n{stdout}");
+    assert!(stdout.contains(r#"failures="0">"#));
+    assert!(!stdout.contains("<failure"));
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_check_proj1_checkstyle_format() {
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--format", "checkstyle"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with("<?xml"), "stdout:\n{stdout}");
+    assert!(stdout.contains(r#"<file name="./src/Counter.sol">"#));
+    assert!(stdout.contains(
+        r#"<error line="19" column="22" severity="error" message="Parameter &apos;newNumber&apos; should have underscore prefix" source="scopelint.SL006"/>"#
+    ));
+    assert_eq!(stdout.matches("<error ").count(), 39);
+    assert!(stdout.trim_end().ends_with("</checkstyle>"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj1_markdown_format() {
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--format", "markdown"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with("## scopelint\n\n39 finding(s)\n"), "stdout:\n{stdout}");
+    assert!(stdout.contains("\n### SL006 `variable` ("));
+    assert!(stdout.contains("| File | Line | Message |\n| --- | --- | --- |\n"));
+    assert!(stdout.contains(
+        "| `./src/Counter.sol` | 19 | Parameter 'newNumber' should have underscore prefix |"
+    ));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj1_html_format_to_file() {
+    let out_dir = TempProject::new("report");
+    let out = out_dir.join("report.html");
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--format", "html", "--out", out.to_str().unwrap()],
+    );
+    let html = fs::read_to_string(&out).unwrap();
+
+    assert!(output.stdout.is_empty());
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<p>39 finding(s) in 10 checked file(s)"), "html:\n{html}");
+    assert!(html.contains("<span>SL006 <code>variable</code>: 16</span>"));
+    assert!(html.contains("<option value=\"./src/Counter.sol\">./src/Counter.sol</option>"));
+    assert!(html.contains(
+        "<span class=\"location\">./src/Counter.sol:19:22</span> <span class=\"code\">SL006 variable</span> (error): Parameter &apos;newNumber&apos; should have underscore prefix"
+    ));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_proj2_markdown_format_no_findings() {
+    let output =
+        run_scopelint_with_args("check-proj2-NoFindings", &["check", "--format", "markdown"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "## scopelint\n\nNo findings.");
+    assert!(output.status.success());
+}
+
 /// Projects with contracts/ instead of src/ must not hit "No such file or directory" for ./src.
 /// This project has [profile.default] src = "contracts" and no src/ directory.
 #[test]
@@ -120,8 +588,6 @@ fn test_check_proj3_contracts_layout_no_io_error() {
 /// symbol.
 #[test]
 fn test_fix_removes_unused_import() {
-    use std::fs;
-
     let cwd = env::current_dir().unwrap();
     let project_path = cwd.join("tests").join("fix-proj1");
     let token_sol = project_path.join("src").join("Token.sol");
@@ -135,3 +601,1325 @@ fn test_fix_removes_unused_import() {
         "Fixed file should not contain unused import IERC20; content:\n{content}"
     );
 }
+
+#[test]
+fn test_check_write_env_manifest() {
+    let project = TempProject::new("env");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[scripts.env]\nrequired = [\"STALE\"]\n");
+    project.write(
+        "script/Deploy.s.sol",
+        r#"contract Deploy {
+    function run() public {
+        address deployer = vm.envAddress("DEPLOYER");
+        string memory rpcUrl = vm.envString("RPC_URL");
+    }
+}
+"#,
+    );
+
+    let output = project.run(&["check", "--write"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let manifest = project.read(".scopelint");
+
+    assert!(stderr.contains("Wrote 2 env var(s)"), "stderr:\n{stderr}");
+    assert!(!stderr.contains("Undeclared env var"), "stderr:\n{stderr}");
+    assert_eq!(manifest, "[scripts.env]\nrequired = [\n    \"DEPLOYER\",\n    \"RPC_URL\",\n]\n");
+}
+
+#[test]
+fn test_check_storage_layout_snapshot() {
+    let project = TempProject::new("storage");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write("src/Vault.sol", "contract Vault {\n    address owner;\n    uint256 total;\n}\n");
+
+    let write_output = project.run(&["storage", "--write"]);
+    let snapshot = project.read(".scopelint-storage.json");
+
+    // Swap the two variables so the layout is no longer append-only.
+    project.write("src/Vault.sol", "contract Vault {\n    uint256 total;\n    address owner;\n}\n");
+    let check_output = project.run(&["check"]);
+    let stderr = String::from_utf8(check_output.stderr).unwrap();
+
+    assert!(write_output.status.success());
+    assert!(snapshot.contains(r#""src/Vault.sol:Vault""#), "snapshot:\n{snapshot}");
+    assert!(
+        stderr.contains(
+            "Incompatible storage layout in ./src/Vault.sol on line 2: storage variable 'owner' moved from position 0 to 1"
+        ),
+        "stderr:\n{stderr}"
+    );
+    assert!(!check_output.status.success());
+}
+
+#[test]
+fn test_check_test_files_mirror_src() {
+    let project = TempProject::new("mirror");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[rules.mirror]\nenabled = true\n");
+    project.write("src/Token.sol", "contract Token {}\n");
+    project.write("src/IToken.sol", "interface IToken {}\n");
+    project.write("src/vault/Vault.sol", "contract Vault {}\n");
+    project.write("test/Token.t.sol", "contract TokenTest {}\n");
+
+    let output = project.run(&["check"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let findings: Vec<&str> = stderr.lines().filter(|line| line.contains("[SL020]")).collect();
+    assert_eq!(
+        findings,
+        [
+            "[SL020] Missing test file in ./src/vault/Vault.sol on line 1: No test file found, expected ./test/vault/Vault.t.sol"
+        ],
+        "stderr:\n{stderr}"
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_baseline() {
+    let project = TempProject::new("baseline");
+    project.write("foundry.toml", "[profile.default]\n");
+    let vault = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.20;\n\ncontract Vault {\n    error Unauthorized();\n}\n";
+    project.write("src/Vault.sol", vault);
+
+    let baseline_output = project.run(&["baseline"]);
+    let baseline = project.read(".scopelint-baseline.json");
+    let baselined_output = project.run(&["check", "--only", "error"]);
+    project.write(
+        "src/Vault.sol",
+        vault.replace(
+            "    error Unauthorized();",
+            "    error Paused();\n\n    error Unauthorized();",
+        ),
+    );
+    let new_finding_output = project.run(&["check", "--only", "error"]);
+
+    assert!(baseline_output.status.success());
+    assert!(baseline.contains(r#""source": "error Unauthorized();""#), "baseline:\n{baseline}");
+    let stderr = String::from_utf8(baselined_output.stderr).unwrap();
+    assert!(stderr.contains("1 finding(s) in .scopelint-baseline.json were not reported"));
+    assert!(baselined_output.status.success(), "stderr:\n{stderr}");
+
+    // Moving a baselined finding doesn't report it, but a new finding fails the check.
+    let stderr = String::from_utf8(new_finding_output.stderr).unwrap();
+    assert!(stderr.contains("Error 'Paused' should be prefixed"), "stderr:\n{stderr}");
+    assert!(!stderr.contains("Error 'Unauthorized'"), "stderr:\n{stderr}");
+    assert_eq!(new_finding_output.status.code(), Some(1));
+}
+
+#[test]
+fn test_check_fix_applies_mechanical_fixes() {
+    let project = TempProject::new("fix");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[fix]\nspdx_license = \"MIT\"\n");
+    let counter = [
+        "pragma solidity ^0.8.17;",
+        "",
+        "contract Counter {",
+        "    uint256 public number;",
+        "",
+        "    function setNumber(uint256 newNumber) public {",
+        "        uint256 doubled = newNumber * 2;",
+        "        number = doubled + helper(newNumber);",
+        "    }",
+        "",
+        "    function helper(uint256 _x) private pure returns (uint256) {",
+        "        return _x;",
+        "    }",
+        "",
+        "    function internalHelper() internal {}",
+        "}",
+        "",
+    ];
+    project.write("src/Counter.sol", counter.join("\n"));
+
+    let output = project.run(&["check", "--fix"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let fixed = project.read("src/Counter.sol");
+
+    let expected = [
+        "// SPDX-License-Identifier: MIT",
+        "pragma solidity ^0.8.17;",
+        "",
+        "contract Counter {",
+        "    uint256 public number;",
+        "",
+        "    function setNumber(uint256 _newNumber) public {",
+        "        uint256 _doubled = _newNumber * 2;",
+        "        number = _doubled + _helper(_newNumber);",
+        "    }",
+        "",
+        "    function _helper(uint256 _x) private pure returns (uint256) {",
+        "        return _x;",
+        "    }",
+        "",
+        // Internal functions may be called from other files, so they are reported, not renamed.
+        "    function internalHelper() internal {}",
+        "}",
+        "",
+    ];
+    assert_eq!(fixed, expected.join("\n"), "stderr:\n{stderr}");
+    let findings: Vec<&str> = stderr.lines().filter(|line| line.starts_with('[')).collect();
+    assert_eq!(
+        findings,
+        ["[SL003] Invalid src method name in ./src/Counter.sol on line 16: internalHelper"],
+        "stderr:\n{stderr}"
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_bug_report_bundle() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--bug-report", "src/Counter.sol"],
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let path = stderr
+        .lines()
+        .find_map(|line| line.split("Wrote bug report to ").nth(1))
+        .unwrap_or_else(|| panic!("stderr:\n{stderr}"));
+    let bundle = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    // Only the given file is checked.
+    assert!(stderr.contains("./src/Counter.sol"), "stderr:\n{stderr}");
+    assert!(!stderr.contains("./test/"), "stderr:\n{stderr}");
+    assert!(bundle.contains(&format!("scopelint version: {}", env!("CARGO_PKG_VERSION"))));
+    assert!(bundle.contains("reason: check failed"), "bundle:\n{bundle}");
+    assert!(bundle.contains("file: src/Counter.sol ("), "bundle:\n{bundle}");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_check_internal_error_bundle() {
+    let project = TempProject::new("internal-error");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write("src/Broken.sol", "// SPDX-License-Identifier: MIT\ncontract Broken {\n");
+    let output = project.run(&["check"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let path = stderr
+        .lines()
+        .find_map(|line| {
+            line.split("attach the bug report at ").nth(1)?.split(" to an issue").next()
+        })
+        .unwrap_or_else(|| panic!("stderr:\n{stderr}"));
+    let bundle = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+    // A `--bug-report` check that fails writes only its own bundle.
+    let bug_report = project.run(&["check", "--bug-report", "src/Broken.sol"]);
+    let bug_report_stderr = String::from_utf8(bug_report.stderr).unwrap();
+    if let Some(path) =
+        bug_report_stderr.lines().find_map(|line| line.split("Wrote bug report to ").nth(1))
+    {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(bundle.contains("reason: error: "), "bundle:\n{bundle}");
+    assert!(bundle.contains("file: ./src/Broken.sol ("), "bundle:\n{bundle}");
+    assert_eq!(bug_report_stderr.matches("bug report").count(), 1, "stderr:\n{bug_report_stderr}");
+}
+
+#[test]
+fn test_init_writes_config_and_hook() {
+    let project = TempProject::new("init");
+    project.write("foundry.toml", "[profile.default]\nsrc = \"contracts\"\n");
+    project.git(&["init", "-q"]);
+
+    let output = project.run(&["init", "--hook"]);
+    let config = project.read(".scopelint");
+    let hook = project.read(".git/hooks/pre-commit");
+    let second_output = project.run(&["init", "--hook"]);
+    let check_output = project.run(&["check", "--only", "src"]);
+
+    assert!(output.status.success(), "stderr:\n{}", String::from_utf8_lossy(&output.stderr));
+    assert!(config.contains(r#"# files = ["contracts/legacy/*.sol""#), "config:\n{config}");
+    assert!(hook.contains("scopelint check"));
+    // A second run doesn't overwrite the config.
+    assert!(String::from_utf8(second_output.stderr).unwrap().contains(".scopelint already exists"));
+    assert!(!second_output.status.success());
+    // The starter config is valid as written.
+    assert!(!String::from_utf8(check_output.stderr)
+        .unwrap()
+        .contains("Failed to parse .scopelint"));
+}
+
+#[test]
+fn test_hook_install_and_uninstall() {
+    let project = TempProject::new("hook");
+    project.git(&["init", "-q"]);
+    project.write(".git/hooks/pre-commit", "#!/bin/sh\nexec npx lint-staged\n");
+
+    let install_output = project.run(&["hook", "install"]);
+    let installed = project.read(".git/hooks/pre-commit");
+    project.run(&["hook", "install"]);
+    let reinstalled = project.read(".git/hooks/pre-commit");
+    let uninstall_output = project.run(&["hook", "install", "--uninstall"]);
+    let uninstalled = project.read(".git/hooks/pre-commit");
+
+    assert!(install_output.status.success());
+    // The section runs before the existing commands.
+    let check = installed.find("scopelint check --staged").unwrap();
+    assert!(installed.contains("scopelint fmt --check"), "hook:\n{installed}");
+    assert!(check < installed.find("exec npx lint-staged").unwrap(), "hook:\n{installed}");
+    // Installing again doesn't add a second section.
+    assert_eq!(reinstalled, installed);
+    assert!(uninstall_output.status.success());
+    assert_eq!(uninstalled, "#!/bin/sh\nexec npx lint-staged\n");
+}
+
+#[test]
+fn test_config_show() {
+    let output = run_scopelint_with_args(
+        "check-proj3-ContractsLayout",
+        &["config", "show", "--format", "json", "contracts/Counter.sol", "src/Counter.sol"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+
+    assert!(output.status.success());
+    assert_eq!(json["paths"]["src"], serde_json::json!(["./contracts"]));
+    assert_eq!(json["run"]["profile"], "default");
+    assert_eq!(json["scopelint"]["rules"]["nesting"]["max_depth"], 4);
+    // Files outside the configured directories aren't checked.
+    assert_eq!(json["files"][0]["kind"], "src");
+    assert_eq!(json["files"][0]["checked"], true);
+    assert_eq!(json["files"][1]["kind"], "none");
+    assert_eq!(json["files"][1]["checked"], false);
+
+    // TOML is printed by default.
+    let output = run_scopelint_with_args("check-proj3-ContractsLayout", &["config", "show"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[paths]\n"), "stdout:\n{stdout}");
+    assert!(stdout.contains("src = [\"./contracts\"]\n"), "stdout:\n{stdout}");
+}
+
+#[test]
+fn test_doctor() {
+    let project = TempProject::new("doctor");
+    project.write("foundry.toml", "[profile.default]\nsrc = \"contracts\"\n");
+    project.write(".scopelint", "[rules.contact]\nenabeld = true\n");
+    project.write("test/Counter.t.sol", "contract CounterTest {}\n");
+
+    let output = project.run(&["doctor"]);
+    project.write("contracts/Counter.sol", "contract Counter {}\n");
+    let fixed_output = project.run(&["doctor"]);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("ok foundry.toml parses"), "stdout:\n{stdout}");
+    assert!(
+        stdout.contains("warning .scopelint: Unknown key `rules.contact.enabeld` is ignored"),
+        "stdout:\n{stdout}"
+    );
+    assert!(stdout.contains("error The src path ./contracts does not exist"), "stdout:\n{stdout}");
+    assert!(stdout.contains("ok The test path ./test has 1 Solidity file(s)"), "stdout:\n{stdout}");
+    assert_eq!(output.status.code(), Some(1));
+
+    // Unknown keys are only warnings.
+    let stdout = String::from_utf8(fixed_output.stdout).unwrap();
+    assert!(
+        stdout.contains("ok The src path ./contracts has 1 Solidity file(s)"),
+        "stdout:\n{stdout}"
+    );
+    assert!(fixed_output.status.success(), "stdout:\n{stdout}");
+}
+
+#[test]
+fn test_check_changed_files() {
+    let project = TempProject::new("changed");
+    project.write("foundry.toml", "[profile.default]\n");
+    let source = |name: &str| {
+        format!("// SPDX-License-Identifier: MIT\ncontract {name} {{\n    uint256 immutable bad{name};\n}}\n")
+    };
+    for name in ["Committed", "Modified"] {
+        project.write(&format!("src/{name}.sol"), source(name));
+    }
+
+    project.git(&["init", "-q"]);
+    project.git(&["add", "-A"]);
+    project.git(&["commit", "-q", "-m", "init"]);
+    let check = |flag: &str| String::from_utf8(project.run(&["check", flag]).stderr).unwrap();
+    let unchanged = check("--diff");
+    project.write("src/Modified.sol", source("Modified") + "\n");
+    project.write("src/Staged.sol", source("Staged"));
+    project.git(&["add", "src/Staged.sol"]);
+    let diff = check("--diff");
+    let staged = check("--staged");
+    // Fixing the file without staging the fix doesn't fix what gets committed.
+    project.write("src/Staged.sol", "// SPDX-License-Identifier: MIT\ncontract Staged {}\n");
+    let staged_unstaged_fix = check("--staged");
+
+    let flagged = |stderr: &str| -> Vec<String> {
+        stderr
+            .lines()
+            .filter(|line| line.starts_with("[SL001]"))
+            .filter_map(|line| Some(line.split(" in ").nth(1)?.split(' ').next()?.to_string()))
+            .collect()
+    };
+    assert!(unchanged.contains("No changed files to check"), "stderr:\n{unchanged}");
+    assert_eq!(flagged(&diff), ["./src/Modified.sol", "./src/Staged.sol"], "stderr:\n{diff}");
+    assert_eq!(flagged(&staged), ["./src/Staged.sol"], "stderr:\n{staged}");
+    assert_eq!(
+        flagged(&staged_unstaged_fix),
+        ["./src/Staged.sol"],
+        "stderr:\n{staged_unstaged_fix}"
+    );
+}
+
+#[test]
+fn test_check_changed_foundry_toml() {
+    let project = TempProject::new("changed-foundry");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[rules.foundry]\nenabled = true\nrequired = []\n");
+    project.write("src/Counter.sol", "// SPDX-License-Identifier: MIT\ncontract Counter {}\n");
+    project.git(&["init", "-q"]);
+    project.git(&["add", "-A"]);
+    project.git(&["commit", "-q", "-m", "init"]);
+    let check = |flag: &str| String::from_utf8(project.run(&["check", flag]).stderr).unwrap();
+
+    project.write("foundry.toml", "[profile.default]\nffi = true\n");
+    let diff = check("--diff");
+    project.git(&["add", "foundry.toml"]);
+    // Reverting the change without staging it doesn't revert what gets committed.
+    project.write("foundry.toml", "[profile.default]\n");
+    let staged = check("--staged");
+
+    let finding = "[profile.default] sets `ffi = true`";
+    assert!(diff.contains(finding), "stderr:\n{diff}");
+    assert!(staged.contains(finding), "stderr:\n{staged}");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_check_staged_formatting() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A `forge` that only formats stdin, and considers sources marked as unformatted unformatted.
+    let bin = TempProject::new("staged-fmt-bin");
+    bin.write(
+        "forge",
+        "#!/bin/sh\n[ \"$*\" = \"fmt --check -\" ] || exit 0\nsrc=$(cat)\ncase \"$src\" in *UNFORMATTED*) exit 1 ;; esac\n",
+    );
+    fs::set_permissions(bin.join("forge"), fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!("{}:{}", bin.path.display(), env::var("PATH").unwrap_or_default());
+
+    let project = TempProject::new("staged-fmt");
+    project.write("foundry.toml", "[profile.default]\n");
+    let formatted = "// SPDX-License-Identifier: MIT\ncontract Counter {}\n";
+    let unformatted = "// SPDX-License-Identifier: MIT\n// UNFORMATTED\ncontract Counter {}\n";
+    project.write("src/Counter.sol", "// SPDX-License-Identifier: MIT\n");
+    project.git(&["init", "-q"]);
+    project.git(&["add", "-A"]);
+    project.git(&["commit", "-q", "-m", "init"]);
+    let check = || {
+        let output = Command::new(env::current_dir().unwrap().join("target/debug/dev-scopelint"))
+            .current_dir(&project.path)
+            .env("PATH", &path)
+            .args(["check", "--staged"])
+            .output()
+            .expect("Failed to execute command");
+        String::from_utf8(output.stderr).unwrap()
+    };
+
+    // Only the staged source is format-checked, not the one on disk.
+    project.write("src/Counter.sol", formatted);
+    project.git(&["add", "src/Counter.sol"]);
+    project.write("src/Counter.sol", unformatted);
+    let staged_formatted = check();
+    project.git(&["add", "src/Counter.sol"]);
+    project.write("src/Counter.sol", formatted);
+    let staged_unformatted = check();
+
+    let failed = "Formatting validation failed";
+    assert!(!staged_formatted.contains(failed), "stderr:\n{staged_formatted}");
+    assert!(staged_unformatted.contains(failed), "stderr:\n{staged_unformatted}");
+}
+
+#[test]
+fn test_check_files_excluded_from_formatting() {
+    let project = TempProject::new("fmt-ignore");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[fmt]\nignore = [\"src/Table.sol\"]\n");
+    let spdx = "// SPDX-License-Identifier: MIT\n";
+    project.write(
+        "src/Art.sol",
+        format!("{spdx}// scopelint: disable-fmt-file\ncontract   Art {{ uint256 constant   art = 1; }}\n"),
+    );
+    project.write(
+        "src/Table.sol",
+        format!(
+            "{spdx}contract Table {{ uint256 constant A   = 1; uint256 constant BB  = 22; }}\n"
+        ),
+    );
+
+    let output = project.run(&["check"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!stderr.contains("Formatting validation failed"), "stderr:\n{stderr}");
+    // The directive is valid, and other rules still apply to the excluded file.
+    assert!(!stderr.contains("[SL005]"), "stderr:\n{stderr}");
+    assert!(stderr.contains("[SL001] Invalid constant or immutable name in ./src/Art.sol"));
+}
+
+#[test]
+fn test_new_fixture_reports_seeded_rules() {
+    use std::collections::BTreeSet;
+
+    let binary_path = env::current_dir().unwrap().join("target/debug/dev-scopelint");
+    let findings = |rules: Option<&str>| -> BTreeSet<String> {
+        let project = TempProject::new(&format!("fixture-{}", rules.unwrap_or("all")));
+        let mut args = vec!["new-fixture".to_string(), project.path.display().to_string()];
+        if let Some(rules) = rules {
+            args.extend(["--rules".to_string(), rules.to_string()]);
+        }
+        let output = Command::new(&binary_path).args(&args).output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let output = project.run(&["check", "--format", "json"]);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|finding| finding["rule"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Each seeded rule is reported on its own, without findings of other rules.
+    let rules: Vec<String> =
+        String::from_utf8(Command::new(&binary_path).arg("explain").output().unwrap().stdout)
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.split_whitespace().nth(1).map(str::to_string))
+            .collect();
+    assert!(!rules.is_empty());
+    for rule in &rules {
+        assert_eq!(findings(Some(rule)), BTreeSet::from([rule.clone()]), "rule {rule}");
+    }
+    assert_eq!(findings(None), rules.iter().cloned().collect());
+
+    // The target directory must be empty.
+    let output = Command::new(&binary_path).args(["new-fixture", "src"]).output().unwrap();
+    assert!(String::from_utf8(output.stderr).unwrap().contains("src is not empty"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_explain_rule_by_code_or_name() {
+    let by_code = run_scopelint_with_args("check-proj2-NoFindings", &["explain", "sl006"]);
+    let by_name = run_scopelint_with_args("check-proj2-NoFindings", &["explain", "variable"]);
+    let stdout = String::from_utf8(by_code.stdout).unwrap();
+
+    assert!(by_code.status.success());
+    assert!(stdout.starts_with("# SL006 `variable`\n"), "stdout:\n{stdout}");
+    assert!(stdout.contains("Severity: error"));
+    assert!(stdout.contains("## Rationale"));
+    assert!(stdout.contains("## Examples"));
+    assert_eq!(stdout, String::from_utf8(by_name.stdout).unwrap());
+
+    let unknown = run_scopelint_with_args("check-proj2-NoFindings", &["explain", "SL999"]);
+    assert!(!unknown.status.success());
+    assert!(String::from_utf8(unknown.stderr).unwrap().contains("Unknown rule 'SL999'"));
+}
+
+#[test]
+fn test_storage_diff_between_git_refs() {
+    let project = TempProject::new("storage-diff");
+    project.write("foundry.toml", "[profile.default]\n");
+
+    let commit = |content: &str, message: &str| {
+        project.write("src/Vault.sol", content);
+        project.git(&["add", "-A"]);
+        project.git(&["commit", "-q", "-m", message]);
+    };
+    project.git(&["init", "-q"]);
+    commit("contract Vault {\n    address owner;\n    uint256 total;\n}\n", "v1");
+    commit("contract Vault {\n    address owner;\n    uint256 total;\n    bool paused;\n}\n", "v2");
+    commit("contract Vault {\n    uint256 total;\n    address owner;\n}\n", "v3");
+
+    let append_only = project.run(&["storage", "diff", "HEAD~2", "HEAD~1"]);
+    let breaking = project.run(&["storage", "diff", "HEAD~1", "HEAD"]);
+
+    let stdout = String::from_utf8(append_only.stdout).unwrap();
+    assert!(append_only.status.success(), "stdout:\n{stdout}");
+    assert!(stdout.contains("src/Vault.sol:Vault: appended bool paused"), "stdout:\n{stdout}");
+    assert!(String::from_utf8(append_only.stderr).unwrap().contains("are append-only"));
+
+    let stdout = String::from_utf8(breaking.stdout).unwrap();
+    assert!(!breaking.status.success());
+    assert!(
+        stdout.contains("src/Vault.sol:Vault: storage variable 'owner' moved from position 0 to 1"),
+        "stdout:\n{stdout}"
+    );
+}
+
+#[test]
+fn test_check_stdin_buffer() {
+    use std::{io::Write, process::Stdio};
+
+    let cwd = env::current_dir().unwrap();
+    let mut child = Command::new(cwd.join("target/debug/dev-scopelint"))
+        .current_dir(cwd.join("tests/check-proj2-NoFindings"))
+        .args(["check", "--stdin", "--stdin-filepath", "src/Unsaved.sol", "--format", "json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    let src = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Unsaved {\n  uint256 constant bad_constant = 1;\n}\n";
+    child.stdin.take().unwrap().write_all(src.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(!output.status.success());
+
+    // Only the buffer is checked, as the given path, even though no such file exists.
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1, "{json}");
+    assert_eq!(findings[0]["file"], "./src/Unsaved.sol");
+    assert_eq!(findings[0]["line"], 5);
+    assert_eq!(findings[0]["code"], "SL001");
+
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--stdin"]);
+    assert!(!output.status.success(), "--stdin requires --stdin-filepath");
+}
+
+#[test]
+fn test_check_unknown_profile() {
+    let output =
+        run_scopelint_with_args("check-proj2-NoFindings", &["check", "--profile", "missing"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("Profile `missing` is not defined in foundry.toml"), "{stderr}");
+}
+
+#[test]
+fn test_check_proj1_max_warnings() {
+    // The only `import` finding is a warning, so it passes within the limit, and fails above it.
+    let args = ["check", "--only", "import", "--max-warnings", "1"];
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("[SL009]"));
+    assert!(stderr.contains("1 warning(s) found, within the limit of 1"));
+
+    let args = ["check", "--only", "import", "--max-warnings", "0"];
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &args);
+    assert!(!output.status.success());
+
+    // Errors always fail the check.
+    let args = ["check", "--only", "import,eip712", "--max-warnings", "100"];
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &args);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_exit_codes() {
+    let output = run_scopelint("check-proj2-NoFindings");
+    assert_eq!(output.status.code(), Some(0));
+
+    // Findings take precedence over formatting failures.
+    let output = run_scopelint("check-proj1-AllFindings");
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["fmt", "--check"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output =
+        run_scopelint_with_args("check-proj2-NoFindings", &["check", "--profile", "missing"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    // A file that can't be parsed.
+    let cwd = env::current_dir().unwrap();
+    let mut child = Command::new(cwd.join("target/debug/dev-scopelint"))
+        .current_dir(cwd.join("tests/check-proj2-NoFindings"))
+        .args(["check", "--stdin", "--stdin-filepath", "src/Broken.sol"])
+        .stdin(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), b"contract Broken {").unwrap();
+    assert_eq!(child.wait_with_output().unwrap().status.code(), Some(3));
+
+    // Invalid arguments are errors too, not formatting failures like clap's default exit code.
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--bogus"]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_check_multiple_roots() {
+    let project = TempProject::new("roots");
+    project.write(
+        "foundry.toml",
+        "[profile.default]\nsrc = [\"src\", \"periphery\"]\ntest = [\"test\", \"integration-tests\"]\n",
+    );
+    let header = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n";
+    project.write("src/Token.sol", format!("{header}contract Token {{}}\n"));
+    project.write(
+        "periphery/Router.sol",
+        format!("{header}contract Router {{\n  function route() internal {{}}\n}}\n"),
+    );
+    project.write(
+        "integration-tests/Router.t.sol",
+        format!("{header}contract RouterTest {{\n  function testBadName() public {{}}\n}}\n"),
+    );
+
+    let output = project.run(&["check", "--format", "json"]);
+
+    // Files in every root are walked and classified by the kind of root they are in.
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings: Vec<(&str, &str)> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["file"].as_str().unwrap(), f["rule"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        findings,
+        [("./periphery/Router.sol", "src"), ("./integration-tests/Router.t.sol", "test")],
+        "{json}"
+    );
+}
+
+#[test]
+fn test_check_handler_and_helper_globs() {
+    let project = TempProject::new("kinds");
+    let header = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n";
+    project.write(
+        "test/invariant/handlers/VaultHandler.sol",
+        format!("{header}contract VaultHandler {{\n  function deposit(uint256 amount) public {{}}\n}}\n"),
+    );
+    project.write(
+        "test/utils/Base.t.sol",
+        format!("{header}contract Base {{\n  function testBadName() public {{}}\n}}\n"),
+    );
+    let run = |foundry_toml: &str| {
+        project.write("foundry.toml", foundry_toml);
+        let output = project.run(&["check", "--format", "json"]);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| format!("{} {}", f["file"].as_str().unwrap(), f["rule"].as_str().unwrap()))
+            .collect::<Vec<_>>()
+    };
+
+    // By convention, the handler is a test helper that isn't checked, and the helper is a test.
+    let default_findings = run("[profile.default]\n");
+    let configured_findings = run(
+        "[profile.default]\n\n[check]\nhandlers = [\"test/invariant/handlers/**\"]\ntest_helpers = [\"test/utils/**\"]\n",
+    );
+
+    assert_eq!(default_findings, ["./test/utils/Base.t.sol test"]);
+    assert_eq!(configured_findings, ["./test/invariant/handlers/VaultHandler.sol variable"]);
+}
+
+#[test]
+fn test_color_choice() {
+    let has_ansi = |output: &Output| String::from_utf8_lossy(&output.stderr).contains("\u{1b}[");
+
+    // Piped output isn't colored by default, but can be forced for CI logs.
+    assert!(!has_ansi(&run_scopelint("check-proj1-AllFindings")));
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--color", "always"]);
+    assert!(has_ansi(&output));
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["--color", "never", "check"]);
+    assert!(!has_ansi(&output));
+}
+
+#[test]
+fn test_check_suggest_ignores() {
+    let project = TempProject::new("suggest");
+    project.write("foundry.toml", "[profile.default]\n");
+    let lines = [
+        "// SPDX-License-Identifier: MIT",
+        "pragma solidity ^0.8.17;",
+        "contract Token {",
+        "  uint256 constant bad_constant = 1;",
+        "  function mint(uint256 amount) internal {}",
+        "}",
+    ];
+    project.write("src/Token.sol", lines.join("\n"));
+    let check = |args: &[&str]| project.run(&[&["check"], args].concat());
+
+    let output = check(&["--suggest-ignores"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.status.success());
+    assert!(stdout.contains("  line 4 (constant): // scopelint: ignore-constant-next-line\n"));
+    assert!(stdout.contains("  line 5 (src, variable): // scopelint: ignore-next-line\n"));
+    assert!(stdout.ends_with(
+        "[ignore.overrides]\n\"src/Token.sol\" = [\"constant\", \"src\", \"variable\"]\n"
+    ));
+
+    // The suggested overrides suppress every finding.
+    let overrides = &stdout[stdout.find("[ignore.overrides]").unwrap()..];
+    project.write(".scopelint", overrides);
+    let with_overrides = check(&["--only", "constant,src,variable"]);
+    fs::remove_file(project.join(".scopelint")).unwrap();
+
+    // And so do the suggested inline directives.
+    let mut annotated = lines.to_vec();
+    annotated.insert(4, "  // scopelint: ignore-next-line");
+    annotated.insert(3, "  // scopelint: ignore-constant-next-line");
+    project.write("src/Token.sol", annotated.join("\n"));
+    let with_directives = check(&["--only", "constant,src,variable"]);
+
+    assert!(with_overrides.status.success(), "{}", String::from_utf8_lossy(&with_overrides.stderr));
+    assert!(
+        with_directives.status.success(),
+        "{}",
+        String::from_utf8_lossy(&with_directives.stderr)
+    );
+}
+
+#[test]
+fn test_check_verbose() {
+    let verbose_lines = |args: &[&str]| {
+        let output = run_scopelint_with_args("check-proj2-NoFindings", args);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        stderr
+            .lines()
+            .filter_map(|line| line.strip_prefix("verbose: "))
+            .map(|line| line.rsplit_once(": ").map_or(line, |(label, _)| label).to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // Nothing is printed by default.
+    assert!(verbose_lines(&["check"]).is_empty());
+
+    // `-v` prints the walked roots, and the time spent on each file and project-level check.
+    let lines = verbose_lines(&["check", "-v"]);
+    assert!(lines.contains(&"Walking ./src".to_string()));
+    assert!(lines.contains(&"./src/Counter.sol".to_string()));
+    assert!(lines.contains(&"storage across 5 file(s)".to_string()));
+    assert!(!lines.contains(&"  parse".to_string()));
+
+    // `-vv` also prints the time spent parsing each file and in each of its checks.
+    let lines = verbose_lines(&["check", "-vv"]);
+    assert!(lines.contains(&"Checking ./src/Counter.sol".to_string()));
+    assert!(lines.contains(&"  parse".to_string()));
+    assert!(lines.contains(&"  constant".to_string()));
+}
+
+#[test]
+fn test_fmt_paths() {
+    let output =
+        run_scopelint_with_args("check-proj2-NoFindings", &["fmt", "--check", "src/*.sol"]);
+    assert!(output.status.success());
+
+    // Only the TOML file is formatted, so forge isn't run and the unformatted Solidity files of
+    // this project aren't flagged.
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["fmt", "--check", "foundry.toml"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("Diff in src/"), "{stdout}");
+
+    let output =
+        run_scopelint_with_args("check-proj2-NoFindings", &["fmt", "--check", "src/Missing*.sol"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr.contains("src/Missing*.sol does not exist or match any file"), "{stderr}");
+}
+
+#[test]
+fn test_check_fix_dry_run() {
+    let project = TempProject::new("dry-run");
+    project.write("foundry.toml", "[profile.default]\n");
+    let token = [
+        "// SPDX-License-Identifier: MIT",
+        "pragma solidity ^0.8.17;",
+        "",
+        "contract Token {",
+        "    function mint(uint256 amount) public {}",
+        "",
+        "    function internalHelper() internal {}",
+        "}",
+        "",
+    ]
+    .join("\n");
+    project.write("src/Token.sol", &token);
+
+    let output = project.run(&["check", "--fix", "--dry-run"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let unchanged = project.read("src/Token.sol");
+
+    assert_eq!(unchanged, token);
+    assert_eq!(
+        stdout,
+        [
+            "--- a/src/Token.sol",
+            "+++ b/src/Token.sol",
+            "@@ -2,7 +2,7 @@",
+            " pragma solidity ^0.8.17;",
+            " ",
+            " contract Token {",
+            "-    function mint(uint256 amount) public {}",
+            "+    function mint(uint256 _amount) public {}",
+            " ",
+            "     function internalHelper() internal {}",
+            " }",
+            "",
+        ]
+        .join("\n")
+    );
+    assert!(
+        stderr
+            .contains("Would apply fixes in 1 file(s), 1 finding(s) can't be fixed automatically"),
+        "stderr:\n{stderr}"
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_check_fix_only_selected_rules() {
+    let project = TempProject::new("fix-only");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write("src/IToken.sol", "interface IToken {}\n");
+    let vault = [
+        "pragma solidity ^0.8.17;",
+        "",
+        "import {IToken} from \"./IToken.sol\";",
+        "contract Vault {",
+        "    function deposit(uint256 amount) public {}",
+        "}",
+        "",
+    ];
+    project.write("src/Vault.sol", vault.join("\n"));
+
+    let output = project.run(&["check", "--fix", "--only", "import,spdx"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let fixed = project.read("src/Vault.sol");
+
+    // The unused import is removed and the SPDX header inserted, but the parameter isn't renamed,
+    // since the `variable` rule wasn't selected.
+    let expected = [
+        "// SPDX-License-Identifier: UNLICENSED",
+        "pragma solidity ^0.8.17;",
+        "",
+        "",
+        "contract Vault {",
+        "    function deposit(uint256 amount) public {}",
+        "}",
+        "",
+    ];
+    assert_eq!(fixed, expected.join("\n"), "stderr:\n{stderr}");
+    assert!(output.status.success(), "stderr:\n{stderr}");
+}
+
+#[test]
+fn test_check_reads_config_once() {
+    let project = TempProject::new("config-once");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[ignore\n");
+    project.write("src/Token.sol", "contract Token { uint256 constant bad = 1; }\n");
+
+    let output = project.run(&["check", "--fix"]);
+
+    // The config is read once per check, so its warning isn't repeated by each step.
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.matches("Failed to parse .scopelint").count(), 1, "stderr:\n{stderr}");
+}
+
+#[test]
+fn test_check_alternate_config() {
+    let project = TempProject::new("config");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[ignore]\nfiles = [\"src/Token.sol\"]\n");
+    project.write(".ci/scopelint.toml", "[ignore]\nfiles = []\n");
+    project.write(".ci/broken.toml", "[ignore\n");
+    project.write("src/Token.sol", "contract Token { uint256 constant bad = 1; }\n");
+    let check = |args: &[&str]| project.run(&[args, &["check", "--only", "constant"]].concat());
+
+    let default_output = check(&[]);
+    let ci_output = check(&["--config", ".ci/scopelint.toml"]);
+    let missing_output = check(&["--config", ".ci/missing.toml"]);
+    let broken_output = check(&["--config", ".ci/broken.toml"]);
+
+    // The file ignored by `.scopelint` is checked with the stricter CI config.
+    assert!(default_output.status.success());
+    let stderr = String::from_utf8(ci_output.stderr).unwrap();
+    assert!(stderr.contains("[SL001] Invalid constant or immutable name in ./src/Token.sol"));
+    assert_eq!(ci_output.status.code(), Some(1));
+
+    // Unlike a missing `.scopelint`, a config file that was asked for must exist and be valid.
+    let stderr = String::from_utf8(missing_output.stderr).unwrap();
+    assert!(stderr.contains("Failed to read .ci/missing.toml"), "stderr:\n{stderr}");
+    assert_eq!(missing_output.status.code(), Some(3));
+    assert_eq!(broken_output.status.code(), Some(3));
+}
+
+#[test]
+fn test_check_fail_fast() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--fail-fast", "--format", "json"],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files: std::collections::BTreeSet<&str> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| finding["file"].as_str().unwrap())
+        .collect();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    // Only the first file with findings is reported, and formatting isn't checked.
+    assert_eq!(files.len(), 1, "files: {files:?}");
+    assert!(!stderr.contains("Formatting validation failed"), "stderr:\n{stderr}");
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--fail-fast"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_check_root() {
+    let cwd = env::current_dir().unwrap();
+    let run_in_tests = |args: &[&str]| {
+        Command::new(cwd.join("target/debug/dev-scopelint"))
+            .current_dir(cwd.join("tests"))
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // Findings are the same as when running in the project, with paths relative to its root.
+    let output = run_in_tests(&["--root", "check-proj1-AllFindings", "check"]);
+    let in_project = run_scopelint("check-proj1-AllFindings");
+    assert_eq!(output.stderr, in_project.stderr);
+    assert_eq!(output.status.code(), in_project.status.code());
+
+    let output = run_in_tests(&["check", "--root", "check-proj2-NoFindings", "src/Counter.sol"]);
+    assert!(output.status.success());
+
+    let output = run_in_tests(&["--root", "missing", "check"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Can't run in missing"), "stderr:\n{stderr}");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_check_manifest() {
+    let out_dir = TempProject::new("manifest");
+    let manifest_path = out_dir.join("manifest.json");
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--only", "constant,import", "--manifest", manifest_path.to_str().unwrap()],
+    );
+    let manifest = fs::read_to_string(&manifest_path).unwrap();
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(manifest["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(manifest["rules"], serde_json::json!(["constant", "import"]));
+    assert_eq!(manifest["passed"], false);
+    let config_paths: Vec<&str> = manifest["config"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(config_paths, ["foundry.toml"]);
+
+    // Every checked file is listed with the hash of its content.
+    let files = manifest["files"].as_array().unwrap();
+    let counter = files.iter().find(|file| file["path"] == "./src/Counter.sol").unwrap();
+    assert!(counter["keccak256"].as_str().unwrap().starts_with("0x"));
+    assert_eq!(counter["keccak256"].as_str().unwrap().len(), 66);
+
+    let findings = &manifest["findings"];
+    let total = findings["by_rule"]["constant"].as_u64().unwrap() +
+        findings["by_rule"]["import"].as_u64().unwrap();
+    assert_eq!(findings["total"].as_u64().unwrap(), total);
+}
+
+#[test]
+fn test_check_ci_denies_warnings() {
+    let project = TempProject::new("ci");
+    project.write("foundry.toml", "[profile.default]\n");
+    project.write(".scopelint", "[check]\nmax_warnings = 10\n");
+    project.write("src/IToken.sol", "interface IToken {}\n");
+    project.write("src/Vault.sol", "import {IToken} from \"./IToken.sol\";\ncontract Vault {}\n");
+    let check = |args: &[&str]| project.run(&[&["check", "--only", "import"], args].concat());
+
+    // The unused import is a warning, tolerated by the config locally but not in CI.
+    let local_output = check(&[]);
+    let ci_output = check(&["--ci"]);
+    let json_output = check(&["--deny-warnings", "--format", "json", "--color", "always"]);
+
+    assert!(local_output.status.success());
+    assert_eq!(ci_output.status.code(), Some(1));
+    assert!(!String::from_utf8_lossy(&ci_output.stderr).contains("\u{1b}["));
+    let json: serde_json::Value = serde_json::from_slice(&json_output.stdout).unwrap();
+    assert_eq!(json["findings"][0]["severity"], "error");
+    // Color can still be forced.
+    assert!(String::from_utf8_lossy(&json_output.stderr).contains("\u{1b}["));
+}
+
+#[test]
+fn test_check_jobs() {
+    let findings = |args: &[&str]| {
+        let output = run_scopelint_with_args("check-proj1-AllFindings", args);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["findings"].clone()
+    };
+
+    // Findings are reported in the same order however many files are checked at once.
+    let sequential = findings(&["check", "-j", "1", "--format", "json"]);
+    assert!(!sequential.as_array().unwrap().is_empty());
+    assert_eq!(findings(&["check", "--jobs", "8", "--format", "json"]), sequential);
+
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "-j", "0"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let cwd = env::current_dir().unwrap();
+    let with_env = |args: &[&str]| {
+        Command::new(cwd.join("target/debug/dev-scopelint"))
+            .current_dir(cwd.join("tests/check-proj1-AllFindings"))
+            .args(args)
+            .env("SCOPELINT_JOBS", "many")
+            .output()
+            .expect("Failed to execute command")
+    };
+    let output = with_env(&["check"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("SCOPELINT_JOBS must be a positive number of jobs"), "{stderr}");
+    // `--jobs` takes precedence over the env var.
+    let output = with_env(&["check", "-j", "2", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["findings"], sequential);
+}
+
+#[test]
+fn test_global_format() {
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["fmt", "--check", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["unformatted"], serde_json::json!(["src/Counter.sol"]));
+    assert_eq!(output.status.code(), Some(2));
+
+    let output =
+        run_scopelint_with_args("check-proj2-NoFindings", &["--format", "json", "fmt", "--check"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["unformatted"], serde_json::json!([]));
+    assert!(output.status.success());
+
+    // The flag can be given before or after the command.
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["--format", "json", "check"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["findings"], serde_json::json!([]));
+
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["spec", "--format", "sarif"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(
+            "`scopelint spec` doesn't support `--format sarif`, use one of: human, json, markdown"
+        ),
+        "stderr:\n{stderr}"
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_check_use_daemon() {
+    use std::{
+        io::{BufRead, BufReader},
+        os::unix::net::UnixStream,
+        process::Stdio,
+    };
+
+    let cwd = env::current_dir().unwrap();
+    let project_path = cwd.join("tests").join("check-proj1-AllFindings");
+    let binary_path = cwd.join("target/debug/dev-scopelint");
+    // Keeps the socket out of the fixture, which would otherwise get a `cache` directory.
+    let runtime_dir = TempProject::new("daemon");
+    let scopelint = || {
+        let mut command = Command::new(&binary_path);
+        command.current_dir(&project_path).env("XDG_RUNTIME_DIR", &runtime_dir.path);
+        command
+    };
+    let run = |args: &[&str]| scopelint().args(args).output().expect("Failed to execute command");
+    let mut daemon = scopelint()
+        .arg("daemon")
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    // The daemon is ready once it's listening.
+    let mut listening = String::new();
+    BufReader::new(daemon.stderr.take().unwrap()).read_line(&mut listening).unwrap();
+    let socket = fs::read_dir(&runtime_dir.path).unwrap().next().unwrap().unwrap().path();
+
+    let in_process = run(&["check", "--format", "json"]);
+    let args = ["check", "--use-daemon", "--format", "json"];
+    let served = run(&args);
+    // The second check reuses the files parsed by the first, and a client that never sends its
+    // request only delays it until the client times out.
+    let stalled = UnixStream::connect(&socket).unwrap();
+    let served_again = run(&args);
+    drop(stalled);
+    let stop = run(&["daemon", "--stop"]);
+    let daemon_status = daemon.wait().unwrap();
+    let stopped = run(&args);
+
+    assert!(listening.contains("Listening on"), "stderr:\n{listening}");
+    assert!(listening.contains(&runtime_dir.path.display().to_string()), "stderr:\n{listening}");
+    assert_eq!(served.stdout, in_process.stdout);
+    assert_eq!(served.status.code(), Some(1));
+    assert_eq!(served_again.stdout, in_process.stdout);
+    assert!(!String::from_utf8(served.stderr).unwrap().contains("No daemon running"));
+    assert!(stop.status.success());
+    assert!(daemon_status.success());
+    assert!(!socket.exists());
+    // Without a daemon, the check runs in-process.
+    assert!(String::from_utf8(stopped.stderr).unwrap().contains("No daemon running"));
+    assert_eq!(stopped.stdout, in_process.stdout);
+
+    // A file that isn't a socket is never removed to make room for the daemon's.
+    fs::write(&socket, "not a socket").unwrap();
+    let refused = run(&["daemon"]);
+    let stderr = String::from_utf8(refused.stderr).unwrap();
+    assert!(stderr.contains("isn't a socket of the current user"), "stderr:\n{stderr}");
+    assert!(!refused.status.success());
+    assert_eq!(fs::read_to_string(&socket).unwrap(), "not a socket");
+}
+
+#[test]
+fn test_check_ignore_path() {
+    let out_dir = TempProject::new("ignore");
+    out_dir.write(".ignore", "# Legacy contracts\nCounter.sol\n/script/\n");
+    let ignore_path = out_dir.join(".ignore");
+    let ignore_arg = ignore_path.display().to_string();
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["--ignore-path", &ignore_arg, "check"],
+    );
+    let missing =
+        run_scopelint_with_args("check-proj1-AllFindings", &["--ignore-path", "missing", "check"]);
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("./src/Counter.sol"), "stderr:\n{stderr}");
+    assert!(!stderr.contains("./script/"), "stderr:\n{stderr}");
+    // Files the ignore file doesn't match are still checked.
+    assert!(stderr.contains("./src/CounterIgnored3.sol"), "stderr:\n{stderr}");
+    assert!(stderr.contains("./test/Counter.t.sol"), "stderr:\n{stderr}");
+    assert_eq!(output.status.code(), Some(1));
+
+    let stderr = String::from_utf8(missing.stderr).unwrap();
+    assert!(stderr.contains("Failed to read missing"), "stderr:\n{stderr}");
+    assert_eq!(missing.status.code(), Some(3));
+}
+
+#[test]
+fn test_stats() {
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["stats", "--format", "json"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+
+    assert!(output.status.success());
+    assert_eq!(json["files"], serde_json::json!({ "src": 5, "test": 1, "script": 4 }));
+    assert_eq!(json["contracts"]["contract"], 5);
+    assert_eq!(json["functions"]["private"], 9);
+    assert_eq!(json["tests"]["test"], 2);
+    assert_eq!(json["tests"]["other"], 1);
+    assert_eq!(json["declarations"]["src/Counter.sol:Counter"]["errors"], 2);
+    assert_eq!(json["sloc"]["directories"]["src"], 125);
+    assert_eq!(json["sloc"]["total"], 195);
+
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["stats"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Files: 5 src, 1 test, 4 script\n"), "stdout:\n{stdout}");
+}
+
+#[test]
+fn test_help_all() {
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["--help-all"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("--use-daemon"), "stdout:\n{stdout}");
+    assert!(stdout.contains("\nSL023 nesting (warn)\n"), "stdout:\n{stdout}");
+    assert!(stdout.contains("    rules.nesting.max_depth = 4\n"), "stdout:\n{stdout}");
+
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--help-all", "man"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.starts_with(".TH "), "stdout:\n{stdout}");
+    assert!(stdout.contains(".SH RULES\n"), "stdout:\n{stdout}");
+}
+
+#[test]
+fn test_quiet() {
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["fmt", "--check"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("All files are properly formatted!"), "stdout:\n{stdout}");
+
+    for args in [&["-q", "fmt", "--check"][..], &["check", "--quiet"], &["--quiet", "storage"]] {
+        let output = run_scopelint_with_args("check-proj2-NoFindings", args);
+        assert!(output.status.success(), "{args:?}");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        if args.contains(&"storage") {
+            // The layout is the output of the command, not a progress message.
+            assert!(!stdout.is_empty(), "{args:?}");
+        } else {
+            assert!(stdout.is_empty(), "{args:?} stdout:\n{stdout}");
+        }
+        assert!(stderr.is_empty(), "{args:?} stderr:\n{stderr}");
+    }
+
+    // Findings are still reported on failure.
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "-q"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("[SL001] Invalid constant or immutable name"), "stderr:\n{stderr}");
+    assert!(stderr.contains("Convention checks failed"), "stderr:\n{stderr}");
+}
+
+#[test]
+fn test_migrate_config() {
+    // No rule has been renamed in these projects, so there is nothing to migrate.
+    for project in ["check-proj1-AllFindings", "check-proj2-NoFindings"] {
+        let output = run_scopelint_with_args(project, &["migrate-config", "--check"]);
+        assert!(output.status.success(), "{project}");
+        assert!(output.stdout.is_empty(), "{project}");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("No deprecated rule names found"), "stderr:\n{stderr}");
+    }
+}
+
+#[test]
+fn test_check_contract() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--format", "json", "--contract", "Counter"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be JSON");
+    let files: std::collections::BTreeSet<&str> =
+        json["findings"].as_array().unwrap().iter().map(|f| f["file"].as_str().unwrap()).collect();
+    // The test imports the contract, the scripts don't.
+    assert_eq!(files, ["./src/Counter.sol", "./test/Counter.t.sol"].into());
+    assert!(!output.status.success());
+
+    let output =
+        run_scopelint_with_args("check-proj1-AllFindings", &["check", "--contract", "Missing"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("No contract named 'Missing'"), "stderr:\n{stderr}");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_selectors_json_alias() {
+    let format =
+        run_scopelint_with_args("check-proj1-AllFindings", &["selectors", "--format", "json"]);
+    let alias = run_scopelint_with_args("check-proj1-AllFindings", &["selectors", "--json"]);
+
+    assert!(format.status.success());
+    assert!(String::from_utf8(format.stdout.clone()).unwrap().starts_with('{'));
+    assert_eq!(alias.stdout, format.stdout);
+    let stderr = String::from_utf8(alias.stderr).unwrap();
+    assert!(stderr.contains("`scopelint selectors --json` is deprecated"), "stderr:\n{stderr}");
+}
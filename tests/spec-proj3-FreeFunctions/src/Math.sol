@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+function square(uint256 x) pure returns (uint256) {
+  return x * x;
+}
+
+contract Math {
+  function cube(uint256 x) external pure returns (uint256) {
+    return square(x) * x;
+  }
+}
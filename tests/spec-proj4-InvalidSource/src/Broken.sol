@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Broken {
+  function missingBody(uint256 x) external returns (uint256)
@@ -95,3 +95,84 @@ fn test_spec_proj2_empty_contract() {
     let expected_spec = "";
     assert_eq!(stdout, expected_spec);
 }
+
+#[test]
+fn test_spec_proj3_free_functions() {
+    let output = run_scopelint("spec-proj3-FreeFunctions");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // Free functions are skipped, and contracts next to them are still specified.
+    assert!(output.status.success());
+    assert!(stdout.contains("Math"), "stdout:\n{stdout}");
+    assert!(stdout.contains("cube"), "stdout:\n{stdout}");
+    assert!(!stdout.contains("square"), "stdout:\n{stdout}");
+}
+
+#[test]
+fn test_spec_proj4_invalid_source() {
+    let output = run_scopelint("spec-proj4-InvalidSource");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Failed to parse ./src/Broken.sol"), "stderr:\n{stderr}");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_spec_proj1_json() {
+    let cwd = env::current_dir().unwrap();
+    let output = Command::new(cwd.join("target/debug/dev-scopelint"))
+        .current_dir(cwd.join("tests/spec-proj1"))
+        .args(["spec", "--format", "json"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let contracts = json["contracts"].as_array().unwrap();
+    assert_eq!(contracts.len(), 1);
+    assert_eq!(contracts[0]["name"], "ERC20");
+    let functions = contracts[0]["functions"].as_array().unwrap();
+    let names: Vec<&str> = functions.iter().map(|f| f["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["approve", "transfer", "transferFrom", "permit", "DOMAIN_SEPARATOR"]);
+
+    // Requirements are trimmed, and link back to the test they were parsed from.
+    let approve = &functions[0];
+    assert_eq!(approve["tested"], true);
+    assert_eq!(approve["scenarios"].as_array().unwrap().len(), 3);
+    assert_eq!(approve["scenarios"][0]["requirement"], "Sets Allowance Mapping To Approved Amount");
+    assert!(approve["scenarios"][0]["test"].as_str().unwrap().starts_with("test"));
+    assert!(approve["scenarios"][0]["path"].as_str().unwrap().ends_with("ERC20.t.sol"));
+
+    // Untested functions have no scenarios.
+    assert_eq!(functions[2]["tested"], false);
+    assert_eq!(functions[2]["scenarios"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_spec_proj1_markdown_out() {
+    let cwd = env::current_dir().unwrap();
+    let out = env::temp_dir().join(format!("scopelint-spec-{}.md", std::process::id()));
+    let spec = |args: &[&str]| {
+        Command::new(cwd.join("target/debug/dev-scopelint"))
+            .current_dir(cwd.join("tests/spec-proj1"))
+            .arg("spec")
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = spec(&["--format", "markdown", "--out", out.to_str().unwrap()]);
+    let markdown = std::fs::read_to_string(&out).unwrap();
+    std::fs::remove_file(&out).unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(markdown.starts_with(
+        "# Specification\n\n## ERC20\n\nDefined in `./src/ERC20.sol`.\n\n### `approve`\n\n- Sets Allowance Mapping To Approved Amount\n"
+    ));
+    assert!(markdown.contains(
+        "### `transferFrom`\n\n_No requirements, this function has no matching tests._\n"
+    ));
+
+    // The tree is meant for terminals, so it can't be written to a file.
+    let output = spec(&["--out", out.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(!out.exists());
+}